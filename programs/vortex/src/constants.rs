@@ -1,4 +1,7 @@
-/// Vortex AMM Constants
+//! Vortex AMM Constants
+
+use anchor_lang::prelude::*;
+use crate::errors::AmmError;
 
 // ============================================================================
 // SCALING CONSTANTS
@@ -10,6 +13,9 @@ pub const BPS_DENOMINATOR: u64 = 10_000;
 /// Precision for price calculations (2^64)
 pub const Q64: u128 = 1u128 << 64;
 
+/// Decimals of the human-readable prices from `Pool::price_a_scaled` (1e9)
+pub const PRICE_SCALE_DECIMALS: u8 = 9;
+
 /// Minimum liquidity locked forever on first deposit
 pub const MINIMUM_LIQUIDITY: u64 = 1_000;
 
@@ -39,12 +45,61 @@ pub const MAX_FEE_BPS: u64 = 1_000;
 /// Minimum fee allowed (0.01% = 1 BPS)
 pub const MIN_FEE_BPS: u64 = 1;
 
-/// Minimum swap amount (prevents dust attacks)
+/// Maximum liquidity withdrawal fee (1% = 100 BPS)
+pub const MAX_WITHDRAW_FEE_BPS: u16 = 100;
+
+/// Maximum relayer fee as a share of swap output (0.5% = 50 BPS)
+pub const MAX_RELAYER_FEE_BPS: u64 = 50;
+
+/// Maximum decimals of a pool's LP mint
+pub const MAX_LP_DECIMALS: u8 = 9;
+
+/// Default length of the rolling swap-volume window (24h, seconds)
+pub const VOLUME_WINDOW_DURATION: i64 = 86_400;
+
+/// Default per-pool minimum swap amount (prevents dust attacks)
 pub const MIN_SWAP_AMOUNT: u64 = 100;
 
+/// Maximum number of chunks `swap_split` may price a trade in
+pub const MAX_SWAP_SPLIT_CHUNKS: u8 = 16;
+
+/// Maximum number of positions `compound_all` may compound at once
+pub const MAX_COMPOUND_POSITIONS: usize = 8;
+
+/// Vault/reserve drift tolerated by strict reconciliation (base units)
+pub const RESERVE_TOLERANCE: u64 = 1;
+
 /// Minimum initial liquidity
 pub const MIN_INITIAL_LIQUIDITY: u64 = 1_000;
 
+/// Swaps kept in the on-chain history ring buffer (bounded so the full
+/// buffer fits in the 1024-byte return data limit)
+pub const SWAP_HISTORY_LEN: usize = 16;
+
+/// TWAP accumulator readings kept in the price checkpoint ring buffer
+pub const PRICE_CHECKPOINTS_LEN: usize = 16;
+
+/// Minimum seconds between price checkpoints, so the buffer always spans
+/// at least PRICE_CHECKPOINTS_LEN * this (32 minutes) and a burst of swaps
+/// cannot evict the history
+pub const PRICE_CHECKPOINT_INTERVAL: i64 = 120;
+
+/// Minimum slots between reserve snapshot checkpoints (~1 minute)
+pub const CHECKPOINT_INTERVAL_SLOTS: u64 = 150;
+
+/// Virtual shares and LP added to both sides of the vault share price, so
+/// a first depositor cannot inflate it by donating and round later
+/// deposits down to zero shares
+pub const VAULT_VIRTUAL_SHARES: u64 = 1_000;
+pub const VAULT_VIRTUAL_ASSETS: u64 = 1_000;
+
+/// TWAP window `harvest` checks the spot price against (seconds); at least
+/// half of it must be covered by recorded checkpoints
+pub const HARVEST_TWAP_PERIOD: i64 = 1_800;
+
+/// Maximum spot/TWAP deviation at which `harvest` may add liquidity
+pub const HARVEST_MAX_PRICE_DEVIATION_BPS: u64 = 100;
+
 // ============================================================================
 // PDA SEEDS
 // ============================================================================
@@ -64,22 +119,230 @@ pub const LP_MINT_SEED: &[u8] = b"lp_mint";
 /// Seed for LP mint authority PDA
 pub const LP_MINT_AUTHORITY_SEED: &[u8] = b"lp_mint_authority";
 
+/// Seed for reference price oracle PDA
+pub const ORACLE_SEED: &[u8] = b"oracle";
+
+/// Seed for the singleton protocol config PDA
+pub const PROTOCOL_CONFIG_SEED: &[u8] = b"protocol_config";
+
+/// Seed for per-mint protocol treasury vault PDA
+pub const TREASURY_SEED: &[u8] = b"treasury";
+
+/// Seed for swap history ring buffer PDA
+pub const SWAP_HISTORY_SEED: &[u8] = b"swap_history";
+
+/// Seed for price checkpoint ring buffer PDA
+pub const PRICE_CHECKPOINTS_SEED: &[u8] = b"price_checkpoints";
+
+/// Seed for per-owner LP position PDAs and nonce-keyed position receipts
+pub const POSITION_SEED: &[u8] = b"position";
+
+/// Seed for owner-signed relayed deposit terms
+pub const DEPOSIT_INTENT_SEED: &[u8] = b"deposit_intent";
+
+/// Seed for auto-compounding LP vault PDA
+pub const LP_VAULT_SEED: &[u8] = b"lp_vault";
+
+/// Seed for LP vault share mint PDA
+pub const VAULT_SHARE_MINT_SEED: &[u8] = b"vault_share_mint";
+
+/// Seed for the token accounts held by an LP vault
+pub const VAULT_HOLDINGS_SEED: &[u8] = b"vault_holdings";
+
+/// Seed for the LP escrow account of a position receipt
+pub const RECEIPT_ESCROW_SEED: &[u8] = b"receipt_escrow";
+
+/// Seed for per-user swap records (swap cooldown)
+pub const USER_SWAP_SEED: &[u8] = b"user_swap";
+
+/// Seed for per-trader fee discounts
+pub const FEE_TIER_SEED: &[u8] = b"fee_tier";
+
+/// Seed for the temporary wSOL account of a native SOL swap
+pub const WSOL_SEED: &[u8] = b"wsol";
+
+/// Seed for the per-pool PDA that may own the locked MINIMUM_LIQUIDITY;
+/// no instruction signs for it, so its LP can never move
+pub const LOCKED_LP_SEED: &[u8] = b"locked_lp";
+
+/// Seed for time-locked LP escrows
+pub const LIQUIDITY_LOCK_SEED: &[u8] = b"liquidity_lock";
+
+/// Seed for the LP escrow account of a liquidity lock
+pub const LOCK_ESCROW_SEED: &[u8] = b"lock_escrow";
+
+// ============================================================================
+// GRANULAR PAUSE FLAGS
+// ============================================================================
+
+/// Swaps selling token A (A -> B) are paused
+pub const PAUSE_SWAP_A_IN: u8 = 1 << 0;
+
+/// Swaps selling token B (B -> A) are paused
+pub const PAUSE_SWAP_B_IN: u8 = 1 << 1;
+
+/// Deposits of token A are paused
+pub const PAUSE_DEPOSIT_A: u8 = 1 << 2;
+
+/// Deposits of token B are paused
+pub const PAUSE_DEPOSIT_B: u8 = 1 << 3;
+
+/// All valid granular pause flags
+pub const PAUSE_FLAGS_MASK: u8 =
+    PAUSE_SWAP_A_IN | PAUSE_SWAP_B_IN | PAUSE_DEPOSIT_A | PAUSE_DEPOSIT_B;
+
+// ============================================================================
+// EVENT VERBOSITY
+// ============================================================================
+//
+// Only the per-swap `SwapExecuted` and `OraclePriceUpdate` events are
+// affected. Liquidity, admin, treasury, rebalance and snapshot events are
+// always emitted in full.
+
+/// Emit the full `SwapExecuted` event on every swap
+pub const EVENT_VERBOSITY_FULL: u8 = 0;
+
+/// Emit `SwapExecutedCompact` (amounts only) and `OraclePriceUpdate` on every swap
+pub const EVENT_VERBOSITY_COMPACT: u8 = 1;
+
+/// Emit no per-swap events
+pub const EVENT_VERBOSITY_NONE: u8 = 2;
+
+// ============================================================================
+// CURVES
+// ============================================================================
+
+/// Constant-product curve (x * y = k)
+pub const CURVE_CONSTANT_PRODUCT: u8 = 0;
+
+/// StableSwap curve for correlated assets
+pub const CURVE_STABLE: u8 = 1;
+
+/// Minimum amplification coefficient for stable pools
+pub const MIN_AMP: u64 = 1;
+
+/// Maximum amplification coefficient for stable pools
+pub const MAX_AMP: u64 = 1_000_000;
+
 // ============================================================================
 // PROTOCOL
 // ============================================================================
 
-/// Current protocol version
-pub const PROTOCOL_VERSION: u8 = 1;
+/// Current protocol version. Bump when `Pool` gains fields that need a
+/// non-zero default, and set that default in `migrate_pool`.
+pub const PROTOCOL_VERSION: u8 = 2;
 
 // ============================================================================
 // HELPERS
 // ============================================================================
 
-/// Validate fee parameters
+/// Validate fee parameters with no protocol share
+///
+/// The BPS conversion runs in u128, so any numerator/denominator pair is
+/// rejected cleanly instead of overflowing.
 pub fn validate_fee(numerator: u64, denominator: u64) -> bool {
-    if denominator == 0 {
-        return false;
+    validate_fee_split(numerator, denominator, 0, 0).is_ok()
+}
+
+/// Validate a swap fee together with the protocol's cut of it
+///
+/// `protocol_numerator / protocol_denominator` (0/0 for none) is the share
+/// of the swap fee taken by the protocol; LPs keep the rest, which must
+/// still be a valid LP fee under `validate_fee_config`.
+pub fn validate_fee_split(
+    fee_numerator: u64,
+    fee_denominator: u64,
+    protocol_numerator: u64,
+    protocol_denominator: u64,
+) -> Result<()> {
+    require!(fee_denominator > 0, AmmError::InvalidFeeParameters);
+    require!(
+        protocol_numerator == 0
+            || (protocol_denominator > 0 && protocol_numerator <= protocol_denominator),
+        AmmError::InvalidFeeParameters
+    );
+
+    let fee_bps = (fee_numerator as u128 * BPS_DENOMINATOR as u128) / fee_denominator as u128;
+    let fee_bps = u64::try_from(fee_bps).map_err(|_| AmmError::InvalidFeeParameters)?;
+    let protocol_bps = if protocol_numerator == 0 {
+        0
+    } else {
+        (fee_bps as u128 * protocol_numerator as u128 / protocol_denominator as u128) as u64
+    };
+
+    validate_fee_config(fee_bps - protocol_bps, protocol_bps, 0, 0)
+}
+
+/// Validate a full swap fee configuration (all values in BPS)
+///
+/// The LP fee must lie within [MIN_FEE_BPS, MAX_FEE_BPS]; the protocol,
+/// creator and referral shares may be zero, but the total of all four must
+/// not exceed MAX_FEE_BPS. Swap fee and protocol share changes reach this
+/// through `validate_fee_split`; the pool has no creator or referral fees
+/// yet, so those are always 0. Withdrawal fees, staleness surcharges,
+/// oracle spreads and fee-tier discounts are not shares of the swap fee
+/// and are bounded by their own setters.
+pub fn validate_fee_config(
+    lp_fee_bps: u64,
+    protocol_fee_bps: u64,
+    creator_fee_bps: u64,
+    referral_fee_bps: u64,
+) -> Result<()> {
+    require!(
+        (MIN_FEE_BPS..=MAX_FEE_BPS).contains(&lp_fee_bps),
+        AmmError::InvalidFeeParameters
+    );
+
+    let total = [protocol_fee_bps, creator_fee_bps, referral_fee_bps]
+        .iter()
+        .try_fold(lp_fee_bps, |acc, fee| acc.checked_add(*fee))
+        .ok_or(AmmError::MathOverflow)?;
+    require!(total <= MAX_FEE_BPS, AmmError::FeeTooHigh);
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn fee_config_accepts_fees_within_bounds() {
+        assert!(validate_fee_config(MIN_FEE_BPS, 0, 0, 0).is_ok());
+        assert!(validate_fee_config(30, 5, 0, 0).is_ok());
+        assert!(validate_fee_config(MAX_FEE_BPS - 30, 10, 10, 10).is_ok());
+    }
+
+    #[test]
+    fn fee_config_rejects_out_of_bounds_lp_fee() {
+        assert!(validate_fee_config(0, 0, 0, 0).is_err());
+        assert!(validate_fee_config(MAX_FEE_BPS + 1, 0, 0, 0).is_err());
+    }
+
+    #[test]
+    fn fee_config_rejects_total_above_max() {
+        assert!(validate_fee_config(MAX_FEE_BPS, 1, 0, 0).is_err());
+        assert!(validate_fee_config(500, 200, 200, 101).is_err());
+        assert!(validate_fee_config(30, u64::MAX, 1, 0).is_err());
+    }
+
+    #[test]
+    fn fee_split_checks_the_lp_remainder() {
+        // 30 bps fee, protocol takes a sixth
+        assert!(validate_fee_split(30, 10_000, 1, 6).is_ok());
+        // Protocol taking the whole fee leaves LPs below MIN_FEE_BPS
+        assert!(validate_fee_split(30, 10_000, 1, 1).is_err());
+        // Share above 100%
+        assert!(validate_fee_split(30, 10_000, 2, 1).is_err());
+        assert!(validate_fee_split(30, 0, 0, 0).is_err());
+    }
+
+    #[test]
+    fn validate_fee_rejects_overflowing_ratios() {
+        assert!(validate_fee(30, 10_000));
+        assert!(!validate_fee(u64::MAX, 1));
+        assert!(!validate_fee(0, 10_000));
+        assert!(!validate_fee(u64::MAX, u64::MAX));
+        assert!(!validate_fee(30, 0));
     }
-    let fee_bps = (numerator * BPS_DENOMINATOR) / denominator;
-    fee_bps >= MIN_FEE_BPS && fee_bps <= MAX_FEE_BPS
 }
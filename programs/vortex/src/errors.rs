@@ -6,34 +6,100 @@ pub enum AmmError {
     #[msg("Invalid fee parameters")]
     InvalidFeeParameters,
 
+    #[msg("Invalid curve type or amplification coefficient")]
+    InvalidCurveParameters,
+
+    #[msg("Minimum swap amount must be between 1 and one whole token")]
+    InvalidMinSwapAmount,
+
+    #[msg("LP mint decimals exceed the maximum")]
+    InvalidLpDecimals,
+
     #[msg("Fee too high")]
     FeeTooHigh,
 
     #[msg("Pool already initialized")]
     PoolAlreadyInitialized,
 
+    #[msg("Pool is already at the current version")]
+    MigrationNotNeeded,
+
     #[msg("Token mints must be different")]
     IdenticalTokenMints,
 
+    #[msg("Token A mint must sort before token B mint")]
+    InvalidTokenOrder,
+
     // Liquidity
     #[msg("Pool not initialized")]
     PoolNotInitialized,
 
+    #[msg("Pool still has liquidity beyond the locked minimum")]
+    PoolNotEmpty,
+
     #[msg("Initial liquidity too small")]
     InitialLiquidityTooSmall,
 
+    #[msg("Invalid locked liquidity recipient")]
+    InvalidLockedLiquidityRecipient,
+
+    #[msg("Invalid protocol fee recipient")]
+    InvalidFeeRecipient,
+
     #[msg("Insufficient liquidity minted")]
     InsufficientLiquidityMinted,
 
     #[msg("Insufficient liquidity burned")]
     InsufficientLiquidityBurned,
 
+    #[msg("Burn too small to redeem any tokens")]
+    BurnAmountTooSmall,
+
     #[msg("Slippage tolerance exceeded")]
     SlippageExceeded,
 
+    #[msg("Slippage tolerance must not exceed 100%")]
+    InvalidSlippage,
+
+    #[msg("Price limit must be non-zero")]
+    InvalidPriceLimit,
+
+    #[msg("Transaction deadline has passed")]
+    DeadlineExceeded,
+
+    #[msg("Price impact exceeds the allowed maximum")]
+    PriceImpactTooHigh,
+
     #[msg("Amount too small")]
     AmountTooSmall,
 
+    #[msg("Deposit would move the reserve ratio outside the configured band")]
+    RatioOutOfBand,
+
+    #[msg("Pool price is outside the requested band")]
+    PriceOutOfBand,
+
+    #[msg("Nothing to harvest")]
+    NothingToHarvest,
+
+    #[msg("Treasury vaults required when a withdrawal fee is set")]
+    TreasuryAccountRequired,
+
+    #[msg("Position account required while an LP hold window is set")]
+    PositionRequired,
+
+    #[msg("Accounts do not match the position receipt")]
+    InvalidPosition,
+
+    #[msg("Liquidity was deposited too recently to be removed")]
+    LiquidityLockedTooRecent,
+
+    #[msg("Liquidity is still locked")]
+    StillLocked,
+
+    #[msg("Unlock time must be in the future")]
+    InvalidUnlockTimestamp,
+
     // Swap
     #[msg("Insufficient output amount")]
     InsufficientOutputAmount,
@@ -47,6 +113,54 @@ pub enum AmmError {
     #[msg("Output exceeds reserves")]
     OutputExceedsReserves,
 
+    #[msg("Post-swap assertion account missing or not a token account")]
+    AssertionAccountMissing,
+
+    #[msg("Post-swap balance assertion failed")]
+    PostSwapAssertionFailed,
+
+    #[msg("Exact-output swaps are only supported on constant-product pools")]
+    ExactOutUnsupported,
+
+    #[msg("Single-sided deposits are only supported on constant-product pools")]
+    SingleSidedUnsupported,
+
+    #[msg("Marginal prices are not available for oracle-priced pools")]
+    MarginalPriceUnsupported,
+
+    #[msg("No protocol fees have accrued")]
+    NoProtocolFees,
+
+    #[msg("Relayer token account missing or not a token account")]
+    RelayerAccountMissing,
+
+    #[msg("Relayer fee exceeds the cap")]
+    RelayerFeeTooHigh,
+
+    #[msg("Swap cooldown has not elapsed for this user")]
+    SwapCooldown,
+
+    #[msg("User swap record required while a swap cooldown is set")]
+    UserSwapStateRequired,
+
+    #[msg("Route is empty, has leftover accounts or hops are not chained")]
+    InvalidRoute,
+
+    #[msg("Batch is empty or has leftover accounts")]
+    InvalidBatch,
+
+    #[msg("Swap split needs 1 to MAX_SWAP_SPLIT_CHUNKS chunks on a curve-priced pool")]
+    InvalidSwapSplit,
+
+    #[msg("Flash loan callback program missing or not allowed")]
+    InvalidFlashLoanCallback,
+
+    #[msg("Flash loan was not repaid with its fee")]
+    FlashLoanNotRepaid,
+
+    #[msg("Reserve would exceed the pool's cap")]
+    ReserveCapExceeded,
+
     // Math
     #[msg("Math overflow")]
     MathOverflow,
@@ -57,20 +171,92 @@ pub enum AmmError {
     #[msg("Invariant violated")]
     InvariantViolation,
 
+    #[msg("StableSwap invariant did not converge")]
+    InvariantConvergenceFailure,
+
     // Accounts
     #[msg("Invalid token mint")]
     InvalidTokenMint,
 
+    #[msg("The same account was passed for two different roles")]
+    DuplicateAccount,
+
     #[msg("Invalid vault")]
     InvalidVault,
 
+    #[msg("Token program does not match the pool's mint")]
+    InvalidTokenProgram,
+
     #[msg("Vault balance mismatch")]
     VaultBalanceMismatch,
 
+    #[msg("Invalid LP mint authority")]
+    InvalidMintAuthority,
+
     // Permissions
     #[msg("Unauthorized")]
     Unauthorized,
 
+    #[msg("Pool is not an approved delegate for this deposit")]
+    InvalidDelegate,
+
+    #[msg("Delegated deposit needs the owner's deposit intent")]
+    DepositIntentRequired,
+
+    #[msg("Deposit does not match the owner's deposit intent")]
+    DepositIntentMismatch,
+
     #[msg("Pool paused")]
     PoolPaused,
+
+    #[msg("Pool is not paused and unwinding")]
+    PoolNotUnwinding,
+
+    #[msg("Operation paused for this token")]
+    TokenPaused,
+
+    #[msg("Deposits are frozen for this pool")]
+    DepositsFrozen,
+
+    #[msg("Withdrawal would drain a reserve below the pool's floor")]
+    WouldDrainPool,
+
+    #[msg("Fee discount exceeds the pool's swap fee")]
+    InvalidFeeDiscount,
+
+    #[msg("Invalid pause flags")]
+    InvalidPauseFlags,
+
+    #[msg("Invalid event verbosity")]
+    InvalidEventVerbosity,
+
+    // Oracle
+    #[msg("Oracle account required for oracle-priced swaps")]
+    OracleRequired,
+
+    #[msg("Oracle price is stale")]
+    OracleStale,
+
+    #[msg("Invalid oracle price")]
+    InvalidOraclePrice,
+
+    // History
+    #[msg("Swap history is empty")]
+    SwapHistoryEmpty,
+
+    // Checkpoints
+    #[msg("Checkpoint called too soon after the last pool update")]
+    CheckpointTooFrequent,
+
+    #[msg("Consult period must be positive")]
+    InvalidConsultPeriod,
+
+    #[msg("No price checkpoint recorded before now")]
+    NoPriceCheckpoint,
+
+    #[msg("Price checkpoints do not cover enough of the TWAP window")]
+    TwapWindowTooShort,
+
+    #[msg("Spot price deviates too far from the TWAP")]
+    PriceDeviatesFromTwap,
 }
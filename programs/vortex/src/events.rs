@@ -11,6 +11,14 @@ pub struct PoolCreated {
     pub timestamp: i64,
 }
 
+/// Emitted when an empty pool is closed
+#[event]
+pub struct PoolClosed {
+    pub pool: Pubkey,
+    pub authority: Pubkey,
+    pub timestamp: i64,
+}
+
 /// Emitted when liquidity is added
 #[event]
 pub struct LiquidityAdded {
@@ -19,6 +27,9 @@ pub struct LiquidityAdded {
     pub amount_a: u64,
     pub amount_b: u64,
     pub liquidity_minted: u64,
+    /// Owner of the account receiving the locked MINIMUM_LIQUIDITY
+    /// (first deposit only, default otherwise)
+    pub locked_liquidity_recipient: Pubkey,
     pub reserve_a: u64,
     pub reserve_b: u64,
     pub timestamp: i64,
@@ -32,6 +43,8 @@ pub struct LiquidityRemoved {
     pub liquidity_burned: u64,
     pub amount_a: u64,
     pub amount_b: u64,
+    pub withdraw_fee_a: u64,
+    pub withdraw_fee_b: u64,
     pub reserve_a: u64,
     pub reserve_b: u64,
     pub timestamp: i64,
@@ -47,7 +60,246 @@ pub struct SwapExecuted {
     pub amount_in: u64,
     pub amount_out: u64,
     pub fee_amount: u64,
+    pub fee_exempt: bool,
+    /// Decimal-independent price impact versus the pre-swap spot price
+    pub price_impact_bps: u64,
+    /// LP tokens minted to the swapper as a fee rebate
+    pub lp_rebate: u64,
+    /// Part of `fee_amount` charged because reserves were stale
+    pub staleness_surcharge: u64,
+    /// Oracle price used (Q64), 0 for curve-priced swaps
+    pub oracle_price: u128,
     pub reserve_a: u64,
     pub reserve_b: u64,
     pub timestamp: i64,
 }
+
+/// Compact form of `SwapExecuted` for pools with `EVENT_VERBOSITY_COMPACT`
+#[event]
+pub struct SwapExecutedCompact {
+    pub pool: Pubkey,
+    pub a_to_b: bool,
+    pub amount_in: u64,
+    pub amount_out: u64,
+    pub fee_amount: u64,
+}
+
+/// Spot price and session range after a swap, for price dashboards
+#[event]
+pub struct OraclePriceUpdate {
+    pub pool: Pubkey,
+    /// Price of token A in token B after the swap (Q64)
+    pub price: u128,
+    pub price_high: u128,
+    pub price_low: u128,
+    pub timestamp: i64,
+}
+
+/// Emitted when an LP vault compounds its token holdings into LP
+#[event]
+pub struct VaultHarvested {
+    pub vault: Pubkey,
+    pub pool: Pubkey,
+    pub caller: Pubkey,
+    pub amount_a: u64,
+    pub amount_b: u64,
+    pub liquidity_minted: u64,
+    pub total_lp: u64,
+    pub total_shares: u64,
+    pub timestamp: i64,
+}
+
+/// Emitted when protocol governance changes a pool's protocol fee recipient
+#[event]
+pub struct FeeRecipientUpdated {
+    pub pool: Pubkey,
+    pub governance: Pubkey,
+    pub old_fee_recipient: Pubkey,
+    pub new_fee_recipient: Pubkey,
+    pub timestamp: i64,
+}
+
+/// Emitted when the pool authority changes the swap fee
+#[event]
+pub struct FeeUpdated {
+    pub pool: Pubkey,
+    pub authority: Pubkey,
+    pub old_fee_numerator: u64,
+    pub old_fee_denominator: u64,
+    pub new_fee_numerator: u64,
+    pub new_fee_denominator: u64,
+    /// Resulting fee in BPS
+    pub fee_bps: u64,
+    pub timestamp: i64,
+}
+
+/// Emitted when accrued protocol fees are collected
+#[event]
+pub struct ProtocolFeesCollected {
+    pub pool: Pubkey,
+    pub authority: Pubkey,
+    pub amount_a: u64,
+    pub amount_b: u64,
+    pub timestamp: i64,
+}
+
+/// What caused a reserve change reported by `ReservesSynced`
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ReserveChangeSource {
+    /// A trade against the curve
+    Swap,
+    /// Tokens injected without a trade, such as a flash loan surplus
+    Donation,
+    /// Books reconciled with the vaults by `sync_reserves`
+    Sync,
+}
+
+/// Emitted when reserves change outside of a swap or liquidity add/remove,
+/// so indexers can tell injections from trades when rebuilding prices
+#[event]
+pub struct ReservesSynced {
+    pub pool: Pubkey,
+    pub source: ReserveChangeSource,
+    pub old_reserve_a: u64,
+    pub old_reserve_b: u64,
+    pub new_reserve_a: u64,
+    pub new_reserve_b: u64,
+    /// Vault excess booked as protocol fees (`Sync` only)
+    pub excess_a: u64,
+    pub excess_b: u64,
+    pub timestamp: i64,
+}
+
+/// Emitted when a pool is paused
+#[event]
+pub struct PoolPaused {
+    pub pool: Pubkey,
+    /// Authority or guardian that signed
+    pub authority: Pubkey,
+    pub timestamp: i64,
+}
+
+/// Emitted when a pool is unpaused
+#[event]
+pub struct PoolUnpaused {
+    pub pool: Pubkey,
+    pub authority: Pubkey,
+    pub timestamp: i64,
+}
+
+/// Emitted when a swap pays a relayer for submitting it
+#[event]
+pub struct RelayerFeePaid {
+    pub pool: Pubkey,
+    pub user: Pubkey,
+    pub relayer_token_account: Pubkey,
+    pub mint: Pubkey,
+    /// Gross swap output before the relayer fee
+    pub amount_out: u64,
+    pub relayer_fee: u64,
+    pub timestamp: i64,
+}
+
+/// Emitted when a flash loan is repaid
+#[event]
+pub struct FlashLoanRepaid {
+    pub pool: Pubkey,
+    pub borrower: Pubkey,
+    pub mint: Pubkey,
+    pub amount: u64,
+    pub fee: u64,
+    pub timestamp: i64,
+}
+
+/// Emitted when the pool authority rebalances protocol-owned liquidity
+/// through the fee-free swap path
+#[event]
+pub struct ProtocolRebalance {
+    pub pool: Pubkey,
+    pub authority: Pubkey,
+    pub token_in: Pubkey,
+    pub token_out: Pubkey,
+    pub amount_in: u64,
+    pub amount_out: u64,
+    pub reserve_a: u64,
+    pub reserve_b: u64,
+    pub timestamp: i64,
+}
+
+/// Emitted by the permissionless checkpoint instruction so charting
+/// services have data points during periods without swaps
+#[event]
+pub struct ReserveSnapshot {
+    pub pool: Pubkey,
+    pub reserve_a: u64,
+    pub reserve_b: u64,
+    pub price_a: u128,
+    pub price_b: u128,
+    pub slot: u64,
+    pub timestamp: i64,
+}
+
+/// Emitted when protocol fees accrue into a treasury vault
+#[event]
+pub struct TreasuryAccrued {
+    pub pool: Pubkey,
+    pub mint: Pubkey,
+    pub amount: u64,
+    pub timestamp: i64,
+}
+
+/// Emitted for each position whose earned fees are compounded
+#[event]
+pub struct FeesCompounded {
+    pub pool: Pubkey,
+    pub owner: Pubkey,
+    pub amount_a: u64,
+    pub amount_b: u64,
+    /// LP tokens attributed to the position, which hold the fees
+    pub liquidity: u64,
+    pub timestamp: i64,
+}
+
+/// Emitted when protocol fees are added to a pool as treasury-owned liquidity
+#[event]
+pub struct ProtocolLiquidityAdded {
+    pub pool: Pubkey,
+    pub governance: Pubkey,
+    pub amount_a: u64,
+    pub amount_b: u64,
+    pub liquidity_minted: u64,
+    pub reserve_a: u64,
+    pub reserve_b: u64,
+    pub timestamp: i64,
+}
+
+/// Emitted when governance sweeps a treasury vault
+#[event]
+pub struct TreasuryWithdrawn {
+    pub mint: Pubkey,
+    pub governance: Pubkey,
+    pub destination: Pubkey,
+    pub amount: u64,
+    pub timestamp: i64,
+}
+
+/// Emitted when LP tokens are locked until a fixed time
+#[event]
+pub struct LiquidityLocked {
+    pub pool: Pubkey,
+    pub owner: Pubkey,
+    pub lock: Pubkey,
+    pub amount: u64,
+    pub unlock_timestamp: i64,
+    pub timestamp: i64,
+}
+
+/// Emitted when a liquidity lock is released to its owner
+#[event]
+pub struct LiquidityUnlocked {
+    pub pool: Pubkey,
+    pub owner: Pubkey,
+    pub lock: Pubkey,
+    pub amount: u64,
+    pub timestamp: i64,
+}
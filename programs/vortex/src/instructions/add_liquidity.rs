@@ -1,15 +1,18 @@
 use anchor_lang::prelude::*;
-use anchor_spl::token::{self, Mint, Token, TokenAccount, Transfer, MintTo};
+use anchor_lang::solana_program::incinerator;
+use anchor_lang::solana_program::program_option::COption;
+use anchor_spl::token::{self, Mint, Token, TokenAccount, MintTo};
+use anchor_spl::token_interface::{self, TokenInterface, TransferChecked};
 
-use crate::state::Pool;
+use crate::state::{DepositIntent, Pool, Position};
 use crate::constants::*;
 use crate::errors::AmmError;
 use crate::events::LiquidityAdded;
-use crate::math::{calculate_initial_liquidity, calculate_liquidity_to_mint};
+use crate::math::{calculate_initial_liquidity, calculate_liquidity_to_mint, lp_share_growth, verify_lp_value};
 
 #[derive(Accounts)]
 pub struct AddLiquidity<'info> {
-    /// Liquidity provider
+    /// Liquidity provider, or a relayer for delegated deposits
     #[account(mut)]
     pub user: Signer<'info>,
 
@@ -29,7 +32,7 @@ pub struct AddLiquidity<'info> {
         bump,
         constraint = token_a_vault.key() == pool.token_a_vault @ AmmError::InvalidVault
     )]
-    pub token_a_vault: Account<'info, TokenAccount>,
+    pub token_a_vault: InterfaceAccount<'info, token_interface::TokenAccount>,
 
     /// Token B vault
     #[account(
@@ -38,7 +41,7 @@ pub struct AddLiquidity<'info> {
         bump,
         constraint = token_b_vault.key() == pool.token_b_vault @ AmmError::InvalidVault
     )]
-    pub token_b_vault: Account<'info, TokenAccount>,
+    pub token_b_vault: InterfaceAccount<'info, token_interface::TokenAccount>,
 
     /// LP mint
     #[account(
@@ -50,10 +53,12 @@ pub struct AddLiquidity<'info> {
     pub lp_mint: Account<'info, Mint>,
 
     /// LP mint authority
-    /// CHECK: PDA used as mint authority
+    /// CHECK: PDA used as mint authority; must also be the LP mint's authority
     #[account(
         seeds = [LP_MINT_AUTHORITY_SEED, pool.key().as_ref()],
-        bump = pool.lp_mint_authority_bump
+        bump = pool.lp_mint_authority_bump,
+        constraint = lp_mint.mint_authority == COption::Some(lp_mint_authority.key())
+            @ AmmError::InvalidMintAuthority
     )]
     pub lp_mint_authority: UncheckedAccount<'info>,
 
@@ -62,14 +67,14 @@ pub struct AddLiquidity<'info> {
         mut,
         constraint = user_token_a.mint == pool.token_a_mint @ AmmError::InvalidTokenMint
     )]
-    pub user_token_a: Account<'info, TokenAccount>,
+    pub user_token_a: InterfaceAccount<'info, token_interface::TokenAccount>,
 
     /// User's token B account
     #[account(
         mut,
         constraint = user_token_b.mint == pool.token_b_mint @ AmmError::InvalidTokenMint
     )]
-    pub user_token_b: Account<'info, TokenAccount>,
+    pub user_token_b: InterfaceAccount<'info, token_interface::TokenAccount>,
 
     /// User's LP token account
     #[account(
@@ -78,20 +83,203 @@ pub struct AddLiquidity<'info> {
     )]
     pub user_lp_token: Account<'info, TokenAccount>,
 
+    /// LP token account receiving the locked MINIMUM_LIQUIDITY on the first
+    /// deposit. Must be owned by the incinerator (burned) or by the pool's
+    /// `[LOCKED_LP_SEED, pool]` PDA (held, attributable, never withdrawable).
+    #[account(
+        mut,
+        constraint = locked_lp_token.mint == pool.lp_mint @ AmmError::InvalidTokenMint,
+        constraint = is_permanent_lock_owner(&locked_lp_token.owner, &pool.key())
+            @ AmmError::InvalidLockedLiquidityRecipient,
+        constraint = locked_lp_token.delegate.is_none() @ AmmError::InvalidLockedLiquidityRecipient,
+        constraint = locked_lp_token.close_authority.is_none() @ AmmError::InvalidLockedLiquidityRecipient
+    )]
+    pub locked_lp_token: Option<Account<'info, TokenAccount>>,
+
+    /// LP owner's position, recording the deposit slot for the hold window
+    #[account(
+        mut,
+        seeds = [POSITION_SEED, pool.key().as_ref(), user_lp_token.owner.as_ref()],
+        bump = position.bump
+    )]
+    pub position: Option<Account<'info, Position>>,
+
+    /// Token owner's signed terms, required (and consumed) by a delegated
+    /// deposit; its rent pays the relayer
+    #[account(
+        mut,
+        close = user,
+        seeds = [DEPOSIT_INTENT_SEED, pool.key().as_ref(), user_token_a.owner.as_ref()],
+        bump = deposit_intent.bump
+    )]
+    pub deposit_intent: Option<Account<'info, DepositIntent>>,
+
+    /// Token A mint
+    #[account(
+        constraint = token_a_mint.key() == pool.token_a_mint @ AmmError::InvalidTokenMint
+    )]
+    pub token_a_mint: Box<InterfaceAccount<'info, token_interface::Mint>>,
+
+    /// Token B mint
+    #[account(
+        constraint = token_b_mint.key() == pool.token_b_mint @ AmmError::InvalidTokenMint
+    )]
+    pub token_b_mint: Box<InterfaceAccount<'info, token_interface::Mint>>,
+
+    /// Token program for token A
+    #[account(
+        constraint = token_a_program.key() == pool.token_program_a() @ AmmError::InvalidTokenProgram
+    )]
+    pub token_a_program: Interface<'info, TokenInterface>,
+
+    /// Token program for token B
+    #[account(
+        constraint = token_b_program.key() == pool.token_program_b() @ AmmError::InvalidTokenProgram
+    )]
+    pub token_b_program: Interface<'info, TokenInterface>,
+
+    /// SPL Token program (LP mint)
     pub token_program: Program<'info, Token>,
 }
 
+/// Whether LP held by `owner` can never move again: the incinerator, or the
+/// pool's locked-LP PDA, which no instruction signs for. Any other owner
+/// could be a second wallet of the depositor, who would then redeem the
+/// "locked" shares and reopen the empty-pool inflation attack.
+pub fn is_permanent_lock_owner(owner: &Pubkey, pool: &Pubkey) -> bool {
+    *owner == incinerator::ID
+        || *owner == Pubkey::find_program_address(&[LOCKED_LP_SEED, pool.as_ref()], &crate::ID).0
+}
+
+/// Check that `pool` may pull `amount` from `account` as its SPL delegate
+fn validate_delegation(account: &token_interface::TokenAccount, pool: Pubkey, amount: u64) -> Result<()> {
+    require!(account.delegate == COption::Some(pool), AmmError::InvalidDelegate);
+    require!(account.delegated_amount >= amount, AmmError::InvalidDelegate);
+    Ok(())
+}
+
 pub fn handler(
     ctx: Context<AddLiquidity>,
     amount_a: u64,
     amount_b: u64,
     min_liquidity: u64,
-) -> Result<()> {
+    deadline: i64,
+) -> Result<u64> {
+    log_compute_units!("add_liquidity: start");
     require!(amount_a > 0 && amount_b > 0, AmmError::AmountTooSmall);
+    let clock = Clock::get()?;
+    require!(clock.unix_timestamp <= deadline, AmmError::DeadlineExceeded);
 
     let pool = &ctx.accounts.pool;
     let total_supply = ctx.accounts.lp_mint.supply;
 
+    require!(!pool.deposits_frozen, AmmError::DepositsFrozen);
+    // Deposits always take both tokens, so either side's flag blocks them
+    require!(
+        !pool.is_token_paused(PAUSE_DEPOSIT_A | PAUSE_DEPOSIT_B),
+        AmmError::TokenPaused
+    );
+
+    // Delegated (permit-style) deposit: the signer is a relayer executing
+    // the token owner's `DepositIntent`. The delegate approval alone is not
+    // enough, since anyone could then choose the amounts and slippage: the
+    // owner's exact amounts, minimum LP and deadline bind the relayer, and
+    // LP goes back to the owner.
+    let owner = ctx.accounts.user_token_a.owner;
+    let delegated = owner != ctx.accounts.user.key();
+    let mut min_liquidity = min_liquidity;
+    if delegated {
+        let intent = ctx
+            .accounts
+            .deposit_intent
+            .as_ref()
+            .ok_or(AmmError::DepositIntentRequired)?;
+        require!(
+            amount_a == intent.amount_a && amount_b == intent.amount_b,
+            AmmError::DepositIntentMismatch
+        );
+        require!(clock.unix_timestamp <= intent.deadline, AmmError::DeadlineExceeded);
+        min_liquidity = min_liquidity.max(intent.min_liquidity);
+
+        let pool_key = ctx.accounts.pool.key();
+        validate_delegation(&ctx.accounts.user_token_a, pool_key, amount_a)?;
+        validate_delegation(&ctx.accounts.user_token_b, pool_key, amount_b)?;
+        require!(ctx.accounts.user_token_b.owner == owner, AmmError::InvalidDelegate);
+        require!(ctx.accounts.user_lp_token.owner == owner, AmmError::InvalidDelegate);
+    }
+
+    let token_a_mint = ctx.accounts.pool.token_a_mint;
+    let token_b_mint = ctx.accounts.pool.token_b_mint;
+    let pool_seeds = &[
+        POOL_SEED,
+        token_a_mint.as_ref(),
+        token_b_mint.as_ref(),
+        &[ctx.accounts.pool.bump],
+    ];
+    let pool_signer_seeds = &[&pool_seeds[..]];
+
+    let transfer_authority = if delegated {
+        ctx.accounts.pool.to_account_info()
+    } else {
+        ctx.accounts.user.to_account_info()
+    };
+
+    let vault_a_before = ctx.accounts.token_a_vault.amount;
+    let vault_b_before = ctx.accounts.token_b_vault.amount;
+
+    // Transfer token A from user to vault
+    token_interface::transfer_checked(
+        CpiContext::new_with_signer(
+            ctx.accounts.token_a_program.to_account_info(),
+            TransferChecked {
+                from: ctx.accounts.user_token_a.to_account_info(),
+                mint: ctx.accounts.token_a_mint.to_account_info(),
+                to: ctx.accounts.token_a_vault.to_account_info(),
+                authority: transfer_authority.clone(),
+            },
+            pool_signer_seeds,
+        ),
+        amount_a,
+        ctx.accounts.token_a_mint.decimals,
+    )?;
+
+    // Transfer token B from user to vault
+    token_interface::transfer_checked(
+        CpiContext::new_with_signer(
+            ctx.accounts.token_b_program.to_account_info(),
+            TransferChecked {
+                from: ctx.accounts.user_token_b.to_account_info(),
+                mint: ctx.accounts.token_b_mint.to_account_info(),
+                to: ctx.accounts.token_b_vault.to_account_info(),
+                authority: transfer_authority,
+            },
+            pool_signer_seeds,
+        ),
+        amount_b,
+        ctx.accounts.token_b_mint.decimals,
+    )?;
+
+    // Credit what actually arrived: transfer-fee (Token-2022) mints deliver
+    // less than was sent, and crediting the requested amounts would leave
+    // reserves above the vault balances (see `Pool::validate_reserves`)
+    ctx.accounts.token_a_vault.reload()?;
+    ctx.accounts.token_b_vault.reload()?;
+    let amount_a = ctx
+        .accounts
+        .token_a_vault
+        .amount
+        .checked_sub(vault_a_before)
+        .ok_or(AmmError::MathOverflow)?;
+    let amount_b = ctx
+        .accounts
+        .token_b_vault
+        .amount
+        .checked_sub(vault_b_before)
+        .ok_or(AmmError::MathOverflow)?;
+    require!(amount_a > 0 && amount_b > 0, AmmError::AmountTooSmall);
+
+    let pool = &ctx.accounts.pool;
+
     // Calculate liquidity to mint
     let liquidity = if !pool.is_initialized() {
         // First deposit - use geometric mean
@@ -115,32 +303,6 @@ pub fn handler(
 
     require!(liquidity >= min_liquidity, AmmError::SlippageExceeded);
 
-    // Transfer token A from user to vault
-    token::transfer(
-        CpiContext::new(
-            ctx.accounts.token_program.to_account_info(),
-            Transfer {
-                from: ctx.accounts.user_token_a.to_account_info(),
-                to: ctx.accounts.token_a_vault.to_account_info(),
-                authority: ctx.accounts.user.to_account_info(),
-            },
-        ),
-        amount_a,
-    )?;
-
-    // Transfer token B from user to vault
-    token::transfer(
-        CpiContext::new(
-            ctx.accounts.token_program.to_account_info(),
-            Transfer {
-                from: ctx.accounts.user_token_b.to_account_info(),
-                to: ctx.accounts.token_b_vault.to_account_info(),
-                authority: ctx.accounts.user.to_account_info(),
-            },
-        ),
-        amount_b,
-    )?;
-
     // Mint LP tokens to user
     let pool_key = ctx.accounts.pool.key();
     let seeds = &[
@@ -150,20 +312,30 @@ pub fn handler(
     ];
     let signer_seeds = &[&seeds[..]];
 
-    // For first deposit, mint MINIMUM_LIQUIDITY to pool (locked forever)
+    // For first deposit, mint MINIMUM_LIQUIDITY to the lock recipient (locked forever)
+    let mut locked_liquidity_recipient = Pubkey::default();
     if !ctx.accounts.pool.is_initialized() {
+        let locked_lp_token = ctx
+            .accounts
+            .locked_lp_token
+            .as_ref()
+            .ok_or(AmmError::InvalidLockedLiquidityRecipient)?;
+        locked_liquidity_recipient = locked_lp_token.owner;
+
         token::mint_to(
             CpiContext::new_with_signer(
                 ctx.accounts.token_program.to_account_info(),
                 MintTo {
                     mint: ctx.accounts.lp_mint.to_account_info(),
-                    to: ctx.accounts.token_a_vault.to_account_info(), // Burn to vault
+                    to: locked_lp_token.to_account_info(),
                     authority: ctx.accounts.lp_mint_authority.to_account_info(),
                 },
                 signer_seeds,
             ),
             MINIMUM_LIQUIDITY,
         )?;
+
+        ctx.accounts.pool.locked_lp_recipient = locked_liquidity_recipient;
     }
 
     // Mint LP tokens to user
@@ -182,9 +354,57 @@ pub fn handler(
 
     // Update pool reserves
     let pool = &mut ctx.accounts.pool;
+    pool.accumulate_prices(clock.unix_timestamp);
+    let (old_reserve_a, old_reserve_b) = (pool.reserve_a, pool.reserve_b);
     pool.reserve_a = pool.reserve_a.checked_add(amount_a).ok_or(AmmError::MathOverflow)?;
     pool.reserve_b = pool.reserve_b.checked_add(amount_b).ok_or(AmmError::MathOverflow)?;
-    pool.last_update_slot = Clock::get()?.slot;
+
+    // Existing LP must not be diluted (trivially true on the first deposit)
+    verify_lp_value(
+        old_reserve_a,
+        old_reserve_b,
+        total_supply,
+        pool.reserve_a,
+        pool.reserve_b,
+        total_supply + liquidity,
+    )?;
+    require!(
+        pool.within_reserve_cap(pool.reserve_a, pool.reserve_b),
+        AmmError::ReserveCapExceeded
+    );
+
+    // Keep the pool's composition inside the configured band
+    require!(pool.ratio_in_band(pool.price_a()), AmmError::RatioOutOfBand);
+    pool.last_update_slot = clock.slot;
+    pool.last_deposit_slot = clock.slot;
+
+    // Start the LP hold window and track entry for this owner
+    if let Some(position) = ctx.accounts.position.as_mut() {
+        // Only the owner (directly or through their intent) moves the hold
+        // window. A third party funding a held position could otherwise
+        // reset it indefinitely, or pair with the owner to cycle liquidity
+        // within one slot.
+        let owner_signed = delegated || position.owner == ctx.accounts.user.key();
+        require!(owner_signed || pool.min_lp_hold_slots == 0, AmmError::Unauthorized);
+        let hold_slot = if owner_signed {
+            pool.last_update_slot
+        } else {
+            position.last_deposit_slot
+        };
+
+        // Supply after this deposit, including the first deposit's locked LP
+        let locked = if total_supply == 0 { MINIMUM_LIQUIDITY } else { 0 };
+        let new_supply = total_supply + liquidity + locked;
+        position.checkpoint_fees(pool);
+        position.record_deposit(
+            hold_slot,
+            liquidity,
+            pool.price_a(),
+            lp_share_growth(pool.reserve_a, pool.reserve_b, new_supply)?,
+        );
+    } else {
+        require!(pool.min_lp_hold_slots == 0, AmmError::PositionRequired);
+    }
 
     emit!(LiquidityAdded {
         pool: pool.key(),
@@ -192,12 +412,14 @@ pub fn handler(
         amount_a,
         amount_b,
         liquidity_minted: liquidity,
+        locked_liquidity_recipient,
         reserve_a: pool.reserve_a,
         reserve_b: pool.reserve_b,
-        timestamp: Clock::get()?.unix_timestamp,
+        timestamp: clock.unix_timestamp,
     });
 
     msg!("Added liquidity: {} A, {} B, minted {} LP", amount_a, amount_b, liquidity);
 
-    Ok(())
+    log_compute_units!("add_liquidity: end");
+    Ok(liquidity)
 }
@@ -0,0 +1,56 @@
+use anchor_lang::prelude::*;
+
+use crate::errors::AmmError;
+use crate::instructions::add_liquidity::{self, AddLiquidity};
+use crate::math::calculate_balanced_deposit;
+
+/// Amounts `add_liquidity_balanced` requested and the LP it minted
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
+pub struct BalancedDeposit {
+    pub amount_a: u64,
+    pub amount_b: u64,
+    pub liquidity: u64,
+}
+
+/// Deposit up to `amount` of one token plus exactly the matching amount of
+/// the other, so `calculate_liquidity_to_mint` wastes neither side.
+///
+/// Uses the regular `AddLiquidity` accounts and runs `add_liquidity` with
+/// the balanced amounts; the pool must already have liquidity. Fails with
+/// `SlippageExceeded` if the counter-amount exceeds `max_other_amount`.
+pub fn handler(
+    ctx: Context<AddLiquidity>,
+    amount: u64,
+    base_is_a: bool,
+    max_other_amount: u64,
+    deadline: i64,
+) -> Result<BalancedDeposit> {
+    let pool = &ctx.accounts.pool;
+    require!(pool.is_initialized(), AmmError::PoolNotInitialized);
+
+    let (reserve_base, reserve_other) = if base_is_a {
+        (pool.reserve_a, pool.reserve_b)
+    } else {
+        (pool.reserve_b, pool.reserve_a)
+    };
+    let (used_base, used_other, liquidity) = calculate_balanced_deposit(
+        amount,
+        reserve_base,
+        reserve_other,
+        ctx.accounts.lp_mint.supply,
+    )?;
+    require!(used_other <= max_other_amount, AmmError::SlippageExceeded);
+
+    let (amount_a, amount_b) = if base_is_a {
+        (used_base, used_other)
+    } else {
+        (used_other, used_base)
+    };
+    let liquidity = add_liquidity::handler(ctx, amount_a, amount_b, liquidity, deadline)?;
+
+    Ok(BalancedDeposit {
+        amount_a,
+        amount_b,
+        liquidity,
+    })
+}
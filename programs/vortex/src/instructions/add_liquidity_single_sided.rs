@@ -0,0 +1,298 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::program_option::COption;
+use anchor_spl::token::{self, Mint, Token, TokenAccount, MintTo};
+use anchor_spl::token_interface::{self, TokenInterface, TransferChecked};
+
+use crate::state::{Pool, Position};
+use crate::constants::*;
+use crate::errors::AmmError;
+use crate::events::LiquidityAdded;
+use crate::math::{
+    calculate_liquidity_to_mint, calculate_swap_output, lp_share_growth,
+    optimal_swap_amount_for_zap, Curve,
+};
+
+#[derive(Accounts)]
+pub struct AddLiquiditySingleSided<'info> {
+    /// Liquidity provider
+    pub user: Signer<'info>,
+
+    /// Pool state
+    #[account(
+        mut,
+        seeds = [POOL_SEED, pool.token_a_mint.as_ref(), pool.token_b_mint.as_ref()],
+        bump = pool.bump,
+        constraint = !pool.paused @ AmmError::PoolPaused
+    )]
+    pub pool: Account<'info, Pool>,
+
+    /// Token A vault
+    #[account(
+        mut,
+        constraint = token_a_vault.key() == pool.token_a_vault @ AmmError::InvalidVault
+    )]
+    pub token_a_vault: InterfaceAccount<'info, token_interface::TokenAccount>,
+
+    /// Token B vault
+    #[account(
+        mut,
+        constraint = token_b_vault.key() == pool.token_b_vault @ AmmError::InvalidVault
+    )]
+    pub token_b_vault: InterfaceAccount<'info, token_interface::TokenAccount>,
+
+    /// LP mint
+    #[account(
+        mut,
+        constraint = lp_mint.key() == pool.lp_mint @ AmmError::InvalidVault
+    )]
+    pub lp_mint: Account<'info, Mint>,
+
+    /// LP mint authority
+    /// CHECK: PDA used as mint authority; must also be the LP mint's authority
+    #[account(
+        seeds = [LP_MINT_AUTHORITY_SEED, pool.key().as_ref()],
+        bump = pool.lp_mint_authority_bump,
+        constraint = lp_mint.mint_authority == COption::Some(lp_mint_authority.key())
+            @ AmmError::InvalidMintAuthority
+    )]
+    pub lp_mint_authority: UncheckedAccount<'info>,
+
+    /// User's account for the deposited token (token A or token B)
+    #[account(
+        mut,
+        constraint = user_token_in.mint == pool.token_a_mint
+            || user_token_in.mint == pool.token_b_mint @ AmmError::InvalidTokenMint
+    )]
+    pub user_token_in: InterfaceAccount<'info, token_interface::TokenAccount>,
+
+    /// User's LP token account
+    #[account(
+        mut,
+        constraint = user_lp_token.mint == pool.lp_mint @ AmmError::InvalidTokenMint
+    )]
+    pub user_lp_token: Account<'info, TokenAccount>,
+
+    /// User's position, recording the deposit slot for the hold window
+    #[account(
+        mut,
+        seeds = [POSITION_SEED, pool.key().as_ref(), user.key().as_ref()],
+        bump = position.bump
+    )]
+    pub position: Option<Account<'info, Position>>,
+
+    /// Mint of the deposited token
+    #[account(
+        constraint = mint_in.key() == user_token_in.mint @ AmmError::InvalidTokenMint
+    )]
+    pub mint_in: Box<InterfaceAccount<'info, token_interface::Mint>>,
+
+    /// Token program for the deposited token
+    pub token_program_in: Interface<'info, TokenInterface>,
+
+    /// SPL Token program (LP mint)
+    pub token_program: Program<'info, Token>,
+}
+
+/// Add liquidity from a single token (zap-in).
+///
+/// Part of the deposit is swapped against the pool for the other token, sized
+/// by `optimal_swap_amount_for_zap` so both halves land in the post-swap
+/// ratio, and LP is minted for the balanced pair. The swapped tokens never
+/// leave the vaults, so the only reserve changes are the deposit itself and
+/// the protocol's share of the swap fee. Unpaired rounding dust stays in the
+/// reserves. Returns the LP minted.
+pub fn handler(
+    ctx: Context<AddLiquiditySingleSided>,
+    amount_in: u64,
+    min_liquidity: u64,
+    deadline: i64,
+) -> Result<u64> {
+    require!(amount_in > 0, AmmError::AmountTooSmall);
+
+    let clock = Clock::get()?;
+    require!(clock.unix_timestamp <= deadline, AmmError::DeadlineExceeded);
+
+    let pool = &ctx.accounts.pool;
+    require!(pool.is_initialized(), AmmError::PoolNotInitialized);
+    require!(
+        !pool.oracle_priced && pool.curve() == Curve::ConstantProduct,
+        AmmError::SingleSidedUnsupported
+    );
+
+    let deposit_a = ctx.accounts.user_token_in.mint == pool.token_a_mint;
+    let (program_in, swap_pause_flag) = if deposit_a {
+        (pool.token_program_a(), PAUSE_SWAP_A_IN)
+    } else {
+        (pool.token_program_b(), PAUSE_SWAP_B_IN)
+    };
+    require!(
+        ctx.accounts.token_program_in.key() == program_in,
+        AmmError::InvalidTokenProgram
+    );
+
+    require!(!pool.deposits_frozen, AmmError::DepositsFrozen);
+    // A zap is both a deposit and a swap of the input token
+    require!(
+        !pool.is_token_paused(PAUSE_DEPOSIT_A | PAUSE_DEPOSIT_B | swap_pause_flag),
+        AmmError::TokenPaused
+    );
+
+    // Transfer the deposit, crediting what actually arrived (see `add_liquidity`)
+    let vault_in_before = if deposit_a {
+        ctx.accounts.token_a_vault.amount
+    } else {
+        ctx.accounts.token_b_vault.amount
+    };
+    let vault_in = if deposit_a {
+        ctx.accounts.token_a_vault.to_account_info()
+    } else {
+        ctx.accounts.token_b_vault.to_account_info()
+    };
+    token_interface::transfer_checked(
+        CpiContext::new(
+            ctx.accounts.token_program_in.to_account_info(),
+            TransferChecked {
+                from: ctx.accounts.user_token_in.to_account_info(),
+                mint: ctx.accounts.mint_in.to_account_info(),
+                to: vault_in,
+                authority: ctx.accounts.user.to_account_info(),
+            },
+        ),
+        amount_in,
+        ctx.accounts.mint_in.decimals,
+    )?;
+
+    let vault_in_after = if deposit_a {
+        ctx.accounts.token_a_vault.reload()?;
+        ctx.accounts.token_a_vault.amount
+    } else {
+        ctx.accounts.token_b_vault.reload()?;
+        ctx.accounts.token_b_vault.amount
+    };
+    let received = vault_in_after
+        .checked_sub(vault_in_before)
+        .ok_or(AmmError::MathOverflow)?;
+
+    let total_supply = ctx.accounts.lp_mint.supply;
+    let pool = &mut ctx.accounts.pool;
+    pool.accumulate_prices(clock.unix_timestamp);
+
+    let (reserve_in, reserve_out) = if deposit_a {
+        (pool.reserve_a, pool.reserve_b)
+    } else {
+        (pool.reserve_b, pool.reserve_a)
+    };
+
+    // Internal swap of the optimal share of the deposit
+    let fee_numerator = pool.swap_fee_numerator(deposit_a);
+    let swap_amount =
+        optimal_swap_amount_for_zap(received, reserve_in, fee_numerator, pool.fee_denominator)?;
+    let (swap_out, fee_amount, protocol_fee) = calculate_swap_output(
+        swap_amount,
+        reserve_in,
+        reserve_out,
+        fee_numerator,
+        pool.fee_denominator,
+        pool.protocol_fee_numerator,
+        pool.protocol_fee_denominator,
+        pool.curve(),
+    )?;
+    let swapped_reserve_in = reserve_in
+        .checked_add(swap_amount - protocol_fee)
+        .ok_or(AmmError::MathOverflow)?;
+    let swapped_reserve_out = reserve_out - swap_out;
+
+    // Deposit the remainder and the swap output at the post-swap ratio
+    let paired_in = received - swap_amount;
+    let (amount_a, amount_b, reserve_a, reserve_b) = if deposit_a {
+        (paired_in, swap_out, swapped_reserve_in, swapped_reserve_out)
+    } else {
+        (swap_out, paired_in, swapped_reserve_out, swapped_reserve_in)
+    };
+    let liquidity =
+        calculate_liquidity_to_mint(amount_a, amount_b, reserve_a, reserve_b, total_supply)?;
+    require!(liquidity > 0, AmmError::InsufficientLiquidityMinted);
+    require!(liquidity >= min_liquidity, AmmError::SlippageExceeded);
+
+    // Net effect on the books: the whole deposit less the protocol fee joins
+    // the input reserve; the output reserve is unchanged
+    let new_reserve_in = reserve_in
+        .checked_add(received - protocol_fee)
+        .ok_or(AmmError::MathOverflow)?;
+    require!(
+        pool.within_reserve_cap(new_reserve_in, reserve_out),
+        AmmError::ReserveCapExceeded
+    );
+    let lp_fee = fee_amount - protocol_fee;
+    if deposit_a {
+        pool.update_reserves(new_reserve_in, reserve_out);
+        pool.protocol_fees_a = pool.protocol_fees_a.checked_add(protocol_fee).ok_or(AmmError::MathOverflow)?;
+        pool.cumulative_fees_a = pool.cumulative_fees_a.saturating_add(lp_fee);
+    } else {
+        pool.update_reserves(reserve_out, new_reserve_in);
+        pool.protocol_fees_b = pool.protocol_fees_b.checked_add(protocol_fee).ok_or(AmmError::MathOverflow)?;
+        pool.cumulative_fees_b = pool.cumulative_fees_b.saturating_add(lp_fee);
+    }
+
+    require!(pool.ratio_in_band(pool.price_a()), AmmError::RatioOutOfBand);
+    pool.last_update_slot = clock.slot;
+    pool.last_deposit_slot = clock.slot;
+
+    // The internal swap's LP fee is earned by the LP existing before this deposit
+    if deposit_a {
+        pool.accrue_fee_growth(lp_fee, 0, total_supply);
+    } else {
+        pool.accrue_fee_growth(0, lp_fee, total_supply);
+    }
+
+    if let Some(position) = ctx.accounts.position.as_mut() {
+        position.checkpoint_fees(pool);
+        position.record_deposit(
+            pool.last_update_slot,
+            liquidity,
+            pool.price_a(),
+            lp_share_growth(pool.reserve_a, pool.reserve_b, total_supply + liquidity)?,
+        );
+    } else {
+        require!(pool.min_lp_hold_slots == 0, AmmError::PositionRequired);
+    }
+
+    // Mint LP tokens to user
+    let pool_key = pool.key();
+    let seeds = &[
+        LP_MINT_AUTHORITY_SEED,
+        pool_key.as_ref(),
+        &[pool.lp_mint_authority_bump],
+    ];
+    let signer_seeds = &[&seeds[..]];
+
+    token::mint_to(
+        CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            MintTo {
+                mint: ctx.accounts.lp_mint.to_account_info(),
+                to: ctx.accounts.user_lp_token.to_account_info(),
+                authority: ctx.accounts.lp_mint_authority.to_account_info(),
+            },
+            signer_seeds,
+        ),
+        liquidity,
+    )?;
+
+    let (deposited_a, deposited_b) = if deposit_a { (received, 0) } else { (0, received) };
+    emit!(LiquidityAdded {
+        pool: pool_key,
+        user: ctx.accounts.user.key(),
+        amount_a: deposited_a,
+        amount_b: deposited_b,
+        liquidity_minted: liquidity,
+        locked_liquidity_recipient: Pubkey::default(),
+        reserve_a: ctx.accounts.pool.reserve_a,
+        reserve_b: ctx.accounts.pool.reserve_b,
+        timestamp: clock.unix_timestamp,
+    });
+
+    msg!("Added single-sided liquidity: {} in, {} swapped, minted {} LP", received, swap_amount, liquidity);
+
+    Ok(liquidity)
+}
@@ -0,0 +1,425 @@
+use anchor_lang::prelude::*;
+
+use crate::state::{Pool, ProtocolConfig};
+use crate::constants::*;
+use crate::errors::AmmError;
+use crate::events::{FeeRecipientUpdated, FeeUpdated, PoolPaused, PoolUnpaused};
+
+/// Accounts shared by authority-gated pool configuration instructions
+#[derive(Accounts)]
+pub struct PoolAdmin<'info> {
+    /// Pool authority
+    pub authority: Signer<'info>,
+
+    /// Pool state
+    #[account(
+        mut,
+        seeds = [POOL_SEED, pool.token_a_mint.as_ref(), pool.token_b_mint.as_ref()],
+        bump = pool.bump,
+        constraint = pool.authority == authority.key() @ AmmError::Unauthorized
+    )]
+    pub pool: Account<'info, Pool>,
+}
+
+/// Accounts for redirecting a pool's protocol fees; protocol governance
+/// signs, since the fees are the protocol's rather than the pool creator's
+#[derive(Accounts)]
+pub struct SetFeeRecipient<'info> {
+    /// Protocol governance
+    pub governance: Signer<'info>,
+
+    /// Protocol config
+    #[account(
+        seeds = [PROTOCOL_CONFIG_SEED],
+        bump = protocol_config.bump,
+        constraint = protocol_config.governance == governance.key() @ AmmError::Unauthorized
+    )]
+    pub protocol_config: Account<'info, ProtocolConfig>,
+
+    /// Pool state
+    #[account(
+        mut,
+        seeds = [POOL_SEED, pool.token_a_mint.as_ref(), pool.token_b_mint.as_ref()],
+        bump = pool.bump
+    )]
+    pub pool: Account<'info, Pool>,
+}
+
+/// Accounts for pausing a pool; the authority or the guardian may sign
+#[derive(Accounts)]
+pub struct PausePool<'info> {
+    /// Pool authority or guardian
+    pub signer: Signer<'info>,
+
+    /// Pool state
+    #[account(
+        mut,
+        seeds = [POOL_SEED, pool.token_a_mint.as_ref(), pool.token_b_mint.as_ref()],
+        bump = pool.bump,
+        constraint = pool.can_pause(&signer.key()) @ AmmError::Unauthorized
+    )]
+    pub pool: Account<'info, Pool>,
+}
+
+/// Pause swaps and deposits. A guardian may pause but only the authority
+/// can unpause, so a leaked guardian key can at worst halt the pool.
+///
+/// `remove_liquidity` deliberately has no pause check: LPs can always exit
+/// a paused pool, which is what halting during an exploit should protect.
+pub fn pause_pool(ctx: Context<PausePool>) -> Result<()> {
+    let pool = &mut ctx.accounts.pool;
+    pool.paused = true;
+
+    emit!(PoolPaused {
+        pool: pool.key(),
+        authority: ctx.accounts.signer.key(),
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+/// Resume a paused pool (authority only)
+pub fn unpause_pool(ctx: Context<PoolAdmin>) -> Result<()> {
+    let pool = &mut ctx.accounts.pool;
+    pool.paused = false;
+    pool.unwinding = false;
+
+    emit!(PoolUnpaused {
+        pool: pool.key(),
+        authority: ctx.accounts.authority.key(),
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+/// Mark a paused pool as unwinding, opening `emergency_withdraw` to LPs.
+/// Cleared again by `unpause_pool`.
+pub fn begin_unwind(ctx: Context<PoolAdmin>) -> Result<()> {
+    let pool = &mut ctx.accounts.pool;
+    require!(pool.paused, AmmError::PoolNotUnwinding);
+    pool.unwinding = true;
+
+    msg!("Pool unwinding; emergency withdrawals enabled");
+
+    Ok(())
+}
+
+/// Change the swap fee. Takes effect from the next swap and makes the fee
+/// symmetric again, overriding any directional fees.
+pub fn update_fee(ctx: Context<PoolAdmin>, fee_numerator: u64, fee_denominator: u64) -> Result<()> {
+    let pool = &mut ctx.accounts.pool;
+    validate_fee_split(
+        fee_numerator,
+        fee_denominator,
+        pool.protocol_fee_numerator,
+        pool.protocol_fee_denominator,
+    )?;
+
+    let old_fee_numerator = pool.fee_numerator;
+    let old_fee_denominator = pool.fee_denominator;
+    pool.fee_numerator = fee_numerator;
+    pool.fee_numerator_b_to_a = fee_numerator;
+    pool.fee_denominator = fee_denominator;
+
+    emit!(FeeUpdated {
+        pool: pool.key(),
+        authority: ctx.accounts.authority.key(),
+        old_fee_numerator,
+        old_fee_denominator,
+        new_fee_numerator: fee_numerator,
+        new_fee_denominator: fee_denominator,
+        fee_bps: pool.fee_bps(),
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+/// Charge different fees on A->B and B->A swaps, over the current fee
+/// denominator. Each direction must be a valid fee on its own.
+pub fn set_directional_fees(
+    ctx: Context<PoolAdmin>,
+    fee_numerator_a_to_b: u64,
+    fee_numerator_b_to_a: u64,
+) -> Result<()> {
+    let pool = &mut ctx.accounts.pool;
+    for fee_numerator in [fee_numerator_a_to_b, fee_numerator_b_to_a] {
+        validate_fee_split(
+            fee_numerator,
+            pool.fee_denominator,
+            pool.protocol_fee_numerator,
+            pool.protocol_fee_denominator,
+        )?;
+    }
+
+    pool.fee_numerator = fee_numerator_a_to_b;
+    pool.fee_numerator_b_to_a = fee_numerator_b_to_a;
+
+    msg!(
+        "Directional fees set: A->B {}/{}, B->A {}/{}",
+        fee_numerator_a_to_b,
+        pool.fee_denominator,
+        fee_numerator_b_to_a,
+        pool.fee_denominator
+    );
+
+    Ok(())
+}
+
+/// Set the protocol's share of each swap fee (numerator / denominator of
+/// the fee, 0 numerator disables)
+pub fn set_protocol_fee(
+    ctx: Context<PoolAdmin>,
+    protocol_fee_numerator: u64,
+    protocol_fee_denominator: u64,
+) -> Result<()> {
+    let pool = &mut ctx.accounts.pool;
+    // The LPs' remaining share must still be a valid fee in both directions
+    for a_to_b in [true, false] {
+        validate_fee_split(
+            pool.swap_fee_numerator(a_to_b),
+            pool.fee_denominator,
+            protocol_fee_numerator,
+            protocol_fee_denominator,
+        )?;
+    }
+
+    pool.protocol_fee_numerator = protocol_fee_numerator;
+    pool.protocol_fee_denominator = protocol_fee_denominator;
+
+    msg!("Protocol fee set to {}/{} of the swap fee", protocol_fee_numerator, protocol_fee_denominator);
+
+    Ok(())
+}
+
+/// Set the pause-only guardian. `Pubkey::default()` removes it.
+pub fn set_guardian(ctx: Context<PoolAdmin>, guardian: Pubkey) -> Result<()> {
+    let pool = &mut ctx.accounts.pool;
+    pool.guardian = guardian;
+
+    msg!("Guardian set to {}", guardian);
+
+    Ok(())
+}
+
+/// Set the market-maker program whose CPIs into `swap` are fee-exempt.
+/// `Pubkey::default()` disables the exemption.
+pub fn set_exempt_program(ctx: Context<PoolAdmin>, exempt_program: Pubkey) -> Result<()> {
+    let pool = &mut ctx.accounts.pool;
+    pool.exempt_program = exempt_program;
+
+    msg!("Fee-exempt program set to {}", exempt_program);
+
+    Ok(())
+}
+
+/// Set the owner of the accounts protocol fees are collected to. Pools
+/// start out paying the treasury (the protocol config PDA).
+pub fn set_fee_recipient(ctx: Context<SetFeeRecipient>, fee_recipient: Pubkey) -> Result<()> {
+    require!(fee_recipient != Pubkey::default(), AmmError::InvalidFeeRecipient);
+
+    let pool = &mut ctx.accounts.pool;
+    let old_fee_recipient = pool.fee_recipient;
+    pool.fee_recipient = fee_recipient;
+
+    emit!(FeeRecipientUpdated {
+        pool: pool.key(),
+        governance: ctx.accounts.governance.key(),
+        old_fee_recipient,
+        new_fee_recipient: fee_recipient,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+/// Cap either reserve at `max_reserve` (0 = unlimited). Deposits and swaps
+/// that would push a reserve above it fail; existing reserves are untouched.
+pub fn set_max_reserve(ctx: Context<PoolAdmin>, max_reserve: u64) -> Result<()> {
+    let pool = &mut ctx.accounts.pool;
+    pool.max_reserve = max_reserve;
+
+    msg!("Max reserve set to {}", max_reserve);
+
+    Ok(())
+}
+
+/// Floor either reserve at `min_reserve` (0 = off). Partial withdrawals
+/// that would leave a reserve below it fail; burning one's whole LP balance
+/// or whole position is always allowed.
+pub fn set_min_reserve(ctx: Context<PoolAdmin>, min_reserve: u64) -> Result<()> {
+    let pool = &mut ctx.accounts.pool;
+    pool.min_reserve = min_reserve;
+
+    msg!("Min reserve set to {}", min_reserve);
+
+    Ok(())
+}
+
+/// Set the per-user swap cooldown in slots (0 = off). Swappers then need a
+/// `UserSwapState` record.
+pub fn set_swap_cooldown(ctx: Context<PoolAdmin>, min_slot_between_swaps: u64) -> Result<()> {
+    let pool = &mut ctx.accounts.pool;
+    pool.min_slot_between_swaps = min_slot_between_swaps;
+
+    msg!("Swap cooldown set to {} slots", min_slot_between_swaps);
+
+    Ok(())
+}
+
+/// Set the flat liquidity withdrawal fee paid to the treasury
+pub fn set_withdraw_fee(ctx: Context<PoolAdmin>, withdraw_fee_bps: u16) -> Result<()> {
+    require!(withdraw_fee_bps <= MAX_WITHDRAW_FEE_BPS, AmmError::FeeTooHigh);
+
+    let pool = &mut ctx.accounts.pool;
+    pool.withdraw_fee_bps = withdraw_fee_bps;
+
+    msg!("Withdraw fee set to {} bps", withdraw_fee_bps);
+
+    Ok(())
+}
+
+/// Switch between curve pricing and oracle pricing with a spread
+pub fn set_oracle_mode(
+    ctx: Context<PoolAdmin>,
+    oracle_priced: bool,
+    oracle_spread_bps: u16,
+    oracle_max_staleness: i64,
+) -> Result<()> {
+    let pool = &mut ctx.accounts.pool;
+
+    require!(oracle_spread_bps as u64 <= MAX_FEE_BPS, AmmError::FeeTooHigh);
+    require!(oracle_max_staleness >= 0, AmmError::InvalidOraclePrice);
+    require!(
+        !oracle_priced || pool.oracle != Pubkey::default(),
+        AmmError::OracleRequired
+    );
+
+    pool.oracle_priced = oracle_priced;
+    pool.oracle_spread_bps = oracle_spread_bps;
+    pool.oracle_max_staleness = oracle_max_staleness;
+
+    msg!("Oracle pricing {}", if oracle_priced { "enabled" } else { "disabled" });
+
+    Ok(())
+}
+
+/// Set the granular per-token pause flags (see PAUSE_* constants)
+pub fn set_token_pause(ctx: Context<PoolAdmin>, pause_flags: u8) -> Result<()> {
+    require!(pause_flags & !PAUSE_FLAGS_MASK == 0, AmmError::InvalidPauseFlags);
+
+    let pool = &mut ctx.accounts.pool;
+    pool.pause_flags = pause_flags;
+
+    msg!("Token pause flags set to {:#06b}", pause_flags);
+
+    Ok(())
+}
+
+/// Configure the surcharge on the first swap after reserves go stale
+pub fn set_staleness_surcharge(
+    ctx: Context<PoolAdmin>,
+    stale_after_slots: u64,
+    stale_surcharge_bps: u16,
+) -> Result<()> {
+    require!(stale_surcharge_bps as u64 <= MAX_FEE_BPS, AmmError::FeeTooHigh);
+
+    let pool = &mut ctx.accounts.pool;
+    pool.stale_after_slots = stale_after_slots;
+    pool.stale_surcharge_bps = stale_surcharge_bps;
+
+    msg!(
+        "Staleness surcharge set to {} bps after {} slots",
+        stale_surcharge_bps,
+        stale_after_slots
+    );
+
+    Ok(())
+}
+
+/// Set the reserve-ratio band enforced on deposits (Q64, 0 disables a bound)
+pub fn set_ratio_band(ctx: Context<PoolAdmin>, min_ratio: u128, max_ratio: u128) -> Result<()> {
+    require!(
+        min_ratio == 0 || max_ratio == 0 || min_ratio <= max_ratio,
+        AmmError::RatioOutOfBand
+    );
+
+    let pool = &mut ctx.accounts.pool;
+    pool.min_ratio = min_ratio;
+    pool.max_ratio = max_ratio;
+
+    msg!("Ratio band set to [{}, {}]", min_ratio, max_ratio);
+
+    Ok(())
+}
+
+/// Configure the LP-token fee rebate for swappers and its budget
+pub fn set_fee_rebate(ctx: Context<PoolAdmin>, rebate_bps: u16, rebate_budget: u64) -> Result<()> {
+    require!(rebate_bps as u64 <= BPS_DENOMINATOR, AmmError::InvalidFeeParameters);
+
+    let pool = &mut ctx.accounts.pool;
+    pool.rebate_bps = rebate_bps;
+    pool.rebate_budget = rebate_budget;
+
+    msg!("Fee rebate set to {} bps, budget {} LP", rebate_bps, rebate_budget);
+
+    Ok(())
+}
+
+/// Set the minimum number of slots LP must be held before removal (0 disables)
+pub fn set_min_lp_hold_slots(ctx: Context<PoolAdmin>, min_lp_hold_slots: u64) -> Result<()> {
+    let pool = &mut ctx.accounts.pool;
+    pool.min_lp_hold_slots = min_lp_hold_slots;
+
+    msg!("LP hold window set to {} slots", min_lp_hold_slots);
+
+    Ok(())
+}
+
+/// Set how much per-swap event data the pool emits
+pub fn set_event_verbosity(ctx: Context<PoolAdmin>, event_verbosity: u8) -> Result<()> {
+    require!(event_verbosity <= EVENT_VERBOSITY_NONE, AmmError::InvalidEventVerbosity);
+
+    let pool = &mut ctx.accounts.pool;
+    pool.event_verbosity = event_verbosity;
+
+    msg!("Event verbosity set to {}", event_verbosity);
+
+    Ok(())
+}
+
+/// Toggle strict reserve reconciliation on swaps
+pub fn set_strict_reserves(ctx: Context<PoolAdmin>, strict_reserves: bool) -> Result<()> {
+    let pool = &mut ctx.accounts.pool;
+    pool.strict_reserves = strict_reserves;
+
+    msg!("Strict reserves set to {}", strict_reserves);
+
+    Ok(())
+}
+
+/// Freeze or unfreeze new liquidity, leaving swaps and withdrawals open
+pub fn set_deposits_frozen(ctx: Context<PoolAdmin>, deposits_frozen: bool) -> Result<()> {
+    let pool = &mut ctx.accounts.pool;
+    pool.deposits_frozen = deposits_frozen;
+
+    msg!("Deposits frozen set to {}", deposits_frozen);
+
+    Ok(())
+}
+
+/// Set the pool's minimum swap input
+pub fn set_min_swap_amount(ctx: Context<PoolAdmin>, min_swap_amount: u64) -> Result<()> {
+    let pool = &mut ctx.accounts.pool;
+    require!(
+        min_swap_amount > 0 && min_swap_amount <= pool.max_min_swap_amount(),
+        AmmError::InvalidMinSwapAmount
+    );
+    pool.min_swap_amount = min_swap_amount;
+
+    msg!("Minimum swap amount set to {}", min_swap_amount);
+
+    Ok(())
+}
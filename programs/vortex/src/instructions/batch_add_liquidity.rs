@@ -0,0 +1,83 @@
+use std::collections::BTreeSet;
+
+use anchor_lang::prelude::*;
+
+use crate::errors::AmmError;
+use crate::instructions::add_liquidity::{self, AddLiquidity, AddLiquidityBumps};
+
+#[derive(Accounts)]
+pub struct BatchAddLiquidity<'info> {
+    /// Liquidity provider; must also be the `user` of every deposit
+    pub user: Signer<'info>,
+}
+
+/// One pool's deposit within a batch
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
+pub struct BatchDeposit {
+    pub amount_a: u64,
+    pub amount_b: u64,
+    /// Minimum LP tokens for this pool's deposit
+    pub min_liquidity: u64,
+}
+
+/// Add liquidity to several pools atomically.
+///
+/// `remaining_accounts` holds one full `AddLiquidity` account list per
+/// entry of `deposits`, in the same order and in `AddLiquidity` field order:
+/// user, pool, token_a_vault, token_b_vault, lp_mint, lp_mint_authority,
+/// user_token_a, user_token_b, user_lp_token, locked_lp_token, position,
+/// deposit_intent, token_a_mint, token_b_mint, token_a_program,
+/// token_b_program, token_program. Unused optional accounts are passed as
+/// this program's id. Each deposit runs the regular handler, so every
+/// per-pool check applies (a delegated entry needs its owner's
+/// `DepositIntent`) and emits its own `LiquidityAdded`; the first failing
+/// entry is logged by index and fails the whole batch.
+pub fn handler<'info>(
+    ctx: Context<'_, '_, 'info, 'info, BatchAddLiquidity<'info>>,
+    deposits: Vec<BatchDeposit>,
+    deadline: i64,
+) -> Result<()> {
+    require!(!deposits.is_empty(), AmmError::InvalidBatch);
+
+    let mut accounts: &'info [AccountInfo<'info>] = ctx.remaining_accounts;
+    let user = ctx.accounts.user.key();
+    let mut total_liquidity: u64 = 0;
+
+    for (index, deposit) in deposits.iter().enumerate() {
+        let liquidity = add_to_pool(ctx.program_id, &mut accounts, user, deposit, deadline)
+            .inspect_err(|_| msg!("Batch deposit {} failed", index))?;
+        total_liquidity = total_liquidity.saturating_add(liquidity);
+    }
+
+    require!(accounts.is_empty(), AmmError::InvalidBatch);
+
+    msg!("Added liquidity to {} pools for {} LP tokens", deposits.len(), total_liquidity);
+
+    Ok(())
+}
+
+/// Parse the next `AddLiquidity` account list and run one deposit
+fn add_to_pool<'info>(
+    program_id: &Pubkey,
+    accounts: &mut &'info [AccountInfo<'info>],
+    user: Pubkey,
+    deposit: &BatchDeposit,
+    deadline: i64,
+) -> Result<u64> {
+    let mut bumps = AddLiquidityBumps::default();
+    let mut deposit_accounts =
+        AddLiquidity::try_accounts(program_id, accounts, &[], &mut bumps, &mut BTreeSet::new())?;
+    require_keys_eq!(deposit_accounts.user.key(), user, AmmError::Unauthorized);
+
+    let liquidity = add_liquidity::handler(
+        Context::new(program_id, &mut deposit_accounts, &[], bumps),
+        deposit.amount_a,
+        deposit.amount_b,
+        deposit.min_liquidity,
+        deadline,
+    )?;
+    // Persist this pool before a later entry can load it again
+    deposit_accounts.exit(program_id)?;
+
+    Ok(liquidity)
+}
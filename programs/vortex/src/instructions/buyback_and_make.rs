@@ -0,0 +1,207 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::program_option::COption;
+use anchor_spl::token::{self, Mint, MintTo, Token, TokenAccount};
+
+use crate::state::{Pool, ProtocolConfig};
+use crate::constants::*;
+use crate::errors::AmmError;
+use crate::events::ProtocolLiquidityAdded;
+use crate::math::{
+    calculate_liquidity_to_mint, calculate_swap_output, optimal_swap_amount_for_zap,
+    pair_in_ratio, verify_lp_value, Curve,
+};
+
+#[derive(Accounts)]
+pub struct BuybackAndMake<'info> {
+    /// Protocol governance
+    pub governance: Signer<'info>,
+
+    /// Protocol config
+    #[account(
+        seeds = [PROTOCOL_CONFIG_SEED],
+        bump = protocol_config.bump,
+        constraint = protocol_config.governance == governance.key() @ AmmError::Unauthorized
+    )]
+    pub protocol_config: Account<'info, ProtocolConfig>,
+
+    /// Pool state
+    #[account(
+        mut,
+        seeds = [POOL_SEED, pool.token_a_mint.as_ref(), pool.token_b_mint.as_ref()],
+        bump = pool.bump,
+        constraint = !pool.paused @ AmmError::PoolPaused
+    )]
+    pub pool: Account<'info, Pool>,
+
+    /// LP mint
+    #[account(
+        mut,
+        constraint = lp_mint.key() == pool.lp_mint @ AmmError::InvalidVault
+    )]
+    pub lp_mint: Account<'info, Mint>,
+
+    /// LP mint authority
+    /// CHECK: PDA used as mint authority; must also be the LP mint's authority
+    #[account(
+        seeds = [LP_MINT_AUTHORITY_SEED, pool.key().as_ref()],
+        bump = pool.lp_mint_authority_bump,
+        constraint = lp_mint.mint_authority == COption::Some(lp_mint_authority.key())
+            @ AmmError::InvalidMintAuthority
+    )]
+    pub lp_mint_authority: UncheckedAccount<'info>,
+
+    /// Protocol treasury vault for the pool's LP mint, receiving the LP
+    #[account(
+        mut,
+        seeds = [TREASURY_SEED, lp_mint.key().as_ref()],
+        bump
+    )]
+    pub treasury_lp: Account<'info, TokenAccount>,
+
+    /// SPL Token program (LP mint)
+    pub token_program: Program<'info, Token>,
+}
+
+/// Turn the pool's accrued protocol fees into protocol-owned liquidity.
+///
+/// The fees already sit in the vaults, outside the reserves, so nothing is
+/// transferred: the part in the pool's ratio joins the reserves as a
+/// balanced deposit, and the excess side is zapped in by an internal swap
+/// sized by `optimal_swap_amount_for_zap`, as in
+/// `add_liquidity_single_sided`. LP for both is minted to the LP mint's
+/// treasury vault. The protocol does not take a share of its own swap's
+/// fee; the LP fee accrues to all LPs, the treasury included.
+///
+/// On pools the zap does not support (oracle pricing, StableSwap) or whose
+/// excess side has swaps paused, the excess stays accrued. Governance
+/// signs, since the deposit and zap are priced at the current spot and a
+/// permissionless caller could skew the pool around them.
+pub fn handler(ctx: Context<BuybackAndMake>, min_liquidity: u64) -> Result<u64> {
+    let clock = Clock::get()?;
+    let total_supply = ctx.accounts.lp_mint.supply;
+    let pool = &mut ctx.accounts.pool;
+    require!(pool.is_initialized(), AmmError::PoolNotInitialized);
+    require!(!pool.deposits_frozen, AmmError::DepositsFrozen);
+    require!(
+        !pool.is_token_paused(PAUSE_DEPOSIT_A | PAUSE_DEPOSIT_B),
+        AmmError::TokenPaused
+    );
+
+    let (fees_a, fees_b) = (pool.protocol_fees_a, pool.protocol_fees_b);
+    require!(fees_a > 0 || fees_b > 0, AmmError::NoProtocolFees);
+    pool.accumulate_prices(clock.unix_timestamp);
+    let (old_reserve_a, old_reserve_b) = (pool.reserve_a, pool.reserve_b);
+
+    // Largest amounts in the pool's current ratio
+    let (paired_a, paired_b) = pair_in_ratio(fees_a, fees_b, pool.reserve_a, pool.reserve_b)?;
+    let mut liquidity = if paired_a > 0 && paired_b > 0 {
+        calculate_liquidity_to_mint(paired_a, paired_b, pool.reserve_a, pool.reserve_b, total_supply)?
+    } else {
+        0
+    };
+    let (mut used_a, mut used_b) = if liquidity > 0 { (paired_a, paired_b) } else { (0, 0) };
+    let mut reserve_a = pool.reserve_a + used_a;
+    let mut reserve_b = pool.reserve_b + used_b;
+    let mut supply = total_supply + liquidity;
+
+    // Zap the unpaired side
+    let excess_is_a = fees_a - used_a > 0;
+    let excess = if excess_is_a { fees_a - used_a } else { fees_b - used_b };
+    let swap_pause_flag = if excess_is_a { PAUSE_SWAP_A_IN } else { PAUSE_SWAP_B_IN };
+    let zap_supported = !pool.oracle_priced
+        && pool.curve() == Curve::ConstantProduct
+        && !pool.is_token_paused(swap_pause_flag);
+    if excess > 0 && zap_supported {
+        let (reserve_in, reserve_out) = if excess_is_a {
+            (reserve_a, reserve_b)
+        } else {
+            (reserve_b, reserve_a)
+        };
+        let fee_numerator = pool.swap_fee_numerator(excess_is_a);
+        let swap_amount =
+            optimal_swap_amount_for_zap(excess, reserve_in, fee_numerator, pool.fee_denominator)?;
+        let (swap_out, lp_fee, _) = calculate_swap_output(
+            swap_amount,
+            reserve_in,
+            reserve_out,
+            fee_numerator,
+            pool.fee_denominator,
+            0,
+            0,
+            pool.curve(),
+        )?;
+        let paired_in = excess - swap_amount;
+        let (zap_a, zap_b, swapped_a, swapped_b) = if excess_is_a {
+            (paired_in, swap_out, reserve_in + swap_amount, reserve_out - swap_out)
+        } else {
+            (swap_out, paired_in, reserve_out - swap_out, reserve_in + swap_amount)
+        };
+        let zap_liquidity = if zap_a > 0 && zap_b > 0 {
+            calculate_liquidity_to_mint(zap_a, zap_b, swapped_a, swapped_b, supply)?
+        } else {
+            0
+        };
+
+        if zap_liquidity > 0 {
+            // The swap's LP fee is earned by the LP existing before the zap
+            if excess_is_a {
+                pool.accrue_fee_growth(lp_fee, 0, supply);
+                pool.cumulative_fees_a = pool.cumulative_fees_a.saturating_add(lp_fee);
+                used_a += excess;
+                reserve_a += excess;
+            } else {
+                pool.accrue_fee_growth(0, lp_fee, supply);
+                pool.cumulative_fees_b = pool.cumulative_fees_b.saturating_add(lp_fee);
+                used_b += excess;
+                reserve_b += excess;
+            }
+            liquidity += zap_liquidity;
+            supply += zap_liquidity;
+        }
+    }
+
+    require!(liquidity > 0, AmmError::InsufficientLiquidityMinted);
+    require!(liquidity >= min_liquidity, AmmError::SlippageExceeded);
+    require!(pool.within_reserve_cap(reserve_a, reserve_b), AmmError::ReserveCapExceeded);
+
+    pool.update_reserves(reserve_a, reserve_b);
+    pool.protocol_fees_a = fees_a - used_a;
+    pool.protocol_fees_b = fees_b - used_b;
+    verify_lp_value(old_reserve_a, old_reserve_b, total_supply, reserve_a, reserve_b, supply)?;
+    require!(pool.ratio_in_band(pool.price_a()), AmmError::RatioOutOfBand);
+    pool.last_update_slot = clock.slot;
+
+    let pool_key = pool.key();
+    let seeds = &[
+        LP_MINT_AUTHORITY_SEED,
+        pool_key.as_ref(),
+        &[pool.lp_mint_authority_bump],
+    ];
+    token::mint_to(
+        CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            MintTo {
+                mint: ctx.accounts.lp_mint.to_account_info(),
+                to: ctx.accounts.treasury_lp.to_account_info(),
+                authority: ctx.accounts.lp_mint_authority.to_account_info(),
+            },
+            &[&seeds[..]],
+        ),
+        liquidity,
+    )?;
+
+    emit!(ProtocolLiquidityAdded {
+        pool: pool_key,
+        governance: ctx.accounts.governance.key(),
+        amount_a: used_a,
+        amount_b: used_b,
+        liquidity_minted: liquidity,
+        reserve_a,
+        reserve_b,
+        timestamp: clock.unix_timestamp,
+    });
+
+    msg!("Bought back {} A, {} B of protocol fees as {} LP", used_a, used_b, liquidity);
+
+    Ok(liquidity)
+}
@@ -0,0 +1,56 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::TokenAccount;
+
+use crate::state::Pool;
+use crate::constants::*;
+use crate::errors::AmmError;
+
+#[derive(Accounts)]
+pub struct CheckReserves<'info> {
+    /// Pool state
+    #[account(
+        seeds = [POOL_SEED, pool.token_a_mint.as_ref(), pool.token_b_mint.as_ref()],
+        bump = pool.bump
+    )]
+    pub pool: Account<'info, Pool>,
+
+    /// Token A vault
+    #[account(
+        constraint = token_a_vault.key() == pool.token_a_vault @ AmmError::InvalidVault
+    )]
+    pub token_a_vault: InterfaceAccount<'info, TokenAccount>,
+
+    /// Token B vault
+    #[account(
+        constraint = token_b_vault.key() == pool.token_b_vault @ AmmError::InvalidVault
+    )]
+    pub token_b_vault: InterfaceAccount<'info, TokenAccount>,
+}
+
+/// Booked reserves next to the actual vault balances
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
+pub struct ReservesCheck {
+    pub reserve_a: u64,
+    pub reserve_b: u64,
+    pub vault_a_balance: u64,
+    pub vault_b_balance: u64,
+    /// Whether the vaults match reserves plus protocol fees, within
+    /// `RESERVE_TOLERANCE` (see `Pool::validate_reserves`)
+    pub in_sync: bool,
+}
+
+/// Report vault/reserve drift, e.g. from donations or transfer-fee mints,
+/// without touching state. `sync_reserves` reconciles a surplus.
+pub fn handler(ctx: Context<CheckReserves>) -> Result<ReservesCheck> {
+    let pool = &ctx.accounts.pool;
+    let vault_a_balance = ctx.accounts.token_a_vault.amount;
+    let vault_b_balance = ctx.accounts.token_b_vault.amount;
+
+    Ok(ReservesCheck {
+        reserve_a: pool.reserve_a,
+        reserve_b: pool.reserve_b,
+        vault_a_balance,
+        vault_b_balance,
+        in_sync: pool.validate_reserves(vault_a_balance, vault_b_balance),
+    })
+}
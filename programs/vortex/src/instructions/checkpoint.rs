@@ -0,0 +1,48 @@
+use anchor_lang::prelude::*;
+
+use crate::state::Pool;
+use crate::constants::*;
+use crate::errors::AmmError;
+use crate::events::ReserveSnapshot;
+
+#[derive(Accounts)]
+pub struct Checkpoint<'info> {
+    /// Pool state
+    #[account(
+        mut,
+        seeds = [POOL_SEED, pool.token_a_mint.as_ref(), pool.token_b_mint.as_ref()],
+        bump = pool.bump
+    )]
+    pub pool: Account<'info, Pool>,
+}
+
+/// Emit a reserve snapshot without touching reserves or stats.
+///
+/// Anyone may call this, at most once per `CHECKPOINT_INTERVAL_SLOTS`
+/// measured from the later of the last pool update and the last checkpoint.
+pub fn handler(ctx: Context<Checkpoint>) -> Result<()> {
+    let clock = Clock::get()?;
+    let pool = &mut ctx.accounts.pool;
+
+    require!(pool.is_initialized(), AmmError::PoolNotInitialized);
+
+    let last_slot = pool.last_update_slot.max(pool.last_checkpoint_slot);
+    require!(
+        clock.slot.saturating_sub(last_slot) >= CHECKPOINT_INTERVAL_SLOTS,
+        AmmError::CheckpointTooFrequent
+    );
+
+    pool.last_checkpoint_slot = clock.slot;
+
+    emit!(ReserveSnapshot {
+        pool: pool.key(),
+        reserve_a: pool.reserve_a,
+        reserve_b: pool.reserve_b,
+        price_a: pool.price_a(),
+        price_b: pool.price_b(),
+        slot: clock.slot,
+        timestamp: clock.unix_timestamp,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,191 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::Mint;
+use anchor_spl::token_interface::{self, CloseAccount, TokenInterface, TransferChecked};
+
+use crate::state::Pool;
+use crate::constants::*;
+use crate::errors::AmmError;
+use crate::events::{PoolClosed, TreasuryAccrued};
+
+#[derive(Accounts)]
+pub struct ClosePool<'info> {
+    /// Pool authority; receives the reclaimed rent
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    /// Pool state
+    #[account(
+        mut,
+        close = authority,
+        seeds = [POOL_SEED, pool.token_a_mint.as_ref(), pool.token_b_mint.as_ref()],
+        bump = pool.bump,
+        constraint = pool.authority == authority.key() @ AmmError::Unauthorized
+    )]
+    pub pool: Account<'info, Pool>,
+
+    /// Token A vault
+    #[account(
+        mut,
+        constraint = token_a_vault.key() == pool.token_a_vault @ AmmError::InvalidVault
+    )]
+    pub token_a_vault: InterfaceAccount<'info, token_interface::TokenAccount>,
+
+    /// Token B vault
+    #[account(
+        mut,
+        constraint = token_b_vault.key() == pool.token_b_vault @ AmmError::InvalidVault
+    )]
+    pub token_b_vault: InterfaceAccount<'info, token_interface::TokenAccount>,
+
+    /// LP mint
+    #[account(
+        constraint = lp_mint.key() == pool.lp_mint @ AmmError::InvalidVault
+    )]
+    pub lp_mint: Account<'info, Mint>,
+
+    /// Protocol treasury vault for token A; receives whatever the vault holds
+    #[account(
+        mut,
+        seeds = [TREASURY_SEED, pool.token_a_mint.as_ref()],
+        bump
+    )]
+    pub treasury_token_a: InterfaceAccount<'info, token_interface::TokenAccount>,
+
+    /// Protocol treasury vault for token B; receives whatever the vault holds
+    #[account(
+        mut,
+        seeds = [TREASURY_SEED, pool.token_b_mint.as_ref()],
+        bump
+    )]
+    pub treasury_token_b: InterfaceAccount<'info, token_interface::TokenAccount>,
+
+    /// Token A mint
+    #[account(
+        constraint = token_a_mint.key() == pool.token_a_mint @ AmmError::InvalidTokenMint
+    )]
+    pub token_a_mint: Box<InterfaceAccount<'info, token_interface::Mint>>,
+
+    /// Token B mint
+    #[account(
+        constraint = token_b_mint.key() == pool.token_b_mint @ AmmError::InvalidTokenMint
+    )]
+    pub token_b_mint: Box<InterfaceAccount<'info, token_interface::Mint>>,
+
+    /// Token program for token A
+    #[account(
+        constraint = token_a_program.key() == pool.token_program_a() @ AmmError::InvalidTokenProgram
+    )]
+    pub token_a_program: Interface<'info, TokenInterface>,
+
+    /// Token program for token B
+    #[account(
+        constraint = token_b_program.key() == pool.token_program_b() @ AmmError::InvalidTokenProgram
+    )]
+    pub token_b_program: Interface<'info, TokenInterface>,
+}
+
+/// Close an emptied pool and its vaults, returning their rent to the authority.
+///
+/// Only the locked MINIMUM_LIQUIDITY may remain outstanding. The dust
+/// reserves backing it, uncollected protocol fees and any donations are
+/// swept from the vaults to the protocol treasury before they are closed.
+///
+/// The legacy SPL LP mint cannot be closed and keeps its
+/// `[LP_MINT_SEED, pool]` address, so `initialize_pool` can never create a
+/// pool for the same pair again.
+pub fn handler(ctx: Context<ClosePool>) -> Result<()> {
+    require!(
+        ctx.accounts.lp_mint.supply <= MINIMUM_LIQUIDITY,
+        AmmError::PoolNotEmpty
+    );
+
+    let pool = &ctx.accounts.pool;
+    let seeds = &[
+        POOL_SEED,
+        pool.token_a_mint.as_ref(),
+        pool.token_b_mint.as_ref(),
+        &[pool.bump],
+    ];
+    let signer_seeds = &[&seeds[..]];
+    let timestamp = Clock::get()?.unix_timestamp;
+
+    let swept_a = ctx.accounts.token_a_vault.amount;
+    if swept_a > 0 {
+        token_interface::transfer_checked(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_a_program.to_account_info(),
+                TransferChecked {
+                    from: ctx.accounts.token_a_vault.to_account_info(),
+                    mint: ctx.accounts.token_a_mint.to_account_info(),
+                    to: ctx.accounts.treasury_token_a.to_account_info(),
+                    authority: ctx.accounts.pool.to_account_info(),
+                },
+                signer_seeds,
+            ),
+            swept_a,
+            ctx.accounts.token_a_mint.decimals,
+        )?;
+
+        emit!(TreasuryAccrued {
+            pool: ctx.accounts.pool.key(),
+            mint: ctx.accounts.pool.token_a_mint,
+            amount: swept_a,
+            timestamp,
+        });
+    }
+
+    let swept_b = ctx.accounts.token_b_vault.amount;
+    if swept_b > 0 {
+        token_interface::transfer_checked(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_b_program.to_account_info(),
+                TransferChecked {
+                    from: ctx.accounts.token_b_vault.to_account_info(),
+                    mint: ctx.accounts.token_b_mint.to_account_info(),
+                    to: ctx.accounts.treasury_token_b.to_account_info(),
+                    authority: ctx.accounts.pool.to_account_info(),
+                },
+                signer_seeds,
+            ),
+            swept_b,
+            ctx.accounts.token_b_mint.decimals,
+        )?;
+
+        emit!(TreasuryAccrued {
+            pool: ctx.accounts.pool.key(),
+            mint: ctx.accounts.pool.token_b_mint,
+            amount: swept_b,
+            timestamp,
+        });
+    }
+
+    token_interface::close_account(CpiContext::new_with_signer(
+        ctx.accounts.token_a_program.to_account_info(),
+        CloseAccount {
+            account: ctx.accounts.token_a_vault.to_account_info(),
+            destination: ctx.accounts.authority.to_account_info(),
+            authority: ctx.accounts.pool.to_account_info(),
+        },
+        signer_seeds,
+    ))?;
+
+    token_interface::close_account(CpiContext::new_with_signer(
+        ctx.accounts.token_b_program.to_account_info(),
+        CloseAccount {
+            account: ctx.accounts.token_b_vault.to_account_info(),
+            destination: ctx.accounts.authority.to_account_info(),
+            authority: ctx.accounts.pool.to_account_info(),
+        },
+        signer_seeds,
+    ))?;
+
+    emit!(PoolClosed {
+        pool: ctx.accounts.pool.key(),
+        authority: ctx.accounts.authority.key(),
+        timestamp,
+    });
+
+    msg!("Pool closed: {}", ctx.accounts.pool.key());
+
+    Ok(())
+}
@@ -0,0 +1,172 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{self, TokenInterface, TransferChecked};
+
+use crate::state::{Pool, ProtocolConfig};
+use crate::constants::*;
+use crate::errors::AmmError;
+use crate::events::{ProtocolFeesCollected, TreasuryAccrued};
+
+#[derive(Accounts)]
+pub struct CollectProtocolFees<'info> {
+    /// Pool authority
+    pub authority: Signer<'info>,
+
+    /// Pool state
+    #[account(
+        mut,
+        seeds = [POOL_SEED, pool.token_a_mint.as_ref(), pool.token_b_mint.as_ref()],
+        bump = pool.bump,
+        constraint = pool.authority == authority.key() @ AmmError::Unauthorized
+    )]
+    pub pool: Account<'info, Pool>,
+
+    /// Token A vault
+    #[account(
+        mut,
+        constraint = token_a_vault.key() == pool.token_a_vault @ AmmError::InvalidVault
+    )]
+    pub token_a_vault: InterfaceAccount<'info, token_interface::TokenAccount>,
+
+    /// Token B vault
+    #[account(
+        mut,
+        constraint = token_b_vault.key() == pool.token_b_vault @ AmmError::InvalidVault
+    )]
+    pub token_b_vault: InterfaceAccount<'info, token_interface::TokenAccount>,
+
+    /// Fee recipient's token A account (the token A treasury vault by default)
+    #[account(
+        mut,
+        constraint = recipient_token_a.mint == pool.token_a_mint @ AmmError::InvalidTokenMint,
+        constraint = is_fee_destination(&recipient_token_a, pool.fee_recipient)
+            @ AmmError::InvalidFeeRecipient
+    )]
+    pub recipient_token_a: InterfaceAccount<'info, token_interface::TokenAccount>,
+
+    /// Fee recipient's token B account (the token B treasury vault by default)
+    #[account(
+        mut,
+        constraint = recipient_token_b.mint == pool.token_b_mint @ AmmError::InvalidTokenMint,
+        constraint = is_fee_destination(&recipient_token_b, pool.fee_recipient)
+            @ AmmError::InvalidFeeRecipient
+    )]
+    pub recipient_token_b: InterfaceAccount<'info, token_interface::TokenAccount>,
+
+    /// Token A mint
+    #[account(
+        constraint = token_a_mint.key() == pool.token_a_mint @ AmmError::InvalidTokenMint
+    )]
+    pub token_a_mint: Box<InterfaceAccount<'info, token_interface::Mint>>,
+
+    /// Token B mint
+    #[account(
+        constraint = token_b_mint.key() == pool.token_b_mint @ AmmError::InvalidTokenMint
+    )]
+    pub token_b_mint: Box<InterfaceAccount<'info, token_interface::Mint>>,
+
+    /// Token program for token A
+    #[account(
+        constraint = token_a_program.key() == pool.token_program_a() @ AmmError::InvalidTokenProgram
+    )]
+    pub token_a_program: Interface<'info, TokenInterface>,
+
+    /// Token program for token B
+    #[account(
+        constraint = token_b_program.key() == pool.token_program_b() @ AmmError::InvalidTokenProgram
+    )]
+    pub token_b_program: Interface<'info, TokenInterface>,
+}
+
+/// Whether protocol fees may be paid into `account`: it must belong to the
+/// pool's fee recipient and, when that is the protocol config (the default),
+/// be the mint's treasury vault, the only account `withdraw_treasury` can
+/// sweep
+fn is_fee_destination(account: &InterfaceAccount<token_interface::TokenAccount>, fee_recipient: Pubkey) -> bool {
+    if account.owner != fee_recipient {
+        return false;
+    }
+    fee_recipient != ProtocolConfig::address()
+        || account.key() == Pubkey::find_program_address(&[TREASURY_SEED, account.mint.as_ref()], &crate::ID).0
+}
+
+/// Transfer the accrued protocol fees out of the vaults to the pool's
+/// `fee_recipient` (by default the protocol treasury) and reset the
+/// counters.
+///
+/// Protocol fees were never added to the reserves, so this leaves
+/// reserve_a/reserve_b (and the LPs' share) untouched.
+pub fn handler(ctx: Context<CollectProtocolFees>) -> Result<()> {
+    let pool = &ctx.accounts.pool;
+    let amount_a = pool.protocol_fees_a;
+    let amount_b = pool.protocol_fees_b;
+
+    let seeds = &[
+        POOL_SEED,
+        pool.token_a_mint.as_ref(),
+        pool.token_b_mint.as_ref(),
+        &[pool.bump],
+    ];
+    let signer_seeds = &[&seeds[..]];
+
+    if amount_a > 0 {
+        token_interface::transfer_checked(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_a_program.to_account_info(),
+                TransferChecked {
+                    from: ctx.accounts.token_a_vault.to_account_info(),
+                    mint: ctx.accounts.token_a_mint.to_account_info(),
+                    to: ctx.accounts.recipient_token_a.to_account_info(),
+                    authority: ctx.accounts.pool.to_account_info(),
+                },
+                signer_seeds,
+            ),
+            amount_a,
+            ctx.accounts.token_a_mint.decimals,
+        )?;
+    }
+
+    if amount_b > 0 {
+        token_interface::transfer_checked(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_b_program.to_account_info(),
+                TransferChecked {
+                    from: ctx.accounts.token_b_vault.to_account_info(),
+                    mint: ctx.accounts.token_b_mint.to_account_info(),
+                    to: ctx.accounts.recipient_token_b.to_account_info(),
+                    authority: ctx.accounts.pool.to_account_info(),
+                },
+                signer_seeds,
+            ),
+            amount_b,
+            ctx.accounts.token_b_mint.decimals,
+        )?;
+    }
+
+    let timestamp = Clock::get()?.unix_timestamp;
+    let pool = &mut ctx.accounts.pool;
+    pool.protocol_fees_a = 0;
+    pool.protocol_fees_b = 0;
+
+    emit!(ProtocolFeesCollected {
+        pool: pool.key(),
+        authority: ctx.accounts.authority.key(),
+        amount_a,
+        amount_b,
+        timestamp,
+    });
+
+    if pool.fee_recipient == ProtocolConfig::address() {
+        for (mint, amount) in [(pool.token_a_mint, amount_a), (pool.token_b_mint, amount_b)] {
+            if amount > 0 {
+                emit!(TreasuryAccrued {
+                    pool: pool.key(),
+                    mint,
+                    amount,
+                    timestamp,
+                });
+            }
+        }
+    }
+
+    Ok(())
+}
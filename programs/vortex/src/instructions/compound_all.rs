@@ -0,0 +1,62 @@
+use anchor_lang::prelude::*;
+
+use crate::state::{Pool, Position};
+use crate::constants::*;
+use crate::errors::AmmError;
+use crate::events::FeesCompounded;
+
+#[derive(Accounts)]
+pub struct CompoundAll<'info> {
+    /// Owner of every position compounded
+    pub owner: Signer<'info>,
+}
+
+/// Compound the owner's earned fees in several pools at once.
+///
+/// `remaining_accounts` holds (pool, position) pairs, at most
+/// `MAX_COMPOUND_POSITIONS` of them, with each position writable. Fees
+/// earned since the last compounding are read off the pool's fee growth
+/// index (`Pool::pending_fees`) and settled into the position; since LP
+/// fees are reinvested into the reserves when charged, the owner's LP
+/// already holds them and compounding moves no tokens. Positions with
+/// nothing earned are skipped rather than failing the batch, and each
+/// compounded one emits `FeesCompounded`.
+pub fn handler<'info>(ctx: Context<'_, '_, 'info, 'info, CompoundAll<'info>>) -> Result<()> {
+    let accounts = ctx.remaining_accounts;
+    require!(
+        !accounts.is_empty() && accounts.len().is_multiple_of(2),
+        AmmError::InvalidBatch
+    );
+    require!(accounts.len() / 2 <= MAX_COMPOUND_POSITIONS, AmmError::InvalidBatch);
+
+    let owner = ctx.accounts.owner.key();
+    let timestamp = Clock::get()?.unix_timestamp;
+    let mut compounded = 0;
+
+    for pair in accounts.chunks(2) {
+        let pool = Account::<Pool>::try_from(&pair[0])?;
+        let mut position = Account::<Position>::try_from(&pair[1])?;
+        require!(pair[1].is_writable, AmmError::InvalidPosition);
+        require_keys_eq!(position.pool, pool.key(), AmmError::InvalidPosition);
+        require_keys_eq!(position.owner, owner, AmmError::Unauthorized);
+
+        let Some((amount_a, amount_b)) = position.compound_fees(&pool) else {
+            continue;
+        };
+        position.exit(ctx.program_id)?;
+        compounded += 1;
+
+        emit!(FeesCompounded {
+            pool: pool.key(),
+            owner,
+            amount_a,
+            amount_b,
+            liquidity: position.liquidity,
+            timestamp,
+        });
+    }
+
+    msg!("Compounded fees in {} of {} positions", compounded, accounts.len() / 2);
+
+    Ok(())
+}
@@ -0,0 +1,47 @@
+use anchor_lang::prelude::*;
+
+use crate::errors::AmmError;
+use crate::math::{calculate_swap_output, Curve};
+
+#[derive(Accounts)]
+pub struct ComputeAmountOut {}
+
+/// Output of a hypothetical constant-product swap
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
+pub struct AmountOut {
+    pub amount_out: u64,
+    /// Fee charged on the input
+    pub fee_amount: u64,
+}
+
+/// Run the constant-product swap math against caller-supplied reserves.
+///
+/// Stateless: no pool is read, so bots can evaluate hypothetical states
+/// with exactly the math `swap` uses. The protocol fee split does not
+/// affect the output and is left out.
+pub fn handler(
+    _ctx: Context<ComputeAmountOut>,
+    amount_in: u64,
+    reserve_in: u64,
+    reserve_out: u64,
+    fee_numerator: u64,
+    fee_denominator: u64,
+) -> Result<AmountOut> {
+    require!(fee_numerator < fee_denominator, AmmError::InvalidFeeParameters);
+
+    let (amount_out, fee_amount, _) = calculate_swap_output(
+        amount_in,
+        reserve_in,
+        reserve_out,
+        fee_numerator,
+        fee_denominator,
+        0,
+        0,
+        Curve::ConstantProduct,
+    )?;
+
+    Ok(AmountOut {
+        amount_out,
+        fee_amount,
+    })
+}
@@ -0,0 +1,23 @@
+use anchor_lang::prelude::*;
+
+use crate::constants::*;
+use crate::errors::AmmError;
+use crate::instructions::quote_swap::{self, QuoteSwap};
+
+/// Minimum output to pass to `swap` for a given slippage tolerance.
+///
+/// Uses the `QuoteSwap` accounts. The expected output comes from the same
+/// quote the swap handler's curve math produces, and `slippage_bps` is taken
+/// off it rounding down, so a swap at unchanged reserves always clears the
+/// returned minimum while any adverse move beyond the tolerance does not.
+pub fn handler(ctx: Context<QuoteSwap>, amount_in: u64, a_to_b: bool, slippage_bps: u64) -> Result<u64> {
+    require!(slippage_bps <= BPS_DENOMINATOR, AmmError::InvalidSlippage);
+
+    let quote = quote_swap::handler(ctx, amount_in, a_to_b)?;
+    let min_amount_out = (quote.amount_out as u128)
+        .checked_mul((BPS_DENOMINATOR - slippage_bps) as u128)
+        .ok_or(AmmError::MathOverflow)?
+        / BPS_DENOMINATOR as u128;
+
+    Ok(min_amount_out as u64)
+}
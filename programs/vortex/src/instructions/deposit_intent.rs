@@ -0,0 +1,169 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{self, Approve, Revoke, TokenInterface};
+
+use crate::state::{DepositIntent, Pool};
+use crate::constants::*;
+use crate::errors::AmmError;
+
+#[derive(Accounts)]
+pub struct CreateDepositIntent<'info> {
+    /// Token owner and rent payer
+    #[account(mut)]
+    pub owner: Signer<'info>,
+
+    /// Pool state
+    #[account(
+        seeds = [POOL_SEED, pool.token_a_mint.as_ref(), pool.token_b_mint.as_ref()],
+        bump = pool.bump
+    )]
+    pub pool: Account<'info, Pool>,
+
+    /// Owner's deposit terms
+    #[account(
+        init,
+        payer = owner,
+        space = 8 + DepositIntent::INIT_SPACE,
+        seeds = [DEPOSIT_INTENT_SEED, pool.key().as_ref(), owner.key().as_ref()],
+        bump
+    )]
+    pub deposit_intent: Account<'info, DepositIntent>,
+
+    /// Owner's token A account, approved to the pool for `amount_a`
+    #[account(
+        mut,
+        constraint = user_token_a.mint == pool.token_a_mint @ AmmError::InvalidTokenMint,
+        constraint = user_token_a.owner == owner.key() @ AmmError::Unauthorized
+    )]
+    pub user_token_a: InterfaceAccount<'info, token_interface::TokenAccount>,
+
+    /// Owner's token B account, approved to the pool for `amount_b`
+    #[account(
+        mut,
+        constraint = user_token_b.mint == pool.token_b_mint @ AmmError::InvalidTokenMint,
+        constraint = user_token_b.owner == owner.key() @ AmmError::Unauthorized
+    )]
+    pub user_token_b: InterfaceAccount<'info, token_interface::TokenAccount>,
+
+    /// Token program for token A
+    #[account(
+        constraint = token_a_program.key() == pool.token_program_a() @ AmmError::InvalidTokenProgram
+    )]
+    pub token_a_program: Interface<'info, TokenInterface>,
+
+    /// Token program for token B
+    #[account(
+        constraint = token_b_program.key() == pool.token_program_b() @ AmmError::InvalidTokenProgram
+    )]
+    pub token_b_program: Interface<'info, TokenInterface>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct CancelDepositIntent<'info> {
+    /// Token owner; receives the intent's rent
+    #[account(mut)]
+    pub owner: Signer<'info>,
+
+    /// Owner's deposit terms
+    #[account(
+        mut,
+        close = owner,
+        seeds = [DEPOSIT_INTENT_SEED, deposit_intent.pool.as_ref(), owner.key().as_ref()],
+        bump = deposit_intent.bump
+    )]
+    pub deposit_intent: Account<'info, DepositIntent>,
+
+    /// Owner's token A account
+    #[account(
+        mut,
+        constraint = user_token_a.owner == owner.key() @ AmmError::Unauthorized
+    )]
+    pub user_token_a: InterfaceAccount<'info, token_interface::TokenAccount>,
+
+    /// Owner's token B account
+    #[account(
+        mut,
+        constraint = user_token_b.owner == owner.key() @ AmmError::Unauthorized
+    )]
+    pub user_token_b: InterfaceAccount<'info, token_interface::TokenAccount>,
+
+    /// Token program for token A
+    pub token_a_program: Interface<'info, TokenInterface>,
+
+    /// Token program for token B
+    pub token_b_program: Interface<'info, TokenInterface>,
+}
+
+/// Authorize a relayer to deposit exactly `amount_a`/`amount_b` for the
+/// owner, for at least `min_liquidity` LP and no later than `deadline`.
+///
+/// Approves the pool PDA as SPL delegate for exactly those amounts. The
+/// relayer's `add_liquidity` must match the terms and closes the intent,
+/// taking its rent as the relayer's fee; the owner can cancel it until then.
+pub fn create_deposit_intent(
+    ctx: Context<CreateDepositIntent>,
+    amount_a: u64,
+    amount_b: u64,
+    min_liquidity: u64,
+    deadline: i64,
+) -> Result<()> {
+    require!(amount_a > 0 && amount_b > 0, AmmError::AmountTooSmall);
+    require!(Clock::get()?.unix_timestamp <= deadline, AmmError::DeadlineExceeded);
+
+    token_interface::approve(
+        CpiContext::new(
+            ctx.accounts.token_a_program.to_account_info(),
+            Approve {
+                to: ctx.accounts.user_token_a.to_account_info(),
+                delegate: ctx.accounts.pool.to_account_info(),
+                authority: ctx.accounts.owner.to_account_info(),
+            },
+        ),
+        amount_a,
+    )?;
+    token_interface::approve(
+        CpiContext::new(
+            ctx.accounts.token_b_program.to_account_info(),
+            Approve {
+                to: ctx.accounts.user_token_b.to_account_info(),
+                delegate: ctx.accounts.pool.to_account_info(),
+                authority: ctx.accounts.owner.to_account_info(),
+            },
+        ),
+        amount_b,
+    )?;
+
+    let intent = &mut ctx.accounts.deposit_intent;
+    intent.bump = ctx.bumps.deposit_intent;
+    intent.pool = ctx.accounts.pool.key();
+    intent.owner = ctx.accounts.owner.key();
+    intent.amount_a = amount_a;
+    intent.amount_b = amount_b;
+    intent.min_liquidity = min_liquidity;
+    intent.deadline = deadline;
+
+    msg!("Deposit intent created: {} A, {} B", amount_a, amount_b);
+
+    Ok(())
+}
+
+/// Withdraw an unexecuted deposit intent and revoke the pool's approvals
+pub fn cancel_deposit_intent(ctx: Context<CancelDepositIntent>) -> Result<()> {
+    for (account, program) in [
+        (&ctx.accounts.user_token_a, &ctx.accounts.token_a_program),
+        (&ctx.accounts.user_token_b, &ctx.accounts.token_b_program),
+    ] {
+        token_interface::revoke(CpiContext::new(
+            program.to_account_info(),
+            Revoke {
+                source: account.to_account_info(),
+                authority: ctx.accounts.owner.to_account_info(),
+            },
+        ))?;
+    }
+
+    msg!("Deposit intent cancelled for {}", ctx.accounts.owner.key());
+
+    Ok(())
+}
@@ -0,0 +1,35 @@
+use anchor_lang::prelude::*;
+
+use crate::state::Pool;
+use crate::errors::AmmError;
+
+#[derive(Accounts)]
+pub struct DerivePoolForPair {}
+
+/// Pool address for a mint pair, with the canonical A/B assignment
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
+pub struct PoolAddress {
+    pub pool: Pubkey,
+    pub bump: u8,
+    pub token_a_mint: Pubkey,
+    pub token_b_mint: Pubkey,
+}
+
+/// Resolve the pool PDA for two mints given in either order
+pub fn handler(
+    _ctx: Context<DerivePoolForPair>,
+    mint_x: Pubkey,
+    mint_y: Pubkey,
+) -> Result<PoolAddress> {
+    require!(mint_x != mint_y, AmmError::IdenticalTokenMints);
+
+    let (token_a_mint, token_b_mint) = Pool::canonical_mints(mint_x, mint_y);
+    let (pool, bump) = Pool::find_address(&token_a_mint, &token_b_mint);
+
+    Ok(PoolAddress {
+        pool,
+        bump,
+        token_a_mint,
+        token_b_mint,
+    })
+}
@@ -0,0 +1,193 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{self, Burn, Mint, Token, TokenAccount};
+use anchor_spl::token_interface::{self, TokenInterface, TransferChecked};
+
+use crate::state::Pool;
+use crate::constants::*;
+use crate::errors::AmmError;
+use crate::events::LiquidityRemoved;
+use crate::math::calculate_amounts_for_liquidity;
+
+#[derive(Accounts)]
+pub struct EmergencyWithdraw<'info> {
+    /// Liquidity provider
+    pub user: Signer<'info>,
+
+    /// Pool state (must be paused and unwinding)
+    #[account(
+        mut,
+        seeds = [POOL_SEED, pool.token_a_mint.as_ref(), pool.token_b_mint.as_ref()],
+        bump = pool.bump,
+        constraint = pool.paused && pool.unwinding @ AmmError::PoolNotUnwinding
+    )]
+    pub pool: Account<'info, Pool>,
+
+    /// Token A vault
+    #[account(
+        mut,
+        constraint = token_a_vault.key() == pool.token_a_vault @ AmmError::InvalidVault
+    )]
+    pub token_a_vault: InterfaceAccount<'info, token_interface::TokenAccount>,
+
+    /// Token B vault
+    #[account(
+        mut,
+        constraint = token_b_vault.key() == pool.token_b_vault @ AmmError::InvalidVault
+    )]
+    pub token_b_vault: InterfaceAccount<'info, token_interface::TokenAccount>,
+
+    /// LP mint
+    #[account(
+        mut,
+        constraint = lp_mint.key() == pool.lp_mint @ AmmError::InvalidVault
+    )]
+    pub lp_mint: Account<'info, Mint>,
+
+    /// User's token A account
+    #[account(
+        mut,
+        constraint = user_token_a.mint == pool.token_a_mint @ AmmError::InvalidTokenMint
+    )]
+    pub user_token_a: InterfaceAccount<'info, token_interface::TokenAccount>,
+
+    /// User's token B account
+    #[account(
+        mut,
+        constraint = user_token_b.mint == pool.token_b_mint @ AmmError::InvalidTokenMint
+    )]
+    pub user_token_b: InterfaceAccount<'info, token_interface::TokenAccount>,
+
+    /// User's LP token account
+    #[account(
+        mut,
+        constraint = user_lp_token.mint == pool.lp_mint @ AmmError::InvalidTokenMint
+    )]
+    pub user_lp_token: Account<'info, TokenAccount>,
+
+    /// Token A mint
+    #[account(
+        constraint = token_a_mint.key() == pool.token_a_mint @ AmmError::InvalidTokenMint
+    )]
+    pub token_a_mint: Box<InterfaceAccount<'info, token_interface::Mint>>,
+
+    /// Token B mint
+    #[account(
+        constraint = token_b_mint.key() == pool.token_b_mint @ AmmError::InvalidTokenMint
+    )]
+    pub token_b_mint: Box<InterfaceAccount<'info, token_interface::Mint>>,
+
+    /// Token program for token A
+    #[account(
+        constraint = token_a_program.key() == pool.token_program_a() @ AmmError::InvalidTokenProgram
+    )]
+    pub token_a_program: Interface<'info, TokenInterface>,
+
+    /// Token program for token B
+    #[account(
+        constraint = token_b_program.key() == pool.token_program_b() @ AmmError::InvalidTokenProgram
+    )]
+    pub token_b_program: Interface<'info, TokenInterface>,
+
+    /// SPL Token program (LP mint)
+    pub token_program: Program<'info, Token>,
+}
+
+/// Exit a paused, unwinding pool with only the core checks.
+///
+/// Only available once the authority has paused the pool and marked it as
+/// unwinding. Skips every optional guard `remove_liquidity` applies (price
+/// band, LP hold window, withdrawal fee and its treasury accounts), so a
+/// dead oracle or misconfigured guard can never trap LP funds. Payouts are
+/// strictly proportional and bounded by the vault balances.
+///
+/// No oracle or volume bookkeeping is touched: a proportional exit leaves
+/// the reserve ratio, and so the accumulated prices, unchanged.
+pub fn handler(ctx: Context<EmergencyWithdraw>, liquidity_amount: u64) -> Result<()> {
+    require!(liquidity_amount > 0, AmmError::AmountTooSmall);
+
+    let pool = &ctx.accounts.pool;
+    let (amount_a, amount_b) = calculate_amounts_for_liquidity(
+        liquidity_amount,
+        pool.reserve_a,
+        pool.reserve_b,
+        ctx.accounts.lp_mint.supply,
+    )?;
+
+    require!(
+        amount_a <= ctx.accounts.token_a_vault.amount && amount_b <= ctx.accounts.token_b_vault.amount,
+        AmmError::VaultBalanceMismatch
+    );
+
+    token::burn(
+        CpiContext::new(
+            ctx.accounts.token_program.to_account_info(),
+            Burn {
+                mint: ctx.accounts.lp_mint.to_account_info(),
+                from: ctx.accounts.user_lp_token.to_account_info(),
+                authority: ctx.accounts.user.to_account_info(),
+            },
+        ),
+        liquidity_amount,
+    )?;
+
+    let seeds = &[
+        POOL_SEED,
+        pool.token_a_mint.as_ref(),
+        pool.token_b_mint.as_ref(),
+        &[pool.bump],
+    ];
+    let signer_seeds = &[&seeds[..]];
+
+    token_interface::transfer_checked(
+        CpiContext::new_with_signer(
+            ctx.accounts.token_a_program.to_account_info(),
+            TransferChecked {
+                from: ctx.accounts.token_a_vault.to_account_info(),
+                mint: ctx.accounts.token_a_mint.to_account_info(),
+                to: ctx.accounts.user_token_a.to_account_info(),
+                authority: ctx.accounts.pool.to_account_info(),
+            },
+            signer_seeds,
+        ),
+        amount_a,
+        ctx.accounts.token_a_mint.decimals,
+    )?;
+
+    token_interface::transfer_checked(
+        CpiContext::new_with_signer(
+            ctx.accounts.token_b_program.to_account_info(),
+            TransferChecked {
+                from: ctx.accounts.token_b_vault.to_account_info(),
+                mint: ctx.accounts.token_b_mint.to_account_info(),
+                to: ctx.accounts.user_token_b.to_account_info(),
+                authority: ctx.accounts.pool.to_account_info(),
+            },
+            signer_seeds,
+        ),
+        amount_b,
+        ctx.accounts.token_b_mint.decimals,
+    )?;
+
+    let clock = Clock::get()?;
+    let pool = &mut ctx.accounts.pool;
+    pool.reserve_a = pool.reserve_a.checked_sub(amount_a).ok_or(AmmError::MathOverflow)?;
+    pool.reserve_b = pool.reserve_b.checked_sub(amount_b).ok_or(AmmError::MathOverflow)?;
+    pool.last_update_slot = clock.slot;
+
+    emit!(LiquidityRemoved {
+        pool: pool.key(),
+        user: ctx.accounts.user.key(),
+        liquidity_burned: liquidity_amount,
+        amount_a,
+        amount_b,
+        withdraw_fee_a: 0,
+        withdraw_fee_b: 0,
+        reserve_a: pool.reserve_a,
+        reserve_b: pool.reserve_b,
+        timestamp: clock.unix_timestamp,
+    });
+
+    msg!("Emergency withdraw: burned {} LP, got {} A, {} B", liquidity_amount, amount_a, amount_b);
+
+    Ok(())
+}
@@ -0,0 +1,89 @@
+use anchor_lang::prelude::*;
+
+use crate::state::{FeeTier, Pool};
+use crate::constants::*;
+use crate::errors::AmmError;
+
+#[derive(Accounts)]
+#[instruction(user: Pubkey)]
+pub struct CreateFeeTier<'info> {
+    /// Pool authority and rent payer
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    /// Pool state
+    #[account(
+        seeds = [POOL_SEED, pool.token_a_mint.as_ref(), pool.token_b_mint.as_ref()],
+        bump = pool.bump,
+        constraint = pool.authority == authority.key() @ AmmError::Unauthorized
+    )]
+    pub pool: Account<'info, Pool>,
+
+    /// Trader's fee tier
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + FeeTier::INIT_SPACE,
+        seeds = [FEE_TIER_SEED, pool.key().as_ref(), user.as_ref()],
+        bump
+    )]
+    pub fee_tier: Account<'info, FeeTier>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct UpdateFeeTier<'info> {
+    /// Pool authority
+    pub authority: Signer<'info>,
+
+    /// Pool state
+    #[account(
+        seeds = [POOL_SEED, pool.token_a_mint.as_ref(), pool.token_b_mint.as_ref()],
+        bump = pool.bump,
+        constraint = pool.authority == authority.key() @ AmmError::Unauthorized
+    )]
+    pub pool: Account<'info, Pool>,
+
+    /// Trader's fee tier
+    #[account(
+        mut,
+        seeds = [FEE_TIER_SEED, pool.key().as_ref(), fee_tier.user.as_ref()],
+        bump = fee_tier.bump
+    )]
+    pub fee_tier: Account<'info, FeeTier>,
+}
+
+/// Discounts are in BPS of volume, so one larger than the pool fee would
+/// make the trader's fee negative
+fn validate_discount(pool: &Pool, discount_bps: u16) -> Result<()> {
+    require!(discount_bps as u64 <= pool.fee_bps(), AmmError::InvalidFeeDiscount);
+    Ok(())
+}
+
+/// Give `user` a swap fee discount on the pool
+pub fn create_fee_tier(ctx: Context<CreateFeeTier>, user: Pubkey, discount_bps: u16) -> Result<()> {
+    validate_discount(&ctx.accounts.pool, discount_bps)?;
+
+    let fee_tier = &mut ctx.accounts.fee_tier;
+    fee_tier.bump = ctx.bumps.fee_tier;
+    fee_tier.pool = ctx.accounts.pool.key();
+    fee_tier.user = user;
+    fee_tier.discount_bps = discount_bps;
+
+    msg!("Fee tier created for {}: {} bps off", user, discount_bps);
+
+    Ok(())
+}
+
+/// Change an existing trader's swap fee discount
+pub fn update_fee_tier(ctx: Context<UpdateFeeTier>, discount_bps: u16) -> Result<()> {
+    validate_discount(&ctx.accounts.pool, discount_bps)?;
+
+    let fee_tier = &mut ctx.accounts.fee_tier;
+    fee_tier.discount_bps = discount_bps;
+
+    msg!("Fee tier for {} set to {} bps off", fee_tier.user, discount_bps);
+
+    Ok(())
+}
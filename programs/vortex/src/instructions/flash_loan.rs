@@ -0,0 +1,205 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::instruction::{AccountMeta, Instruction};
+use anchor_lang::solana_program::program::invoke;
+use anchor_spl::token::Mint;
+use anchor_spl::token_interface::{self, TokenInterface, TransferChecked};
+
+use crate::state::Pool;
+use crate::constants::*;
+use crate::errors::AmmError;
+use crate::events::{FlashLoanRepaid, ReserveChangeSource, ReservesSynced};
+use crate::math::calculate_flash_fee;
+
+#[derive(Accounts)]
+pub struct FlashLoan<'info> {
+    /// Borrower
+    pub borrower: Signer<'info>,
+
+    /// Pool state
+    #[account(
+        mut,
+        seeds = [POOL_SEED, pool.token_a_mint.as_ref(), pool.token_b_mint.as_ref()],
+        bump = pool.bump,
+        constraint = !pool.paused @ AmmError::PoolPaused
+    )]
+    pub pool: Account<'info, Pool>,
+
+    /// LP mint; its supply scales the fee growth index
+    #[account(
+        constraint = lp_mint.key() == pool.lp_mint @ AmmError::InvalidVault
+    )]
+    pub lp_mint: Account<'info, Mint>,
+
+    /// Token A vault
+    #[account(
+        mut,
+        constraint = token_a_vault.key() == pool.token_a_vault @ AmmError::InvalidVault
+    )]
+    pub token_a_vault: InterfaceAccount<'info, token_interface::TokenAccount>,
+
+    /// Token B vault
+    #[account(
+        mut,
+        constraint = token_b_vault.key() == pool.token_b_vault @ AmmError::InvalidVault
+    )]
+    pub token_b_vault: InterfaceAccount<'info, token_interface::TokenAccount>,
+
+    /// Borrower's account for the borrowed token (token A or token B)
+    #[account(
+        mut,
+        constraint = borrower_token.mint == pool.token_a_mint
+            || borrower_token.mint == pool.token_b_mint @ AmmError::InvalidTokenMint
+    )]
+    pub borrower_token: InterfaceAccount<'info, token_interface::TokenAccount>,
+
+    /// Mint of the borrowed token
+    #[account(
+        constraint = mint.key() == borrower_token.mint @ AmmError::InvalidTokenMint
+    )]
+    pub mint: Box<InterfaceAccount<'info, token_interface::Mint>>,
+
+    /// Token program for the borrowed token
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+/// Lend `amount` of one reserve for the duration of a callback.
+///
+/// The vault sends `amount` to `borrower_token`, then the instruction
+/// `data` is invoked on `remaining_accounts[0]` with the rest of the
+/// remaining accounts, signer and writable flags passed through. The
+/// callback must return `amount` plus the flash fee to the vault.
+///
+/// The fee is charged at the rate of swaps paying in the borrowed token:
+/// the A->B fee for token A loans, the B->A fee for token B loans.
+///
+/// The callback can't re-enter this program: Solana only permits direct
+/// self-recursion, and a callback targeting this program is rejected.
+pub fn handler<'info>(
+    ctx: Context<'_, '_, '_, 'info, FlashLoan<'info>>,
+    amount: u64,
+    data: Vec<u8>,
+) -> Result<()> {
+    require!(amount > 0, AmmError::AmountTooSmall);
+
+    let pool = &ctx.accounts.pool;
+    let borrow_a = ctx.accounts.borrower_token.mint == pool.token_a_mint;
+    let (reserve, token_program) = if borrow_a {
+        (pool.reserve_a, pool.token_program_a())
+    } else {
+        (pool.reserve_b, pool.token_program_b())
+    };
+    require!(amount <= reserve, AmmError::InsufficientLiquidity);
+    require!(
+        ctx.accounts.token_program.key() == token_program,
+        AmmError::InvalidTokenProgram
+    );
+
+    let fee = calculate_flash_fee(amount, pool.swap_fee_numerator(borrow_a), pool.fee_denominator)?;
+
+    let (callback_program, callback_accounts) = ctx
+        .remaining_accounts
+        .split_first()
+        .ok_or(AmmError::InvalidFlashLoanCallback)?;
+    require!(
+        callback_program.executable && callback_program.key() != crate::ID,
+        AmmError::InvalidFlashLoanCallback
+    );
+
+    let (vault, vault_before) = if borrow_a {
+        (ctx.accounts.token_a_vault.to_account_info(), ctx.accounts.token_a_vault.amount)
+    } else {
+        (ctx.accounts.token_b_vault.to_account_info(), ctx.accounts.token_b_vault.amount)
+    };
+
+    let seeds = &[
+        POOL_SEED,
+        pool.token_a_mint.as_ref(),
+        pool.token_b_mint.as_ref(),
+        &[pool.bump],
+    ];
+    let signer_seeds = &[&seeds[..]];
+
+    // Lend
+    token_interface::transfer_checked(
+        CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            TransferChecked {
+                from: vault,
+                mint: ctx.accounts.mint.to_account_info(),
+                to: ctx.accounts.borrower_token.to_account_info(),
+                authority: ctx.accounts.pool.to_account_info(),
+            },
+            signer_seeds,
+        ),
+        amount,
+        ctx.accounts.mint.decimals,
+    )?;
+
+    // Hand control to the borrower
+    let callback = Instruction {
+        program_id: callback_program.key(),
+        accounts: callback_accounts
+            .iter()
+            .map(|account| AccountMeta {
+                pubkey: account.key(),
+                is_signer: account.is_signer,
+                is_writable: account.is_writable,
+            })
+            .collect(),
+        data,
+    };
+    invoke(&callback, callback_accounts)?;
+
+    // Repayment check against the vault balance itself
+    let vault_after = if borrow_a {
+        ctx.accounts.token_a_vault.reload()?;
+        ctx.accounts.token_a_vault.amount
+    } else {
+        ctx.accounts.token_b_vault.reload()?;
+        ctx.accounts.token_b_vault.amount
+    };
+    let repaid_surplus = vault_after
+        .checked_sub(vault_before)
+        .ok_or(AmmError::FlashLoanNotRepaid)?;
+    require!(repaid_surplus >= fee, AmmError::FlashLoanNotRepaid);
+
+    // The fee (and any overpayment) stays in the pool for LPs
+    let clock = Clock::get()?;
+    let pool = &mut ctx.accounts.pool;
+    pool.accumulate_prices(clock.unix_timestamp);
+    let (old_reserve_a, old_reserve_b) = (pool.reserve_a, pool.reserve_b);
+    if borrow_a {
+        pool.reserve_a = pool.reserve_a.checked_add(repaid_surplus).ok_or(AmmError::MathOverflow)?;
+        pool.cumulative_fees_a = pool.cumulative_fees_a.saturating_add(fee);
+        pool.accrue_fee_growth(fee, 0, ctx.accounts.lp_mint.supply);
+    } else {
+        pool.reserve_b = pool.reserve_b.checked_add(repaid_surplus).ok_or(AmmError::MathOverflow)?;
+        pool.cumulative_fees_b = pool.cumulative_fees_b.saturating_add(fee);
+        pool.accrue_fee_growth(0, fee, ctx.accounts.lp_mint.supply);
+    }
+    pool.last_update_slot = clock.slot;
+
+    emit!(FlashLoanRepaid {
+        pool: pool.key(),
+        borrower: ctx.accounts.borrower.key(),
+        mint: ctx.accounts.mint.key(),
+        amount,
+        fee,
+        timestamp: clock.unix_timestamp,
+    });
+
+    // The surplus lands on one side only, moving the price without a trade
+    emit!(ReservesSynced {
+        pool: pool.key(),
+        source: ReserveChangeSource::Donation,
+        old_reserve_a,
+        old_reserve_b,
+        new_reserve_a: pool.reserve_a,
+        new_reserve_b: pool.reserve_b,
+        excess_a: 0,
+        excess_b: 0,
+        timestamp: clock.unix_timestamp,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,62 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::Mint;
+
+use crate::state::{Pool, Position};
+use crate::constants::*;
+use crate::errors::AmmError;
+use crate::math::{calculate_breakeven_prices, lp_share_growth};
+
+#[derive(Accounts)]
+pub struct GetBreakevenPrice<'info> {
+    /// Pool state
+    #[account(
+        seeds = [POOL_SEED, pool.token_a_mint.as_ref(), pool.token_b_mint.as_ref()],
+        bump = pool.bump
+    )]
+    pub pool: Account<'info, Pool>,
+
+    /// LP mint
+    #[account(
+        constraint = lp_mint.key() == pool.lp_mint @ AmmError::InvalidVault
+    )]
+    pub lp_mint: Account<'info, Mint>,
+
+    /// Position to evaluate
+    #[account(
+        seeds = [POSITION_SEED, pool.key().as_ref(), position.owner.as_ref()],
+        bump = position.bump
+    )]
+    pub position: Account<'info, Position>,
+}
+
+/// Prices of token A in token B (Q64) between which the position beats holding
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
+pub struct BreakevenPrice {
+    /// Liquidity-weighted entry price
+    pub entry_price: u128,
+    /// Current spot price
+    pub current_price: u128,
+    /// Break-even price below the entry price
+    pub lower_price: u128,
+    /// Break-even price above the entry price
+    pub upper_price: u128,
+}
+
+pub fn handler(ctx: Context<GetBreakevenPrice>) -> Result<BreakevenPrice> {
+    let pool = &ctx.accounts.pool;
+    let position = &ctx.accounts.position;
+
+    require!(pool.is_initialized(), AmmError::PoolNotInitialized);
+    require!(position.liquidity > 0, AmmError::AmountTooSmall);
+
+    let current_growth = lp_share_growth(pool.reserve_a, pool.reserve_b, ctx.accounts.lp_mint.supply)?;
+    let (lower_price, upper_price) =
+        calculate_breakeven_prices(position.entry_price, position.entry_growth, current_growth)?;
+
+    Ok(BreakevenPrice {
+        entry_price: position.entry_price,
+        current_price: pool.price_a(),
+        lower_price,
+        upper_price,
+    })
+}
@@ -0,0 +1,67 @@
+use anchor_lang::prelude::*;
+
+use crate::state::Pool;
+use crate::constants::*;
+use crate::errors::AmmError;
+use crate::math::reserves_at_price_multiple;
+
+#[derive(Accounts)]
+pub struct GetCurveState<'info> {
+    /// Pool state
+    #[account(
+        seeds = [POOL_SEED, pool.token_a_mint.as_ref(), pool.token_b_mint.as_ref()],
+        bump = pool.bump
+    )]
+    pub pool: Account<'info, Pool>,
+}
+
+/// A point on the pool's curve
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
+pub struct CurvePoint {
+    /// Price of token A in token B (Q64)
+    pub price: u128,
+    pub reserve_a: u128,
+    pub reserve_b: u128,
+}
+
+/// Where the pool sits on its constant-product curve, plus reference points
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
+pub struct CurveState {
+    /// Invariant k = reserve_a * reserve_b
+    pub k: u128,
+    /// Current reserves and price
+    pub current: CurvePoint,
+    /// Reserves at twice the current price of token A
+    pub at_double_price: CurvePoint,
+    /// Reserves at half the current price of token A
+    pub at_half_price: CurvePoint,
+}
+
+pub fn handler(ctx: Context<GetCurveState>) -> Result<CurveState> {
+    let pool = &ctx.accounts.pool;
+
+    require!(pool.is_initialized(), AmmError::PoolNotInitialized);
+
+    let price = pool.price_a();
+    let (double_a, double_b) = reserves_at_price_multiple(pool.reserve_a, pool.reserve_b, 2, 1)?;
+    let (half_a, half_b) = reserves_at_price_multiple(pool.reserve_a, pool.reserve_b, 1, 2)?;
+
+    Ok(CurveState {
+        k: pool.k(),
+        current: CurvePoint {
+            price,
+            reserve_a: pool.reserve_a as u128,
+            reserve_b: pool.reserve_b as u128,
+        },
+        at_double_price: CurvePoint {
+            price: price.saturating_mul(2),
+            reserve_a: double_a,
+            reserve_b: double_b,
+        },
+        at_half_price: CurvePoint {
+            price: price / 2,
+            reserve_a: half_a,
+            reserve_b: half_b,
+        },
+    })
+}
@@ -0,0 +1,78 @@
+use anchor_lang::prelude::*;
+
+use crate::state::Pool;
+use crate::constants::*;
+use crate::errors::AmmError;
+use crate::math::{calculate_swap_output, curve_spot_price};
+
+#[derive(Accounts)]
+pub struct GetMarginalPriceAfter<'info> {
+    /// Pool state
+    #[account(
+        seeds = [POOL_SEED, pool.token_a_mint.as_ref(), pool.token_b_mint.as_ref()],
+        bump = pool.bump
+    )]
+    pub pool: Account<'info, Pool>,
+}
+
+/// Spot prices the pool would quote after a hypothetical swap
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
+pub struct MarginalPrice {
+    pub amount_out: u64,
+    /// Price of token A in token B after the swap (Q64)
+    pub price_a: u128,
+    /// Price of token B in token A after the swap (Q64)
+    pub price_b: u128,
+}
+
+/// Marginal (post-swap spot) price after swapping `amount_in`.
+///
+/// Unlike the average execution price `amount_out / amount_in`, this is the
+/// price the next infinitesimal trade would get, which routers equalize
+/// across venues when splitting an order. The price is the slope of the
+/// pool's curve at the new reserves (see `curve_spot_price`). Oracle-priced
+/// pools quote the oracle rather than their reserves and are rejected.
+pub fn handler(
+    ctx: Context<GetMarginalPriceAfter>,
+    amount_in: u64,
+    a_to_b: bool,
+) -> Result<MarginalPrice> {
+    let pool = &ctx.accounts.pool;
+    require!(pool.is_initialized(), AmmError::PoolNotInitialized);
+    require!(!pool.oracle_priced, AmmError::MarginalPriceUnsupported);
+
+    let (reserve_in, reserve_out) = if a_to_b {
+        (pool.reserve_a, pool.reserve_b)
+    } else {
+        (pool.reserve_b, pool.reserve_a)
+    };
+
+    let (amount_out, _, protocol_fee) = calculate_swap_output(
+        amount_in,
+        reserve_in,
+        reserve_out,
+        pool.swap_fee_numerator(a_to_b),
+        pool.fee_denominator,
+        pool.protocol_fee_numerator,
+        pool.protocol_fee_denominator,
+        pool.curve(),
+    )?;
+
+    // The protocol's share of the fee does not stay in the reserves
+    let new_reserve_in = reserve_in
+        .checked_add(amount_in - protocol_fee)
+        .ok_or(AmmError::MathOverflow)?;
+    let new_reserve_out = reserve_out - amount_out;
+
+    let (new_reserve_a, new_reserve_b) = if a_to_b {
+        (new_reserve_in, new_reserve_out)
+    } else {
+        (new_reserve_out, new_reserve_in)
+    };
+
+    Ok(MarginalPrice {
+        amount_out,
+        price_a: curve_spot_price(new_reserve_a, new_reserve_b, pool.curve())?,
+        price_b: curve_spot_price(new_reserve_b, new_reserve_a, pool.curve())?,
+    })
+}
@@ -0,0 +1,54 @@
+use anchor_lang::prelude::*;
+
+use crate::state::Pool;
+use crate::constants::*;
+
+#[derive(Accounts)]
+pub struct GetPoolInfo<'info> {
+    /// Pool state
+    #[account(
+        seeds = [POOL_SEED, pool.token_a_mint.as_ref(), pool.token_b_mint.as_ref()],
+        bump = pool.bump
+    )]
+    pub pool: Account<'info, Pool>,
+}
+
+/// Derived pool metrics for dashboards, computed from live state
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
+pub struct PoolInfo {
+    /// Price of token A in token B (Q64)
+    pub price_a: u128,
+    /// Price of token B in token A (Q64)
+    pub price_b: u128,
+    /// Invariant reserve_a * reserve_b
+    pub k: u128,
+    pub fee_bps: u64,
+    pub total_swaps: u64,
+    pub cumulative_volume_a: u64,
+    pub cumulative_volume_b: u64,
+    pub cumulative_fees_a: u64,
+    pub cumulative_fees_b: u64,
+    /// Token A swap volume in the current window
+    pub window_volume_a: u64,
+    /// Token B swap volume in the current window
+    pub window_volume_b: u64,
+}
+
+pub fn handler(ctx: Context<GetPoolInfo>) -> Result<PoolInfo> {
+    let pool = &ctx.accounts.pool;
+    let (window_volume_a, window_volume_b) = pool.window_volume(Clock::get()?.unix_timestamp);
+
+    Ok(PoolInfo {
+        price_a: pool.price_a(),
+        price_b: pool.price_b(),
+        k: pool.k(),
+        fee_bps: pool.fee_bps(),
+        total_swaps: pool.total_swaps,
+        cumulative_volume_a: pool.cumulative_volume_a,
+        cumulative_volume_b: pool.cumulative_volume_b,
+        cumulative_fees_a: pool.cumulative_fees_a,
+        cumulative_fees_b: pool.cumulative_fees_b,
+        window_volume_a,
+        window_volume_b,
+    })
+}
@@ -0,0 +1,32 @@
+use anchor_lang::prelude::*;
+
+use crate::state::Pool;
+use crate::constants::*;
+
+#[derive(Accounts)]
+pub struct GetSpotPrice<'info> {
+    /// Pool state
+    #[account(
+        seeds = [POOL_SEED, pool.token_a_mint.as_ref(), pool.token_b_mint.as_ref()],
+        bump = pool.bump
+    )]
+    pub pool: Account<'info, Pool>,
+}
+
+/// Current spot prices; `None` when the pool has no liquidity
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
+pub struct SpotPrice {
+    /// Price of token A in token B (Q64)
+    pub price_a: Option<u128>,
+    /// Price of token B in token A (Q64)
+    pub price_b: Option<u128>,
+}
+
+pub fn handler(ctx: Context<GetSpotPrice>) -> Result<SpotPrice> {
+    let pool = &ctx.accounts.pool;
+
+    Ok(SpotPrice {
+        price_a: pool.try_price_a(),
+        price_b: pool.try_price_b(),
+    })
+}
@@ -1,22 +1,33 @@
 use anchor_lang::prelude::*;
-use anchor_spl::token::{Mint, Token, TokenAccount};
+use anchor_spl::token::{self, Token};
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
 
-use crate::state::Pool;
+use crate::state::{Pool, ProtocolConfig};
 use crate::constants::*;
 use crate::errors::AmmError;
 use crate::events::PoolCreated;
 
 #[derive(Accounts)]
+#[instruction(
+    fee_numerator: u64,
+    fee_denominator: u64,
+    curve_type: u8,
+    amp: u64,
+    min_swap_amount: u64,
+    lp_decimals: u8
+)]
 pub struct InitializePool<'info> {
     /// Pool creator and authority
     #[account(mut)]
     pub authority: Signer<'info>,
 
     /// Token A mint (must be < token B mint lexicographically)
-    pub token_a_mint: Account<'info, Mint>,
+    #[account(mint::token_program = token_a_program)]
+    pub token_a_mint: Box<InterfaceAccount<'info, Mint>>,
 
     /// Token B mint
-    pub token_b_mint: Account<'info, Mint>,
+    #[account(mint::token_program = token_b_program)]
+    pub token_b_mint: Box<InterfaceAccount<'info, Mint>>,
 
     /// Pool state account
     #[account(
@@ -35,9 +46,10 @@ pub struct InitializePool<'info> {
         seeds = [VAULT_A_SEED, pool.key().as_ref()],
         bump,
         token::mint = token_a_mint,
-        token::authority = pool
+        token::authority = pool,
+        token::token_program = token_a_program
     )]
-    pub token_a_vault: Account<'info, TokenAccount>,
+    pub token_a_vault: Box<InterfaceAccount<'info, TokenAccount>>,
 
     /// Token B vault
     #[account(
@@ -46,20 +58,21 @@ pub struct InitializePool<'info> {
         seeds = [VAULT_B_SEED, pool.key().as_ref()],
         bump,
         token::mint = token_b_mint,
-        token::authority = pool
+        token::authority = pool,
+        token::token_program = token_b_program
     )]
-    pub token_b_vault: Account<'info, TokenAccount>,
+    pub token_b_vault: Box<InterfaceAccount<'info, TokenAccount>>,
 
-    /// LP token mint
+    /// LP token mint (`lp_decimals` decimals, at most MAX_LP_DECIMALS)
     #[account(
         init,
         payer = authority,
         seeds = [LP_MINT_SEED, pool.key().as_ref()],
         bump,
-        mint::decimals = 6,
+        mint::decimals = lp_decimals,
         mint::authority = lp_mint_authority
     )]
-    pub lp_mint: Account<'info, Mint>,
+    pub lp_mint: Box<Account<'info, token::Mint>>,
 
     /// LP mint authority PDA
     /// CHECK: PDA used as mint authority
@@ -69,6 +82,13 @@ pub struct InitializePool<'info> {
     )]
     pub lp_mint_authority: UncheckedAccount<'info>,
 
+    /// Token program for token A (SPL Token or Token-2022)
+    pub token_a_program: Interface<'info, TokenInterface>,
+
+    /// Token program for token B (SPL Token or Token-2022)
+    pub token_b_program: Interface<'info, TokenInterface>,
+
+    /// SPL Token program (LP mint)
     pub token_program: Program<'info, Token>,
     pub system_program: Program<'info, System>,
     pub rent: Sysvar<'info, Rent>,
@@ -78,6 +98,10 @@ pub fn handler(
     ctx: Context<InitializePool>,
     fee_numerator: u64,
     fee_denominator: u64,
+    curve_type: u8,
+    amp: u64,
+    min_swap_amount: u64,
+    lp_decimals: u8,
 ) -> Result<()> {
     // Validate token mints are different
     require!(
@@ -85,12 +109,31 @@ pub fn handler(
         AmmError::IdenticalTokenMints
     );
 
+    // Canonical ordering, so each pair maps to exactly one pool PDA
+    require!(
+        ctx.accounts.token_a_mint.key().as_ref() < ctx.accounts.token_b_mint.key().as_ref(),
+        AmmError::InvalidTokenOrder
+    );
+
     // Validate fee parameters
     require!(
         validate_fee(fee_numerator, fee_denominator),
         AmmError::InvalidFeeParameters
     );
 
+    require!(lp_decimals <= MAX_LP_DECIMALS, AmmError::InvalidLpDecimals);
+
+    // Stable pools price tokens 1:1 in base units, so decimals must match
+    match curve_type {
+        CURVE_CONSTANT_PRODUCT => require!(amp == 0, AmmError::InvalidCurveParameters),
+        CURVE_STABLE => require!(
+            (MIN_AMP..=MAX_AMP).contains(&amp)
+                && ctx.accounts.token_a_mint.decimals == ctx.accounts.token_b_mint.decimals,
+            AmmError::InvalidCurveParameters
+        ),
+        _ => return err!(AmmError::InvalidCurveParameters),
+    }
+
     let clock = Clock::get()?;
     let pool = &mut ctx.accounts.pool;
 
@@ -104,25 +147,83 @@ pub fn handler(
     pool.token_a_vault = ctx.accounts.token_a_vault.key();
     pool.token_b_vault = ctx.accounts.token_b_vault.key();
     pool.lp_mint = ctx.accounts.lp_mint.key();
+    pool.decimals_a = ctx.accounts.token_a_mint.decimals;
+    pool.decimals_b = ctx.accounts.token_b_mint.decimals;
+    pool.token_a_program = ctx.accounts.token_a_program.key();
+    pool.token_b_program = ctx.accounts.token_b_program.key();
 
     pool.reserve_a = 0;
     pool.reserve_b = 0;
 
     pool.fee_numerator = fee_numerator;
+    pool.fee_numerator_b_to_a = fee_numerator;
     pool.fee_denominator = fee_denominator;
 
     pool.authority = ctx.accounts.authority.key();
     pool.paused = false;
+    pool.pause_flags = 0;
+    pool.exempt_program = Pubkey::default();
+    pool.withdraw_fee_bps = 0;
+    pool.locked_lp_recipient = Pubkey::default();
+    pool.oracle_priced = false;
+    pool.oracle_spread_bps = 0;
+    pool.oracle_max_staleness = 0;
+    pool.oracle = Pubkey::default();
+    pool.stale_after_slots = 0;
+    pool.stale_surcharge_bps = 0;
+    pool.min_ratio = 0;
+    pool.max_ratio = 0;
+    pool.rebate_bps = 0;
+    pool.rebate_budget = 0;
+    pool.min_lp_hold_slots = 0;
+    pool.event_verbosity = EVENT_VERBOSITY_FULL;
+    pool.guardian = Pubkey::default();
+    pool.unwinding = false;
+    pool.protocol_fee_numerator = 0;
+    pool.protocol_fee_denominator = 0;
+    pool.protocol_fees_a = 0;
+    pool.protocol_fees_b = 0;
+    pool.fee_recipient = ProtocolConfig::address();
+    pool.deposits_frozen = false;
+    pool.max_reserve = 0;
+    pool.min_reserve = 0;
+    pool.last_deposit_slot = 0;
+    pool.min_slot_between_swaps = 0;
+    pool.fee_growth_global_a = 0;
+    pool.fee_growth_global_b = 0;
+    pool.price_cumulative_a = 0;
+    pool.price_cumulative_b = 0;
+    pool.last_oracle_timestamp = clock.unix_timestamp;
+    pool.strict_reserves = false;
+    pool.curve_type = curve_type;
+    pool.amp = amp;
+    pool.price_high = 0;
+    pool.price_low = u128::MAX;
+    // 0 selects the default, capped for low-decimal mints
+    pool.min_swap_amount = if min_swap_amount == 0 {
+        MIN_SWAP_AMOUNT.min(pool.max_min_swap_amount())
+    } else {
+        require!(
+            min_swap_amount <= pool.max_min_swap_amount(),
+            AmmError::InvalidMinSwapAmount
+        );
+        min_swap_amount
+    };
 
     pool.total_swaps = 0;
     pool.cumulative_volume_a = 0;
     pool.cumulative_volume_b = 0;
     pool.cumulative_fees_a = 0;
     pool.cumulative_fees_b = 0;
+    pool.window_volume_a = 0;
+    pool.window_volume_b = 0;
+    pool.window_start_timestamp = clock.unix_timestamp;
+    pool.window_duration = VOLUME_WINDOW_DURATION;
 
     pool.created_at = clock.unix_timestamp;
     pool.last_swap_timestamp = 0;
     pool.last_update_slot = clock.slot;
+    pool.last_checkpoint_slot = 0;
 
     emit!(PoolCreated {
         pool: pool.key(),
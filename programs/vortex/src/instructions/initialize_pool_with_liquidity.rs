@@ -0,0 +1,123 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::incinerator;
+use anchor_spl::associated_token::AssociatedToken;
+use anchor_spl::token::{Token, TokenAccount};
+use anchor_spl::token_interface;
+
+use crate::errors::AmmError;
+use crate::instructions::add_liquidity::{self, AddLiquidity, AddLiquidityBumps};
+use crate::instructions::initialize_pool::{self, *};
+
+#[derive(Accounts)]
+pub struct InitializePoolWithLiquidity<'info> {
+    /// Accounts of a plain `initialize_pool`; the authority also deposits
+    pub init: InitializePool<'info>,
+
+    /// Authority's token A account
+    #[account(
+        mut,
+        constraint = user_token_a.mint == init.token_a_mint.key() @ AmmError::InvalidTokenMint
+    )]
+    pub user_token_a: InterfaceAccount<'info, token_interface::TokenAccount>,
+
+    /// Authority's token B account
+    #[account(
+        mut,
+        constraint = user_token_b.mint == init.token_b_mint.key() @ AmmError::InvalidTokenMint
+    )]
+    pub user_token_b: InterfaceAccount<'info, token_interface::TokenAccount>,
+
+    /// Authority's LP token account, created here since the LP mint is new
+    #[account(
+        init,
+        payer = init.authority,
+        associated_token::mint = init.lp_mint,
+        associated_token::authority = init.authority,
+        associated_token::token_program = token_program
+    )]
+    pub user_lp_token: Box<Account<'info, TokenAccount>>,
+
+    /// Incinerator-owned LP account receiving the locked MINIMUM_LIQUIDITY
+    #[account(
+        init,
+        payer = init.authority,
+        associated_token::mint = init.lp_mint,
+        associated_token::authority = incinerator,
+        associated_token::token_program = token_program
+    )]
+    pub locked_lp_token: Box<Account<'info, TokenAccount>>,
+
+    /// CHECK: the incinerator address, owner of the locked LP account
+    #[account(address = incinerator::ID)]
+    pub incinerator: UncheckedAccount<'info>,
+
+    /// SPL Token program (LP mint)
+    pub token_program: Program<'info, Token>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+}
+
+/// Create a pool and make its first deposit in one instruction.
+///
+/// Runs `initialize_pool` and then `add_liquidity` on the same accounts, so
+/// the pool is never observable empty. The locked MINIMUM_LIQUIDITY goes to
+/// the incinerator's LP account. Returns the LP minted to the authority.
+#[allow(clippy::too_many_arguments)]
+pub fn handler(
+    ctx: Context<InitializePoolWithLiquidity>,
+    fee_numerator: u64,
+    fee_denominator: u64,
+    curve_type: u8,
+    amp: u64,
+    min_swap_amount: u64,
+    lp_decimals: u8,
+    amount_a: u64,
+    amount_b: u64,
+    min_liquidity: u64,
+    deadline: i64,
+) -> Result<u64> {
+    let accounts = ctx.accounts;
+    initialize_pool::handler(
+        Context::new(ctx.program_id, &mut accounts.init, &[], ctx.bumps.init),
+        fee_numerator,
+        fee_denominator,
+        curve_type,
+        amp,
+        min_swap_amount,
+        lp_decimals,
+    )?;
+
+    let init = &accounts.init;
+    let mut deposit = AddLiquidity {
+        user: init.authority.clone(),
+        pool: init.pool.clone(),
+        token_a_vault: (*init.token_a_vault).clone(),
+        token_b_vault: (*init.token_b_vault).clone(),
+        lp_mint: (*init.lp_mint).clone(),
+        lp_mint_authority: init.lp_mint_authority.clone(),
+        user_token_a: accounts.user_token_a.clone(),
+        user_token_b: accounts.user_token_b.clone(),
+        user_lp_token: (*accounts.user_lp_token).clone(),
+        locked_lp_token: Some((*accounts.locked_lp_token).clone()),
+        position: None,
+        deposit_intent: None,
+        token_a_mint: init.token_a_mint.clone(),
+        token_b_mint: init.token_b_mint.clone(),
+        token_a_program: init.token_a_program.clone(),
+        token_b_program: init.token_b_program.clone(),
+        token_program: init.token_program.clone(),
+    };
+    let liquidity = add_liquidity::handler(
+        Context::new(ctx.program_id, &mut deposit, &[], AddLiquidityBumps::default()),
+        amount_a,
+        amount_b,
+        min_liquidity,
+        deadline,
+    )?;
+
+    // The deposit worked on a copy of the pool; hand its state back so the
+    // outer context persists it
+    accounts.init.pool.set_inner(deposit.pool.into_inner());
+
+    Ok(liquidity)
+}
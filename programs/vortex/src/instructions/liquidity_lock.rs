@@ -0,0 +1,193 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{self, CloseAccount, Token, TokenAccount, Transfer};
+
+use crate::state::{LiquidityLock, Pool};
+use crate::constants::*;
+use crate::errors::AmmError;
+use crate::events::{LiquidityLocked, LiquidityUnlocked};
+
+#[derive(Accounts)]
+#[instruction(nonce: u64)]
+pub struct LockLiquidity<'info> {
+    /// LP owner and rent payer
+    #[account(mut)]
+    pub owner: Signer<'info>,
+
+    /// Pool state
+    #[account(
+        seeds = [POOL_SEED, pool.token_a_mint.as_ref(), pool.token_b_mint.as_ref()],
+        bump = pool.bump
+    )]
+    pub pool: Account<'info, Pool>,
+
+    /// LP mint
+    #[account(
+        constraint = lp_mint.key() == pool.lp_mint @ AmmError::InvalidVault
+    )]
+    pub lp_mint: Account<'info, token::Mint>,
+
+    /// Lock account
+    #[account(
+        init,
+        payer = owner,
+        space = 8 + LiquidityLock::INIT_SPACE,
+        seeds = [LIQUIDITY_LOCK_SEED, pool.key().as_ref(), owner.key().as_ref(), &nonce.to_le_bytes()],
+        bump
+    )]
+    pub lock: Account<'info, LiquidityLock>,
+
+    /// LP escrow owned by the lock
+    #[account(
+        init,
+        payer = owner,
+        seeds = [LOCK_ESCROW_SEED, lock.key().as_ref()],
+        bump,
+        token::mint = lp_mint,
+        token::authority = lock
+    )]
+    pub lock_lp_token: Account<'info, TokenAccount>,
+
+    /// Owner's LP token account funding the lock
+    #[account(
+        mut,
+        constraint = owner_lp_token.mint == pool.lp_mint @ AmmError::InvalidTokenMint
+    )]
+    pub owner_lp_token: Account<'info, TokenAccount>,
+
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct UnlockLiquidity<'info> {
+    /// LP owner
+    #[account(mut)]
+    pub owner: Signer<'info>,
+
+    /// Lock being released; closed to the owner
+    #[account(
+        mut,
+        close = owner,
+        seeds = [
+            LIQUIDITY_LOCK_SEED,
+            lock.pool.as_ref(),
+            owner.key().as_ref(),
+            &lock.nonce.to_le_bytes(),
+        ],
+        bump = lock.bump
+    )]
+    pub lock: Account<'info, LiquidityLock>,
+
+    /// LP escrow owned by the lock
+    #[account(
+        mut,
+        seeds = [LOCK_ESCROW_SEED, lock.key().as_ref()],
+        bump = lock.escrow_bump
+    )]
+    pub lock_lp_token: Account<'info, TokenAccount>,
+
+    /// Owner's LP token account receiving the released LP
+    #[account(mut)]
+    pub owner_lp_token: Account<'info, TokenAccount>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+/// Escrow `amount` LP in a new nonce-keyed lock until `unlock_timestamp`.
+///
+/// The LP keeps earning fees in the pool while locked; only its transfer
+/// is blocked, which is what farming programs need to verify commitment.
+pub fn lock_liquidity(
+    ctx: Context<LockLiquidity>,
+    nonce: u64,
+    amount: u64,
+    unlock_timestamp: i64,
+) -> Result<()> {
+    require!(amount > 0, AmmError::AmountTooSmall);
+    let now = Clock::get()?.unix_timestamp;
+    require!(unlock_timestamp > now, AmmError::InvalidUnlockTimestamp);
+
+    token::transfer(
+        CpiContext::new(
+            ctx.accounts.token_program.to_account_info(),
+            Transfer {
+                from: ctx.accounts.owner_lp_token.to_account_info(),
+                to: ctx.accounts.lock_lp_token.to_account_info(),
+                authority: ctx.accounts.owner.to_account_info(),
+            },
+        ),
+        amount,
+    )?;
+
+    let lock = &mut ctx.accounts.lock;
+    lock.bump = ctx.bumps.lock;
+    lock.escrow_bump = ctx.bumps.lock_lp_token;
+    lock.pool = ctx.accounts.pool.key();
+    lock.owner = ctx.accounts.owner.key();
+    lock.nonce = nonce;
+    lock.amount = amount;
+    lock.unlock_timestamp = unlock_timestamp;
+    lock.created_at = now;
+
+    emit!(LiquidityLocked {
+        pool: lock.pool,
+        owner: lock.owner,
+        lock: lock.key(),
+        amount,
+        unlock_timestamp,
+        timestamp: now,
+    });
+
+    Ok(())
+}
+
+/// Return a lock's LP to its owner once `unlock_timestamp` has passed,
+/// closing the escrow and the lock.
+pub fn unlock_liquidity(ctx: Context<UnlockLiquidity>) -> Result<()> {
+    let lock = &ctx.accounts.lock;
+    let now = Clock::get()?.unix_timestamp;
+    require!(lock.is_unlocked(now), AmmError::StillLocked);
+
+    let amount = lock.amount;
+    let seeds = &[
+        LIQUIDITY_LOCK_SEED,
+        lock.pool.as_ref(),
+        lock.owner.as_ref(),
+        &lock.nonce.to_le_bytes(),
+        &[lock.bump],
+    ];
+    let signer_seeds = &[&seeds[..]];
+
+    token::transfer(
+        CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            Transfer {
+                from: ctx.accounts.lock_lp_token.to_account_info(),
+                to: ctx.accounts.owner_lp_token.to_account_info(),
+                authority: ctx.accounts.lock.to_account_info(),
+            },
+            signer_seeds,
+        ),
+        amount,
+    )?;
+
+    token::close_account(CpiContext::new_with_signer(
+        ctx.accounts.token_program.to_account_info(),
+        CloseAccount {
+            account: ctx.accounts.lock_lp_token.to_account_info(),
+            destination: ctx.accounts.owner.to_account_info(),
+            authority: ctx.accounts.lock.to_account_info(),
+        },
+        signer_seeds,
+    ))?;
+
+    emit!(LiquidityUnlocked {
+        pool: lock.pool,
+        owner: lock.owner,
+        lock: lock.key(),
+        amount,
+        timestamp: now,
+    });
+
+    Ok(())
+}
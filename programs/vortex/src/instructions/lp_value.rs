@@ -0,0 +1,52 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::Mint;
+
+use crate::state::Pool;
+use crate::constants::*;
+use crate::errors::AmmError;
+use crate::math::{calculate_amounts_for_liquidity, calculate_withdraw_fee};
+
+#[derive(Accounts)]
+pub struct LpValue<'info> {
+    /// Pool state
+    #[account(
+        seeds = [POOL_SEED, pool.token_a_mint.as_ref(), pool.token_b_mint.as_ref()],
+        bump = pool.bump
+    )]
+    pub pool: Account<'info, Pool>,
+
+    /// LP mint
+    #[account(
+        constraint = lp_mint.key() == pool.lp_mint @ AmmError::InvalidVault
+    )]
+    pub lp_mint: Account<'info, Mint>,
+}
+
+/// Underlying tokens an LP amount redeems for, net of withdrawal fee
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
+pub struct LpTokenValue {
+    pub amount_a: u64,
+    pub amount_b: u64,
+}
+
+/// Value `lp_amount` in the pool's tokens without burning anything.
+///
+/// Uses the same share math and withdrawal fee as `remove_liquidity`, so
+/// the result is exactly what burning `lp_amount` would pay out now.
+pub fn handler(ctx: Context<LpValue>, lp_amount: u64) -> Result<LpTokenValue> {
+    let pool = &ctx.accounts.pool;
+    require!(pool.is_initialized(), AmmError::PoolNotInitialized);
+    require!(lp_amount > 0, AmmError::InsufficientLiquidityBurned);
+
+    let (amount_a, amount_b) = calculate_amounts_for_liquidity(
+        lp_amount,
+        pool.reserve_a,
+        pool.reserve_b,
+        ctx.accounts.lp_mint.supply,
+    )?;
+
+    Ok(LpTokenValue {
+        amount_a: amount_a - calculate_withdraw_fee(amount_a, pool.withdraw_fee_bps)?,
+        amount_b: amount_b - calculate_withdraw_fee(amount_b, pool.withdraw_fee_bps)?,
+    })
+}
@@ -0,0 +1,82 @@
+use anchor_lang::prelude::*;
+use anchor_lang::system_program::{self, Transfer};
+use anchor_spl::token_interface::Mint;
+
+use crate::state::Pool;
+use crate::constants::*;
+use crate::errors::AmmError;
+
+#[derive(Accounts)]
+pub struct MigratePool<'info> {
+    /// Pool authority; pays the rent for any added space
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    /// Pool state in a possibly older, shorter layout
+    /// CHECK: owner and discriminator are checked here; the authority is
+    /// checked once the account has been grown and deserialized
+    #[account(mut, owner = crate::ID)]
+    pub pool: UncheckedAccount<'info>,
+
+    /// Token A mint, for the stored decimals (checked against the pool)
+    pub token_a_mint: InterfaceAccount<'info, Mint>,
+
+    /// Token B mint, for the stored decimals (checked against the pool)
+    pub token_b_mint: InterfaceAccount<'info, Mint>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Bring a pool created by an older program version up to date.
+///
+/// New `Pool` fields are always added just before `_reserved`, so an old
+/// account is the current layout truncated: growing it zero-fills exactly
+/// the fields it lacks. Those that need a non-zero default are then set
+/// (see `Pool::apply_migration_defaults`), and `version` is bumped to
+/// `PROTOCOL_VERSION`.
+pub fn handler(ctx: Context<MigratePool>) -> Result<()> {
+    let pool_info = ctx.accounts.pool.to_account_info();
+    require!(
+        pool_info.try_borrow_data()?.starts_with(Pool::DISCRIMINATOR),
+        ErrorCode::AccountDiscriminatorMismatch
+    );
+
+    let space = 8 + Pool::INIT_SPACE;
+    if pool_info.data_len() < space {
+        let shortfall = Rent::get()?
+            .minimum_balance(space)
+            .saturating_sub(pool_info.lamports());
+        if shortfall > 0 {
+            system_program::transfer(
+                CpiContext::new(
+                    ctx.accounts.system_program.to_account_info(),
+                    Transfer {
+                        from: ctx.accounts.authority.to_account_info(),
+                        to: pool_info.clone(),
+                    },
+                ),
+                shortfall,
+            )?;
+        }
+        pool_info.resize(space)?;
+    }
+
+    let mut pool = Pool::try_deserialize(&mut &pool_info.try_borrow_data()?[..])?;
+    require_keys_eq!(pool.authority, ctx.accounts.authority.key(), AmmError::Unauthorized);
+    require!(pool.version < PROTOCOL_VERSION, AmmError::MigrationNotNeeded);
+    require_keys_eq!(pool.token_a_mint, ctx.accounts.token_a_mint.key(), AmmError::InvalidTokenMint);
+    require_keys_eq!(pool.token_b_mint, ctx.accounts.token_b_mint.key(), AmmError::InvalidTokenMint);
+
+    let old_version = pool.version;
+    pool.apply_migration_defaults(
+        Clock::get()?.unix_timestamp,
+        ctx.accounts.token_a_mint.decimals,
+        ctx.accounts.token_b_mint.decimals,
+    );
+
+    pool.try_serialize(&mut &mut pool_info.try_borrow_mut_data()?[..])?;
+
+    msg!("Pool migrated from version {} to {}", old_version, PROTOCOL_VERSION);
+
+    Ok(())
+}
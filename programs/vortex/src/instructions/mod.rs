@@ -1,9 +1,100 @@
 pub mod initialize_pool;
+pub mod initialize_pool_with_liquidity;
 pub mod add_liquidity;
+pub mod add_liquidity_single_sided;
+pub mod add_liquidity_balanced;
 pub mod remove_liquidity;
+pub mod route;
+pub mod batch_add_liquidity;
+pub mod rebalance_position;
 pub mod swap;
+pub mod swap_no_fee;
+pub mod checkpoint;
+pub mod close_pool;
+pub mod migrate_pool;
+pub mod derive_pool_for_pair;
+pub mod admin;
+pub mod preview_withdraw_for_value;
+pub mod lp_value;
+pub mod oracle;
+pub mod get_marginal_price_after;
+pub mod quote_swap;
+pub mod compute_min_out;
+pub mod compute_amount_out;
+pub mod simulate_swap;
+pub mod swap_history;
+pub mod price_checkpoints;
+pub mod position;
+pub mod compound_all;
+pub mod deposit_intent;
+pub mod user_swap_state;
+pub mod fee_tier;
+pub mod position_receipt;
+pub mod liquidity_lock;
+pub mod get_breakeven_price;
+pub mod get_curve_state;
+pub mod vault;
+pub mod emergency_withdraw;
+pub mod flash_loan;
+pub mod collect_protocol_fees;
+pub mod buyback_and_make;
+pub mod sync_reserves;
+pub mod swap_with_assertion;
+pub mod swap_with_price_limit;
+pub mod swap_with_relayer_fee;
+pub mod swap_exact_out;
+pub mod swap_split;
+pub mod swap_sol;
+pub mod get_spot_price;
+pub mod get_pool_info;
+pub mod check_reserves;
+pub mod treasury;
 
+// Every module exports a `handler`; the globs are for the accounts structs
+// and their Anchor client modules, and handlers are always called by path
+#[allow(ambiguous_glob_reexports)]
 pub use initialize_pool::*;
+pub use initialize_pool_with_liquidity::*;
 pub use add_liquidity::*;
+pub use add_liquidity_single_sided::*;
+pub use add_liquidity_balanced::*;
 pub use remove_liquidity::*;
+pub use route::*;
+pub use batch_add_liquidity::*;
+pub use rebalance_position::*;
+pub use swap_sol::*;
 pub use swap::*;
+pub use swap_no_fee::*;
+pub use checkpoint::*;
+pub use close_pool::*;
+pub use migrate_pool::*;
+pub use derive_pool_for_pair::*;
+pub use admin::*;
+pub use preview_withdraw_for_value::*;
+pub use lp_value::*;
+pub use oracle::*;
+pub use get_marginal_price_after::*;
+pub use quote_swap::*;
+pub use compute_amount_out::*;
+pub use simulate_swap::*;
+pub use swap_history::*;
+pub use price_checkpoints::*;
+pub use position::*;
+pub use compound_all::*;
+pub use deposit_intent::*;
+pub use user_swap_state::*;
+pub use fee_tier::*;
+pub use position_receipt::*;
+pub use liquidity_lock::*;
+pub use get_breakeven_price::*;
+pub use get_curve_state::*;
+pub use vault::*;
+pub use emergency_withdraw::*;
+pub use flash_loan::*;
+pub use collect_protocol_fees::*;
+pub use buyback_and_make::*;
+pub use sync_reserves::*;
+pub use get_spot_price::*;
+pub use get_pool_info::*;
+pub use check_reserves::*;
+pub use treasury::*;
@@ -0,0 +1,75 @@
+use anchor_lang::prelude::*;
+
+use crate::state::{Pool, PriceOracle};
+use crate::constants::*;
+use crate::errors::AmmError;
+
+#[derive(Accounts)]
+pub struct InitializeOracle<'info> {
+    /// Pool authority
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    /// Pool state
+    #[account(
+        mut,
+        seeds = [POOL_SEED, pool.token_a_mint.as_ref(), pool.token_b_mint.as_ref()],
+        bump = pool.bump,
+        constraint = pool.authority == authority.key() @ AmmError::Unauthorized
+    )]
+    pub pool: Account<'info, Pool>,
+
+    /// Reference price oracle
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + PriceOracle::INIT_SPACE,
+        seeds = [ORACLE_SEED, pool.key().as_ref()],
+        bump
+    )]
+    pub oracle: Account<'info, PriceOracle>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct UpdateOraclePrice<'info> {
+    /// Oracle updater
+    pub updater: Signer<'info>,
+
+    /// Reference price oracle
+    #[account(
+        mut,
+        seeds = [ORACLE_SEED, oracle.pool.as_ref()],
+        bump = oracle.bump,
+        constraint = oracle.updater == updater.key() @ AmmError::Unauthorized
+    )]
+    pub oracle: Account<'info, PriceOracle>,
+}
+
+/// Create the pool's reference price oracle and attach it to the pool
+pub fn initialize_oracle(ctx: Context<InitializeOracle>, updater: Pubkey) -> Result<()> {
+    let oracle = &mut ctx.accounts.oracle;
+    oracle.bump = ctx.bumps.oracle;
+    oracle.pool = ctx.accounts.pool.key();
+    oracle.updater = updater;
+    oracle.price = 0;
+    oracle.last_update_timestamp = 0;
+
+    ctx.accounts.pool.oracle = oracle.key();
+
+    msg!("Oracle initialized: {}", oracle.key());
+
+    Ok(())
+}
+
+/// Push a new reference price (token A in token B, Q64)
+pub fn update_oracle_price(ctx: Context<UpdateOraclePrice>, price: u128) -> Result<()> {
+    require!(price > 0, AmmError::InvalidOraclePrice);
+
+    let oracle = &mut ctx.accounts.oracle;
+    oracle.price = price;
+    oracle.last_update_timestamp = Clock::get()?.unix_timestamp;
+
+    Ok(())
+}
@@ -0,0 +1,50 @@
+use anchor_lang::prelude::*;
+
+use crate::state::{Pool, Position};
+use crate::constants::*;
+
+#[derive(Accounts)]
+pub struct OpenPosition<'info> {
+    /// Position owner and rent payer
+    #[account(mut)]
+    pub owner: Signer<'info>,
+
+    /// Pool state
+    #[account(
+        seeds = [POOL_SEED, pool.token_a_mint.as_ref(), pool.token_b_mint.as_ref()],
+        bump = pool.bump
+    )]
+    pub pool: Account<'info, Pool>,
+
+    /// Position account
+    #[account(
+        init,
+        payer = owner,
+        space = 8 + Position::INIT_SPACE,
+        seeds = [POSITION_SEED, pool.key().as_ref(), owner.key().as_ref()],
+        bump
+    )]
+    pub position: Account<'info, Position>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Create the caller's position account for a pool
+pub fn open_position(ctx: Context<OpenPosition>) -> Result<()> {
+    let position = &mut ctx.accounts.position;
+    position.bump = ctx.bumps.position;
+    position.pool = ctx.accounts.pool.key();
+    position.owner = ctx.accounts.owner.key();
+    position.last_deposit_slot = 0;
+    position.liquidity = 0;
+    position.entry_price = 0;
+    position.entry_growth = 0;
+    position.fee_growth_checkpoint_a = ctx.accounts.pool.fee_growth_global_a;
+    position.fee_growth_checkpoint_b = ctx.accounts.pool.fee_growth_global_b;
+    position.fees_owed_a = 0;
+    position.fees_owed_b = 0;
+
+    msg!("Position opened for {}", position.owner);
+
+    Ok(())
+}
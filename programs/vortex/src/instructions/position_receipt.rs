@@ -0,0 +1,218 @@
+use std::collections::BTreeSet;
+
+use anchor_lang::prelude::*;
+use anchor_spl::token::{self, CloseAccount, Mint, Token, TokenAccount, Transfer};
+
+use crate::state::{Pool, PositionReceipt};
+use crate::constants::*;
+use crate::errors::AmmError;
+use crate::instructions::remove_liquidity::{self, RemoveLiquidity, RemoveLiquidityBumps, RemovedLiquidity};
+
+#[derive(Accounts)]
+#[instruction(nonce: u64)]
+pub struct OpenPositionReceipt<'info> {
+    /// Receipt owner and rent payer
+    #[account(mut)]
+    pub owner: Signer<'info>,
+
+    /// Pool state
+    #[account(
+        seeds = [POOL_SEED, pool.token_a_mint.as_ref(), pool.token_b_mint.as_ref()],
+        bump = pool.bump
+    )]
+    pub pool: Account<'info, Pool>,
+
+    /// LP mint
+    #[account(
+        constraint = lp_mint.key() == pool.lp_mint @ AmmError::InvalidVault
+    )]
+    pub lp_mint: Account<'info, Mint>,
+
+    /// Receipt account
+    #[account(
+        init,
+        payer = owner,
+        space = 8 + PositionReceipt::INIT_SPACE,
+        seeds = [POSITION_SEED, pool.key().as_ref(), owner.key().as_ref(), &nonce.to_le_bytes()],
+        bump
+    )]
+    pub receipt: Account<'info, PositionReceipt>,
+
+    /// LP escrow owned by the receipt
+    #[account(
+        init,
+        payer = owner,
+        seeds = [RECEIPT_ESCROW_SEED, receipt.key().as_ref()],
+        bump,
+        token::mint = lp_mint,
+        token::authority = receipt
+    )]
+    pub receipt_lp_token: Account<'info, TokenAccount>,
+
+    /// Owner's LP token account funding the receipt
+    #[account(
+        mut,
+        constraint = owner_lp_token.mint == pool.lp_mint @ AmmError::InvalidTokenMint
+    )]
+    pub owner_lp_token: Account<'info, TokenAccount>,
+
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct RemoveLiquidityFromPosition<'info> {
+    /// Receipt owner
+    #[account(mut)]
+    pub owner: Signer<'info>,
+
+    /// Receipt being redeemed; closed to the owner
+    #[account(
+        mut,
+        close = owner,
+        seeds = [
+            POSITION_SEED,
+            receipt.pool.as_ref(),
+            owner.key().as_ref(),
+            &receipt.nonce.to_le_bytes(),
+        ],
+        bump = receipt.bump
+    )]
+    pub receipt: Account<'info, PositionReceipt>,
+
+    /// LP escrow owned by the receipt
+    #[account(
+        mut,
+        seeds = [RECEIPT_ESCROW_SEED, receipt.key().as_ref()],
+        bump = receipt.escrow_bump
+    )]
+    pub receipt_lp_token: Account<'info, TokenAccount>,
+
+    /// Owner's LP token account; the escrow is released here and burned
+    #[account(mut)]
+    pub owner_lp_token: Account<'info, TokenAccount>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+/// Move `liquidity` LP into a new receipt-owned escrow and record the entry.
+///
+/// Pair with `add_liquidity` in the same transaction to deposit straight
+/// into a receipt; fungible LP stays the default.
+pub fn open_position_receipt(
+    ctx: Context<OpenPositionReceipt>,
+    nonce: u64,
+    liquidity: u64,
+) -> Result<()> {
+    require!(liquidity > 0, AmmError::AmountTooSmall);
+
+    token::transfer(
+        CpiContext::new(
+            ctx.accounts.token_program.to_account_info(),
+            Transfer {
+                from: ctx.accounts.owner_lp_token.to_account_info(),
+                to: ctx.accounts.receipt_lp_token.to_account_info(),
+                authority: ctx.accounts.owner.to_account_info(),
+            },
+        ),
+        liquidity,
+    )?;
+
+    let pool = &ctx.accounts.pool;
+    let receipt = &mut ctx.accounts.receipt;
+    receipt.bump = ctx.bumps.receipt;
+    receipt.escrow_bump = ctx.bumps.receipt_lp_token;
+    receipt.pool = pool.key();
+    receipt.owner = ctx.accounts.owner.key();
+    receipt.nonce = nonce;
+    receipt.liquidity = liquidity;
+    receipt.entry_reserve_a = pool.reserve_a;
+    receipt.entry_reserve_b = pool.reserve_b;
+    receipt.entry_lp_supply = ctx.accounts.lp_mint.supply;
+    receipt.created_at = Clock::get()?.unix_timestamp;
+
+    msg!("Position receipt {} opened with {} LP", nonce, liquidity);
+
+    Ok(())
+}
+
+/// Redeem a receipt: release its escrowed LP to the owner, close the
+/// escrow and receipt, and withdraw that LP through `remove_liquidity`.
+///
+/// `remaining_accounts` holds the full `RemoveLiquidity` account list for
+/// the receipt's pool, with the owner as `user` and `owner_lp_token` as
+/// `user_lp_token`, so every withdrawal guard and fee still applies.
+pub fn remove_liquidity_from_position<'info>(
+    ctx: Context<'_, '_, 'info, 'info, RemoveLiquidityFromPosition<'info>>,
+    min_amount_a: u64,
+    min_amount_b: u64,
+    deadline: i64,
+) -> Result<RemovedLiquidity> {
+    let receipt = &ctx.accounts.receipt;
+    let liquidity = receipt.liquidity;
+    let receipt_key = receipt.key();
+    let seeds = &[
+        POSITION_SEED,
+        receipt.pool.as_ref(),
+        receipt.owner.as_ref(),
+        &receipt.nonce.to_le_bytes(),
+        &[receipt.bump],
+    ];
+    let signer_seeds = &[&seeds[..]];
+
+    token::transfer(
+        CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            Transfer {
+                from: ctx.accounts.receipt_lp_token.to_account_info(),
+                to: ctx.accounts.owner_lp_token.to_account_info(),
+                authority: ctx.accounts.receipt.to_account_info(),
+            },
+            signer_seeds,
+        ),
+        liquidity,
+    )?;
+
+    token::close_account(CpiContext::new_with_signer(
+        ctx.accounts.token_program.to_account_info(),
+        CloseAccount {
+            account: ctx.accounts.receipt_lp_token.to_account_info(),
+            destination: ctx.accounts.owner.to_account_info(),
+            authority: ctx.accounts.receipt.to_account_info(),
+        },
+        signer_seeds,
+    ))?;
+
+    // Parsed after the release so the inner accounts see the new balance
+    let mut accounts: &'info [AccountInfo<'info>] = ctx.remaining_accounts;
+    let mut bumps = RemoveLiquidityBumps::default();
+    let mut remove_accounts = RemoveLiquidity::try_accounts(
+        ctx.program_id,
+        &mut accounts,
+        &[],
+        &mut bumps,
+        &mut BTreeSet::new(),
+    )?;
+    require_keys_eq!(remove_accounts.pool.key(), ctx.accounts.receipt.pool, AmmError::InvalidPosition);
+    require_keys_eq!(remove_accounts.user.key(), ctx.accounts.owner.key(), AmmError::Unauthorized);
+    require_keys_eq!(
+        remove_accounts.user_lp_token.key(),
+        ctx.accounts.owner_lp_token.key(),
+        AmmError::InvalidPosition
+    );
+
+    let removed = remove_liquidity::handler(
+        Context::new(ctx.program_id, &mut remove_accounts, &[], bumps),
+        liquidity,
+        min_amount_a,
+        min_amount_b,
+        0,
+        0,
+        deadline,
+    )?;
+    remove_accounts.exit(ctx.program_id)?;
+
+    msg!("Position receipt {} redeemed", receipt_key);
+
+    Ok(removed)
+}
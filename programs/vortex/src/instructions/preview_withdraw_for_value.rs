@@ -0,0 +1,84 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{Mint, TokenAccount};
+
+use crate::state::Pool;
+use crate::constants::*;
+use crate::errors::AmmError;
+use crate::math::{
+    calculate_amounts_for_liquidity, calculate_liquidity_for_value, calculate_withdraw_fee,
+};
+
+#[derive(Accounts)]
+pub struct PreviewWithdrawForValue<'info> {
+    /// Pool state
+    #[account(
+        seeds = [POOL_SEED, pool.token_a_mint.as_ref(), pool.token_b_mint.as_ref()],
+        bump = pool.bump
+    )]
+    pub pool: Account<'info, Pool>,
+
+    /// LP mint
+    #[account(
+        constraint = lp_mint.key() == pool.lp_mint @ AmmError::InvalidVault
+    )]
+    pub lp_mint: Account<'info, Mint>,
+
+    /// LP token account whose balance caps the preview
+    #[account(
+        constraint = user_lp_token.mint == pool.lp_mint @ AmmError::InvalidTokenMint
+    )]
+    pub user_lp_token: Account<'info, TokenAccount>,
+}
+
+/// LP amount to burn and the tokens it would pay out, net of withdrawal fee
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
+pub struct WithdrawPreview {
+    pub lp_amount: u64,
+    pub amount_a: u64,
+    pub amount_b: u64,
+}
+
+/// Preview the LP burn needed to realize `target_value` at current reserves.
+///
+/// `price_a`/`price_b` are external Q64 prices per base unit; the result is
+/// capped at the holder's full LP balance.
+pub fn handler(
+    ctx: Context<PreviewWithdrawForValue>,
+    target_value: u64,
+    price_a: u128,
+    price_b: u128,
+) -> Result<WithdrawPreview> {
+    let pool = &ctx.accounts.pool;
+    let total_supply = ctx.accounts.lp_mint.supply;
+
+    require!(pool.is_initialized(), AmmError::PoolNotInitialized);
+    require!(target_value > 0, AmmError::AmountTooSmall);
+
+    let lp_amount = calculate_liquidity_for_value(
+        target_value,
+        pool.reserve_a,
+        pool.reserve_b,
+        price_a,
+        price_b,
+        total_supply,
+    )?
+    .min(ctx.accounts.user_lp_token.amount);
+
+    require!(lp_amount > 0, AmmError::InsufficientLiquidityBurned);
+
+    let (amount_a, amount_b) = calculate_amounts_for_liquidity(
+        lp_amount,
+        pool.reserve_a,
+        pool.reserve_b,
+        total_supply,
+    )?;
+
+    let amount_a = amount_a - calculate_withdraw_fee(amount_a, pool.withdraw_fee_bps)?;
+    let amount_b = amount_b - calculate_withdraw_fee(amount_b, pool.withdraw_fee_bps)?;
+
+    Ok(WithdrawPreview {
+        lp_amount,
+        amount_a,
+        amount_b,
+    })
+}
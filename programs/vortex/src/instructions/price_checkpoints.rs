@@ -0,0 +1,101 @@
+use anchor_lang::prelude::*;
+
+use crate::state::{Pool, PriceCheckpoint, PriceCheckpoints};
+use crate::constants::*;
+use crate::errors::AmmError;
+
+#[derive(Accounts)]
+pub struct InitializePriceCheckpoints<'info> {
+    /// Rent payer
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    /// Pool state
+    #[account(
+        seeds = [POOL_SEED, pool.token_a_mint.as_ref(), pool.token_b_mint.as_ref()],
+        bump = pool.bump
+    )]
+    pub pool: Account<'info, Pool>,
+
+    /// Price checkpoint ring buffer
+    #[account(
+        init,
+        payer = payer,
+        space = 8 + PriceCheckpoints::INIT_SPACE,
+        seeds = [PRICE_CHECKPOINTS_SEED, pool.key().as_ref()],
+        bump
+    )]
+    pub price_checkpoints: Account<'info, PriceCheckpoints>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct Consult<'info> {
+    /// Pool state
+    #[account(
+        seeds = [POOL_SEED, pool.token_a_mint.as_ref(), pool.token_b_mint.as_ref()],
+        bump = pool.bump
+    )]
+    pub pool: Account<'info, Pool>,
+
+    /// Price checkpoint ring buffer
+    #[account(
+        seeds = [PRICE_CHECKPOINTS_SEED, pool.key().as_ref()],
+        bump = price_checkpoints.bump
+    )]
+    pub price_checkpoints: Account<'info, PriceCheckpoints>,
+}
+
+/// Time-weighted average prices over a past window
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
+pub struct TwapResult {
+    /// Average price of token A in token B (Q64)
+    pub price_a: u128,
+    /// Average price of token B in token A (Q64)
+    pub price_b: u128,
+    /// Start of the window actually averaged (the checkpoint used)
+    pub start_timestamp: i64,
+    /// End of the window (now)
+    pub end_timestamp: i64,
+}
+
+/// Create the optional price checkpoint buffer for a pool (anyone may pay
+/// for it). Once it exists, passing it to `swap` appends a checkpoint at
+/// most every PRICE_CHECKPOINT_INTERVAL seconds.
+pub fn initialize_price_checkpoints(ctx: Context<InitializePriceCheckpoints>) -> Result<()> {
+    let buffer = &mut ctx.accounts.price_checkpoints;
+    buffer.bump = ctx.bumps.price_checkpoints;
+    buffer.pool = ctx.accounts.pool.key();
+    buffer.head = 0;
+    buffer.count = 0;
+    buffer.checkpoints = [PriceCheckpoint::default(); PRICE_CHECKPOINTS_LEN];
+
+    msg!("Price checkpoints initialized for pool {}", buffer.pool);
+
+    Ok(())
+}
+
+/// Average prices from the checkpoint closest to `now - period` until now.
+///
+/// The accumulators are extrapolated to now at the current spot price, as
+/// in `Pool::observe`. The window actually used depends on when swaps
+/// recorded checkpoints and is returned alongside the prices; a caller
+/// needing a minimum window should check `start_timestamp`.
+pub fn consult(ctx: Context<Consult>, period: i64) -> Result<TwapResult> {
+    require!(period > 0, AmmError::InvalidConsultPeriod);
+
+    let now = Clock::get()?.unix_timestamp;
+    let (price_a, price_b, start_timestamp) = ctx
+        .accounts
+        .price_checkpoints
+        .average_prices(&ctx.accounts.pool, period, now)
+        .ok_or(AmmError::NoPriceCheckpoint)?;
+
+    Ok(TwapResult {
+        price_a,
+        price_b,
+        start_timestamp,
+        end_timestamp: now,
+    })
+}
@@ -0,0 +1,59 @@
+use anchor_lang::prelude::*;
+
+use crate::state::Pool;
+use crate::constants::*;
+use crate::errors::AmmError;
+use crate::math::{calculate_price_impact_bps, calculate_swap_output};
+
+#[derive(Accounts)]
+pub struct QuoteSwap<'info> {
+    /// Pool state
+    #[account(
+        seeds = [POOL_SEED, pool.token_a_mint.as_ref(), pool.token_b_mint.as_ref()],
+        bump = pool.bump
+    )]
+    pub pool: Account<'info, Pool>,
+}
+
+/// Expected result of an exact-input swap at the current reserves
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
+pub struct SwapQuote {
+    pub amount_out: u64,
+    /// Total fee charged on the input, protocol share included
+    pub fee_amount: u64,
+    /// Execution price shortfall against the spot price (BPS)
+    pub price_impact_bps: u64,
+}
+
+/// Quote an exact-input curve swap for clients to read via simulation.
+///
+/// Runs the same `calculate_swap_output` the swap handler uses, so front-ends
+/// don't have to mirror the curve math. Oracle pricing and the stale-reserve
+/// surcharge are not applied.
+pub fn handler(ctx: Context<QuoteSwap>, amount_in: u64, a_to_b: bool) -> Result<SwapQuote> {
+    let pool = &ctx.accounts.pool;
+    require!(pool.is_initialized(), AmmError::PoolNotInitialized);
+
+    let (reserve_in, reserve_out) = if a_to_b {
+        (pool.reserve_a, pool.reserve_b)
+    } else {
+        (pool.reserve_b, pool.reserve_a)
+    };
+
+    let (amount_out, fee_amount, _) = calculate_swap_output(
+        amount_in,
+        reserve_in,
+        reserve_out,
+        pool.swap_fee_numerator(a_to_b),
+        pool.fee_denominator,
+        pool.protocol_fee_numerator,
+        pool.protocol_fee_denominator,
+        pool.curve(),
+    )?;
+
+    Ok(SwapQuote {
+        amount_out,
+        fee_amount,
+        price_impact_bps: calculate_price_impact_bps(amount_in, amount_out, reserve_in, reserve_out)?,
+    })
+}
@@ -0,0 +1,184 @@
+use anchor_lang::prelude::*;
+
+use crate::constants::*;
+use crate::errors::AmmError;
+use crate::instructions::remove_liquidity::{self, *};
+use crate::instructions::swap::{self, Swap, SwapBumps};
+use crate::instructions::swap_exact_out;
+use crate::math::{
+    calculate_amounts_for_liquidity, calculate_swap_input, calculate_withdraw_fee, mul_div, Curve,
+};
+
+#[derive(Accounts)]
+pub struct RebalancePosition<'info> {
+    /// Accounts of a plain `remove_liquidity`; its user also swaps
+    pub remove: RemoveLiquidity<'info>,
+}
+
+/// Where a rebalance left the user
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
+pub struct RebalanceResult {
+    /// LP burned to fund the swap (0 if wallet balances sufficed)
+    pub lp_burned: u64,
+    pub token_a_balance: u64,
+    pub token_b_balance: u64,
+}
+
+/// Bring the user's token A balance to `target_a`, burning LP only if the
+/// wallet's token B can't pay for the missing A.
+///
+/// Holding more than `target_a`, the excess A is sold for B. Holding less,
+/// the shortfall is bought with B as an exact-output swap; if that costs
+/// more B than the wallet holds, just enough LP to cover the gap (plus
+/// `max_slippage_bps` of headroom) is withdrawn first, and the rest of the
+/// LP is left in the pool. Curve-priced constant-product pools only.
+///
+/// Every step runs the regular handler and is bounded by the caller's
+/// `reference_price` (Q64 price of token A in token B, as `Pool::price_a`)
+/// rather than by reserves read in the same transaction, which a sandwich
+/// could have moved: the withdrawal requires the pool price within
+/// `max_slippage_bps` of it, and each swap must fill no more than
+/// `max_slippage_bps` worse than it.
+pub fn handler(
+    ctx: Context<RebalancePosition>,
+    target_a: u64,
+    reference_price: u128,
+    max_slippage_bps: u64,
+    deadline: i64,
+) -> Result<RebalanceResult> {
+    require!(max_slippage_bps <= BPS_DENOMINATOR, AmmError::InvalidSlippage);
+    require!(reference_price > 0, AmmError::InvalidPriceLimit);
+    require!(Clock::get()?.unix_timestamp <= deadline, AmmError::DeadlineExceeded);
+
+    let worse = |value: u128| {
+        value.saturating_mul((BPS_DENOMINATOR - max_slippage_bps) as u128) / BPS_DENOMINATOR as u128
+    };
+    let better = |value: u128| {
+        value.saturating_mul((BPS_DENOMINATOR + max_slippage_bps) as u128) / BPS_DENOMINATOR as u128
+    };
+
+    let accounts = &mut ctx.accounts.remove;
+    let pool = &accounts.pool;
+    require!(pool.is_initialized(), AmmError::PoolNotInitialized);
+    require!(
+        !pool.oracle_priced && pool.curve() == Curve::ConstantProduct,
+        AmmError::ExactOutUnsupported
+    );
+    require!(pool.reserve_a > 0 && pool.reserve_b > 0, AmmError::InsufficientLiquidity);
+
+    // Withdraw only if the wallet's B can't buy the missing A
+    let mut lp_burned = 0;
+    let shortfall = target_a.saturating_sub(accounts.user_token_a.amount);
+    if shortfall > 0 {
+        let (b_needed, _) = calculate_swap_input(
+            shortfall,
+            pool.reserve_b,
+            pool.reserve_a,
+            pool.swap_fee_numerator(false),
+            pool.fee_denominator,
+        )?;
+        let b_missing = b_needed.saturating_sub(accounts.user_token_b.amount);
+        if b_missing > 0 {
+            lp_burned = lp_to_cover(b_missing, pool.reserve_b, accounts.lp_mint.supply, max_slippage_bps)?
+                .min(accounts.user_lp_token.amount);
+            // The price band pins the mix of the withdrawal to the
+            // reference; the minimums bound each side within that band
+            let (amount_a, amount_b) = calculate_amounts_for_liquidity(
+                lp_burned,
+                pool.reserve_a,
+                pool.reserve_b,
+                accounts.lp_mint.supply,
+            )?;
+            let amount_a = amount_a - calculate_withdraw_fee(amount_a, pool.withdraw_fee_bps)?;
+            let amount_b = amount_b - calculate_withdraw_fee(amount_b, pool.withdraw_fee_bps)?;
+            remove_liquidity::handler(
+                Context::new(ctx.program_id, accounts, &[], ctx.bumps.remove),
+                lp_burned,
+                worse(amount_a as u128) as u64,
+                worse(amount_b as u128) as u64,
+                worse(reference_price),
+                better(reference_price),
+                deadline,
+            )?;
+            accounts.token_a_vault.reload()?;
+            accounts.token_b_vault.reload()?;
+            accounts.user_token_a.reload()?;
+            accounts.user_token_b.reload()?;
+            accounts.lp_mint.reload()?;
+        }
+    }
+
+    let held_a = accounts.user_token_a.amount;
+    if held_a != target_a {
+        require!(!accounts.pool.paused, AmmError::PoolPaused);
+        let mut swap_accounts = Swap {
+            user: accounts.user.clone(),
+            pool: accounts.pool.clone(),
+            token_a_vault: accounts.token_a_vault.clone(),
+            token_b_vault: accounts.token_b_vault.clone(),
+            user_token_a: accounts.user_token_a.clone(),
+            user_token_b: accounts.user_token_b.clone(),
+            token_a_mint: accounts.token_a_mint.clone(),
+            token_b_mint: accounts.token_b_mint.clone(),
+            token_a_program: accounts.token_a_program.clone(),
+            token_b_program: accounts.token_b_program.clone(),
+            token_program: accounts.token_program.clone(),
+            instructions_sysvar: None,
+            oracle: None,
+            swap_history: None,
+            price_checkpoints: None,
+            lp_mint: accounts.lp_mint.clone(),
+            lp_mint_authority: None,
+            user_lp_token: None,
+            user_swap_state: None,
+            fee_tier: None,
+        };
+        let swap_ctx = Context::new(ctx.program_id, &mut swap_accounts, &[], SwapBumps::default());
+
+        if held_a > target_a {
+            // Sell the excess A, accepting at most the slippage off the reference
+            let excess = held_a - target_a;
+            let fair_out = mul_div(excess as u128, reference_price, Q64).ok_or(AmmError::MathOverflow)?;
+            let min_out = u64::try_from(worse(fair_out)).unwrap_or(u64::MAX);
+            swap::handler(swap_ctx, excess, min_out, true, 0, 0, deadline, 0)?;
+        } else {
+            // Buy the shortfall with B, paying at most the slippage over the reference
+            let shortfall = target_a - held_a;
+            let fair_in = mul_div(shortfall as u128, reference_price, Q64).ok_or(AmmError::MathOverflow)?;
+            let max_in = u64::try_from(better(fair_in))
+                .unwrap_or(u64::MAX)
+                .min(accounts.user_token_b.amount);
+            swap_exact_out::handler(swap_ctx, shortfall, max_in, false, deadline)?;
+        }
+
+        // The swap worked on a copy of the pool; hand its state back so the
+        // outer context persists it
+        accounts.pool.set_inner(swap_accounts.pool.into_inner());
+        accounts.user_token_a.reload()?;
+        accounts.user_token_b.reload()?;
+    }
+
+    msg!(
+        "Rebalanced to {} A, {} B, burning {} LP",
+        accounts.user_token_a.amount,
+        accounts.user_token_b.amount,
+        lp_burned
+    );
+
+    Ok(RebalanceResult {
+        lp_burned,
+        token_a_balance: accounts.user_token_a.amount,
+        token_b_balance: accounts.user_token_b.amount,
+    })
+}
+
+/// LP whose withdrawal yields about `b_missing` worth of B once its A side
+/// is counted at spot, with `headroom_bps` extra for the swap's fee and
+/// price impact
+fn lp_to_cover(b_missing: u64, reserve_b: u64, lp_supply: u64, headroom_bps: u64) -> Result<u64> {
+    require!(reserve_b > 0, AmmError::InsufficientLiquidity);
+    // A pro-rata share is worth twice its B side at spot
+    let lp = (b_missing as u128 * lp_supply as u128).div_ceil(2 * reserve_b as u128);
+    let lp = (lp * (BPS_DENOMINATOR + headroom_bps) as u128).div_ceil(BPS_DENOMINATOR as u128);
+    Ok(u64::try_from(lp).unwrap_or(u64::MAX))
+}
@@ -1,11 +1,12 @@
 use anchor_lang::prelude::*;
-use anchor_spl::token::{self, Mint, Token, TokenAccount, Transfer, Burn};
+use anchor_spl::token::{self, Mint, Token, TokenAccount, Burn};
+use anchor_spl::token_interface::{self, TokenInterface, TransferChecked};
 
-use crate::state::Pool;
+use crate::state::{Pool, Position};
 use crate::constants::*;
 use crate::errors::AmmError;
-use crate::events::LiquidityRemoved;
-use crate::math::calculate_amounts_for_liquidity;
+use crate::events::{LiquidityRemoved, TreasuryAccrued};
+use crate::math::{calculate_amounts_for_liquidity, calculate_withdraw_fee, verify_lp_value};
 
 #[derive(Accounts)]
 pub struct RemoveLiquidity<'info> {
@@ -28,7 +29,7 @@ pub struct RemoveLiquidity<'info> {
         bump,
         constraint = token_a_vault.key() == pool.token_a_vault @ AmmError::InvalidVault
     )]
-    pub token_a_vault: Account<'info, TokenAccount>,
+    pub token_a_vault: InterfaceAccount<'info, token_interface::TokenAccount>,
 
     /// Token B vault
     #[account(
@@ -37,7 +38,7 @@ pub struct RemoveLiquidity<'info> {
         bump,
         constraint = token_b_vault.key() == pool.token_b_vault @ AmmError::InvalidVault
     )]
-    pub token_b_vault: Account<'info, TokenAccount>,
+    pub token_b_vault: InterfaceAccount<'info, token_interface::TokenAccount>,
 
     /// LP mint
     #[account(
@@ -53,14 +54,14 @@ pub struct RemoveLiquidity<'info> {
         mut,
         constraint = user_token_a.mint == pool.token_a_mint @ AmmError::InvalidTokenMint
     )]
-    pub user_token_a: Account<'info, TokenAccount>,
+    pub user_token_a: InterfaceAccount<'info, token_interface::TokenAccount>,
 
     /// User's token B account
     #[account(
         mut,
         constraint = user_token_b.mint == pool.token_b_mint @ AmmError::InvalidTokenMint
     )]
-    pub user_token_b: Account<'info, TokenAccount>,
+    pub user_token_b: InterfaceAccount<'info, token_interface::TokenAccount>,
 
     /// User's LP token account
     #[account(
@@ -69,22 +70,110 @@ pub struct RemoveLiquidity<'info> {
     )]
     pub user_lp_token: Account<'info, TokenAccount>,
 
+    /// Protocol treasury vault for token A (required when a withdrawal fee is set)
+    #[account(
+        mut,
+        seeds = [TREASURY_SEED, pool.token_a_mint.as_ref()],
+        bump
+    )]
+    pub treasury_token_a: Option<InterfaceAccount<'info, token_interface::TokenAccount>>,
+
+    /// Protocol treasury vault for token B (required when a withdrawal fee is set)
+    #[account(
+        mut,
+        seeds = [TREASURY_SEED, pool.token_b_mint.as_ref()],
+        bump
+    )]
+    pub treasury_token_b: Option<InterfaceAccount<'info, token_interface::TokenAccount>>,
+
+    /// User's position; times the LP hold window for the liquidity it records
+    #[account(
+        mut,
+        seeds = [POSITION_SEED, pool.key().as_ref(), user.key().as_ref()],
+        bump = position.bump
+    )]
+    pub position: Option<Account<'info, Position>>,
+
+    /// Token A mint
+    #[account(
+        constraint = token_a_mint.key() == pool.token_a_mint @ AmmError::InvalidTokenMint
+    )]
+    pub token_a_mint: Box<InterfaceAccount<'info, token_interface::Mint>>,
+
+    /// Token B mint
+    #[account(
+        constraint = token_b_mint.key() == pool.token_b_mint @ AmmError::InvalidTokenMint
+    )]
+    pub token_b_mint: Box<InterfaceAccount<'info, token_interface::Mint>>,
+
+    /// Token program for token A
+    #[account(
+        constraint = token_a_program.key() == pool.token_program_a() @ AmmError::InvalidTokenProgram
+    )]
+    pub token_a_program: Interface<'info, TokenInterface>,
+
+    /// Token program for token B
+    #[account(
+        constraint = token_b_program.key() == pool.token_program_b() @ AmmError::InvalidTokenProgram
+    )]
+    pub token_b_program: Interface<'info, TokenInterface>,
+
+    /// SPL Token program (LP mint)
     pub token_program: Program<'info, Token>,
 }
 
+/// Amounts paid out by `remove_liquidity`, net of the withdrawal fee
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
+pub struct RemovedLiquidity {
+    pub amount_a: u64,
+    pub amount_b: u64,
+}
+
+/// Withdraw liquidity. Intentionally allowed while the pool is paused so
+/// LPs can always exit.
 pub fn handler(
     ctx: Context<RemoveLiquidity>,
     liquidity_amount: u64,
     min_amount_a: u64,
     min_amount_b: u64,
-) -> Result<()> {
+    min_price: u128,
+    max_price: u128,
+    deadline: i64,
+) -> Result<RemovedLiquidity> {
+    log_compute_units!("remove_liquidity: start");
     require!(liquidity_amount > 0, AmmError::AmountTooSmall);
+    let clock = Clock::get()?;
+    require!(clock.unix_timestamp <= deadline, AmmError::DeadlineExceeded);
 
     let pool = &ctx.accounts.pool;
     let total_supply = ctx.accounts.lp_mint.supply;
 
     require!(pool.is_initialized(), AmmError::PoolNotInitialized);
 
+    // Block same-slot (flash-loan) add/remove cycles. A burn the position
+    // records is timed from the position's last deposit. Any other LP
+    // (transferred, or out of a vault or lock) is timed from the pool's last
+    // deposit, so moving fresh LP to another wallet cannot skip the window.
+    if pool.min_lp_hold_slots > 0 {
+        let hold_elapsed = match ctx.accounts.position.as_ref() {
+            Some(position) if position.liquidity >= liquidity_amount => {
+                position.is_hold_elapsed(clock.slot, pool.min_lp_hold_slots)
+            }
+            _ => pool.is_hold_elapsed(clock.slot),
+        };
+        require!(hold_elapsed, AmmError::LiquidityLockedTooRecent);
+    }
+
+    // Opt-in price band (0 = no bound) on the price of token A in token B.
+    // Mitigates sandwiching: an attacker skews the ratio before the
+    // withdrawal so the LP exits heavy in the cheapened token, then swaps
+    // back. min_amount_a/b alone only bound each side, not their mix.
+    let price = pool.price_a();
+    require!(
+        price >= min_price && (max_price == 0 || price <= max_price),
+        AmmError::PriceOutOfBand
+    );
+
     // Calculate amounts to return
     let (amount_a, amount_b) = calculate_amounts_for_liquidity(
         liquidity_amount,
@@ -93,9 +182,33 @@ pub fn handler(
         total_supply,
     )?;
 
-    // Slippage check
-    require!(amount_a >= min_amount_a, AmmError::SlippageExceeded);
-    require!(amount_b >= min_amount_b, AmmError::SlippageExceeded);
+    // A tiny non-zero reserve prices the pool pathologically, so only a
+    // holder burning their whole LP balance or whole position may take a
+    // reserve below the floor; the floor never traps anyone's last exit
+    let full_position = ctx
+        .accounts
+        .position
+        .as_ref()
+        .is_some_and(|position| position.liquidity > 0 && liquidity_amount >= position.liquidity);
+    let full_exit = liquidity_amount == ctx.accounts.user_lp_token.amount || full_position;
+    require!(
+        full_exit
+            || pool.above_reserve_floor(
+                pool.reserve_a.saturating_sub(amount_a),
+                pool.reserve_b.saturating_sub(amount_b)
+            ),
+        AmmError::WouldDrainPool
+    );
+
+    // Withdrawal fee, diverted to the treasury
+    let withdraw_fee_a = calculate_withdraw_fee(amount_a, pool.withdraw_fee_bps)?;
+    let withdraw_fee_b = calculate_withdraw_fee(amount_b, pool.withdraw_fee_bps)?;
+    let user_amount_a = amount_a - withdraw_fee_a;
+    let user_amount_b = amount_b - withdraw_fee_b;
+
+    // Slippage check (against what the user actually receives)
+    require!(user_amount_a >= min_amount_a, AmmError::SlippageExceeded);
+    require!(user_amount_b >= min_amount_b, AmmError::SlippageExceeded);
 
     // Burn LP tokens from user
     token::burn(
@@ -111,7 +224,6 @@ pub fn handler(
     )?;
 
     // Transfer token A from vault to user
-    let pool_key = ctx.accounts.pool.key();
     let token_a_mint = ctx.accounts.pool.token_a_mint;
     let token_b_mint = ctx.accounts.pool.token_b_mint;
     let bump = ctx.accounts.pool.bump;
@@ -124,51 +236,136 @@ pub fn handler(
     ];
     let signer_seeds = &[&seeds[..]];
 
-    token::transfer(
+    token_interface::transfer_checked(
         CpiContext::new_with_signer(
-            ctx.accounts.token_program.to_account_info(),
-            Transfer {
+            ctx.accounts.token_a_program.to_account_info(),
+            TransferChecked {
                 from: ctx.accounts.token_a_vault.to_account_info(),
+                mint: ctx.accounts.token_a_mint.to_account_info(),
                 to: ctx.accounts.user_token_a.to_account_info(),
                 authority: ctx.accounts.pool.to_account_info(),
             },
             signer_seeds,
         ),
-        amount_a,
+        user_amount_a,
+        ctx.accounts.token_a_mint.decimals,
     )?;
 
     // Transfer token B from vault to user
-    token::transfer(
+    token_interface::transfer_checked(
         CpiContext::new_with_signer(
-            ctx.accounts.token_program.to_account_info(),
-            Transfer {
+            ctx.accounts.token_b_program.to_account_info(),
+            TransferChecked {
                 from: ctx.accounts.token_b_vault.to_account_info(),
+                mint: ctx.accounts.token_b_mint.to_account_info(),
                 to: ctx.accounts.user_token_b.to_account_info(),
                 authority: ctx.accounts.pool.to_account_info(),
             },
             signer_seeds,
         ),
-        amount_b,
+        user_amount_b,
+        ctx.accounts.token_b_mint.decimals,
     )?;
 
+    // Transfer withdrawal fees from vaults to treasury
+    if withdraw_fee_a > 0 || withdraw_fee_b > 0 {
+        let (Some(treasury_a), Some(treasury_b)) = (
+            ctx.accounts.treasury_token_a.as_ref(),
+            ctx.accounts.treasury_token_b.as_ref(),
+        ) else {
+            return err!(AmmError::TreasuryAccountRequired);
+        };
+
+        if withdraw_fee_a > 0 {
+            token_interface::transfer_checked(
+                CpiContext::new_with_signer(
+                    ctx.accounts.token_a_program.to_account_info(),
+                    TransferChecked {
+                        from: ctx.accounts.token_a_vault.to_account_info(),
+                        mint: ctx.accounts.token_a_mint.to_account_info(),
+                        to: treasury_a.to_account_info(),
+                        authority: ctx.accounts.pool.to_account_info(),
+                    },
+                    signer_seeds,
+                ),
+                withdraw_fee_a,
+                ctx.accounts.token_a_mint.decimals,
+            )?;
+
+            emit!(TreasuryAccrued {
+                pool: ctx.accounts.pool.key(),
+                mint: ctx.accounts.pool.token_a_mint,
+                amount: withdraw_fee_a,
+                timestamp: clock.unix_timestamp,
+            });
+        }
+
+        if withdraw_fee_b > 0 {
+            token_interface::transfer_checked(
+                CpiContext::new_with_signer(
+                    ctx.accounts.token_b_program.to_account_info(),
+                    TransferChecked {
+                        from: ctx.accounts.token_b_vault.to_account_info(),
+                        mint: ctx.accounts.token_b_mint.to_account_info(),
+                        to: treasury_b.to_account_info(),
+                        authority: ctx.accounts.pool.to_account_info(),
+                    },
+                    signer_seeds,
+                ),
+                withdraw_fee_b,
+                ctx.accounts.token_b_mint.decimals,
+            )?;
+
+            emit!(TreasuryAccrued {
+                pool: ctx.accounts.pool.key(),
+                mint: ctx.accounts.pool.token_b_mint,
+                amount: withdraw_fee_b,
+                timestamp: clock.unix_timestamp,
+            });
+        }
+    }
+
     // Update pool reserves
     let pool = &mut ctx.accounts.pool;
+    pool.accumulate_prices(clock.unix_timestamp);
+    let (old_reserve_a, old_reserve_b) = (pool.reserve_a, pool.reserve_b);
     pool.reserve_a = pool.reserve_a.checked_sub(amount_a).ok_or(AmmError::MathOverflow)?;
     pool.reserve_b = pool.reserve_b.checked_sub(amount_b).ok_or(AmmError::MathOverflow)?;
-    pool.last_update_slot = Clock::get()?.slot;
+
+    // The LP left behind must not be diluted by the burn's rounding
+    verify_lp_value(
+        old_reserve_a,
+        old_reserve_b,
+        total_supply,
+        pool.reserve_a,
+        pool.reserve_b,
+        total_supply - liquidity_amount,
+    )?;
+    pool.last_update_slot = clock.slot;
+
+    if let Some(position) = ctx.accounts.position.as_mut() {
+        position.checkpoint_fees(pool);
+        position.liquidity = position.liquidity.saturating_sub(liquidity_amount);
+    }
 
     emit!(LiquidityRemoved {
         pool: pool.key(),
         user: ctx.accounts.user.key(),
         liquidity_burned: liquidity_amount,
-        amount_a,
-        amount_b,
+        amount_a: user_amount_a,
+        amount_b: user_amount_b,
+        withdraw_fee_a,
+        withdraw_fee_b,
         reserve_a: pool.reserve_a,
         reserve_b: pool.reserve_b,
-        timestamp: Clock::get()?.unix_timestamp,
+        timestamp: clock.unix_timestamp,
     });
 
-    msg!("Removed liquidity: burned {} LP, got {} A, {} B", liquidity_amount, amount_a, amount_b);
+    msg!("Removed liquidity: burned {} LP, got {} A, {} B", liquidity_amount, user_amount_a, user_amount_b);
 
-    Ok(())
+    log_compute_units!("remove_liquidity: end");
+    Ok(RemovedLiquidity {
+        amount_a: user_amount_a,
+        amount_b: user_amount_b,
+    })
 }
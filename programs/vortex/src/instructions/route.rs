@@ -0,0 +1,101 @@
+use std::collections::BTreeSet;
+
+use anchor_lang::prelude::*;
+
+use crate::errors::AmmError;
+use crate::instructions::swap::{self, Swap, SwapBumps};
+
+#[derive(Accounts)]
+pub struct RouteSwap<'info> {
+    /// Trader; must also be the `user` of every hop
+    pub user: Signer<'info>,
+}
+
+/// One leg of a routed swap
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
+pub struct RouteHop {
+    /// Direction through this hop's pool
+    pub a_to_b: bool,
+}
+
+/// Swap through several pools atomically, e.g. A -> B -> C.
+///
+/// `remaining_accounts` holds one full `Swap` account list per hop, in
+/// order (unused optional accounts are passed as this program's id). Each
+/// hop runs the regular swap handler, so every per-pool check applies and a
+/// paused pool fails the whole route. A hop's input account must be the
+/// previous hop's output account, and it spends exactly what that hop
+/// delivered. Slippage is only checked against the final output. Only the
+/// first hop is held to its pool's `min_swap_amount`; later hops spend an
+/// intermediate amount the trader does not choose.
+pub fn route_swap<'info>(
+    ctx: Context<'_, '_, 'info, 'info, RouteSwap<'info>>,
+    amount_in: u64,
+    min_amount_out: u64,
+    hops: Vec<RouteHop>,
+    deadline: i64,
+) -> Result<()> {
+    require!(!hops.is_empty(), AmmError::InvalidRoute);
+
+    let mut accounts: &'info [AccountInfo<'info>] = ctx.remaining_accounts;
+    let mut hop_amount = amount_in;
+    let mut previous_out: Option<Pubkey> = None;
+
+    for (index, hop) in hops.iter().enumerate() {
+        let mut bumps = SwapBumps::default();
+        let mut swap_accounts = Swap::try_accounts(
+            ctx.program_id,
+            &mut accounts,
+            &[],
+            &mut bumps,
+            &mut BTreeSet::new(),
+        )?;
+        require_keys_eq!(swap_accounts.user.key(), ctx.accounts.user.key(), AmmError::Unauthorized);
+
+        let (account_in, account_out) = if hop.a_to_b {
+            (swap_accounts.user_token_a.key(), swap_accounts.user_token_b.key())
+        } else {
+            (swap_accounts.user_token_b.key(), swap_accounts.user_token_a.key())
+        };
+        if let Some(previous_out) = previous_out {
+            require_keys_eq!(account_in, previous_out, AmmError::InvalidRoute);
+        }
+
+        let out_before = if hop.a_to_b {
+            swap_accounts.user_token_b.amount
+        } else {
+            swap_accounts.user_token_a.amount
+        };
+
+        swap::execute(
+            Context::new(ctx.program_id, &mut swap_accounts, &[], bumps),
+            hop_amount,
+            0,
+            hop.a_to_b,
+            0,
+            0,
+            deadline,
+            0,
+            index == 0,
+        )?;
+        // Persist this hop's pool before a later hop can load it again
+        swap_accounts.exit(ctx.program_id)?;
+
+        let out_after = if hop.a_to_b {
+            swap_accounts.user_token_b.reload()?;
+            swap_accounts.user_token_b.amount
+        } else {
+            swap_accounts.user_token_a.reload()?;
+            swap_accounts.user_token_a.amount
+        };
+        hop_amount = out_after.checked_sub(out_before).ok_or(AmmError::MathOverflow)?;
+        previous_out = Some(account_out);
+    }
+
+    require!(accounts.is_empty(), AmmError::InvalidRoute);
+    require!(hop_amount >= min_amount_out, AmmError::SlippageExceeded);
+
+    msg!("Routed {} through {} hops for {}", amount_in, hops.len(), hop_amount);
+
+    Ok(())
+}
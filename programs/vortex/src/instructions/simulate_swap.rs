@@ -0,0 +1,142 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_2022::spl_token_2022::extension::transfer_fee::TransferFeeConfig;
+use anchor_spl::token_2022::spl_token_2022::extension::{BaseStateWithExtensions, StateWithExtensions};
+use anchor_spl::token_2022::spl_token_2022::state::Mint as MintState;
+use anchor_spl::token_interface::{Mint, TokenAccount};
+
+use crate::state::Pool;
+use crate::constants::*;
+use crate::errors::AmmError;
+use crate::math::{calculate_price_impact_bps, calculate_staleness_surcharge, calculate_swap_output};
+
+#[derive(Accounts)]
+pub struct SimulateSwap<'info> {
+    /// Pool state
+    #[account(
+        seeds = [POOL_SEED, pool.token_a_mint.as_ref(), pool.token_b_mint.as_ref()],
+        bump = pool.bump
+    )]
+    pub pool: Account<'info, Pool>,
+
+    /// Token A vault
+    #[account(
+        constraint = token_a_vault.key() == pool.token_a_vault @ AmmError::InvalidVault
+    )]
+    pub token_a_vault: InterfaceAccount<'info, TokenAccount>,
+
+    /// Token B vault
+    #[account(
+        constraint = token_b_vault.key() == pool.token_b_vault @ AmmError::InvalidVault
+    )]
+    pub token_b_vault: InterfaceAccount<'info, TokenAccount>,
+
+    /// Token A mint
+    #[account(
+        constraint = token_a_mint.key() == pool.token_a_mint @ AmmError::InvalidTokenMint
+    )]
+    pub token_a_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    /// Token B mint
+    #[account(
+        constraint = token_b_mint.key() == pool.token_b_mint @ AmmError::InvalidTokenMint
+    )]
+    pub token_b_mint: Box<InterfaceAccount<'info, Mint>>,
+}
+
+/// Simulated result of an exact-input swap as it would execute now
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
+pub struct SwapSimulation {
+    /// Input reaching the vault after any Token-2022 transfer fee
+    pub amount_in_received: u64,
+    /// Output the pool sends
+    pub amount_out: u64,
+    /// Output reaching the user after any Token-2022 transfer fee
+    pub amount_out_received: u64,
+    /// Total fee charged on the input, protocol share and surcharge included
+    pub fee_amount: u64,
+    /// Execution price shortfall against the spot price (BPS)
+    pub price_impact_bps: u64,
+    /// Whether the vaults match the booked reserves (see `check_reserves`)
+    pub in_sync: bool,
+}
+
+/// Token-2022 transfer fee `mint` withholds from a transfer of `amount`
+fn transfer_fee(mint: &AccountInfo, amount: u64) -> Result<u64> {
+    let data = mint.try_borrow_data()?;
+    let mint = StateWithExtensions::<MintState>::unpack(&data)?;
+    let Ok(config) = mint.get_extension::<TransferFeeConfig>() else {
+        return Ok(0);
+    };
+    let fee = config
+        .calculate_epoch_fee(Clock::get()?.epoch, amount)
+        .ok_or(AmmError::MathOverflow)?;
+    Ok(fee)
+}
+
+/// Simulate an exact-input curve swap the way `swap` would execute it.
+///
+/// Unlike `quote_swap`, which prices the nominal `amount_in` against the
+/// reserves, this nets Token-2022 transfer fees off the input and output and
+/// applies the stale-reserve surcharge. It still prices off the booked
+/// reserves, not the vault balances: swaps never price donations or
+/// uncollected protocol fees, so a vault-based quote would overstate the
+/// output. Vault drift is reported in `in_sync` instead, and the output is
+/// checked against the actual vault balance. Oracle pricing is not applied.
+pub fn handler(ctx: Context<SimulateSwap>, amount_in: u64, a_to_b: bool) -> Result<SwapSimulation> {
+    let pool = &ctx.accounts.pool;
+    require!(pool.is_initialized(), AmmError::PoolNotInitialized);
+
+    let (reserve_in, reserve_out, mint_in, mint_out, vault_out) = if a_to_b {
+        (
+            pool.reserve_a,
+            pool.reserve_b,
+            &ctx.accounts.token_a_mint,
+            &ctx.accounts.token_b_mint,
+            &ctx.accounts.token_b_vault,
+        )
+    } else {
+        (
+            pool.reserve_b,
+            pool.reserve_a,
+            &ctx.accounts.token_b_mint,
+            &ctx.accounts.token_a_mint,
+            &ctx.accounts.token_a_vault,
+        )
+    };
+
+    let amount_in_received = amount_in - transfer_fee(&mint_in.to_account_info(), amount_in)?;
+
+    let staleness_surcharge = calculate_staleness_surcharge(
+        amount_in_received,
+        Clock::get()?.slot.saturating_sub(pool.last_update_slot),
+        pool.stale_after_slots,
+        pool.stale_surcharge_bps,
+    )?;
+    let (amount_out, fee_amount, _) = calculate_swap_output(
+        amount_in_received - staleness_surcharge,
+        reserve_in,
+        reserve_out,
+        pool.swap_fee_numerator(a_to_b),
+        pool.fee_denominator,
+        pool.protocol_fee_numerator,
+        pool.protocol_fee_denominator,
+        pool.curve(),
+    )?;
+    require!(amount_out <= vault_out.amount, AmmError::VaultBalanceMismatch);
+
+    let amount_out_received = amount_out - transfer_fee(&mint_out.to_account_info(), amount_out)?;
+
+    Ok(SwapSimulation {
+        amount_in_received,
+        amount_out,
+        amount_out_received,
+        fee_amount: fee_amount + staleness_surcharge,
+        price_impact_bps: calculate_price_impact_bps(
+            amount_in_received,
+            amount_out,
+            reserve_in,
+            reserve_out,
+        )?,
+        in_sync: pool.validate_reserves(ctx.accounts.token_a_vault.amount, ctx.accounts.token_b_vault.amount),
+    })
+}
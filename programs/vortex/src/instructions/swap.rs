@@ -1,11 +1,20 @@
 use anchor_lang::prelude::*;
-use anchor_spl::token::{self, Token, TokenAccount, Transfer};
+use anchor_lang::solana_program::sysvar::instructions::{
+    get_instruction_relative, ID as INSTRUCTIONS_SYSVAR_ID,
+};
+use anchor_spl::token::{self, Mint, MintTo, Token, TokenAccount};
+use anchor_spl::token_interface::{self, TokenInterface, TransferChecked};
 
-use crate::state::Pool;
+use crate::state::{
+    FeeTier, Pool, PriceCheckpoint, PriceCheckpoints, PriceOracle, SwapHistory, SwapRecord, UserSwapState,
+};
 use crate::constants::*;
 use crate::errors::AmmError;
-use crate::events::SwapExecuted;
-use crate::math::{calculate_swap_output, verify_invariant};
+use crate::events::{OraclePriceUpdate, SwapExecuted, SwapExecutedCompact};
+use crate::math::{
+    calculate_lp_rebate, calculate_oracle_swap_output, calculate_price_impact_bps,
+    calculate_staleness_surcharge, calculate_swap_output, verify_invariant,
+};
 
 #[derive(Accounts)]
 pub struct Swap<'info> {
@@ -29,45 +38,240 @@ pub struct Swap<'info> {
         bump,
         constraint = token_a_vault.key() == pool.token_a_vault @ AmmError::InvalidVault
     )]
-    pub token_a_vault: Account<'info, TokenAccount>,
+    pub token_a_vault: InterfaceAccount<'info, token_interface::TokenAccount>,
 
     /// Token B vault
     #[account(
         mut,
         seeds = [VAULT_B_SEED, pool.key().as_ref()],
         bump,
-        constraint = token_b_vault.key() == pool.token_b_vault @ AmmError::InvalidVault
+        constraint = token_b_vault.key() == pool.token_b_vault @ AmmError::InvalidVault,
+        constraint = token_b_vault.key() != token_a_vault.key() @ AmmError::InvalidVault
     )]
-    pub token_b_vault: Account<'info, TokenAccount>,
+    pub token_b_vault: InterfaceAccount<'info, token_interface::TokenAccount>,
 
     /// User's token A account
+    ///
+    /// Must belong to the user when it is the input side; as the output side
+    /// it may be any token A account, so proceeds can go to a third party.
     #[account(
         mut,
         constraint = user_token_a.mint == pool.token_a_mint @ AmmError::InvalidTokenMint
     )]
-    pub user_token_a: Account<'info, TokenAccount>,
+    pub user_token_a: InterfaceAccount<'info, token_interface::TokenAccount>,
 
-    /// User's token B account
+    /// User's token B account, owned by the user only when it is the input
+    ///
+    /// Distinct mints already keep the two user accounts apart; the key
+    /// check makes the invariant explicit rather than implied.
     #[account(
         mut,
-        constraint = user_token_b.mint == pool.token_b_mint @ AmmError::InvalidTokenMint
+        constraint = user_token_b.mint == pool.token_b_mint @ AmmError::InvalidTokenMint,
+        constraint = user_token_b.key() != user_token_a.key() @ AmmError::DuplicateAccount
     )]
-    pub user_token_b: Account<'info, TokenAccount>,
+    pub user_token_b: InterfaceAccount<'info, token_interface::TokenAccount>,
 
+    /// Token A mint
+    #[account(
+        constraint = token_a_mint.key() == pool.token_a_mint @ AmmError::InvalidTokenMint
+    )]
+    pub token_a_mint: Box<InterfaceAccount<'info, token_interface::Mint>>,
+
+    /// Token B mint
+    #[account(
+        constraint = token_b_mint.key() == pool.token_b_mint @ AmmError::InvalidTokenMint
+    )]
+    pub token_b_mint: Box<InterfaceAccount<'info, token_interface::Mint>>,
+
+    /// Token program for token A
+    #[account(
+        constraint = token_a_program.key() == pool.token_program_a() @ AmmError::InvalidTokenProgram
+    )]
+    pub token_a_program: Interface<'info, TokenInterface>,
+
+    /// Token program for token B
+    #[account(
+        constraint = token_b_program.key() == pool.token_program_b() @ AmmError::InvalidTokenProgram
+    )]
+    pub token_b_program: Interface<'info, TokenInterface>,
+
+    /// SPL Token program (LP mint)
     pub token_program: Program<'info, Token>,
+
+    /// Instructions sysvar, only needed when the caller expects the
+    /// market-maker fee exemption to apply
+    /// CHECK: address is constrained to the instructions sysvar
+    #[account(address = INSTRUCTIONS_SYSVAR_ID)]
+    pub instructions_sysvar: Option<UncheckedAccount<'info>>,
+
+    /// Reference price oracle, required for oracle-priced pools
+    #[account(
+        constraint = oracle.key() == pool.oracle @ AmmError::OracleRequired
+    )]
+    pub oracle: Option<Account<'info, PriceOracle>>,
+
+    /// Optional trade tape; pools without one pay no overhead
+    #[account(
+        mut,
+        seeds = [SWAP_HISTORY_SEED, pool.key().as_ref()],
+        bump = swap_history.bump
+    )]
+    pub swap_history: Option<Account<'info, SwapHistory>>,
+
+    /// Optional TWAP checkpoint buffer, appended to on each swap
+    #[account(
+        mut,
+        constraint = price_checkpoints.pool == pool.key()
+    )]
+    pub price_checkpoints: Option<Account<'info, PriceCheckpoints>>,
+
+    /// LP mint; its supply scales the fee growth index, and fee rebates
+    /// are minted from it
+    #[account(
+        mut,
+        constraint = lp_mint.key() == pool.lp_mint @ AmmError::InvalidVault
+    )]
+    pub lp_mint: Account<'info, Mint>,
+
+    /// LP mint authority, needed to receive a fee rebate
+    /// CHECK: PDA used as mint authority
+    #[account(
+        seeds = [LP_MINT_AUTHORITY_SEED, pool.key().as_ref()],
+        bump = pool.lp_mint_authority_bump
+    )]
+    pub lp_mint_authority: Option<UncheckedAccount<'info>>,
+
+    /// User's LP token account receiving the fee rebate
+    #[account(
+        mut,
+        constraint = user_lp_token.mint == pool.lp_mint @ AmmError::InvalidTokenMint
+    )]
+    pub user_lp_token: Option<Account<'info, TokenAccount>>,
+
+    /// User's swap record, required while the pool has a swap cooldown
+    #[account(
+        mut,
+        seeds = [USER_SWAP_SEED, pool.key().as_ref(), user.key().as_ref()],
+        bump = user_swap_state.bump
+    )]
+    pub user_swap_state: Option<Account<'info, UserSwapState>>,
+
+    /// User's fee discount, if the pool has granted one
+    #[account(
+        seeds = [FEE_TIER_SEED, pool.key().as_ref(), user.key().as_ref()],
+        bump = fee_tier.bump
+    )]
+    pub fee_tier: Option<Account<'info, FeeTier>>,
+}
+
+/// Whether this swap was reached via CPI from the pool's exempt program.
+///
+/// The top-level instruction is read from the instructions sysvar: if its
+/// program is the exempt program, this swap is running somewhere inside
+/// that program's CPI tree. The pool authority is therefore trusting the
+/// exempt program (and anything it lets callers CPI through) not to proxy
+/// arbitrary third-party swaps.
+fn is_fee_exempt(pool: &Pool, instructions_sysvar: Option<&UncheckedAccount>) -> Result<bool> {
+    if !pool.has_exempt_program() {
+        return Ok(false);
+    }
+    let Some(sysvar) = instructions_sysvar else {
+        return Ok(false);
+    };
+    let top_level = get_instruction_relative(0, &sysvar.to_account_info())?;
+    Ok(top_level.program_id == pool.exempt_program && top_level.program_id != crate::ID)
 }
 
+/// Fee numerator this swap is charged: waived for CPIs from the configured
+/// market-maker program, otherwise the direction's fee less any fee-tier
+/// discount. Shared with the instructions that price a swap ahead of it.
+pub(crate) fn effective_fee_numerator(accounts: &Swap, a_to_b: bool) -> Result<u64> {
+    let fee_exempt = is_fee_exempt(&accounts.pool, accounts.instructions_sysvar.as_ref())?;
+    Ok(fee_numerator_for(accounts, fee_exempt, a_to_b))
+}
+
+fn fee_numerator_for(accounts: &Swap, fee_exempt: bool, a_to_b: bool) -> u64 {
+    let pool = &accounts.pool;
+    match (fee_exempt, accounts.fee_tier.as_ref()) {
+        (true, _) => 0,
+        (false, Some(fee_tier)) => {
+            fee_tier.discounted_fee_numerator(pool.swap_fee_numerator(a_to_b), pool.fee_denominator)
+        }
+        (false, None) => pool.swap_fee_numerator(a_to_b),
+    }
+}
+
+/// Execute an exact-input swap.
+///
+/// This is the hot path integrators compose into larger transactions, so it
+/// fetches the clock once, reads pool fields through a single borrow and logs
+/// only the event.
+///
+/// `max_price_impact_bps` (0 = off) bounds how far the execution price may
+/// fall below the pre-swap spot price, guarding against fat-finger trades
+/// that a loose `min_amount_out` would let through.
+#[allow(clippy::too_many_arguments)]
 pub fn handler(
     ctx: Context<Swap>,
     amount_in: u64,
     min_amount_out: u64,
     a_to_b: bool,
+    min_reserve_in: u64,
+    min_reserve_out: u64,
+    deadline: i64,
+    max_price_impact_bps: u64,
 ) -> Result<()> {
-    require!(amount_in >= MIN_SWAP_AMOUNT, AmmError::AmountTooSmall);
+    execute(
+        ctx,
+        amount_in,
+        min_amount_out,
+        a_to_b,
+        min_reserve_in,
+        min_reserve_out,
+        deadline,
+        max_price_impact_bps,
+        true,
+    )
+}
 
+/// `handler` with the `min_swap_amount` dust check optional.
+///
+/// Composed swaps whose input is the output of an earlier leg (route hops
+/// after the first) pass `enforce_min_swap = false`: the trader chose the
+/// route's input, not the intermediate amount, and that amount is in
+/// another pool's token so its dust threshold does not apply to it.
+#[allow(clippy::too_many_arguments)]
+pub(crate) fn execute(
+    ctx: Context<Swap>,
+    amount_in: u64,
+    min_amount_out: u64,
+    a_to_b: bool,
+    min_reserve_in: u64,
+    min_reserve_out: u64,
+    deadline: i64,
+    max_price_impact_bps: u64,
+    enforce_min_swap: bool,
+) -> Result<()> {
+    log_compute_units!("swap: start");
     let pool = &ctx.accounts.pool;
+    require!(
+        !enforce_min_swap || amount_in >= pool.min_swap_amount,
+        AmmError::AmountTooSmall
+    );
     require!(pool.is_initialized(), AmmError::PoolNotInitialized);
 
+    // Strict mode: refuse to price off reserves that no longer match the vaults
+    if pool.strict_reserves {
+        require!(
+            pool.validate_reserves(ctx.accounts.token_a_vault.amount, ctx.accounts.token_b_vault.amount),
+            AmmError::VaultBalanceMismatch
+        );
+    }
+
+    // One-sided pause on the token being sold
+    let pause_flag = if a_to_b { PAUSE_SWAP_A_IN } else { PAUSE_SWAP_B_IN };
+    require!(!pool.is_token_paused(pause_flag), AmmError::TokenPaused);
+
     // Determine reserves based on direction
     let (reserve_in, reserve_out) = if a_to_b {
         (pool.reserve_a, pool.reserve_b)
@@ -75,17 +279,39 @@ pub fn handler(
         (pool.reserve_b, pool.reserve_a)
     };
 
-    // Calculate output amount
-    let (amount_out, fee_amount) = calculate_swap_output(
-        amount_in,
-        reserve_in,
-        reserve_out,
-        pool.fee_numerator,
-        pool.fee_denominator,
-    )?;
+    // Opt-in depth guard (0 = off): reject if the pool was drained below
+    // what the trader expected, e.g. by a large withdrawal landing first
+    require!(reserve_in >= min_reserve_in, AmmError::InsufficientLiquidity);
+    require!(reserve_out >= min_reserve_out, AmmError::InsufficientLiquidity);
 
-    // Slippage check
-    require!(amount_out >= min_amount_out, AmmError::SlippageExceeded);
+    // Waive the fee for CPIs from the configured market-maker program
+    let fee_exempt = is_fee_exempt(pool, ctx.accounts.instructions_sysvar.as_ref())?;
+    let fee_numerator = fee_numerator_for(ctx.accounts, fee_exempt, a_to_b);
+
+    let clock = Clock::get()?;
+    require!(clock.unix_timestamp <= deadline, AmmError::DeadlineExceeded);
+
+    // Per-user cooldown (0 = off). Slows same-wallet sandwiches; a bot
+    // spreading its legs across wallets is not stopped by it.
+    if pool.min_slot_between_swaps > 0 {
+        let user_swap_state = ctx
+            .accounts
+            .user_swap_state
+            .as_ref()
+            .ok_or(AmmError::UserSwapStateRequired)?;
+        require!(
+            user_swap_state.is_cooldown_elapsed(clock.slot, pool.min_slot_between_swaps),
+            AmmError::SwapCooldown
+        );
+    }
+
+    // Only the debited account must be the signer's; the output may pay anyone
+    let input_owner = if a_to_b {
+        ctx.accounts.user_token_a.owner
+    } else {
+        ctx.accounts.user_token_b.owner
+    };
+    require_keys_eq!(input_owner, ctx.accounts.user.key(), AmmError::Unauthorized);
 
     // Determine accounts based on direction
     let (user_in, user_out, vault_in, vault_out) = if a_to_b {
@@ -104,75 +330,237 @@ pub fn handler(
         )
     };
 
+    let (mint_in, mint_out, program_in, program_out) = if a_to_b {
+        (
+            &ctx.accounts.token_a_mint,
+            &ctx.accounts.token_b_mint,
+            ctx.accounts.token_a_program.to_account_info(),
+            ctx.accounts.token_b_program.to_account_info(),
+        )
+    } else {
+        (
+            &ctx.accounts.token_b_mint,
+            &ctx.accounts.token_a_mint,
+            ctx.accounts.token_b_program.to_account_info(),
+            ctx.accounts.token_a_program.to_account_info(),
+        )
+    };
+
     // Transfer input tokens from user to vault
-    token::transfer(
+    let vault_in_before = if a_to_b {
+        ctx.accounts.token_a_vault.amount
+    } else {
+        ctx.accounts.token_b_vault.amount
+    };
+    token_interface::transfer_checked(
         CpiContext::new(
-            ctx.accounts.token_program.to_account_info(),
-            Transfer {
+            program_in,
+            TransferChecked {
                 from: user_in,
+                mint: mint_in.to_account_info(),
                 to: vault_in,
                 authority: ctx.accounts.user.to_account_info(),
             },
         ),
         amount_in,
+        mint_in.decimals,
     )?;
 
-    // Transfer output tokens from vault to user
-    let token_a_mint = ctx.accounts.pool.token_a_mint;
-    let token_b_mint = ctx.accounts.pool.token_b_mint;
-    let bump = ctx.accounts.pool.bump;
+    // Price the swap off what actually arrived: transfer-fee (Token-2022)
+    // mints deliver less than was sent
+    let vault_in_after = if a_to_b {
+        ctx.accounts.token_a_vault.reload()?;
+        ctx.accounts.token_a_vault.amount
+    } else {
+        ctx.accounts.token_b_vault.reload()?;
+        ctx.accounts.token_b_vault.amount
+    };
+    let amount_in = vault_in_after
+        .checked_sub(vault_in_before)
+        .ok_or(AmmError::MathOverflow)?;
+    require!(amount_in > 0, AmmError::AmountTooSmall);
 
+    // Calculate output amount: oracle price plus spread, or the curve
+    let mut staleness_surcharge = 0;
+    let mut protocol_fee = 0;
+    let (amount_out, fee_amount, oracle_price) = if pool.oracle_priced {
+        let oracle = ctx.accounts.oracle.as_ref().ok_or(AmmError::OracleRequired)?;
+        require!(
+            !oracle.is_stale(clock.unix_timestamp, pool.oracle_max_staleness),
+            AmmError::OracleStale
+        );
+        let (amount_out, spread_amount) = calculate_oracle_swap_output(
+            amount_in,
+            reserve_out,
+            oracle.price,
+            pool.oracle_spread_bps,
+            a_to_b,
+        )?;
+        (amount_out, spread_amount, oracle.price)
+    } else {
+        // Surcharge is taken off the input up front and left in the pool
+        staleness_surcharge = calculate_staleness_surcharge(
+            amount_in,
+            clock.slot.saturating_sub(pool.last_update_slot),
+            pool.stale_after_slots,
+            pool.stale_surcharge_bps,
+        )?;
+        let (amount_out, fee_amount, protocol_share) = calculate_swap_output(
+            amount_in - staleness_surcharge,
+            reserve_in,
+            reserve_out,
+            fee_numerator,
+            pool.fee_denominator,
+            pool.protocol_fee_numerator,
+            pool.protocol_fee_denominator,
+            pool.curve(),
+        )?;
+        protocol_fee = protocol_share;
+        (amount_out, fee_amount + staleness_surcharge, 0)
+    };
+
+    // Slippage check
+    require!(amount_out >= min_amount_out, AmmError::SlippageExceeded);
+
+    // Price impact: shortfall of the execution price amount_out / amount_in
+    // against the pre-swap spot price reserve_out / reserve_in, fees included
+    let price_impact_bps =
+        calculate_price_impact_bps(amount_in, amount_out, reserve_in, reserve_out)?;
+    require!(
+        max_price_impact_bps == 0 || price_impact_bps <= max_price_impact_bps,
+        AmmError::PriceImpactTooHigh
+    );
+
+    // Transfer output tokens from vault to user
     let seeds = &[
         POOL_SEED,
-        token_a_mint.as_ref(),
-        token_b_mint.as_ref(),
-        &[bump],
+        pool.token_a_mint.as_ref(),
+        pool.token_b_mint.as_ref(),
+        &[pool.bump],
     ];
     let signer_seeds = &[&seeds[..]];
 
-    token::transfer(
+    token_interface::transfer_checked(
         CpiContext::new_with_signer(
-            ctx.accounts.token_program.to_account_info(),
-            Transfer {
+            program_out,
+            TransferChecked {
                 from: vault_out,
+                mint: mint_out.to_account_info(),
                 to: user_out,
                 authority: ctx.accounts.pool.to_account_info(),
             },
             signer_seeds,
         ),
         amount_out,
+        mint_out.decimals,
     )?;
 
-    // Calculate new reserves
+    // Calculate new reserves. The protocol fee stays in the vault but is
+    // tracked in protocol_fees_a/b, not the reserves, so LPs never own it.
+    let new_reserve_in = reserve_in
+        .checked_add(amount_in - protocol_fee)
+        .ok_or(AmmError::MathOverflow)?;
+    let new_reserve_out = reserve_out.checked_sub(amount_out).ok_or(AmmError::MathOverflow)?;
     let (new_reserve_a, new_reserve_b) = if a_to_b {
-        (
-            pool.reserve_a.checked_add(amount_in).ok_or(AmmError::MathOverflow)?,
-            pool.reserve_b.checked_sub(amount_out).ok_or(AmmError::MathOverflow)?,
-        )
+        (new_reserve_in, new_reserve_out)
     } else {
-        (
-            pool.reserve_a.checked_sub(amount_out).ok_or(AmmError::MathOverflow)?,
-            pool.reserve_b.checked_add(amount_in).ok_or(AmmError::MathOverflow)?,
-        )
+        (new_reserve_out, new_reserve_in)
     };
 
-    // Verify invariant k did not decrease
-    verify_invariant(pool.reserve_a, pool.reserve_b, new_reserve_a, new_reserve_b)?;
+    require!(
+        pool.within_reserve_cap(new_reserve_a, new_reserve_b),
+        AmmError::ReserveCapExceeded
+    );
+
+    // Verify invariant k did not decrease (oracle-priced pools are
+    // inventory only and do not follow the curve)
+    if !pool.oracle_priced {
+        verify_invariant(pool.reserve_a, pool.reserve_b, new_reserve_a, new_reserve_b, pool.curve())?;
+    }
+
+    // Rebate part of the fee as LP tokens, if configured and the swapper
+    // supplied the LP accounts; capped by the remaining budget
+    let mut lp_rebate = 0;
+    let lp_mint = &ctx.accounts.lp_mint;
+    let lp_supply = lp_mint.supply;
+    if let (true, Some(lp_mint_authority), Some(user_lp_token)) = (
+        pool.rebate_bps > 0 && pool.rebate_budget > 0,
+        ctx.accounts.lp_mint_authority.as_ref(),
+        ctx.accounts.user_lp_token.as_ref(),
+    ) {
+        lp_rebate = calculate_lp_rebate(
+            fee_amount - protocol_fee,
+            pool.rebate_bps,
+            new_reserve_in,
+            lp_mint.supply,
+        )?
+        .min(pool.rebate_budget);
+
+        if lp_rebate > 0 {
+            let pool_key = pool.key();
+            let seeds = &[
+                LP_MINT_AUTHORITY_SEED,
+                pool_key.as_ref(),
+                &[pool.lp_mint_authority_bump],
+            ];
+            token::mint_to(
+                CpiContext::new_with_signer(
+                    ctx.accounts.token_program.to_account_info(),
+                    MintTo {
+                        mint: lp_mint.to_account_info(),
+                        to: user_lp_token.to_account_info(),
+                        authority: lp_mint_authority.to_account_info(),
+                    },
+                    &[&seeds[..]],
+                ),
+                lp_rebate,
+            )?;
+        }
+    }
 
     // Update pool state
-    let clock = Clock::get()?;
     let pool = &mut ctx.accounts.pool;
+    pool.accumulate_prices(clock.unix_timestamp);
+
+    pool.rebate_budget -= lp_rebate;
 
     pool.update_reserves(new_reserve_a, new_reserve_b);
 
-    // Record stats
+    // Record stats; cumulative fees count only the LPs' share
+    let lp_fee = fee_amount - protocol_fee;
     let (volume_a, volume_b, fee_a, fee_b) = if a_to_b {
-        (amount_in, amount_out, fee_amount, 0u64)
+        pool.protocol_fees_a = pool.protocol_fees_a.checked_add(protocol_fee).ok_or(AmmError::MathOverflow)?;
+        (amount_in, amount_out, lp_fee, 0u64)
     } else {
-        (amount_out, amount_in, 0u64, fee_amount)
+        pool.protocol_fees_b = pool.protocol_fees_b.checked_add(protocol_fee).ok_or(AmmError::MathOverflow)?;
+        (amount_out, amount_in, 0u64, lp_fee)
     };
 
     pool.record_swap(volume_a, volume_b, fee_a, fee_b, clock.unix_timestamp, clock.slot);
+    pool.accrue_fee_growth(fee_a, fee_b, lp_supply);
+
+    if let Some(user_swap_state) = ctx.accounts.user_swap_state.as_mut() {
+        user_swap_state.last_swap_slot = clock.slot;
+    }
+
+    if let Some(history) = ctx.accounts.swap_history.as_mut() {
+        history.push(SwapRecord {
+            amount_in,
+            amount_out,
+            a_to_b,
+            price: pool.price_a(),
+            timestamp: clock.unix_timestamp,
+        });
+    }
+
+    // Accumulators were brought up to now before the reserves changed
+    if let Some(buffer) = ctx.accounts.price_checkpoints.as_mut() {
+        buffer.push(PriceCheckpoint {
+            timestamp: clock.unix_timestamp,
+            price_cumulative_a: pool.price_cumulative_a,
+            price_cumulative_b: pool.price_cumulative_b,
+        });
+    }
 
     let (token_in, token_out) = if a_to_b {
         (pool.token_a_mint, pool.token_b_mint)
@@ -180,20 +568,44 @@ pub fn handler(
         (pool.token_b_mint, pool.token_a_mint)
     };
 
-    emit!(SwapExecuted {
-        pool: pool.key(),
-        user: ctx.accounts.user.key(),
-        token_in,
-        token_out,
-        amount_in,
-        amount_out,
-        fee_amount,
-        reserve_a: pool.reserve_a,
-        reserve_b: pool.reserve_b,
-        timestamp: clock.unix_timestamp,
-    });
+    match pool.event_verbosity {
+        EVENT_VERBOSITY_COMPACT => emit!(SwapExecutedCompact {
+            pool: pool.key(),
+            a_to_b,
+            amount_in,
+            amount_out,
+            fee_amount,
+        }),
+        EVENT_VERBOSITY_NONE => {}
+        _ => emit!(SwapExecuted {
+            pool: pool.key(),
+            user: ctx.accounts.user.key(),
+            token_in,
+            token_out,
+            amount_in,
+            amount_out,
+            fee_amount,
+            fee_exempt,
+            price_impact_bps,
+            lp_rebate,
+            staleness_surcharge,
+            oracle_price,
+            reserve_a: pool.reserve_a,
+            reserve_b: pool.reserve_b,
+            timestamp: clock.unix_timestamp,
+        }),
+    }
 
-    msg!("Swapped {} for {}, fee: {}", amount_in, amount_out, fee_amount);
+    if pool.event_verbosity != EVENT_VERBOSITY_NONE {
+        emit!(OraclePriceUpdate {
+            pool: pool.key(),
+            price: pool.price_a(),
+            price_high: pool.price_high,
+            price_low: pool.price_low,
+            timestamp: clock.unix_timestamp,
+        });
+    }
 
+    log_compute_units!("swap: end");
     Ok(())
 }
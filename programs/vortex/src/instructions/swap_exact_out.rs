@@ -0,0 +1,56 @@
+use anchor_lang::prelude::*;
+
+use crate::constants::*;
+use crate::errors::AmmError;
+use crate::instructions::swap::{self, Swap};
+use crate::math::{calculate_swap_input, Curve};
+
+/// Swap for an exact `amount_out`, spending at most `max_amount_in`.
+///
+/// Uses the regular `Swap` accounts. The required input is solved from the
+/// curve (grossed up for any staleness surcharge) and then executed as an
+/// exact-input swap with `amount_out` as its minimum, so every swap-side
+/// feature and check still applies. Rounding may deliver a unit or so more
+/// than `amount_out`, never less.
+pub fn handler<'info>(
+    ctx: Context<'_, '_, '_, 'info, Swap<'info>>,
+    amount_out: u64,
+    max_amount_in: u64,
+    a_to_b: bool,
+    deadline: i64,
+) -> Result<()> {
+    let pool = &ctx.accounts.pool;
+    require!(
+        !pool.oracle_priced && pool.curve() == Curve::ConstantProduct,
+        AmmError::ExactOutUnsupported
+    );
+
+    let (reserve_in, reserve_out) = if a_to_b {
+        (pool.reserve_a, pool.reserve_b)
+    } else {
+        (pool.reserve_b, pool.reserve_a)
+    };
+
+    // Solve with the fee the swap will charge
+    let fee_numerator = swap::effective_fee_numerator(ctx.accounts, a_to_b)?;
+    let (mut amount_in, _) = calculate_swap_input(
+        amount_out,
+        reserve_in,
+        reserve_out,
+        fee_numerator,
+        pool.fee_denominator,
+    )?;
+
+    // The staleness surcharge is taken off the input before the curve
+    let slots_since_update = Clock::get()?.slot.saturating_sub(pool.last_update_slot);
+    if pool.stale_after_slots > 0 && slots_since_update > pool.stale_after_slots {
+        let keep_bps = BPS_DENOMINATOR - pool.stale_surcharge_bps as u64;
+        amount_in = ((amount_in as u128 * BPS_DENOMINATOR as u128).div_ceil(keep_bps as u128))
+            .try_into()
+            .map_err(|_| AmmError::MathOverflow)?;
+    }
+
+    require!(amount_in <= max_amount_in, AmmError::SlippageExceeded);
+
+    swap::handler(ctx, amount_in, amount_out, a_to_b, 0, 0, deadline, 0)
+}
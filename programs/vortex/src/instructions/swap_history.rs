@@ -0,0 +1,62 @@
+use anchor_lang::prelude::*;
+
+use crate::state::{Pool, SwapHistory, SwapRecord};
+use crate::constants::*;
+use crate::errors::AmmError;
+
+#[derive(Accounts)]
+pub struct InitializeSwapHistory<'info> {
+    /// Rent payer
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    /// Pool state
+    #[account(
+        seeds = [POOL_SEED, pool.token_a_mint.as_ref(), pool.token_b_mint.as_ref()],
+        bump = pool.bump
+    )]
+    pub pool: Account<'info, Pool>,
+
+    /// Swap history ring buffer
+    #[account(
+        init,
+        payer = payer,
+        space = 8 + SwapHistory::INIT_SPACE,
+        seeds = [SWAP_HISTORY_SEED, pool.key().as_ref()],
+        bump
+    )]
+    pub swap_history: Account<'info, SwapHistory>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct GetRecentSwaps<'info> {
+    /// Swap history ring buffer
+    #[account(
+        seeds = [SWAP_HISTORY_SEED, swap_history.pool.as_ref()],
+        bump = swap_history.bump
+    )]
+    pub swap_history: Account<'info, SwapHistory>,
+}
+
+/// Create the optional trade tape for a pool (anyone may pay for it)
+pub fn initialize_swap_history(ctx: Context<InitializeSwapHistory>) -> Result<()> {
+    let history = &mut ctx.accounts.swap_history;
+    history.bump = ctx.bumps.swap_history;
+    history.pool = ctx.accounts.pool.key();
+    history.head = 0;
+    history.count = 0;
+    history.records = [SwapRecord::default(); SWAP_HISTORY_LEN];
+
+    msg!("Swap history initialized for pool {}", history.pool);
+
+    Ok(())
+}
+
+/// Return the recorded swaps, oldest first
+pub fn get_recent_swaps(ctx: Context<GetRecentSwaps>) -> Result<Vec<SwapRecord>> {
+    let history = &ctx.accounts.swap_history;
+    require!(history.count > 0, AmmError::SwapHistoryEmpty);
+    Ok(history.recent())
+}
@@ -0,0 +1,260 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{self, TokenInterface, TransferChecked};
+
+use crate::state::Pool;
+use crate::constants::*;
+use crate::errors::AmmError;
+use crate::events::ProtocolRebalance;
+use crate::math::{calculate_swap_output, verify_invariant};
+
+/// Fee-free swap reserved for the pool authority to rebalance
+/// protocol-owned liquidity. Not counted towards swap statistics.
+#[derive(Accounts)]
+pub struct SwapNoFee<'info> {
+    /// Pool authority
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    /// Pool state
+    #[account(
+        mut,
+        seeds = [POOL_SEED, pool.token_a_mint.as_ref(), pool.token_b_mint.as_ref()],
+        bump = pool.bump,
+        constraint = pool.authority == authority.key() @ AmmError::Unauthorized,
+        constraint = !pool.paused @ AmmError::PoolPaused
+    )]
+    pub pool: Account<'info, Pool>,
+
+    /// Token A vault
+    #[account(
+        mut,
+        seeds = [VAULT_A_SEED, pool.key().as_ref()],
+        bump,
+        constraint = token_a_vault.key() == pool.token_a_vault @ AmmError::InvalidVault
+    )]
+    pub token_a_vault: InterfaceAccount<'info, token_interface::TokenAccount>,
+
+    /// Token B vault
+    #[account(
+        mut,
+        seeds = [VAULT_B_SEED, pool.key().as_ref()],
+        bump,
+        constraint = token_b_vault.key() == pool.token_b_vault @ AmmError::InvalidVault
+    )]
+    pub token_b_vault: InterfaceAccount<'info, token_interface::TokenAccount>,
+
+    /// Authority's token A account
+    #[account(
+        mut,
+        constraint = authority_token_a.mint == pool.token_a_mint @ AmmError::InvalidTokenMint
+    )]
+    pub authority_token_a: InterfaceAccount<'info, token_interface::TokenAccount>,
+
+    /// Authority's token B account
+    #[account(
+        mut,
+        constraint = authority_token_b.mint == pool.token_b_mint @ AmmError::InvalidTokenMint
+    )]
+    pub authority_token_b: InterfaceAccount<'info, token_interface::TokenAccount>,
+
+    /// Token A mint
+    #[account(
+        constraint = token_a_mint.key() == pool.token_a_mint @ AmmError::InvalidTokenMint
+    )]
+    pub token_a_mint: Box<InterfaceAccount<'info, token_interface::Mint>>,
+
+    /// Token B mint
+    #[account(
+        constraint = token_b_mint.key() == pool.token_b_mint @ AmmError::InvalidTokenMint
+    )]
+    pub token_b_mint: Box<InterfaceAccount<'info, token_interface::Mint>>,
+
+    /// Token program for token A
+    #[account(
+        constraint = token_a_program.key() == pool.token_program_a() @ AmmError::InvalidTokenProgram
+    )]
+    pub token_a_program: Interface<'info, TokenInterface>,
+
+    /// Token program for token B
+    #[account(
+        constraint = token_b_program.key() == pool.token_program_b() @ AmmError::InvalidTokenProgram
+    )]
+    pub token_b_program: Interface<'info, TokenInterface>,
+}
+
+pub fn handler(
+    ctx: Context<SwapNoFee>,
+    amount_in: u64,
+    min_amount_out: u64,
+    a_to_b: bool,
+) -> Result<()> {
+    let pool = &ctx.accounts.pool;
+    require!(amount_in >= pool.min_swap_amount, AmmError::AmountTooSmall);
+    require!(pool.is_initialized(), AmmError::PoolNotInitialized);
+
+    let (reserve_in, reserve_out) = if a_to_b {
+        (pool.reserve_a, pool.reserve_b)
+    } else {
+        (pool.reserve_b, pool.reserve_a)
+    };
+
+    let (user_in, user_out, vault_in, vault_out) = if a_to_b {
+        (
+            ctx.accounts.authority_token_a.to_account_info(),
+            ctx.accounts.authority_token_b.to_account_info(),
+            ctx.accounts.token_a_vault.to_account_info(),
+            ctx.accounts.token_b_vault.to_account_info(),
+        )
+    } else {
+        (
+            ctx.accounts.authority_token_b.to_account_info(),
+            ctx.accounts.authority_token_a.to_account_info(),
+            ctx.accounts.token_b_vault.to_account_info(),
+            ctx.accounts.token_a_vault.to_account_info(),
+        )
+    };
+
+    let (mint_in, mint_out, program_in, program_out) = if a_to_b {
+        (
+            &ctx.accounts.token_a_mint,
+            &ctx.accounts.token_b_mint,
+            ctx.accounts.token_a_program.to_account_info(),
+            ctx.accounts.token_b_program.to_account_info(),
+        )
+    } else {
+        (
+            &ctx.accounts.token_b_mint,
+            &ctx.accounts.token_a_mint,
+            ctx.accounts.token_b_program.to_account_info(),
+            ctx.accounts.token_a_program.to_account_info(),
+        )
+    };
+
+    // Transfer input tokens from authority to vault
+    let vault_in_before = if a_to_b {
+        ctx.accounts.token_a_vault.amount
+    } else {
+        ctx.accounts.token_b_vault.amount
+    };
+    token_interface::transfer_checked(
+        CpiContext::new(
+            program_in,
+            TransferChecked {
+                from: user_in,
+                mint: mint_in.to_account_info(),
+                to: vault_in,
+                authority: ctx.accounts.authority.to_account_info(),
+            },
+        ),
+        amount_in,
+        mint_in.decimals,
+    )?;
+
+    // Price the swap off what actually arrived: transfer-fee (Token-2022)
+    // mints deliver less than was sent
+    let vault_in_after = if a_to_b {
+        ctx.accounts.token_a_vault.reload()?;
+        ctx.accounts.token_a_vault.amount
+    } else {
+        ctx.accounts.token_b_vault.reload()?;
+        ctx.accounts.token_b_vault.amount
+    };
+    let amount_in = vault_in_after
+        .checked_sub(vault_in_before)
+        .ok_or(AmmError::MathOverflow)?;
+    require!(amount_in > 0, AmmError::AmountTooSmall);
+
+    // Zero fee numerator: output follows the bare constant-product curve
+    let pool = &ctx.accounts.pool;
+    let (amount_out, _, _) = calculate_swap_output(
+        amount_in,
+        reserve_in,
+        reserve_out,
+        0,
+        pool.fee_denominator,
+        0,
+        0,
+        pool.curve(),
+    )?;
+
+    require!(amount_out >= min_amount_out, AmmError::SlippageExceeded);
+
+    // Transfer output tokens from vault to authority
+    let token_a_mint = ctx.accounts.pool.token_a_mint;
+    let token_b_mint = ctx.accounts.pool.token_b_mint;
+    let bump = ctx.accounts.pool.bump;
+
+    let seeds = &[
+        POOL_SEED,
+        token_a_mint.as_ref(),
+        token_b_mint.as_ref(),
+        &[bump],
+    ];
+    let signer_seeds = &[&seeds[..]];
+
+    token_interface::transfer_checked(
+        CpiContext::new_with_signer(
+            program_out,
+            TransferChecked {
+                from: vault_out,
+                mint: mint_out.to_account_info(),
+                to: user_out,
+                authority: ctx.accounts.pool.to_account_info(),
+            },
+            signer_seeds,
+        ),
+        amount_out,
+        mint_out.decimals,
+    )?;
+
+    let (new_reserve_a, new_reserve_b) = if a_to_b {
+        (
+            pool.reserve_a.checked_add(amount_in).ok_or(AmmError::MathOverflow)?,
+            pool.reserve_b.checked_sub(amount_out).ok_or(AmmError::MathOverflow)?,
+        )
+    } else {
+        (
+            pool.reserve_a.checked_sub(amount_out).ok_or(AmmError::MathOverflow)?,
+            pool.reserve_b.checked_add(amount_in).ok_or(AmmError::MathOverflow)?,
+        )
+    };
+
+    require!(
+        pool.within_reserve_cap(new_reserve_a, new_reserve_b),
+        AmmError::ReserveCapExceeded
+    );
+
+    // With no fee, k only moves by output rounding; it must never decrease
+    verify_invariant(pool.reserve_a, pool.reserve_b, new_reserve_a, new_reserve_b, pool.curve())?;
+
+    let clock = Clock::get()?;
+    let pool = &mut ctx.accounts.pool;
+    pool.accumulate_prices(clock.unix_timestamp);
+
+    // Reserves only: protocol rebalancing is not user volume, so
+    // record_swap is intentionally skipped
+    pool.update_reserves(new_reserve_a, new_reserve_b);
+    pool.last_update_slot = clock.slot;
+
+    let (token_in, token_out) = if a_to_b {
+        (pool.token_a_mint, pool.token_b_mint)
+    } else {
+        (pool.token_b_mint, pool.token_a_mint)
+    };
+
+    emit!(ProtocolRebalance {
+        pool: pool.key(),
+        authority: ctx.accounts.authority.key(),
+        token_in,
+        token_out,
+        amount_in,
+        amount_out,
+        reserve_a: pool.reserve_a,
+        reserve_b: pool.reserve_b,
+        timestamp: clock.unix_timestamp,
+    });
+
+    msg!("Protocol rebalance: swapped {} for {} (no fee)", amount_in, amount_out);
+
+    Ok(())
+}
@@ -0,0 +1,183 @@
+use std::collections::BTreeSet;
+
+use anchor_lang::prelude::*;
+use anchor_lang::system_program::{self, Allocate, Assign, CreateAccount, Transfer};
+use anchor_spl::token::spl_token::native_mint;
+use anchor_spl::token::{self, CloseAccount, InitializeAccount3, SyncNative, Token, TokenAccount};
+
+use crate::state::Pool;
+use crate::constants::*;
+use crate::errors::AmmError;
+use crate::instructions::swap::{self, Swap, SwapBumps};
+
+#[derive(Accounts)]
+pub struct SwapSol<'info> {
+    /// Trader paying or receiving native SOL; must also be the swap's `user`
+    #[account(mut)]
+    pub user: Signer<'info>,
+
+    /// Temporary wSOL account, created and closed within the instruction
+    /// CHECK: must be empty on entry; initialized here as a token account
+    #[account(
+        mut,
+        seeds = [WSOL_SEED, user.key().as_ref()],
+        bump
+    )]
+    pub wsol_account: UncheckedAccount<'info>,
+
+    /// Native SOL mint
+    /// CHECK: address is constrained to the native mint
+    #[account(address = native_mint::ID)]
+    pub native_mint: UncheckedAccount<'info>,
+
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}
+
+/// Swap native SOL against a SOL pool without a standing wSOL account.
+///
+/// `remaining_accounts` holds the full `Swap` account list, with
+/// `wsol_account` as the user's token account for the SOL side. The wSOL
+/// account is created (funded with `amount_in` when selling SOL), the swap
+/// runs through the regular handler, and the account is then closed so all
+/// of its lamports, including any SOL bought, return to the user.
+pub fn handler<'info>(
+    ctx: Context<'_, '_, 'info, 'info, SwapSol<'info>>,
+    amount_in: u64,
+    min_amount_out: u64,
+    a_to_b: bool,
+    deadline: i64,
+) -> Result<()> {
+    let user_key = ctx.accounts.user.key();
+    let seeds = &[WSOL_SEED, user_key.as_ref(), &[ctx.bumps.wsol_account]];
+    let signer_seeds = &[&seeds[..]];
+
+    // The SOL side is only known once the swap accounts are parsed, so
+    // peek at the pool first: an input of SOL funds the wSOL account
+    let pool_info = ctx.remaining_accounts.get(1).ok_or(ErrorCode::AccountNotEnoughKeys)?;
+    let pool = Account::<Pool>::try_from(pool_info)?;
+    let sol_is_a = pool.token_a_mint == native_mint::ID;
+    require!(
+        sol_is_a || pool.token_b_mint == native_mint::ID,
+        AmmError::InvalidTokenMint
+    );
+    let sol_in = sol_is_a == a_to_b;
+
+    let rent = Rent::get()?.minimum_balance(TokenAccount::LEN);
+    let lamports = if sol_in {
+        rent.checked_add(amount_in).ok_or(AmmError::MathOverflow)?
+    } else {
+        rent
+    };
+    let system_program = ctx.accounts.system_program.to_account_info();
+    let wsol_info = ctx.accounts.wsol_account.to_account_info();
+    let funded = wsol_info.lamports();
+    if funded == 0 {
+        system_program::create_account(
+            CpiContext::new_with_signer(
+                system_program,
+                CreateAccount {
+                    from: ctx.accounts.user.to_account_info(),
+                    to: wsol_info,
+                },
+                signer_seeds,
+            ),
+            lamports,
+            TokenAccount::LEN as u64,
+            &token::ID,
+        )?;
+    } else {
+        // Anyone can send lamports to the PDA, which would make
+        // create_account fail; top up, allocate and assign instead, as
+        // Anchor's `init` does. Donated lamports end up wrapped and are
+        // returned to the user on close.
+        let top_up = lamports.saturating_sub(funded);
+        if top_up > 0 {
+            system_program::transfer(
+                CpiContext::new(
+                    system_program.clone(),
+                    Transfer {
+                        from: ctx.accounts.user.to_account_info(),
+                        to: wsol_info.clone(),
+                    },
+                ),
+                top_up,
+            )?;
+        }
+        system_program::allocate(
+            CpiContext::new_with_signer(
+                system_program.clone(),
+                Allocate {
+                    account_to_allocate: wsol_info.clone(),
+                },
+                signer_seeds,
+            ),
+            TokenAccount::LEN as u64,
+        )?;
+        system_program::assign(
+            CpiContext::new_with_signer(
+                system_program,
+                Assign {
+                    account_to_assign: wsol_info,
+                },
+                signer_seeds,
+            ),
+            &token::ID,
+        )?;
+    }
+    token::initialize_account3(CpiContext::new(
+        ctx.accounts.token_program.to_account_info(),
+        InitializeAccount3 {
+            account: ctx.accounts.wsol_account.to_account_info(),
+            mint: ctx.accounts.native_mint.to_account_info(),
+            authority: ctx.accounts.user.to_account_info(),
+        },
+    ))?;
+    token::sync_native(CpiContext::new(
+        ctx.accounts.token_program.to_account_info(),
+        SyncNative {
+            account: ctx.accounts.wsol_account.to_account_info(),
+        },
+    ))?;
+
+    // Parsed after the wSOL account exists so it validates as a token account
+    let mut accounts: &'info [AccountInfo<'info>] = ctx.remaining_accounts;
+    let mut bumps = SwapBumps::default();
+    let mut swap_accounts = Swap::try_accounts(
+        ctx.program_id,
+        &mut accounts,
+        &[],
+        &mut bumps,
+        &mut BTreeSet::new(),
+    )?;
+    require_keys_eq!(swap_accounts.user.key(), user_key, AmmError::Unauthorized);
+    let sol_account = if sol_is_a {
+        swap_accounts.user_token_a.key()
+    } else {
+        swap_accounts.user_token_b.key()
+    };
+    require_keys_eq!(sol_account, ctx.accounts.wsol_account.key(), AmmError::InvalidTokenMint);
+
+    swap::handler(
+        Context::new(ctx.program_id, &mut swap_accounts, &[], bumps),
+        amount_in,
+        min_amount_out,
+        a_to_b,
+        0,
+        0,
+        deadline,
+        0,
+    )?;
+    swap_accounts.exit(ctx.program_id)?;
+
+    token::close_account(CpiContext::new(
+        ctx.accounts.token_program.to_account_info(),
+        CloseAccount {
+            account: ctx.accounts.wsol_account.to_account_info(),
+            destination: ctx.accounts.user.to_account_info(),
+            authority: ctx.accounts.user.to_account_info(),
+        },
+    ))?;
+
+    Ok(())
+}
@@ -0,0 +1,84 @@
+use anchor_lang::prelude::*;
+
+use crate::constants::*;
+use crate::errors::AmmError;
+use crate::instructions::swap::{self, Swap};
+use crate::math::{calculate_price_impact_bps, calculate_staleness_surcharge, calculate_swap_output};
+
+/// Exact-input swap that checkpoints price impact across `chunks` slices.
+///
+/// Uses the regular `Swap` accounts. `amount_in` is split into equal chunks
+/// (the last takes the remainder) that are priced one after another against
+/// in-memory reserves. After each chunk the cumulative execution price is
+/// checked against the pre-swap spot price, so an oversized trade aborts at
+/// the first chunk that breaches `max_price_impact_bps` (0 = off). Chunks
+/// are charged the fee the swap will charge (fee exemption, fee tier and
+/// staleness surcharge included). The trade itself then executes as a
+/// single swap, which is what the chunks sum to up to rounding.
+#[allow(clippy::too_many_arguments)]
+pub fn handler<'info>(
+    ctx: Context<'_, '_, '_, 'info, Swap<'info>>,
+    amount_in: u64,
+    min_amount_out: u64,
+    a_to_b: bool,
+    chunks: u8,
+    max_price_impact_bps: u64,
+    deadline: i64,
+) -> Result<()> {
+    let pool = &ctx.accounts.pool;
+    require!(
+        !pool.oracle_priced && (1..=MAX_SWAP_SPLIT_CHUNKS).contains(&chunks),
+        AmmError::InvalidSwapSplit
+    );
+
+    let (reserve_in, reserve_out) = if a_to_b {
+        (pool.reserve_a, pool.reserve_b)
+    } else {
+        (pool.reserve_b, pool.reserve_a)
+    };
+
+    let fee_numerator = swap::effective_fee_numerator(ctx.accounts, a_to_b)?;
+    let slots_since_update = Clock::get()?.slot.saturating_sub(pool.last_update_slot);
+
+    let chunk_size = amount_in / chunks as u64;
+    require!(chunk_size > 0, AmmError::AmountTooSmall);
+    let (mut chunk_reserve_in, mut chunk_reserve_out) = (reserve_in, reserve_out);
+    let (mut total_in, mut total_out) = (0u64, 0u64);
+    for i in 0..chunks {
+        let chunk_in = if i + 1 == chunks { amount_in - total_in } else { chunk_size };
+        // The surcharge stays in the pool without buying anything
+        let surcharge = calculate_staleness_surcharge(
+            chunk_in,
+            slots_since_update,
+            pool.stale_after_slots,
+            pool.stale_surcharge_bps,
+        )?;
+        let (chunk_out, _, protocol_share) = calculate_swap_output(
+            chunk_in - surcharge,
+            chunk_reserve_in,
+            chunk_reserve_out,
+            fee_numerator,
+            pool.fee_denominator,
+            pool.protocol_fee_numerator,
+            pool.protocol_fee_denominator,
+            pool.curve(),
+        )?;
+
+        chunk_reserve_in = chunk_reserve_in
+            .checked_add(chunk_in - protocol_share)
+            .ok_or(AmmError::MathOverflow)?;
+        chunk_reserve_out = chunk_reserve_out.checked_sub(chunk_out).ok_or(AmmError::MathOverflow)?;
+        total_in += chunk_in;
+        total_out = total_out.checked_add(chunk_out).ok_or(AmmError::MathOverflow)?;
+
+        let price_impact_bps = calculate_price_impact_bps(total_in, total_out, reserve_in, reserve_out)?;
+        require!(
+            max_price_impact_bps == 0 || price_impact_bps <= max_price_impact_bps,
+            AmmError::PriceImpactTooHigh
+        );
+    }
+
+    msg!("Split swap of {} cleared {} chunks for ~{}", amount_in, chunks, total_out);
+
+    swap::handler(ctx, amount_in, min_amount_out, a_to_b, 0, 0, deadline, 0)
+}
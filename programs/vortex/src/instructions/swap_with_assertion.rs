@@ -0,0 +1,39 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::TokenAccount;
+use anchor_spl::{token, token_2022};
+
+use crate::errors::AmmError;
+use crate::instructions::swap::{self, Swap};
+
+/// Swap, then require a user-chosen token account to hold at least
+/// `min_balance`, reverting the whole swap otherwise.
+///
+/// Uses the regular `Swap` accounts; the token account to check is passed
+/// as the first remaining account. It is read after the swap's transfers,
+/// so it may be one of the swap's own token accounts.
+pub fn handler<'info>(
+    ctx: Context<'_, '_, '_, 'info, Swap<'info>>,
+    amount_in: u64,
+    min_amount_out: u64,
+    a_to_b: bool,
+    min_balance: u64,
+    deadline: i64,
+) -> Result<()> {
+    let asserted = ctx
+        .remaining_accounts
+        .first()
+        .ok_or(AmmError::AssertionAccountMissing)?
+        .clone();
+
+    swap::handler(ctx, amount_in, min_amount_out, a_to_b, 0, 0, deadline, 0)?;
+
+    // Re-read post-swap state from a genuine token account
+    require!(
+        asserted.owner == &token::ID || asserted.owner == &token_2022::ID,
+        AmmError::AssertionAccountMissing
+    );
+    let account = TokenAccount::try_deserialize(&mut &asserted.try_borrow_data()?[..])?;
+    require!(account.amount >= min_balance, AmmError::PostSwapAssertionFailed);
+
+    Ok(())
+}
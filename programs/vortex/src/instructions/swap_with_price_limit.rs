@@ -0,0 +1,24 @@
+use anchor_lang::prelude::*;
+
+use crate::instructions::swap::{self, Swap};
+use crate::math::min_out_for_price_limit;
+
+/// Swap unless the execution price is worse than `price_limit`.
+///
+/// Uses the regular `Swap` accounts. `price_limit` is a Q64 price of token A
+/// in token B, the same unit as `Pool::price_a`: a floor when selling A and
+/// a ceiling when selling B. Unlike an absolute `min_amount_out`, the limit
+/// stays meaningful as reserves move between quote and execution. It is
+/// turned into the equivalent minimum output, so a breach fails with
+/// `SlippageExceeded` like any other swap.
+pub fn handler<'info>(
+    ctx: Context<'_, '_, '_, 'info, Swap<'info>>,
+    amount_in: u64,
+    price_limit: u128,
+    a_to_b: bool,
+    deadline: i64,
+) -> Result<()> {
+    let min_amount_out = min_out_for_price_limit(amount_in, price_limit, a_to_b)?;
+
+    swap::handler(ctx, amount_in, min_amount_out, a_to_b, 0, 0, deadline, 0)
+}
@@ -0,0 +1,93 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{self, TokenAccount, TransferChecked};
+
+use crate::constants::*;
+use crate::errors::AmmError;
+use crate::events::RelayerFeePaid;
+use crate::instructions::swap::{self, Swap};
+
+/// Swap, then pay `relayer_fee` of the output token to the relayer that
+/// submitted the transaction on the user's behalf.
+///
+/// Uses the regular `Swap` accounts; the relayer's token account for the
+/// output mint is passed as the first remaining account. The user signs the
+/// fee amount as an instruction argument, it is capped at
+/// MAX_RELAYER_FEE_BPS of the swap output, and `min_amount_out` applies to
+/// what the user keeps after the fee.
+pub fn handler<'info>(
+    ctx: Context<'_, '_, '_, 'info, Swap<'info>>,
+    amount_in: u64,
+    min_amount_out: u64,
+    a_to_b: bool,
+    relayer_fee: u64,
+    deadline: i64,
+) -> Result<()> {
+    let relayer = ctx
+        .remaining_accounts
+        .first()
+        .ok_or(AmmError::RelayerAccountMissing)?
+        .clone();
+
+    let (user_out, mint_out, token_program) = if a_to_b {
+        (
+            ctx.accounts.user_token_b.clone(),
+            ctx.accounts.token_b_mint.clone(),
+            ctx.accounts.token_b_program.to_account_info(),
+        )
+    } else {
+        (
+            ctx.accounts.user_token_a.clone(),
+            ctx.accounts.token_a_mint.clone(),
+            ctx.accounts.token_a_program.to_account_info(),
+        )
+    };
+    let user = ctx.accounts.user.to_account_info();
+    let pool = ctx.accounts.pool.key();
+    let balance_before = user_out.amount;
+
+    // Validate the relayer's account before doing any work
+    require!(relayer.owner == token_program.key, AmmError::RelayerAccountMissing);
+    let relayer_account = TokenAccount::try_deserialize(&mut &relayer.try_borrow_data()?[..])?;
+    require!(relayer_account.mint == mint_out.key(), AmmError::InvalidTokenMint);
+
+    let gross_min_out = min_amount_out.checked_add(relayer_fee).ok_or(AmmError::MathOverflow)?;
+    swap::handler(ctx, amount_in, gross_min_out, a_to_b, 0, 0, deadline, 0)?;
+
+    // Re-read the output account; the swap transferred into it
+    let user_out_info = user_out.to_account_info();
+    let amount_out = {
+        let data = user_out_info.try_borrow_data()?;
+        TokenAccount::try_deserialize(&mut &data[..])?.amount - balance_before
+    };
+
+    let max_fee = (amount_out as u128 * MAX_RELAYER_FEE_BPS as u128 / BPS_DENOMINATOR as u128) as u64;
+    require!(relayer_fee <= max_fee, AmmError::RelayerFeeTooHigh);
+
+    if relayer_fee > 0 {
+        token_interface::transfer_checked(
+            CpiContext::new(
+                token_program,
+                TransferChecked {
+                    from: user_out_info,
+                    mint: mint_out.to_account_info(),
+                    to: relayer.clone(),
+                    authority: user.clone(),
+                },
+            ),
+            relayer_fee,
+            mint_out.decimals,
+        )?;
+    }
+
+    emit!(RelayerFeePaid {
+        pool,
+        user: user.key(),
+        relayer_token_account: relayer.key(),
+        mint: mint_out.key(),
+        amount_out,
+        relayer_fee,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,75 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface;
+
+use crate::state::Pool;
+use crate::constants::*;
+use crate::errors::AmmError;
+use crate::events::{ReserveChangeSource, ReservesSynced};
+
+#[derive(Accounts)]
+pub struct SyncReserves<'info> {
+    /// Pool authority
+    pub authority: Signer<'info>,
+
+    /// Pool state
+    #[account(
+        mut,
+        seeds = [POOL_SEED, pool.token_a_mint.as_ref(), pool.token_b_mint.as_ref()],
+        bump = pool.bump,
+        constraint = pool.authority == authority.key() @ AmmError::Unauthorized
+    )]
+    pub pool: Account<'info, Pool>,
+
+    /// Token A vault
+    #[account(
+        constraint = token_a_vault.key() == pool.token_a_vault @ AmmError::InvalidVault
+    )]
+    pub token_a_vault: InterfaceAccount<'info, token_interface::TokenAccount>,
+
+    /// Token B vault
+    #[account(
+        constraint = token_b_vault.key() == pool.token_b_vault @ AmmError::InvalidVault
+    )]
+    pub token_b_vault: InterfaceAccount<'info, token_interface::TokenAccount>,
+}
+
+/// Reconcile the pool's books with its vaults after direct donations.
+///
+/// Anything in a vault beyond its reserve and uncollected protocol fees is
+/// booked as protocol fees, so a donation can't move the price or be
+/// captured by LPs. A vault holding less than that is a real shortfall and
+/// is rejected rather than papered over.
+pub fn handler(ctx: Context<SyncReserves>) -> Result<()> {
+    let vault_a = ctx.accounts.token_a_vault.amount;
+    let vault_b = ctx.accounts.token_b_vault.amount;
+
+    let pool = &mut ctx.accounts.pool;
+    let excess_a = vault_a
+        .checked_sub(pool.reserve_a)
+        .and_then(|v| v.checked_sub(pool.protocol_fees_a))
+        .ok_or(AmmError::VaultBalanceMismatch)?;
+    let excess_b = vault_b
+        .checked_sub(pool.reserve_b)
+        .and_then(|v| v.checked_sub(pool.protocol_fees_b))
+        .ok_or(AmmError::VaultBalanceMismatch)?;
+
+    pool.protocol_fees_a += excess_a;
+    pool.protocol_fees_b += excess_b;
+
+    // Excess goes to protocol fees, so the reserves themselves are unchanged
+    emit!(ReservesSynced {
+        pool: pool.key(),
+        source: ReserveChangeSource::Sync,
+        old_reserve_a: pool.reserve_a,
+        old_reserve_b: pool.reserve_b,
+        new_reserve_a: pool.reserve_a,
+        new_reserve_b: pool.reserve_b,
+        excess_a,
+        excess_b,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    msg!("Synced reserves: {} A, {} B booked as protocol fees", excess_a, excess_b);
+
+    Ok(())
+}
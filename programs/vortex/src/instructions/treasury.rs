@@ -0,0 +1,195 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{self, Mint, TokenAccount, TokenInterface, TransferChecked};
+
+use crate::program::Vortex;
+use crate::state::ProtocolConfig;
+use crate::constants::*;
+use crate::errors::AmmError;
+use crate::events::TreasuryWithdrawn;
+
+#[derive(Accounts)]
+pub struct InitializeProtocolConfig<'info> {
+    /// Initial protocol governance key; must be the program's upgrade
+    /// authority so the one-time init cannot be front-run
+    #[account(mut)]
+    pub governance: Signer<'info>,
+
+    /// Protocol config
+    #[account(
+        init,
+        payer = governance,
+        space = 8 + ProtocolConfig::INIT_SPACE,
+        seeds = [PROTOCOL_CONFIG_SEED],
+        bump
+    )]
+    pub protocol_config: Account<'info, ProtocolConfig>,
+
+    /// This program
+    #[account(
+        constraint = program.programdata_address()? == Some(program_data.key()) @ AmmError::Unauthorized
+    )]
+    pub program: Program<'info, Vortex>,
+
+    /// This program's ProgramData (holds the upgrade authority)
+    #[account(
+        constraint = program_data.upgrade_authority_address == Some(governance.key()) @ AmmError::Unauthorized
+    )]
+    pub program_data: Account<'info, ProgramData>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct SetGovernance<'info> {
+    /// Current protocol governance
+    pub governance: Signer<'info>,
+
+    /// Protocol config
+    #[account(
+        mut,
+        seeds = [PROTOCOL_CONFIG_SEED],
+        bump = protocol_config.bump,
+        constraint = protocol_config.governance == governance.key() @ AmmError::Unauthorized
+    )]
+    pub protocol_config: Account<'info, ProtocolConfig>,
+}
+
+#[derive(Accounts)]
+pub struct InitializeTreasury<'info> {
+    /// Rent payer
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    /// Protocol config (treasury vault authority)
+    #[account(
+        seeds = [PROTOCOL_CONFIG_SEED],
+        bump = protocol_config.bump
+    )]
+    pub protocol_config: Account<'info, ProtocolConfig>,
+
+    /// Mint the treasury holds
+    #[account(mint::token_program = token_program)]
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    /// Treasury vault for `mint`
+    #[account(
+        init,
+        payer = payer,
+        seeds = [TREASURY_SEED, mint.key().as_ref()],
+        bump,
+        token::mint = mint,
+        token::authority = protocol_config,
+        token::token_program = token_program
+    )]
+    pub treasury: InterfaceAccount<'info, TokenAccount>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+    pub rent: Sysvar<'info, Rent>,
+}
+
+#[derive(Accounts)]
+pub struct WithdrawTreasury<'info> {
+    /// Protocol governance
+    pub governance: Signer<'info>,
+
+    /// Protocol config
+    #[account(
+        seeds = [PROTOCOL_CONFIG_SEED],
+        bump = protocol_config.bump,
+        constraint = protocol_config.governance == governance.key() @ AmmError::Unauthorized
+    )]
+    pub protocol_config: Account<'info, ProtocolConfig>,
+
+    /// Treasury vault
+    #[account(
+        mut,
+        seeds = [TREASURY_SEED, treasury.mint.as_ref()],
+        bump
+    )]
+    pub treasury: InterfaceAccount<'info, TokenAccount>,
+
+    /// Mint the treasury holds
+    #[account(
+        constraint = mint.key() == treasury.mint @ AmmError::InvalidTokenMint
+    )]
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    /// Destination token account
+    #[account(
+        mut,
+        constraint = destination.mint == treasury.mint @ AmmError::InvalidTokenMint
+    )]
+    pub destination: InterfaceAccount<'info, TokenAccount>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+/// Create the singleton protocol config with its governance key
+pub fn initialize_protocol_config(ctx: Context<InitializeProtocolConfig>) -> Result<()> {
+    let config = &mut ctx.accounts.protocol_config;
+    config.bump = ctx.bumps.protocol_config;
+    config.governance = ctx.accounts.governance.key();
+
+    msg!("Protocol governance: {}", config.governance);
+
+    Ok(())
+}
+
+/// Hand protocol governance to a new key (current governance only)
+pub fn set_governance(ctx: Context<SetGovernance>, new_governance: Pubkey) -> Result<()> {
+    require!(new_governance != Pubkey::default(), AmmError::Unauthorized);
+
+    let config = &mut ctx.accounts.protocol_config;
+    let old_governance = config.governance;
+    config.governance = new_governance;
+
+    msg!("Protocol governance: {} -> {}", old_governance, new_governance);
+
+    Ok(())
+}
+
+/// Create the program-owned treasury vault for a mint (permissionless)
+pub fn initialize_treasury(ctx: Context<InitializeTreasury>) -> Result<()> {
+    msg!(
+        "Treasury initialized for mint {}: {}",
+        ctx.accounts.mint.key(),
+        ctx.accounts.treasury.key()
+    );
+
+    Ok(())
+}
+
+/// Sweep tokens out of a treasury vault (governance only)
+pub fn withdraw_treasury(ctx: Context<WithdrawTreasury>, amount: u64) -> Result<()> {
+    require!(amount > 0, AmmError::AmountTooSmall);
+    require!(amount <= ctx.accounts.treasury.amount, AmmError::InsufficientLiquidity);
+
+    let seeds = &[PROTOCOL_CONFIG_SEED, &[ctx.accounts.protocol_config.bump]];
+    let signer_seeds = &[&seeds[..]];
+
+    token_interface::transfer_checked(
+        CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            TransferChecked {
+                from: ctx.accounts.treasury.to_account_info(),
+                mint: ctx.accounts.mint.to_account_info(),
+                to: ctx.accounts.destination.to_account_info(),
+                authority: ctx.accounts.protocol_config.to_account_info(),
+            },
+            signer_seeds,
+        ),
+        amount,
+        ctx.accounts.mint.decimals,
+    )?;
+
+    emit!(TreasuryWithdrawn {
+        mint: ctx.accounts.treasury.mint,
+        governance: ctx.accounts.governance.key(),
+        destination: ctx.accounts.destination.key(),
+        amount,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
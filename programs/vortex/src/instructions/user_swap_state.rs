@@ -0,0 +1,44 @@
+use anchor_lang::prelude::*;
+
+use crate::state::{Pool, UserSwapState};
+use crate::constants::*;
+
+#[derive(Accounts)]
+pub struct OpenUserSwapState<'info> {
+    /// Swapping user and rent payer
+    #[account(mut)]
+    pub user: Signer<'info>,
+
+    /// Pool state
+    #[account(
+        seeds = [POOL_SEED, pool.token_a_mint.as_ref(), pool.token_b_mint.as_ref()],
+        bump = pool.bump
+    )]
+    pub pool: Account<'info, Pool>,
+
+    /// User swap record
+    #[account(
+        init,
+        payer = user,
+        space = 8 + UserSwapState::INIT_SPACE,
+        seeds = [USER_SWAP_SEED, pool.key().as_ref(), user.key().as_ref()],
+        bump
+    )]
+    pub user_swap_state: Account<'info, UserSwapState>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Create the caller's swap record for a pool, required for swapping
+/// while the pool has a swap cooldown set
+pub fn open_user_swap_state(ctx: Context<OpenUserSwapState>) -> Result<()> {
+    let state = &mut ctx.accounts.user_swap_state;
+    state.bump = ctx.bumps.user_swap_state;
+    state.pool = ctx.accounts.pool.key();
+    state.user = ctx.accounts.user.key();
+    state.last_swap_slot = 0;
+
+    msg!("Swap record opened for {}", state.user);
+
+    Ok(())
+}
@@ -0,0 +1,588 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{self, Burn, Mint, MintTo, Token, TokenAccount, Transfer};
+use anchor_spl::token_interface::{self, TokenInterface, TransferChecked};
+
+use crate::state::{Pool, PriceCheckpoints, Vault};
+use crate::constants::*;
+use crate::errors::AmmError;
+use crate::events::{LiquidityAdded, VaultHarvested};
+use crate::math::{calculate_liquidity_to_mint, calculate_vault_assets, calculate_vault_shares, pair_in_ratio};
+
+#[derive(Accounts)]
+pub struct InitializeVault<'info> {
+    /// Rent payer
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    /// Pool state
+    #[account(
+        seeds = [POOL_SEED, pool.token_a_mint.as_ref(), pool.token_b_mint.as_ref()],
+        bump = pool.bump
+    )]
+    pub pool: Box<Account<'info, Pool>>,
+
+    /// LP vault
+    #[account(
+        init,
+        payer = payer,
+        space = 8 + Vault::INIT_SPACE,
+        seeds = [LP_VAULT_SEED, pool.key().as_ref()],
+        bump
+    )]
+    pub vault: Box<Account<'info, Vault>>,
+
+    /// LP mint
+    #[account(
+        constraint = lp_mint.key() == pool.lp_mint @ AmmError::InvalidVault
+    )]
+    pub lp_mint: Box<Account<'info, Mint>>,
+
+    /// Vault share mint (same decimals as the LP mint)
+    #[account(
+        init,
+        payer = payer,
+        seeds = [VAULT_SHARE_MINT_SEED, vault.key().as_ref()],
+        bump,
+        mint::decimals = lp_mint.decimals,
+        mint::authority = vault
+    )]
+    pub share_mint: Box<Account<'info, Mint>>,
+
+    /// Token A mint
+    #[account(
+        constraint = token_a_mint.key() == pool.token_a_mint @ AmmError::InvalidTokenMint,
+        mint::token_program = token_a_program
+    )]
+    pub token_a_mint: Box<InterfaceAccount<'info, token_interface::Mint>>,
+
+    /// Token B mint
+    #[account(
+        constraint = token_b_mint.key() == pool.token_b_mint @ AmmError::InvalidTokenMint,
+        mint::token_program = token_b_program
+    )]
+    pub token_b_mint: Box<InterfaceAccount<'info, token_interface::Mint>>,
+
+    /// Vault's LP token account
+    #[account(
+        init,
+        payer = payer,
+        seeds = [VAULT_HOLDINGS_SEED, vault.key().as_ref(), lp_mint.key().as_ref()],
+        bump,
+        token::mint = lp_mint,
+        token::authority = vault
+    )]
+    pub lp_holdings: Box<Account<'info, TokenAccount>>,
+
+    /// Vault's token A account
+    #[account(
+        init,
+        payer = payer,
+        seeds = [VAULT_HOLDINGS_SEED, vault.key().as_ref(), token_a_mint.key().as_ref()],
+        bump,
+        token::mint = token_a_mint,
+        token::authority = vault,
+        token::token_program = token_a_program
+    )]
+    pub token_a_holdings: Box<InterfaceAccount<'info, token_interface::TokenAccount>>,
+
+    /// Vault's token B account
+    #[account(
+        init,
+        payer = payer,
+        seeds = [VAULT_HOLDINGS_SEED, vault.key().as_ref(), token_b_mint.key().as_ref()],
+        bump,
+        token::mint = token_b_mint,
+        token::authority = vault,
+        token::token_program = token_b_program
+    )]
+    pub token_b_holdings: Box<InterfaceAccount<'info, token_interface::TokenAccount>>,
+
+    /// Token program for token A
+    #[account(
+        constraint = token_a_program.key() == pool.token_program_a() @ AmmError::InvalidTokenProgram
+    )]
+    pub token_a_program: Interface<'info, TokenInterface>,
+
+    /// Token program for token B
+    #[account(
+        constraint = token_b_program.key() == pool.token_program_b() @ AmmError::InvalidTokenProgram
+    )]
+    pub token_b_program: Interface<'info, TokenInterface>,
+
+    /// SPL Token program (LP and share mints)
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+    pub rent: Sysvar<'info, Rent>,
+}
+
+#[derive(Accounts)]
+pub struct VaultDeposit<'info> {
+    /// Depositor
+    pub user: Signer<'info>,
+
+    /// LP vault
+    #[account(
+        mut,
+        seeds = [LP_VAULT_SEED, vault.pool.as_ref()],
+        bump = vault.bump
+    )]
+    pub vault: Account<'info, Vault>,
+
+    /// Vault share mint
+    #[account(
+        mut,
+        constraint = share_mint.key() == vault.share_mint @ AmmError::InvalidVault
+    )]
+    pub share_mint: Account<'info, Mint>,
+
+    /// Vault's LP token account
+    #[account(
+        mut,
+        constraint = lp_holdings.key() == vault.lp_holdings @ AmmError::InvalidVault
+    )]
+    pub lp_holdings: Account<'info, TokenAccount>,
+
+    /// User's LP token account
+    #[account(
+        mut,
+        constraint = user_lp_token.mint == lp_holdings.mint @ AmmError::InvalidTokenMint
+    )]
+    pub user_lp_token: Account<'info, TokenAccount>,
+
+    /// User's vault share account
+    #[account(
+        mut,
+        constraint = user_share_token.mint == vault.share_mint @ AmmError::InvalidTokenMint
+    )]
+    pub user_share_token: Account<'info, TokenAccount>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+#[derive(Accounts)]
+pub struct VaultWithdraw<'info> {
+    /// Shareholder
+    pub user: Signer<'info>,
+
+    /// LP vault
+    #[account(
+        mut,
+        seeds = [LP_VAULT_SEED, vault.pool.as_ref()],
+        bump = vault.bump
+    )]
+    pub vault: Account<'info, Vault>,
+
+    /// Vault share mint
+    #[account(
+        mut,
+        constraint = share_mint.key() == vault.share_mint @ AmmError::InvalidVault
+    )]
+    pub share_mint: Account<'info, Mint>,
+
+    /// Vault's LP token account
+    #[account(
+        mut,
+        constraint = lp_holdings.key() == vault.lp_holdings @ AmmError::InvalidVault
+    )]
+    pub lp_holdings: Account<'info, TokenAccount>,
+
+    /// User's LP token account
+    #[account(
+        mut,
+        constraint = user_lp_token.mint == lp_holdings.mint @ AmmError::InvalidTokenMint
+    )]
+    pub user_lp_token: Account<'info, TokenAccount>,
+
+    /// User's vault share account
+    #[account(
+        mut,
+        constraint = user_share_token.mint == vault.share_mint @ AmmError::InvalidTokenMint
+    )]
+    pub user_share_token: Account<'info, TokenAccount>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+#[derive(Accounts)]
+pub struct Harvest<'info> {
+    /// Anyone may trigger a harvest
+    pub caller: Signer<'info>,
+
+    /// Pool state
+    #[account(
+        mut,
+        seeds = [POOL_SEED, pool.token_a_mint.as_ref(), pool.token_b_mint.as_ref()],
+        bump = pool.bump,
+        constraint = !pool.paused @ AmmError::PoolPaused
+    )]
+    pub pool: Box<Account<'info, Pool>>,
+
+    /// LP vault
+    #[account(
+        mut,
+        seeds = [LP_VAULT_SEED, pool.key().as_ref()],
+        bump = vault.bump
+    )]
+    pub vault: Box<Account<'info, Vault>>,
+
+    /// Token A vault
+    #[account(
+        mut,
+        constraint = token_a_vault.key() == pool.token_a_vault @ AmmError::InvalidVault
+    )]
+    pub token_a_vault: Box<InterfaceAccount<'info, token_interface::TokenAccount>>,
+
+    /// Token B vault
+    #[account(
+        mut,
+        constraint = token_b_vault.key() == pool.token_b_vault @ AmmError::InvalidVault
+    )]
+    pub token_b_vault: Box<InterfaceAccount<'info, token_interface::TokenAccount>>,
+
+    /// LP mint
+    #[account(
+        mut,
+        constraint = lp_mint.key() == pool.lp_mint @ AmmError::InvalidVault
+    )]
+    pub lp_mint: Box<Account<'info, Mint>>,
+
+    /// LP mint authority
+    /// CHECK: PDA used as mint authority
+    #[account(
+        seeds = [LP_MINT_AUTHORITY_SEED, pool.key().as_ref()],
+        bump = pool.lp_mint_authority_bump
+    )]
+    pub lp_mint_authority: UncheckedAccount<'info>,
+
+    /// Vault share mint
+    #[account(
+        constraint = share_mint.key() == vault.share_mint @ AmmError::InvalidVault
+    )]
+    pub share_mint: Box<Account<'info, Mint>>,
+
+    /// Vault's LP token account
+    #[account(
+        mut,
+        constraint = lp_holdings.key() == vault.lp_holdings @ AmmError::InvalidVault
+    )]
+    pub lp_holdings: Box<Account<'info, TokenAccount>>,
+
+    /// Vault's token A account
+    #[account(
+        mut,
+        constraint = token_a_holdings.key() == vault.token_a_holdings @ AmmError::InvalidVault
+    )]
+    pub token_a_holdings: Box<InterfaceAccount<'info, token_interface::TokenAccount>>,
+
+    /// Vault's token B account
+    #[account(
+        mut,
+        constraint = token_b_holdings.key() == vault.token_b_holdings @ AmmError::InvalidVault
+    )]
+    pub token_b_holdings: Box<InterfaceAccount<'info, token_interface::TokenAccount>>,
+
+    /// Token A mint
+    #[account(
+        constraint = token_a_mint.key() == pool.token_a_mint @ AmmError::InvalidTokenMint
+    )]
+    pub token_a_mint: Box<InterfaceAccount<'info, token_interface::Mint>>,
+
+    /// Token B mint
+    #[account(
+        constraint = token_b_mint.key() == pool.token_b_mint @ AmmError::InvalidTokenMint
+    )]
+    pub token_b_mint: Box<InterfaceAccount<'info, token_interface::Mint>>,
+
+    /// Pool's price checkpoints (TWAP the harvest price is checked against)
+    #[account(
+        seeds = [PRICE_CHECKPOINTS_SEED, pool.key().as_ref()],
+        bump = price_checkpoints.bump
+    )]
+    pub price_checkpoints: Box<Account<'info, PriceCheckpoints>>,
+
+    /// Token program for token A
+    #[account(
+        constraint = token_a_program.key() == pool.token_program_a() @ AmmError::InvalidTokenProgram
+    )]
+    pub token_a_program: Interface<'info, TokenInterface>,
+
+    /// Token program for token B
+    #[account(
+        constraint = token_b_program.key() == pool.token_program_b() @ AmmError::InvalidTokenProgram
+    )]
+    pub token_b_program: Interface<'info, TokenInterface>,
+
+    /// SPL Token program (LP mint)
+    pub token_program: Program<'info, Token>,
+}
+
+/// Create the auto-compounding LP vault for a pool (permissionless)
+pub fn initialize_vault(ctx: Context<InitializeVault>) -> Result<()> {
+    let vault = &mut ctx.accounts.vault;
+    vault.bump = ctx.bumps.vault;
+    vault.pool = ctx.accounts.pool.key();
+    vault.share_mint = ctx.accounts.share_mint.key();
+    vault.lp_holdings = ctx.accounts.lp_holdings.key();
+    vault.token_a_holdings = ctx.accounts.token_a_holdings.key();
+    vault.token_b_holdings = ctx.accounts.token_b_holdings.key();
+    vault.total_lp = 0;
+    vault.last_harvest_slot = 0;
+
+    msg!("LP vault initialized for pool {}", vault.pool);
+
+    Ok(())
+}
+
+/// Deposit LP tokens into the vault in exchange for shares
+pub fn vault_deposit(ctx: Context<VaultDeposit>, lp_amount: u64, min_shares: u64) -> Result<()> {
+    require!(lp_amount > 0, AmmError::AmountTooSmall);
+
+    let vault = &ctx.accounts.vault;
+    let shares = calculate_vault_shares(lp_amount, vault.total_lp, ctx.accounts.share_mint.supply)?;
+    require!(shares > 0, AmmError::AmountTooSmall);
+    require!(shares >= min_shares, AmmError::SlippageExceeded);
+
+    token::transfer(
+        CpiContext::new(
+            ctx.accounts.token_program.to_account_info(),
+            Transfer {
+                from: ctx.accounts.user_lp_token.to_account_info(),
+                to: ctx.accounts.lp_holdings.to_account_info(),
+                authority: ctx.accounts.user.to_account_info(),
+            },
+        ),
+        lp_amount,
+    )?;
+
+    let seeds = &[LP_VAULT_SEED, vault.pool.as_ref(), &[vault.bump]];
+    token::mint_to(
+        CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            MintTo {
+                mint: ctx.accounts.share_mint.to_account_info(),
+                to: ctx.accounts.user_share_token.to_account_info(),
+                authority: ctx.accounts.vault.to_account_info(),
+            },
+            &[&seeds[..]],
+        ),
+        shares,
+    )?;
+
+    let vault = &mut ctx.accounts.vault;
+    vault.total_lp = vault.total_lp.checked_add(lp_amount).ok_or(AmmError::MathOverflow)?;
+
+    msg!("Vault deposit: {} LP for {} shares", lp_amount, shares);
+
+    Ok(())
+}
+
+/// Burn vault shares and receive the underlying LP tokens
+pub fn vault_withdraw(ctx: Context<VaultWithdraw>, shares: u64, min_lp_amount: u64) -> Result<()> {
+    require!(shares > 0, AmmError::AmountTooSmall);
+
+    let vault = &ctx.accounts.vault;
+    let lp_amount = calculate_vault_assets(shares, vault.total_lp, ctx.accounts.share_mint.supply)?;
+    require!(lp_amount > 0, AmmError::AmountTooSmall);
+    require!(lp_amount >= min_lp_amount, AmmError::SlippageExceeded);
+
+    token::burn(
+        CpiContext::new(
+            ctx.accounts.token_program.to_account_info(),
+            Burn {
+                mint: ctx.accounts.share_mint.to_account_info(),
+                from: ctx.accounts.user_share_token.to_account_info(),
+                authority: ctx.accounts.user.to_account_info(),
+            },
+        ),
+        shares,
+    )?;
+
+    let seeds = &[LP_VAULT_SEED, vault.pool.as_ref(), &[vault.bump]];
+    token::transfer(
+        CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            Transfer {
+                from: ctx.accounts.lp_holdings.to_account_info(),
+                to: ctx.accounts.user_lp_token.to_account_info(),
+                authority: ctx.accounts.vault.to_account_info(),
+            },
+            &[&seeds[..]],
+        ),
+        lp_amount,
+    )?;
+
+    let vault = &mut ctx.accounts.vault;
+    vault.total_lp -= lp_amount;
+
+    msg!("Vault withdraw: {} shares for {} LP", shares, lp_amount);
+
+    Ok(())
+}
+
+/// Compound the vault's token A/B holdings (incentives, donations) into LP.
+///
+/// Swap fees already accrue to the pool reserves backing the vault's LP, so
+/// they compound without a harvest. This adds whatever A/B the vault holds
+/// as balanced liquidity; the excess side stays for the next harvest. New LP
+/// is credited to `total_lp` without minting shares, raising every share's
+/// value pro rata.
+///
+/// Anyone may call this, so the spot price must sit within
+/// `HARVEST_MAX_PRICE_DEVIATION_BPS` of the pool's TWAP; otherwise a caller
+/// could skew the pool, harvest at the skewed ratio and swap back.
+pub fn harvest(ctx: Context<Harvest>) -> Result<()> {
+    let pool = &ctx.accounts.pool;
+    require!(pool.is_initialized(), AmmError::PoolNotInitialized);
+    require!(!pool.deposits_frozen, AmmError::DepositsFrozen);
+    require!(
+        !pool.is_token_paused(PAUSE_DEPOSIT_A | PAUSE_DEPOSIT_B),
+        AmmError::TokenPaused
+    );
+
+    let clock = Clock::get()?;
+    let (twap, _, window_start) = ctx
+        .accounts
+        .price_checkpoints
+        .average_prices(pool, HARVEST_TWAP_PERIOD, clock.unix_timestamp)
+        .ok_or(AmmError::NoPriceCheckpoint)?;
+    require!(
+        clock.unix_timestamp - window_start >= HARVEST_TWAP_PERIOD / 2,
+        AmmError::TwapWindowTooShort
+    );
+    require!(
+        pool.price_a().abs_diff(twap).saturating_mul(BPS_DENOMINATOR as u128)
+            <= twap.saturating_mul(HARVEST_MAX_PRICE_DEVIATION_BPS as u128),
+        AmmError::PriceDeviatesFromTwap
+    );
+
+    // Largest amounts in the pool's current ratio
+    let (amount_a, amount_b) = pair_in_ratio(
+        ctx.accounts.token_a_holdings.amount,
+        ctx.accounts.token_b_holdings.amount,
+        pool.reserve_a,
+        pool.reserve_b,
+    )?;
+    require!(amount_a > 0 && amount_b > 0, AmmError::NothingToHarvest);
+
+    let vault_a_before = ctx.accounts.token_a_vault.amount;
+    let vault_b_before = ctx.accounts.token_b_vault.amount;
+
+    let vault_seeds = &[LP_VAULT_SEED, ctx.accounts.vault.pool.as_ref(), &[ctx.accounts.vault.bump]];
+    let vault_signer = &[&vault_seeds[..]];
+
+    token_interface::transfer_checked(
+        CpiContext::new_with_signer(
+            ctx.accounts.token_a_program.to_account_info(),
+            TransferChecked {
+                from: ctx.accounts.token_a_holdings.to_account_info(),
+                mint: ctx.accounts.token_a_mint.to_account_info(),
+                to: ctx.accounts.token_a_vault.to_account_info(),
+                authority: ctx.accounts.vault.to_account_info(),
+            },
+            vault_signer,
+        ),
+        amount_a,
+        ctx.accounts.token_a_mint.decimals,
+    )?;
+
+    token_interface::transfer_checked(
+        CpiContext::new_with_signer(
+            ctx.accounts.token_b_program.to_account_info(),
+            TransferChecked {
+                from: ctx.accounts.token_b_holdings.to_account_info(),
+                mint: ctx.accounts.token_b_mint.to_account_info(),
+                to: ctx.accounts.token_b_vault.to_account_info(),
+                authority: ctx.accounts.vault.to_account_info(),
+            },
+            vault_signer,
+        ),
+        amount_b,
+        ctx.accounts.token_b_mint.decimals,
+    )?;
+
+    // Credit what actually arrived: transfer-fee (Token-2022) mints deliver
+    // less than was sent
+    ctx.accounts.token_a_vault.reload()?;
+    ctx.accounts.token_b_vault.reload()?;
+    let amount_a = ctx
+        .accounts
+        .token_a_vault
+        .amount
+        .checked_sub(vault_a_before)
+        .ok_or(AmmError::MathOverflow)?;
+    let amount_b = ctx
+        .accounts
+        .token_b_vault
+        .amount
+        .checked_sub(vault_b_before)
+        .ok_or(AmmError::MathOverflow)?;
+
+    let pool = &ctx.accounts.pool;
+    let liquidity = calculate_liquidity_to_mint(
+        amount_a,
+        amount_b,
+        pool.reserve_a,
+        pool.reserve_b,
+        ctx.accounts.lp_mint.supply,
+    )?;
+    require!(liquidity > 0, AmmError::NothingToHarvest);
+
+    let pool_key = pool.key();
+    let mint_seeds = &[
+        LP_MINT_AUTHORITY_SEED,
+        pool_key.as_ref(),
+        &[pool.lp_mint_authority_bump],
+    ];
+    token::mint_to(
+        CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            MintTo {
+                mint: ctx.accounts.lp_mint.to_account_info(),
+                to: ctx.accounts.lp_holdings.to_account_info(),
+                authority: ctx.accounts.lp_mint_authority.to_account_info(),
+            },
+            &[&mint_seeds[..]],
+        ),
+        liquidity,
+    )?;
+
+    let pool = &mut ctx.accounts.pool;
+    pool.accumulate_prices(clock.unix_timestamp);
+    pool.reserve_a = pool.reserve_a.checked_add(amount_a).ok_or(AmmError::MathOverflow)?;
+    pool.reserve_b = pool.reserve_b.checked_add(amount_b).ok_or(AmmError::MathOverflow)?;
+    require!(
+        pool.within_reserve_cap(pool.reserve_a, pool.reserve_b),
+        AmmError::ReserveCapExceeded
+    );
+    pool.last_update_slot = clock.slot;
+
+    let vault = &mut ctx.accounts.vault;
+    vault.total_lp = vault.total_lp.checked_add(liquidity).ok_or(AmmError::MathOverflow)?;
+    vault.last_harvest_slot = clock.slot;
+
+    emit!(LiquidityAdded {
+        pool: pool.key(),
+        user: vault.key(),
+        amount_a,
+        amount_b,
+        liquidity_minted: liquidity,
+        locked_liquidity_recipient: Pubkey::default(),
+        reserve_a: pool.reserve_a,
+        reserve_b: pool.reserve_b,
+        timestamp: clock.unix_timestamp,
+    });
+
+    emit!(VaultHarvested {
+        vault: vault.key(),
+        pool: pool.key(),
+        caller: ctx.accounts.caller.key(),
+        amount_a,
+        amount_b,
+        liquidity_minted: liquidity,
+        total_lp: vault.total_lp,
+        total_shares: ctx.accounts.share_mint.supply,
+        timestamp: clock.unix_timestamp,
+    });
+
+    Ok(())
+}
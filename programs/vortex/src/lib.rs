@@ -1,5 +1,22 @@
+// Only for Anchor's IDL instructions, which still call the deprecated
+// `AccountInfo::realloc`: `#[program]` emits them into a crate-root module
+// of its own, so no narrower item can carry the allow
+#![allow(deprecated)]
+
 use anchor_lang::prelude::*;
 
+/// Log the remaining compute units under `label` when built with the
+/// `debug` feature; expands to nothing otherwise
+macro_rules! log_compute_units {
+    ($label:expr) => {
+        #[cfg(feature = "debug")]
+        {
+            anchor_lang::prelude::msg!($label);
+            anchor_lang::solana_program::log::sol_log_compute_units();
+        }
+    };
+}
+
 pub mod constants;
 pub mod errors;
 pub mod events;
@@ -8,6 +25,7 @@ pub mod state;
 pub mod instructions;
 
 use instructions::*;
+use state::SwapRecord;
 
 declare_id!("71kECueXZuecQ7ngyxbThU22XyTM1jfk4SpGk7PSVbGY");
 
@@ -20,37 +38,694 @@ pub mod vortex {
         ctx: Context<InitializePool>,
         fee_numerator: u64,
         fee_denominator: u64,
+        curve_type: u8,
+        amp: u64,
+        min_swap_amount: u64,
+        lp_decimals: u8,
     ) -> Result<()> {
-        instructions::initialize_pool::handler(ctx, fee_numerator, fee_denominator)
+        instructions::initialize_pool::handler(
+            ctx,
+            fee_numerator,
+            fee_denominator,
+            curve_type,
+            amp,
+            min_swap_amount,
+            lp_decimals,
+        )
     }
 
-    /// Add liquidity to pool
+    /// Add liquidity to pool, returning the LP minted
     pub fn add_liquidity(
         ctx: Context<AddLiquidity>,
         amount_a: u64,
         amount_b: u64,
         min_liquidity: u64,
-    ) -> Result<()> {
-        instructions::add_liquidity::handler(ctx, amount_a, amount_b, min_liquidity)
+        deadline: i64,
+    ) -> Result<u64> {
+        instructions::add_liquidity::handler(ctx, amount_a, amount_b, min_liquidity, deadline)
     }
 
-    /// Remove liquidity from pool
+    /// Add one token plus exactly the matching amount of the other
+    pub fn add_liquidity_balanced(
+        ctx: Context<AddLiquidity>,
+        amount: u64,
+        base_is_a: bool,
+        max_other_amount: u64,
+        deadline: i64,
+    ) -> Result<BalancedDeposit> {
+        instructions::add_liquidity_balanced::handler(ctx, amount, base_is_a, max_other_amount, deadline)
+    }
+
+    /// Add liquidity from a single token, swapping part of it internally
+    pub fn add_liquidity_single_sided(
+        ctx: Context<AddLiquiditySingleSided>,
+        amount_in: u64,
+        min_liquidity: u64,
+        deadline: i64,
+    ) -> Result<u64> {
+        instructions::add_liquidity_single_sided::handler(ctx, amount_in, min_liquidity, deadline)
+    }
+
+    /// Remove liquidity from pool, returning the amounts paid out
     pub fn remove_liquidity(
         ctx: Context<RemoveLiquidity>,
         liquidity_amount: u64,
         min_amount_a: u64,
         min_amount_b: u64,
-    ) -> Result<()> {
-        instructions::remove_liquidity::handler(ctx, liquidity_amount, min_amount_a, min_amount_b)
+        min_price: u128,
+        max_price: u128,
+        deadline: i64,
+    ) -> Result<RemovedLiquidity> {
+        instructions::remove_liquidity::handler(
+            ctx,
+            liquidity_amount,
+            min_amount_a,
+            min_amount_b,
+            min_price,
+            max_price,
+            deadline,
+        )
     }
 
     /// Swap tokens
+    #[allow(clippy::too_many_arguments)]
     pub fn swap(
         ctx: Context<Swap>,
         amount_in: u64,
         min_amount_out: u64,
         a_to_b: bool,
+        min_reserve_in: u64,
+        min_reserve_out: u64,
+        deadline: i64,
+        max_price_impact_bps: u64,
+    ) -> Result<()> {
+        instructions::swap::handler(
+            ctx,
+            amount_in,
+            min_amount_out,
+            a_to_b,
+            min_reserve_in,
+            min_reserve_out,
+            deadline,
+            max_price_impact_bps,
+        )
+    }
+
+    /// Fee-free swap for protocol rebalancing (authority only)
+    pub fn swap_no_fee(
+        ctx: Context<SwapNoFee>,
+        amount_in: u64,
+        min_amount_out: u64,
+        a_to_b: bool,
+    ) -> Result<()> {
+        instructions::swap_no_fee::handler(ctx, amount_in, min_amount_out, a_to_b)
+    }
+
+    /// Emit a reserve snapshot for charting (permissionless, rate-limited)
+    pub fn checkpoint(ctx: Context<Checkpoint>) -> Result<()> {
+        instructions::checkpoint::handler(ctx)
+    }
+
+    /// Derive the pool PDA for two mints in any order (read-only)
+    pub fn derive_pool_for_pair(
+        ctx: Context<DerivePoolForPair>,
+        mint_x: Pubkey,
+        mint_y: Pubkey,
+    ) -> Result<PoolAddress> {
+        instructions::derive_pool_for_pair::handler(ctx, mint_x, mint_y)
+    }
+
+    /// Constant-product swap output for caller-supplied reserves (read-only)
+    pub fn compute_amount_out(
+        ctx: Context<ComputeAmountOut>,
+        amount_in: u64,
+        reserve_in: u64,
+        reserve_out: u64,
+        fee_numerator: u64,
+        fee_denominator: u64,
+    ) -> Result<AmountOut> {
+        instructions::compute_amount_out::handler(
+            ctx,
+            amount_in,
+            reserve_in,
+            reserve_out,
+            fee_numerator,
+            fee_denominator,
+        )
+    }
+
+    /// Set the fee-exempt market-maker program (authority only)
+    pub fn set_exempt_program(ctx: Context<PoolAdmin>, exempt_program: Pubkey) -> Result<()> {
+        instructions::admin::set_exempt_program(ctx, exempt_program)
+    }
+
+    /// Set separate A->B and B->A swap fees (authority only)
+    pub fn set_directional_fees(
+        ctx: Context<PoolAdmin>,
+        fee_numerator_a_to_b: u64,
+        fee_numerator_b_to_a: u64,
+    ) -> Result<()> {
+        instructions::admin::set_directional_fees(ctx, fee_numerator_a_to_b, fee_numerator_b_to_a)
+    }
+
+    /// Set the protocol fee recipient (protocol governance only)
+    pub fn set_fee_recipient(ctx: Context<SetFeeRecipient>, fee_recipient: Pubkey) -> Result<()> {
+        instructions::admin::set_fee_recipient(ctx, fee_recipient)
+    }
+
+    /// Cap the pool's reserves, 0 for unlimited (authority only)
+    pub fn set_max_reserve(ctx: Context<PoolAdmin>, max_reserve: u64) -> Result<()> {
+        instructions::admin::set_max_reserve(ctx, max_reserve)
+    }
+
+    /// Floor the reserves partial withdrawals may leave, 0 to disable (authority only)
+    pub fn set_min_reserve(ctx: Context<PoolAdmin>, min_reserve: u64) -> Result<()> {
+        instructions::admin::set_min_reserve(ctx, min_reserve)
+    }
+
+    /// Set the per-user swap cooldown in slots, 0 to disable (authority only)
+    pub fn set_swap_cooldown(ctx: Context<PoolAdmin>, min_slot_between_swaps: u64) -> Result<()> {
+        instructions::admin::set_swap_cooldown(ctx, min_slot_between_swaps)
+    }
+
+    /// Set the liquidity withdrawal fee (authority only)
+    pub fn set_withdraw_fee(ctx: Context<PoolAdmin>, withdraw_fee_bps: u16) -> Result<()> {
+        instructions::admin::set_withdraw_fee(ctx, withdraw_fee_bps)
+    }
+
+    /// Preview the LP burn needed to withdraw a target value (read-only)
+    pub fn preview_withdraw_for_value(
+        ctx: Context<PreviewWithdrawForValue>,
+        target_value: u64,
+        price_a: u128,
+        price_b: u128,
+    ) -> Result<WithdrawPreview> {
+        instructions::preview_withdraw_for_value::handler(ctx, target_value, price_a, price_b)
+    }
+
+    /// Tokens an LP amount would redeem for right now (read-only)
+    pub fn lp_value(ctx: Context<LpValue>, lp_amount: u64) -> Result<LpTokenValue> {
+        instructions::lp_value::handler(ctx, lp_amount)
+    }
+
+    /// Create the pool's reference price oracle (authority only)
+    pub fn initialize_oracle(ctx: Context<InitializeOracle>, updater: Pubkey) -> Result<()> {
+        instructions::oracle::initialize_oracle(ctx, updater)
+    }
+
+    /// Push a new reference price (oracle updater only)
+    pub fn update_oracle_price(ctx: Context<UpdateOraclePrice>, price: u128) -> Result<()> {
+        instructions::oracle::update_oracle_price(ctx, price)
+    }
+
+    /// Configure oracle-priced swaps (authority only)
+    pub fn set_oracle_mode(
+        ctx: Context<PoolAdmin>,
+        oracle_priced: bool,
+        oracle_spread_bps: u16,
+        oracle_max_staleness: i64,
+    ) -> Result<()> {
+        instructions::admin::set_oracle_mode(ctx, oracle_priced, oracle_spread_bps, oracle_max_staleness)
+    }
+
+    /// Set granular per-token pause flags (authority only)
+    pub fn set_token_pause(ctx: Context<PoolAdmin>, pause_flags: u8) -> Result<()> {
+        instructions::admin::set_token_pause(ctx, pause_flags)
+    }
+
+    /// Marginal price after a hypothetical swap (read-only)
+    pub fn get_marginal_price_after(
+        ctx: Context<GetMarginalPriceAfter>,
+        amount_in: u64,
+        a_to_b: bool,
+    ) -> Result<MarginalPrice> {
+        instructions::get_marginal_price_after::handler(ctx, amount_in, a_to_b)
+    }
+
+    /// Expected output, fee and price impact of a swap (read-only)
+    pub fn quote_swap(ctx: Context<QuoteSwap>, amount_in: u64, a_to_b: bool) -> Result<SwapQuote> {
+        instructions::quote_swap::handler(ctx, amount_in, a_to_b)
+    }
+
+    /// Minimum swap output for a slippage tolerance in BPS (read-only)
+    pub fn compute_min_out(
+        ctx: Context<QuoteSwap>,
+        amount_in: u64,
+        a_to_b: bool,
+        slippage_bps: u64,
+    ) -> Result<u64> {
+        instructions::compute_min_out::handler(ctx, amount_in, a_to_b, slippage_bps)
+    }
+
+    /// Swap result net of transfer fees and surcharges, as executed now (read-only)
+    pub fn simulate_swap(ctx: Context<SimulateSwap>, amount_in: u64, a_to_b: bool) -> Result<SwapSimulation> {
+        instructions::simulate_swap::handler(ctx, amount_in, a_to_b)
+    }
+
+    /// Configure the stale-reserve swap surcharge (authority only)
+    pub fn set_staleness_surcharge(
+        ctx: Context<PoolAdmin>,
+        stale_after_slots: u64,
+        stale_surcharge_bps: u16,
+    ) -> Result<()> {
+        instructions::admin::set_staleness_surcharge(ctx, stale_after_slots, stale_surcharge_bps)
+    }
+
+    /// Set the deposit reserve-ratio band (authority only)
+    pub fn set_ratio_band(ctx: Context<PoolAdmin>, min_ratio: u128, max_ratio: u128) -> Result<()> {
+        instructions::admin::set_ratio_band(ctx, min_ratio, max_ratio)
+    }
+
+    /// Create the optional swap history ring buffer
+    pub fn initialize_swap_history(ctx: Context<InitializeSwapHistory>) -> Result<()> {
+        instructions::swap_history::initialize_swap_history(ctx)
+    }
+
+    /// Recent swaps from the on-chain ring buffer (read-only)
+    pub fn get_recent_swaps(ctx: Context<GetRecentSwaps>) -> Result<Vec<SwapRecord>> {
+        instructions::swap_history::get_recent_swaps(ctx)
+    }
+
+    /// Create the optional TWAP checkpoint ring buffer
+    pub fn initialize_price_checkpoints(ctx: Context<InitializePriceCheckpoints>) -> Result<()> {
+        instructions::price_checkpoints::initialize_price_checkpoints(ctx)
+    }
+
+    /// Average prices over roughly the last `period` seconds (read-only)
+    pub fn consult(ctx: Context<Consult>, period: i64) -> Result<TwapResult> {
+        instructions::price_checkpoints::consult(ctx, period)
+    }
+
+    /// Swap, then assert a token account balance (first remaining account)
+    pub fn swap_with_assertion<'info>(
+        ctx: Context<'_, '_, '_, 'info, Swap<'info>>,
+        amount_in: u64,
+        min_amount_out: u64,
+        a_to_b: bool,
+        min_balance: u64,
+        deadline: i64,
     ) -> Result<()> {
-        instructions::swap::handler(ctx, amount_in, min_amount_out, a_to_b)
+        instructions::swap_with_assertion::handler(
+            ctx,
+            amount_in,
+            min_amount_out,
+            a_to_b,
+            min_balance,
+            deadline,
+        )
+    }
+
+    /// Swap with slippage bounded by a Q64 price of A in B instead of an amount
+    pub fn swap_with_price_limit<'info>(
+        ctx: Context<'_, '_, '_, 'info, Swap<'info>>,
+        amount_in: u64,
+        price_limit: u128,
+        a_to_b: bool,
+        deadline: i64,
+    ) -> Result<()> {
+        instructions::swap_with_price_limit::handler(ctx, amount_in, price_limit, a_to_b, deadline)
+    }
+
+    /// Current spot prices, `None` for an empty pool (read-only)
+    pub fn get_spot_price(ctx: Context<GetSpotPrice>) -> Result<SpotPrice> {
+        instructions::get_spot_price::handler(ctx)
+    }
+
+    /// Prices, invariant, fee and lifetime stats in one call (read-only)
+    pub fn get_pool_info(ctx: Context<GetPoolInfo>) -> Result<PoolInfo> {
+        instructions::get_pool_info::handler(ctx)
+    }
+
+    /// Compare booked reserves against the vault balances (read-only)
+    pub fn check_reserves(ctx: Context<CheckReserves>) -> Result<ReservesCheck> {
+        instructions::check_reserves::handler(ctx)
+    }
+
+    /// Create the protocol config and set the governance key (one-time,
+    /// upgrade authority only)
+    pub fn initialize_protocol_config(ctx: Context<InitializeProtocolConfig>) -> Result<()> {
+        instructions::treasury::initialize_protocol_config(ctx)
+    }
+
+    /// Transfer protocol governance (governance only)
+    pub fn set_governance(ctx: Context<SetGovernance>, new_governance: Pubkey) -> Result<()> {
+        instructions::treasury::set_governance(ctx, new_governance)
+    }
+
+    /// Create the protocol treasury vault for a mint
+    pub fn initialize_treasury(ctx: Context<InitializeTreasury>) -> Result<()> {
+        instructions::treasury::initialize_treasury(ctx)
+    }
+
+    /// Withdraw from a protocol treasury vault (governance only)
+    pub fn withdraw_treasury(ctx: Context<WithdrawTreasury>, amount: u64) -> Result<()> {
+        instructions::treasury::withdraw_treasury(ctx, amount)
+    }
+
+    /// Configure the LP-token swap fee rebate (authority only)
+    pub fn set_fee_rebate(ctx: Context<PoolAdmin>, rebate_bps: u16, rebate_budget: u64) -> Result<()> {
+        instructions::admin::set_fee_rebate(ctx, rebate_bps, rebate_budget)
+    }
+
+    /// Open the caller's LP position account for a pool
+    pub fn open_position(ctx: Context<OpenPosition>) -> Result<()> {
+        instructions::position::open_position(ctx)
+    }
+
+    /// Compound the caller's earned fees across several positions
+    pub fn compound_all<'info>(
+        ctx: Context<'_, '_, 'info, 'info, CompoundAll<'info>>,
+    ) -> Result<()> {
+        instructions::compound_all::handler(ctx)
+    }
+
+    /// Open the caller's swap record for a pool (needed under a swap cooldown)
+    pub fn open_user_swap_state(ctx: Context<OpenUserSwapState>) -> Result<()> {
+        instructions::user_swap_state::open_user_swap_state(ctx)
+    }
+
+    /// Grant a trader a swap fee discount in BPS (authority only)
+    pub fn create_fee_tier(ctx: Context<CreateFeeTier>, user: Pubkey, discount_bps: u16) -> Result<()> {
+        instructions::fee_tier::create_fee_tier(ctx, user, discount_bps)
+    }
+
+    /// Change a trader's swap fee discount (authority only)
+    pub fn update_fee_tier(ctx: Context<UpdateFeeTier>, discount_bps: u16) -> Result<()> {
+        instructions::fee_tier::update_fee_tier(ctx, discount_bps)
+    }
+
+    /// Set the minimum LP hold window in slots (authority only)
+    pub fn set_min_lp_hold_slots(ctx: Context<PoolAdmin>, min_lp_hold_slots: u64) -> Result<()> {
+        instructions::admin::set_min_lp_hold_slots(ctx, min_lp_hold_slots)
+    }
+
+    /// Prices at which a position breaks even versus holding (read-only)
+    pub fn get_breakeven_price(ctx: Context<GetBreakevenPrice>) -> Result<BreakevenPrice> {
+        instructions::get_breakeven_price::handler(ctx)
+    }
+
+    /// Set the per-swap event verbosity (authority only)
+    pub fn set_event_verbosity(ctx: Context<PoolAdmin>, event_verbosity: u8) -> Result<()> {
+        instructions::admin::set_event_verbosity(ctx, event_verbosity)
+    }
+
+    /// Current position on the bonding curve with reference points (read-only)
+    pub fn get_curve_state(ctx: Context<GetCurveState>) -> Result<CurveState> {
+        instructions::get_curve_state::handler(ctx)
+    }
+
+    /// Create the auto-compounding LP vault for a pool
+    pub fn initialize_vault(ctx: Context<InitializeVault>) -> Result<()> {
+        instructions::vault::initialize_vault(ctx)
+    }
+
+    /// Deposit LP tokens into the vault for shares
+    pub fn vault_deposit(ctx: Context<VaultDeposit>, lp_amount: u64, min_shares: u64) -> Result<()> {
+        instructions::vault::vault_deposit(ctx, lp_amount, min_shares)
+    }
+
+    /// Redeem vault shares for LP tokens
+    pub fn vault_withdraw(ctx: Context<VaultWithdraw>, shares: u64, min_lp_amount: u64) -> Result<()> {
+        instructions::vault::vault_withdraw(ctx, shares, min_lp_amount)
+    }
+
+    /// Compound the vault's token holdings into LP (permissionless)
+    pub fn harvest(ctx: Context<Harvest>) -> Result<()> {
+        instructions::vault::harvest(ctx)
+    }
+
+    /// Pause the pool (authority or guardian)
+    pub fn pause_pool(ctx: Context<PausePool>) -> Result<()> {
+        instructions::admin::pause_pool(ctx)
+    }
+
+    /// Unpause the pool (authority only)
+    pub fn unpause_pool(ctx: Context<PoolAdmin>) -> Result<()> {
+        instructions::admin::unpause_pool(ctx)
+    }
+
+    /// Set the pause-only guardian (authority only)
+    pub fn set_guardian(ctx: Context<PoolAdmin>, guardian: Pubkey) -> Result<()> {
+        instructions::admin::set_guardian(ctx, guardian)
+    }
+
+    /// Mark a paused pool as unwinding (authority only)
+    pub fn begin_unwind(ctx: Context<PoolAdmin>) -> Result<()> {
+        instructions::admin::begin_unwind(ctx)
+    }
+
+    /// Withdraw liquidity from a paused, unwinding pool bypassing optional guards
+    pub fn emergency_withdraw(ctx: Context<EmergencyWithdraw>, liquidity_amount: u64) -> Result<()> {
+        instructions::emergency_withdraw::handler(ctx, liquidity_amount)
+    }
+
+    /// Swap and pay a relayer fee in the output token (first remaining account)
+    pub fn swap_with_relayer_fee<'info>(
+        ctx: Context<'_, '_, '_, 'info, Swap<'info>>,
+        amount_in: u64,
+        min_amount_out: u64,
+        a_to_b: bool,
+        relayer_fee: u64,
+        deadline: i64,
+    ) -> Result<()> {
+        instructions::swap_with_relayer_fee::handler(
+            ctx,
+            amount_in,
+            min_amount_out,
+            a_to_b,
+            relayer_fee,
+            deadline,
+        )
+    }
+
+    /// Change the swap fee (authority only)
+    pub fn update_fee(ctx: Context<PoolAdmin>, fee_numerator: u64, fee_denominator: u64) -> Result<()> {
+        instructions::admin::update_fee(ctx, fee_numerator, fee_denominator)
+    }
+
+    /// Set the protocol's share of the swap fee (authority only)
+    pub fn set_protocol_fee(
+        ctx: Context<PoolAdmin>,
+        protocol_fee_numerator: u64,
+        protocol_fee_denominator: u64,
+    ) -> Result<()> {
+        instructions::admin::set_protocol_fee(ctx, protocol_fee_numerator, protocol_fee_denominator)
+    }
+
+    /// Collect accrued protocol fees to the fee recipient, the treasury by
+    /// default (authority only)
+    pub fn collect_protocol_fees(ctx: Context<CollectProtocolFees>) -> Result<()> {
+        instructions::collect_protocol_fees::handler(ctx)
+    }
+
+    /// Add accrued protocol fees to the pool as treasury-owned liquidity
+    /// (protocol governance only)
+    pub fn buyback_and_make(ctx: Context<BuybackAndMake>, min_liquidity: u64) -> Result<u64> {
+        instructions::buyback_and_make::handler(ctx, min_liquidity)
+    }
+
+    /// Book vault balances above reserves as protocol fees (authority only)
+    pub fn sync_reserves(ctx: Context<SyncReserves>) -> Result<()> {
+        instructions::sync_reserves::handler(ctx)
+    }
+
+    /// Swap for an exact output amount, bounded by a maximum input
+    pub fn swap_exact_out<'info>(
+        ctx: Context<'_, '_, '_, 'info, Swap<'info>>,
+        amount_out: u64,
+        max_amount_in: u64,
+        a_to_b: bool,
+        deadline: i64,
+    ) -> Result<()> {
+        instructions::swap_exact_out::handler(ctx, amount_out, max_amount_in, a_to_b, deadline)
+    }
+
+    /// Toggle strict reserve reconciliation on swaps (authority only)
+    pub fn set_strict_reserves(ctx: Context<PoolAdmin>, strict_reserves: bool) -> Result<()> {
+        instructions::admin::set_strict_reserves(ctx, strict_reserves)
+    }
+
+    /// Freeze or unfreeze new liquidity without pausing swaps (authority only)
+    pub fn set_deposits_frozen(ctx: Context<PoolAdmin>, deposits_frozen: bool) -> Result<()> {
+        instructions::admin::set_deposits_frozen(ctx, deposits_frozen)
+    }
+
+    /// Borrow from a reserve for the duration of a callback, repaying with a fee
+    pub fn flash_loan<'info>(
+        ctx: Context<'_, '_, '_, 'info, FlashLoan<'info>>,
+        amount: u64,
+        data: Vec<u8>,
+    ) -> Result<()> {
+        instructions::flash_loan::handler(ctx, amount, data)
+    }
+
+    /// Close an emptied pool and its vaults, reclaiming rent (authority only)
+    pub fn close_pool(ctx: Context<ClosePool>) -> Result<()> {
+        instructions::close_pool::handler(ctx)
+    }
+
+    /// Set the minimum swap input (authority only)
+    pub fn set_min_swap_amount(ctx: Context<PoolAdmin>, min_swap_amount: u64) -> Result<()> {
+        instructions::admin::set_min_swap_amount(ctx, min_swap_amount)
+    }
+
+    /// Swap through several pools in one instruction
+    pub fn route_swap<'info>(
+        ctx: Context<'_, '_, 'info, 'info, RouteSwap<'info>>,
+        amount_in: u64,
+        min_amount_out: u64,
+        hops: Vec<RouteHop>,
+        deadline: i64,
+    ) -> Result<()> {
+        instructions::route::route_swap(ctx, amount_in, min_amount_out, hops, deadline)
+    }
+
+    /// Authorize a relayer to make one deposit on exact terms
+    pub fn create_deposit_intent(
+        ctx: Context<CreateDepositIntent>,
+        amount_a: u64,
+        amount_b: u64,
+        min_liquidity: u64,
+        deadline: i64,
+    ) -> Result<()> {
+        instructions::deposit_intent::create_deposit_intent(ctx, amount_a, amount_b, min_liquidity, deadline)
+    }
+
+    /// Cancel an unexecuted deposit intent, revoking the pool's approvals
+    pub fn cancel_deposit_intent(ctx: Context<CancelDepositIntent>) -> Result<()> {
+        instructions::deposit_intent::cancel_deposit_intent(ctx)
+    }
+
+    /// Add liquidity to several pools in one instruction
+    pub fn batch_add_liquidity<'info>(
+        ctx: Context<'_, '_, 'info, 'info, BatchAddLiquidity<'info>>,
+        deposits: Vec<BatchDeposit>,
+        deadline: i64,
+    ) -> Result<()> {
+        instructions::batch_add_liquidity::handler(ctx, deposits, deadline)
+    }
+
+    /// Escrow LP into a new nonce-keyed position receipt
+    pub fn open_position_receipt(
+        ctx: Context<OpenPositionReceipt>,
+        nonce: u64,
+        liquidity: u64,
+    ) -> Result<()> {
+        instructions::position_receipt::open_position_receipt(ctx, nonce, liquidity)
+    }
+
+    /// Redeem a position receipt and withdraw its liquidity
+    pub fn remove_liquidity_from_position<'info>(
+        ctx: Context<'_, '_, 'info, 'info, RemoveLiquidityFromPosition<'info>>,
+        min_amount_a: u64,
+        min_amount_b: u64,
+        deadline: i64,
+    ) -> Result<RemovedLiquidity> {
+        instructions::position_receipt::remove_liquidity_from_position(
+            ctx,
+            min_amount_a,
+            min_amount_b,
+            deadline,
+        )
+    }
+
+    /// Swap in chunks, aborting once cumulative price impact exceeds a cap
+    #[allow(clippy::too_many_arguments)]
+    pub fn swap_split<'info>(
+        ctx: Context<'_, '_, '_, 'info, Swap<'info>>,
+        amount_in: u64,
+        min_amount_out: u64,
+        a_to_b: bool,
+        chunks: u8,
+        max_price_impact_bps: u64,
+        deadline: i64,
+    ) -> Result<()> {
+        instructions::swap_split::handler(
+            ctx,
+            amount_in,
+            min_amount_out,
+            a_to_b,
+            chunks,
+            max_price_impact_bps,
+            deadline,
+        )
+    }
+
+    /// Create a pool and make its first deposit atomically
+    #[allow(clippy::too_many_arguments)]
+    pub fn initialize_pool_with_liquidity(
+        ctx: Context<InitializePoolWithLiquidity>,
+        fee_numerator: u64,
+        fee_denominator: u64,
+        curve_type: u8,
+        amp: u64,
+        min_swap_amount: u64,
+        lp_decimals: u8,
+        amount_a: u64,
+        amount_b: u64,
+        min_liquidity: u64,
+        deadline: i64,
+    ) -> Result<u64> {
+        instructions::initialize_pool_with_liquidity::handler(
+            ctx,
+            fee_numerator,
+            fee_denominator,
+            curve_type,
+            amp,
+            min_swap_amount,
+            lp_decimals,
+            amount_a,
+            amount_b,
+            min_liquidity,
+            deadline,
+        )
+    }
+
+    /// Escrow LP until an unlock time
+    pub fn lock_liquidity(
+        ctx: Context<LockLiquidity>,
+        nonce: u64,
+        amount: u64,
+        unlock_timestamp: i64,
+    ) -> Result<()> {
+        instructions::liquidity_lock::lock_liquidity(ctx, nonce, amount, unlock_timestamp)
+    }
+
+    /// Release a liquidity lock once its unlock time has passed
+    pub fn unlock_liquidity(ctx: Context<UnlockLiquidity>) -> Result<()> {
+        instructions::liquidity_lock::unlock_liquidity(ctx)
+    }
+
+    /// Upgrade a pool created by an older program version (authority only)
+    pub fn migrate_pool(ctx: Context<MigratePool>) -> Result<()> {
+        instructions::migrate_pool::handler(ctx)
+    }
+
+    /// Swap native SOL, wrapping and unwrapping it in a temporary wSOL account
+    pub fn swap_sol<'info>(
+        ctx: Context<'_, '_, 'info, 'info, SwapSol<'info>>,
+        amount_in: u64,
+        min_amount_out: u64,
+        a_to_b: bool,
+        deadline: i64,
+    ) -> Result<()> {
+        instructions::swap_sol::handler(ctx, amount_in, min_amount_out, a_to_b, deadline)
+    }
+
+    /// Withdraw and swap as needed to hold a target amount of token A
+    pub fn rebalance_position(
+        ctx: Context<RebalancePosition>,
+        target_a: u64,
+        reference_price: u128,
+        max_slippage_bps: u64,
+        deadline: i64,
+    ) -> Result<RebalanceResult> {
+        instructions::rebalance_position::handler(
+            ctx,
+            target_a,
+            reference_price,
+            max_slippage_bps,
+            deadline,
+        )
     }
 }
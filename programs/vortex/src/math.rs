@@ -2,34 +2,194 @@ use anchor_lang::prelude::*;
 use crate::errors::AmmError;
 use crate::constants::*;
 
-/// Calculate square root using Babylonian method
-pub fn sqrt(y: u64) -> Result<u64> {
-    if y == 0 {
-        return Ok(0);
+/// Upper bound on Newton steps in `sqrt`
+const SQRT_MAX_ITERATIONS: u32 = 64;
+
+/// Calculate the floor square root of a u128 using the Babylonian method
+///
+/// Starts from a power of two at or above the root, so the iterates decrease
+/// monotonically and `y / x + x` stays within u128 even near `u128::MAX`. The
+/// root of any u128 fits in u64.
+///
+/// That start is within a factor of two of the root, so Newton's method
+/// converges in a handful of steps; the loop is still capped at
+/// `SQRT_MAX_ITERATIONS` and the result checked against
+/// `x * x <= y < (x + 1) * (x + 1)`, failing rather than returning a wrong root.
+pub fn sqrt(y: u128) -> Result<u64> {
+    if y < 2 {
+        return Ok(y as u64);
     }
 
-    let mut z = (y + 1) / 2;
-    let mut x = y;
+    let bits = 128 - y.leading_zeros();
+    let mut x = 1u128 << bits.div_ceil(2);
 
-    while z < x {
+    let mut converged = false;
+    for _ in 0..SQRT_MAX_ITERATIONS {
+        let z = (y / x + x) / 2;
+        if z >= x {
+            converged = true;
+            break;
+        }
         x = z;
-        z = (y / z + z) / 2;
     }
+    require!(converged, AmmError::MathOverflow);
+
+    let root = u64::try_from(x).map_err(|_| error!(AmmError::MathOverflow))?;
+    let root_sq = (root as u128) * (root as u128);
+    let next_sq = (root as u128 + 1).checked_mul(root as u128 + 1);
+    require!(
+        root_sq <= y && next_sq.is_none_or(|next| y < next),
+        AmmError::MathOverflow
+    );
+
+    Ok(root)
+}
+
+/// Spot price of the base token in the quote token (Q64 format)
+///
+/// Returns 0 when the base reserve is empty.
+pub fn spot_price(reserve_base: u64, reserve_quote: u64) -> u128 {
+    if reserve_base == 0 {
+        return 0;
+    }
+    (reserve_quote as u128)
+        .saturating_mul(Q64)
+        .saturating_div(reserve_base as u128)
+}
+
+/// Smallest output that keeps a swap of `amount_in` at or better than
+/// `price_limit`, a Q64 price of token A in token B (as `Pool::price_a`)
+///
+/// Selling A the execution price is out / in and may not fall below the
+/// limit; selling B it is in / out and may not rise above it. Rounds up, so
+/// an output exactly at the limit passes. Fails with `SlippageExceeded` when
+/// no u64 output could satisfy the limit.
+pub fn min_out_for_price_limit(amount_in: u64, price_limit: u128, a_to_b: bool) -> Result<u64> {
+    require!(price_limit > 0, AmmError::InvalidPriceLimit);
+    let (numerator, denominator) = if a_to_b { (price_limit, Q64) } else { (Q64, price_limit) };
+
+    let floor =
+        mul_div(amount_in as u128, numerator, denominator).ok_or(AmmError::SlippageExceeded)?;
+    let exact = mul_wide(floor, denominator) == mul_wide(amount_in as u128, numerator);
+    let min_out = if exact { floor } else { floor + 1 };
+
+    u64::try_from(min_out).map_err(|_| error!(AmmError::SlippageExceeded))
+}
+
+/// Whole-token price of the base token in the quote token, scaled by
+/// 10^PRICE_SCALE_DECIMALS
+///
+///   price = (reserve_quote / 10^decimals_quote) / (reserve_base / 10^decimals_base)
+///
+/// Computed straight from the reserves rather than from the Q64 price, so no
+/// precision is lost to the fixed point. Returns 0 for an empty base reserve
+/// (or a price below the scale's resolution) and saturates at u128::MAX.
+pub fn scaled_price(reserve_base: u64, reserve_quote: u64, decimals_base: u8, decimals_quote: u8) -> u128 {
+    if reserve_base == 0 {
+        return 0;
+    }
+    let exponent = decimals_base as i32 + PRICE_SCALE_DECIMALS as i32 - decimals_quote as i32;
+    let factor = 10u128.checked_pow(exponent.unsigned_abs());
+    if exponent >= 0 {
+        factor
+            .and_then(|factor| mul_div(reserve_quote as u128, factor, reserve_base as u128))
+            .unwrap_or(u128::MAX)
+    } else {
+        factor
+            .and_then(|factor| (reserve_base as u128).checked_mul(factor))
+            .map_or(0, |denominator| reserve_quote as u128 / denominator)
+    }
+}
+
+/// Increase of a fee growth index (Q64 fee per LP token) when `fee` is
+/// earned by `lp_supply` LP tokens; 0 while there is no supply
+pub fn fee_growth_delta(fee: u64, lp_supply: u64) -> u128 {
+    if lp_supply == 0 {
+        return 0;
+    }
+    // fee < 2^64, so fee * Q64 < 2^128
+    ((fee as u128) << 64) / lp_supply as u128
+}
 
-    Ok(x)
+/// Fees earned by `liquidity` LP tokens over a fee growth index increase,
+/// rounded down and saturating at u64::MAX
+pub fn fees_for_growth(liquidity: u64, growth_delta: u128) -> u64 {
+    mul_div(liquidity as u128, growth_delta, Q64)
+        .and_then(|fees| u64::try_from(fees).ok())
+        .unwrap_or(u64::MAX)
 }
 
+/// Calculate price impact of a swap in basis points
+///
+/// impact = 1 - execution_price / spot_price, where
+///   spot_price = reserve_out / reserve_in
+///   execution_price = amount_out / amount_in
+/// Both prices are quoted in the same units, so any decimal normalization
+/// (as in `scaled_price`) multiplies them by the same 10^(d_in - d_out)
+/// factor and cancels: computing the ratio from raw amounts gives exactly the
+/// decimal-normalized (economic) impact. Mixing a raw price with a
+/// normalized one is what produces misleading impacts for cross-decimal
+/// pairs, so callers must not compare prices across the two scales.
+pub fn calculate_price_impact_bps(
+    amount_in: u64,
+    amount_out: u64,
+    reserve_in: u64,
+    reserve_out: u64,
+) -> Result<u64> {
+    require!(amount_in > 0 && reserve_out > 0, AmmError::DivisionByZero);
+
+    // execution / spot = (amount_out * reserve_in) / (amount_in * reserve_out)
+    let numerator = (amount_out as u128) * (reserve_in as u128);
+    let mut denominator = (amount_in as u128) * (reserve_out as u128);
+    if numerator >= denominator {
+        return Ok(0);
+    }
+
+    let mut shortfall = denominator - numerator;
+    // Keep shortfall * BPS_DENOMINATOR within u128 (BPS_DENOMINATOR < 2^14)
+    if shortfall > u128::MAX / BPS_DENOMINATOR as u128 {
+        shortfall >>= 14;
+        denominator >>= 14;
+    }
+
+    let impact = shortfall * BPS_DENOMINATOR as u128 / denominator;
+    Ok(impact as u64)
+}
+
+/// Pricing curve of a pool
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Curve {
+    /// x * y = k
+    ConstantProduct,
+    /// StableSwap with amplification coefficient `amp`
+    Stable { amp: u64 },
+}
+
+/// Newton iterations allowed before a StableSwap solve is abandoned
+const STABLE_MAX_ITERATIONS: usize = 64;
+
 /// Calculate output amount for a swap
 ///
-/// Formula: amount_out = (amount_in_with_fee * reserve_out) / (reserve_in + amount_in_with_fee)
-/// where amount_in_with_fee = amount_in * (1 - fee)
+/// Constant product:
+///   amount_out = (amount_in_with_fee * reserve_out) / (reserve_in + amount_in_with_fee)
+/// StableSwap: amount_out = reserve_out - y - 1, where y is the output
+/// reserve that keeps D unchanged (see `stable_swap_output`).
+/// In both, amount_in_with_fee = amount_in * (1 - fee).
+///
+/// Returns (amount_out, fee_amount, protocol_fee), where protocol_fee is the
+/// protocol's share of fee_amount (protocol_fee_numerator / protocol_fee_denominator
+/// of it; a zero numerator means no protocol fee).
+#[allow(clippy::too_many_arguments)]
 pub fn calculate_swap_output(
     amount_in: u64,
     reserve_in: u64,
     reserve_out: u64,
     fee_numerator: u64,
     fee_denominator: u64,
-) -> Result<(u64, u64)> {
+    protocol_fee_numerator: u64,
+    protocol_fee_denominator: u64,
+    curve: Curve,
+) -> Result<(u64, u64, u64)> {
     require!(amount_in > 0, AmmError::AmountTooSmall);
     require!(reserve_in > 0, AmmError::PoolNotInitialized);
     require!(reserve_out > 0, AmmError::PoolNotInitialized);
@@ -47,22 +207,546 @@ pub fn calculate_swap_output(
         .ok_or(AmmError::MathOverflow)?;
 
     // Calculate output
-    let numerator = (amount_in_with_fee as u128)
-        .checked_mul(reserve_out as u128)
+    let amount_out = match curve {
+        Curve::ConstantProduct => {
+            let numerator = (amount_in_with_fee as u128)
+                .checked_mul(reserve_out as u128)
+                .ok_or(AmmError::MathOverflow)?;
+
+            let denominator = (reserve_in as u128)
+                .checked_add(amount_in_with_fee as u128)
+                .ok_or(AmmError::MathOverflow)?;
+
+            numerator
+                .checked_div(denominator)
+                .ok_or(AmmError::DivisionByZero)? as u64
+        }
+        Curve::Stable { amp } => {
+            stable_swap_output(amount_in_with_fee, reserve_in, reserve_out, amp)?
+        }
+    };
+
+    require!(amount_out > 0, AmmError::InsufficientOutputAmount);
+    require!(amount_out < reserve_out, AmmError::InsufficientLiquidity);
+
+    // Protocol's share of the fee
+    let protocol_fee = if protocol_fee_numerator == 0 {
+        0
+    } else {
+        (fee_amount as u128)
+            .checked_mul(protocol_fee_numerator as u128)
+            .ok_or(AmmError::MathOverflow)?
+            .checked_div(protocol_fee_denominator as u128)
+            .ok_or(AmmError::DivisionByZero)? as u64
+    };
+
+    Ok((amount_out, fee_amount, protocol_fee))
+}
+
+/// a * b / c with a 256-bit intermediate product, or `None` if c is zero
+/// or the quotient exceeds u128
+pub fn mul_div(a: u128, b: u128, c: u128) -> Option<u128> {
+    if c == 0 {
+        return None;
+    }
+    let (high, low) = mul_wide(a, b);
+    if high == 0 {
+        return Some(low / c);
+    }
+    if high >= c {
+        return None;
+    }
+
+    // Long division of (high, low) by c; the remainder stays below c, so a
+    // bit shifted out of it means the true remainder is >= 2^128 > c
+    let mut remainder = high;
+    let mut quotient = 0u128;
+    for i in (0..128).rev() {
+        let carry = remainder >> 127;
+        remainder = (remainder << 1) | ((low >> i) & 1);
+        quotient <<= 1;
+        if carry == 1 || remainder >= c {
+            remainder = remainder.wrapping_sub(c);
+            quotient |= 1;
+        }
+    }
+    Some(quotient)
+}
+
+/// StableSwap invariant D for a two-token pool
+///
+/// Solves A * n^n * S + D = A * n^n * D + D^(n+1) / (n^n * x * y) for D
+/// (n = 2, S = x + y) by Newton's method:
+///   D' = (Ann * S + n * D_P) * D / ((Ann - 1) * D + (n + 1) * D_P)
+/// with D_P = D^(n+1) / (n^n * x * y). Fails with
+/// `InvariantConvergenceFailure` if it does not converge within
+/// `STABLE_MAX_ITERATIONS`.
+pub fn compute_stable_d(reserve_a: u64, reserve_b: u64, amp: u64) -> Result<u128> {
+    require!(reserve_a > 0 && reserve_b > 0, AmmError::PoolNotInitialized);
+    require!(amp > 0, AmmError::InvalidCurveParameters);
+
+    let (x, y) = (reserve_a as u128, reserve_b as u128);
+    let sum = x + y;
+    let ann = amp as u128 * 4;
+
+    let mut d = sum;
+    for _ in 0..STABLE_MAX_ITERATIONS {
+        let d_p = mul_div(d, d, x * 2)
+            .and_then(|d_p| mul_div(d_p, d, y * 2))
+            .ok_or(AmmError::MathOverflow)?;
+
+        let numerator = ann
+            .checked_mul(sum)
+            .and_then(|v| v.checked_add(d_p.checked_mul(2)?))
+            .ok_or(AmmError::MathOverflow)?;
+        let denominator = (ann - 1)
+            .checked_mul(d)
+            .and_then(|v| v.checked_add(d_p.checked_mul(3)?))
+            .ok_or(AmmError::MathOverflow)?;
+
+        let previous = d;
+        d = mul_div(d, numerator, denominator).ok_or(AmmError::MathOverflow)?;
+        if d.abs_diff(previous) <= 1 {
+            return Ok(d);
+        }
+    }
+
+    err!(AmmError::InvariantConvergenceFailure)
+}
+
+/// Reserve of one token that keeps the StableSwap invariant at `d` when the
+/// other token's reserve is `reserve_other`
+///
+/// Newton's method on y^2 + (b - D) * y = c, where
+///   b = x + D / Ann,  c = D^(n+1) / (n^n * x * Ann)
+/// iterating y' = (y^2 + c) / (2y + b - D). Fails with
+/// `InvariantConvergenceFailure` if it does not converge within
+/// `STABLE_MAX_ITERATIONS`.
+pub fn compute_stable_y(reserve_other: u128, d: u128, amp: u64) -> Result<u128> {
+    require!(reserve_other > 0, AmmError::PoolNotInitialized);
+    require!(amp > 0, AmmError::InvalidCurveParameters);
+
+    let ann = amp as u128 * 4;
+    let c = mul_div(d, d, reserve_other * 2)
+        .and_then(|c| mul_div(c, d, ann * 2))
         .ok_or(AmmError::MathOverflow)?;
+    let b = reserve_other + d / ann;
+
+    let mut y = d;
+    for _ in 0..STABLE_MAX_ITERATIONS {
+        let denominator = (y * 2)
+            .checked_add(b)
+            .and_then(|v| v.checked_sub(d))
+            .filter(|v| *v > 0)
+            .ok_or(AmmError::MathOverflow)?;
+        let previous = y;
+        y = mul_div(y, y, denominator)
+            .and_then(|v| v.checked_add(c / denominator))
+            .ok_or(AmmError::MathOverflow)?;
+        if y.abs_diff(previous) <= 1 {
+            return Ok(y);
+        }
+    }
 
-    let denominator = (reserve_in as u128)
-        .checked_add(amount_in_with_fee as u128)
+    err!(AmmError::InvariantConvergenceFailure)
+}
+
+/// StableSwap output for an input already net of fees
+///
+/// Rounds down by one unit beyond the solved reserve, checked against D,
+/// so Newton's tolerance and the floored divisions always err in the
+/// pool's favour.
+fn stable_swap_output(
+    amount_in_with_fee: u64,
+    reserve_in: u64,
+    reserve_out: u64,
+    amp: u64,
+) -> Result<u64> {
+    let d = compute_stable_d(reserve_in, reserve_out, amp)?;
+    let new_reserve_in = (reserve_in as u128) + (amount_in_with_fee as u128);
+    let mut new_reserve_out = compute_stable_y(new_reserve_in, d, amp)?;
+
+    // Under heavy imbalance the solvers' unit tolerances can leave the
+    // reserve short by more than the unit rounded off below, so that the
+    // swap would lower D; raise it until D holds
+    let new_reserve_in = u64::try_from(new_reserve_in).map_err(|_| AmmError::MathOverflow)?;
+    let mut steps = 0;
+    while new_reserve_out < reserve_out as u128
+        && compute_stable_d(new_reserve_in, new_reserve_out.max(1) as u64, amp)? < d
+    {
+        steps += 1;
+        require!(steps <= STABLE_MAX_ITERATIONS, AmmError::InvariantConvergenceFailure);
+        new_reserve_out += 1;
+    }
+
+    let amount_out = (reserve_out as u128)
+        .checked_sub(new_reserve_out)
+        .and_then(|v| v.checked_sub(1))
+        .unwrap_or(0);
+    Ok(amount_out as u64)
+}
+
+/// Marginal price of the base token in the quote token (Q64) on `curve`
+///
+/// Constant product: `spot_price`. StableSwap: -dy/dx along the invariant
+///   F = Ann * (x + y) + D - Ann * D - D^3 / (4xy)
+/// which is F_x / F_y = y * (Ann * x + D_P) / (x * (Ann * y + D_P)), with
+/// x the base reserve, y the quote reserve and D_P = D^3 / (4xy) as in
+/// `compute_stable_d`. It is exactly 1 at the peg and tends to the
+/// constant-product price y / x as the amplification falls.
+pub fn curve_spot_price(reserve_base: u64, reserve_quote: u64, curve: Curve) -> Result<u128> {
+    let amp = match curve {
+        Curve::ConstantProduct => return Ok(spot_price(reserve_base, reserve_quote)),
+        Curve::Stable { amp } => amp,
+    };
+
+    let d = compute_stable_d(reserve_base, reserve_quote, amp)?;
+    let (x, y) = (reserve_base as u128, reserve_quote as u128);
+    let ann = amp as u128 * 4;
+    let d_p = mul_div(d, d, x * 2)
+        .and_then(|d_p| mul_div(d_p, d, y * 2))
         .ok_or(AmmError::MathOverflow)?;
 
-    let amount_out = numerator
-        .checked_div(denominator)
+    let numerator = ann.checked_mul(x).and_then(|v| v.checked_add(d_p));
+    let denominator = ann.checked_mul(y).and_then(|v| v.checked_add(d_p));
+    numerator
+        .zip(denominator)
+        .and_then(|(numerator, denominator)| mul_div(numerator, Q64, denominator))
+        .and_then(|ratio| mul_div(ratio, y, x))
+        .ok_or(error!(AmmError::MathOverflow))
+}
+
+/// Calculate the input required for an exact-output swap
+///
+/// Inverse of `calculate_swap_output`, rounding up at each step so the
+/// returned input always yields at least `amount_out`:
+///   amount_in_with_fee = ceil(amount_out * reserve_in / (reserve_out - amount_out))
+///   amount_in = ceil(amount_in_with_fee * fee_denominator / (fee_denominator - fee_numerator))
+/// Returns (amount_in, fee_amount) with the fee computed as in the forward swap.
+pub fn calculate_swap_input(
+    amount_out: u64,
+    reserve_in: u64,
+    reserve_out: u64,
+    fee_numerator: u64,
+    fee_denominator: u64,
+) -> Result<(u64, u64)> {
+    require!(amount_out > 0, AmmError::AmountTooSmall);
+    require!(reserve_in > 0, AmmError::PoolNotInitialized);
+    require!(amount_out < reserve_out, AmmError::InsufficientLiquidity);
+    require!(fee_numerator < fee_denominator, AmmError::InvalidFeeParameters);
+
+    let amount_in_with_fee = (amount_out as u128)
+        .checked_mul(reserve_in as u128)
+        .ok_or(AmmError::MathOverflow)?
+        .div_ceil((reserve_out - amount_out) as u128);
+
+    let amount_in = amount_in_with_fee
+        .checked_mul(fee_denominator as u128)
+        .ok_or(AmmError::MathOverflow)?
+        .div_ceil((fee_denominator - fee_numerator) as u128);
+    require!(amount_in <= u64::MAX as u128, AmmError::MathOverflow);
+
+    let fee_amount = amount_in * fee_numerator as u128 / fee_denominator as u128;
+
+    Ok((amount_in as u64, fee_amount as u64))
+}
+
+/// Calculate the fee owed on a flash loan, at the pool's swap fee rate for
+/// the borrowed token
+///
+/// Formula: ceil(amount * fee_numerator / fee_denominator), so even tiny
+/// loans on a fee-charging pool pay at least one unit.
+pub fn calculate_flash_fee(amount: u64, fee_numerator: u64, fee_denominator: u64) -> Result<u64> {
+    require!(fee_denominator > 0, AmmError::DivisionByZero);
+
+    let fee = (amount as u128)
+        .checked_mul(fee_numerator as u128)
+        .ok_or(AmmError::MathOverflow)?
+        .div_ceil(fee_denominator as u128);
+
+    u64::try_from(fee).map_err(|_| error!(AmmError::MathOverflow))
+}
+
+/// Calculate the stale-reserve surcharge on a swap input
+///
+/// When more than `stale_after_slots` slots have passed since the pool was
+/// last updated, the reserves may lag the market and the first swapper
+/// captures the arbitrage. That swap pays `surcharge_bps` extra, which stays
+/// in the pool for LPs. Because the swap itself refreshes `last_update_slot`,
+/// the surcharge falls away again for subsequent swaps.
+pub fn calculate_staleness_surcharge(
+    amount_in: u64,
+    slots_since_update: u64,
+    stale_after_slots: u64,
+    surcharge_bps: u16,
+) -> Result<u64> {
+    if stale_after_slots == 0 || slots_since_update <= stale_after_slots {
+        return Ok(0);
+    }
+
+    let surcharge = (amount_in as u128)
+        .checked_mul(surcharge_bps as u128)
+        .ok_or(AmmError::MathOverflow)?
+        .checked_div(BPS_DENOMINATOR as u128)
         .ok_or(AmmError::DivisionByZero)? as u64;
 
+    Ok(surcharge)
+}
+
+/// Calculate output amount for an oracle-priced swap
+///
+/// Formula (a_to_b): amount_out = (amount_in - spread) * price / Q64
+/// Formula (b_to_a): amount_out = (amount_in - spread) * Q64 / price
+/// where spread = amount_in * spread_bps / BPS_DENOMINATOR and price is
+/// token A in token B (Q64). The pool is used only as inventory.
+pub fn calculate_oracle_swap_output(
+    amount_in: u64,
+    reserve_out: u64,
+    price: u128,
+    spread_bps: u16,
+    a_to_b: bool,
+) -> Result<(u64, u64)> {
+    require!(amount_in > 0, AmmError::AmountTooSmall);
+    require!(price > 0, AmmError::DivisionByZero);
+
+    let spread_amount = (amount_in as u128)
+        .checked_mul(spread_bps as u128)
+        .ok_or(AmmError::MathOverflow)?
+        .checked_div(BPS_DENOMINATOR as u128)
+        .ok_or(AmmError::DivisionByZero)? as u64;
+    let amount_in_after_spread = (amount_in - spread_amount) as u128;
+
+    let amount_out = if a_to_b {
+        amount_in_after_spread
+            .checked_mul(price)
+            .ok_or(AmmError::MathOverflow)?
+            / Q64
+    } else {
+        amount_in_after_spread
+            .checked_mul(Q64)
+            .ok_or(AmmError::MathOverflow)?
+            .checked_div(price)
+            .ok_or(AmmError::DivisionByZero)?
+    };
+
     require!(amount_out > 0, AmmError::InsufficientOutputAmount);
-    require!(amount_out < reserve_out, AmmError::InsufficientLiquidity);
+    require!(amount_out < reserve_out as u128, AmmError::InsufficientLiquidity);
+
+    Ok((amount_out as u64, spread_amount))
+}
+
+/// Full 256-bit product of two u128 values as (high, low) limbs
+fn mul_wide(a: u128, b: u128) -> (u128, u128) {
+    const MASK: u128 = u64::MAX as u128;
+    let (a_hi, a_lo) = (a >> 64, a & MASK);
+    let (b_hi, b_lo) = (b >> 64, b & MASK);
+
+    let lo_lo = a_lo * b_lo;
+    let hi_lo = a_hi * b_lo;
+    let lo_hi = a_lo * b_hi;
+    let hi_hi = a_hi * b_hi;
+
+    let cross = (lo_lo >> 64) + (hi_lo & MASK) + (lo_hi & MASK);
+    let low = (lo_lo & MASK) | (cross << 64);
+    let high = hi_hi + (hi_lo >> 64) + (lo_hi >> 64) + (cross >> 64);
 
-    Ok((amount_out, fee_amount))
+    (high, low)
+}
+
+/// Reserves the constant-product curve would hold if the price of token A
+/// were scaled by `num / den` from its current value
+///
+/// Along x * y = k with price p = y / x, x = sqrt(k / p) and y = sqrt(k * p),
+/// so reserve_a scales by sqrt(den / num) and reserve_b by sqrt(num / den).
+pub fn reserves_at_price_multiple(
+    reserve_a: u64,
+    reserve_b: u64,
+    num: u64,
+    den: u64,
+) -> Result<(u128, u128)> {
+    require!(num > 0 && den > 0, AmmError::DivisionByZero);
+
+    let scaled = |reserve: u64, mul: u64, div: u64| -> Result<u128> {
+        let squared = (reserve as u128) * (reserve as u128);
+        Ok(sqrt(
+            (squared / div as u128)
+                .checked_mul(mul as u128)
+                .ok_or(AmmError::MathOverflow)?,
+        )? as u128)
+    };
+
+    Ok((scaled(reserve_a, den, num)?, scaled(reserve_b, num, den)?))
+}
+
+/// Value backing each LP token, as sqrt(reserve_a * reserve_b) / total_supply (Q64)
+///
+/// Swap fees stay in the reserves, so this only grows between deposits and
+/// withdrawals; its ratio across two points in time is the fee growth an LP
+/// earned over that period.
+pub fn lp_share_growth(reserve_a: u64, reserve_b: u64, total_supply: u64) -> Result<u128> {
+    require!(total_supply > 0, AmmError::DivisionByZero);
+
+    let root_k = sqrt((reserve_a as u128) * (reserve_b as u128))? as u128;
+
+    root_k
+        .checked_mul(Q64)
+        .ok_or(AmmError::MathOverflow)?
+        .checked_div(total_supply as u128)
+        .ok_or(error!(AmmError::DivisionByZero))
+}
+
+/// Calculate the prices (Q64) at which an LP position is worth exactly as
+/// much as holding the deposited tokens
+///
+/// With fee growth g = current_growth / entry_growth and r = price / entry_price,
+/// LP value / hold value = g * 2 * sqrt(r) / (1 + r). Setting this to 1 gives
+///   sqrt(r) = g -/+ sqrt(g^2 - 1)
+/// so the position beats holding anywhere between the returned (lower, upper)
+/// prices. Without fee growth (g <= 1) both bounds are the entry price.
+pub fn calculate_breakeven_prices(
+    entry_price: u128,
+    entry_growth: u128,
+    current_growth: u128,
+) -> Result<(u128, u128)> {
+    require!(entry_growth > 0, AmmError::DivisionByZero);
+
+    const ONE_Q32: u128 = 1 << 32;
+
+    // Fee growth factor in Q32
+    let growth = current_growth
+        .checked_mul(ONE_Q32)
+        .ok_or(AmmError::MathOverflow)?
+        / entry_growth;
+
+    if growth <= ONE_Q32 {
+        return Ok((entry_price, entry_price));
+    }
+
+    let growth_sq = growth.checked_mul(growth).ok_or(AmmError::MathOverflow)?;
+    let root = sqrt(growth_sq - Q64)? as u128;
+
+    let scale = |sqrt_ratio: u128| -> Result<u128> {
+        // ratio = sqrt_ratio^2 (Q64); price = entry_price * ratio >> 64
+        let ratio = sqrt_ratio.checked_mul(sqrt_ratio).ok_or(AmmError::MathOverflow)?;
+        let (high, low) = mul_wide(entry_price, ratio);
+        require!(high <= u64::MAX as u128, AmmError::MathOverflow);
+        Ok((high << 64) | (low >> 64))
+    };
+
+    Ok((scale(growth - root)?, scale(growth + root)?))
+}
+
+/// Calculate how much of a single-token deposit to swap before adding
+/// balanced liquidity (zap-in), so nothing is left stranded
+///
+/// With r = 1 - fee = m / n, swapping s of the input leaves (A - s) of it and
+/// the swap output in exactly the post-swap pool ratio when
+///   m * s^2 + (n + m) * R * s - n * A * R = 0
+/// (R = reserve_in, A = amount_in). The terms exceed u128, so the largest
+/// integer s satisfying the left side <= 0 is found by binary search using
+/// 256-bit products; the result is the floor of the real root.
+pub fn optimal_swap_amount_for_zap(
+    amount_in: u64,
+    reserve_in: u64,
+    fee_numerator: u64,
+    fee_denominator: u64,
+) -> Result<u64> {
+    require!(amount_in > 0, AmmError::AmountTooSmall);
+    require!(reserve_in > 0, AmmError::PoolNotInitialized);
+    require!(fee_numerator < fee_denominator, AmmError::InvalidFeeParameters);
+
+    let n = fee_denominator as u128;
+    let m = (fee_denominator - fee_numerator) as u128;
+    let reserve = reserve_in as u128;
+
+    // k * R, the linear coefficient
+    let linear = (n + m).checked_mul(reserve).ok_or(AmmError::MathOverflow)?;
+    // n * A * R, the constant term
+    let target = mul_wide(
+        n.checked_mul(amount_in as u128).ok_or(AmmError::MathOverflow)?,
+        reserve,
+    );
+
+    let (mut lo, mut hi) = (0u64, amount_in);
+    while lo < hi {
+        let mid = lo + (hi - lo).div_ceil(2);
+        // s * (m * s + k * R)
+        let factor = m
+            .checked_mul(mid as u128)
+            .and_then(|quadratic| quadratic.checked_add(linear))
+            .ok_or(AmmError::MathOverflow)?;
+        if mul_wide(mid as u128, factor) <= target {
+            lo = mid;
+        } else {
+            hi = mid - 1;
+        }
+    }
+
+    Ok(lo)
+}
+
+/// Calculate LP tokens rebated to a swapper out of the swap fee
+///
+/// The rebated share of the fee is valued against the pool's post-swap
+/// input-side reserve: one LP token is worth 2 * reserve_in / total_supply
+/// units of the input token (both sides of the pool, priced at spot).
+/// Formula: rebate_lp = fee * rebate_bps / BPS * total_supply / (2 * reserve_in)
+/// The fee has already been added to reserves, so minting at most its value
+/// in LP never dilutes existing LPs below their pre-swap position.
+pub fn calculate_lp_rebate(
+    fee_amount: u64,
+    rebate_bps: u16,
+    reserve_in: u64,
+    total_supply: u64,
+) -> Result<u64> {
+    require!(reserve_in > 0, AmmError::PoolNotInitialized);
+
+    let rebate = (fee_amount as u128)
+        .checked_mul(rebate_bps as u128)
+        .ok_or(AmmError::MathOverflow)?
+        .checked_mul(total_supply as u128)
+        .ok_or(AmmError::MathOverflow)?
+        .checked_div(
+            (BPS_DENOMINATOR as u128)
+                .checked_mul(2 * reserve_in as u128)
+                .ok_or(AmmError::MathOverflow)?,
+        )
+        .ok_or(AmmError::DivisionByZero)?;
+
+    Ok(rebate as u64)
+}
+
+/// Calculate vault shares to issue for a deposit of `amount` assets
+///
+/// Formula: amount * (total_shares + VAULT_VIRTUAL_SHARES)
+///          / (total_assets + VAULT_VIRTUAL_ASSETS)
+/// (1:1 for an empty vault). The virtual offsets make a donation-inflated
+/// share price cost the attacker more than it can round away from others.
+pub fn calculate_vault_shares(amount: u64, total_assets: u64, total_shares: u64) -> Result<u64> {
+    let shares = (amount as u128)
+        .checked_mul(total_shares as u128 + VAULT_VIRTUAL_SHARES as u128)
+        .ok_or(AmmError::MathOverflow)?
+        .checked_div(total_assets as u128 + VAULT_VIRTUAL_ASSETS as u128)
+        .ok_or(AmmError::DivisionByZero)?;
+
+    u64::try_from(shares).map_err(|_| AmmError::MathOverflow.into())
+}
+
+/// Calculate vault assets redeemed by burning `shares`
+///
+/// Formula: shares * (total_assets + VAULT_VIRTUAL_ASSETS)
+///          / (total_shares + VAULT_VIRTUAL_SHARES)
+pub fn calculate_vault_assets(shares: u64, total_assets: u64, total_shares: u64) -> Result<u64> {
+    require!(total_shares > 0, AmmError::DivisionByZero);
+
+    let assets = (shares as u128)
+        .checked_mul(total_assets as u128 + VAULT_VIRTUAL_ASSETS as u128)
+        .ok_or(AmmError::MathOverflow)?
+        .checked_div(total_shares as u128 + VAULT_VIRTUAL_SHARES as u128)
+        .ok_or(AmmError::DivisionByZero)?;
+
+    // Never pay out more than the vault accounts for
+    Ok((assets as u64).min(total_assets))
 }
 
 /// Calculate liquidity tokens to mint for initial deposit
@@ -73,9 +757,7 @@ pub fn calculate_initial_liquidity(amount_a: u64, amount_b: u64) -> Result<u64>
         .checked_mul(amount_b as u128)
         .ok_or(AmmError::MathOverflow)?;
 
-    require!(product <= u64::MAX as u128, AmmError::MathOverflow);
-
-    let liquidity = sqrt(product as u64)?;
+    let liquidity = sqrt(product)?;
 
     require!(
         liquidity >= MINIMUM_LIQUIDITY,
@@ -123,11 +805,79 @@ pub fn calculate_liquidity_to_mint(
     Ok(liquidity)
 }
 
+/// Calculate the smallest balanced deposit worth the LP that `amount_base`
+/// of one token would mint, so neither side is wasted
+///
+/// Formula:
+///   liquidity = floor(amount_base * total_supply / reserve_base)
+///   used_base = ceil(liquidity * reserve_base / total_supply)
+///   used_other = ceil(liquidity * reserve_other / total_supply)
+/// Rounding up keeps both sides at least worth `liquidity`, so
+/// `calculate_liquidity_to_mint(used_base, used_other)` returns it exactly.
+/// Returns (used_base, used_other, liquidity).
+pub fn calculate_balanced_deposit(
+    amount_base: u64,
+    reserve_base: u64,
+    reserve_other: u64,
+    total_supply: u64,
+) -> Result<(u64, u64, u64)> {
+    require!(reserve_base > 0 && reserve_other > 0, AmmError::PoolNotInitialized);
+    require!(total_supply > 0, AmmError::PoolNotInitialized);
+
+    let liquidity = (amount_base as u128)
+        .checked_mul(total_supply as u128)
+        .ok_or(AmmError::MathOverflow)?
+        / reserve_base as u128;
+    require!(liquidity > 0, AmmError::InsufficientLiquidityMinted);
+
+    let backing = |reserve: u64| -> Result<u64> {
+        let amount = liquidity
+            .checked_mul(reserve as u128)
+            .ok_or(AmmError::MathOverflow)?
+            .div_ceil(total_supply as u128);
+        u64::try_from(amount).map_err(|_| error!(AmmError::MathOverflow))
+    };
+
+    Ok((backing(reserve_base)?, backing(reserve_other)?, liquidity as u64))
+}
+
+/// Calculate the largest amounts of `available_a` and `available_b` in the
+/// pool's `reserve_a : reserve_b` ratio
+///
+/// Formula:
+///   b_for_a = floor(available_a * reserve_b / reserve_a)
+///   if b_for_a <= available_b: (available_a, b_for_a)
+///   else: (floor(available_b * reserve_a / reserve_b), available_b)
+/// The pairing is decided in u128, and a derived side too large for u64
+/// saturates rather than truncating, so a steep raw price (e.g. mints with
+/// very different decimals) pairs against the other side instead of
+/// passing an unbalanced pair.
+pub fn pair_in_ratio(
+    available_a: u64,
+    available_b: u64,
+    reserve_a: u64,
+    reserve_b: u64,
+) -> Result<(u64, u64)> {
+    require!(reserve_a > 0 && reserve_b > 0, AmmError::PoolNotInitialized);
+
+    let b_for_a = available_a as u128 * reserve_b as u128 / reserve_a as u128;
+    if b_for_a <= available_b as u128 {
+        return Ok((available_a, b_for_a as u64));
+    }
+
+    let a_for_b = available_b as u128 * reserve_a as u128 / reserve_b as u128;
+    Ok((u64::try_from(a_for_b).unwrap_or(u64::MAX), available_b))
+}
+
 /// Calculate token amounts to return when burning liquidity
 ///
 /// Formula:
-///   amount_a = (liquidity / total_supply) * reserve_a
-///   amount_b = (liquidity / total_supply) * reserve_b
+///   amount_a = floor(reserve_a * liquidity / total_supply)
+///   amount_b = floor(reserve_b * liquidity / total_supply)
+///
+/// Both amounts round down, in the pool's favour. A dust burn against a
+/// lopsided pool may round one side to zero; it still redeems the other,
+/// and only fails with `BurnAmountTooSmall` when both sides are zero.
 pub fn calculate_amounts_for_liquidity(
     liquidity: u64,
     reserve_a: u64,
@@ -150,18 +900,75 @@ pub fn calculate_amounts_for_liquidity(
         .checked_div(total_supply as u128)
         .ok_or(AmmError::DivisionByZero)? as u64;
 
-    require!(amount_a > 0 && amount_b > 0, AmmError::InsufficientOutputAmount);
+    require!(amount_a > 0 || amount_b > 0, AmmError::BurnAmountTooSmall);
 
     Ok((amount_a, amount_b))
 }
 
-/// Verify invariant k does not decrease after swap
+/// Calculate LP tokens to burn to withdraw approximately `target_value`
+///
+/// Prices are external values per base unit of each token in Q64, and
+/// `target_value` is in the same quote units (value = amount * price / Q64).
+/// Formula: ceil(target_value * total_supply / pool_value)
+pub fn calculate_liquidity_for_value(
+    target_value: u64,
+    reserve_a: u64,
+    reserve_b: u64,
+    price_a: u128,
+    price_b: u128,
+    total_supply: u64,
+) -> Result<u64> {
+    require!(total_supply > 0, AmmError::PoolNotInitialized);
+
+    let value_a = (reserve_a as u128)
+        .checked_mul(price_a)
+        .ok_or(AmmError::MathOverflow)?
+        / Q64;
+    let value_b = (reserve_b as u128)
+        .checked_mul(price_b)
+        .ok_or(AmmError::MathOverflow)?
+        / Q64;
+    let pool_value = value_a.checked_add(value_b).ok_or(AmmError::MathOverflow)?;
+
+    require!(pool_value > 0, AmmError::DivisionByZero);
+
+    let liquidity = (target_value as u128)
+        .checked_mul(total_supply as u128)
+        .ok_or(AmmError::MathOverflow)?
+        .div_ceil(pool_value);
+
+    Ok(liquidity.min(total_supply as u128) as u64)
+}
+
+/// Calculate the withdrawal fee taken from a withdrawn amount
+///
+/// Formula: fee = amount * withdraw_fee_bps / BPS_DENOMINATOR (rounded down)
+pub fn calculate_withdraw_fee(amount: u64, withdraw_fee_bps: u16) -> Result<u64> {
+    let fee = (amount as u128)
+        .checked_mul(withdraw_fee_bps as u128)
+        .ok_or(AmmError::MathOverflow)?
+        .checked_div(BPS_DENOMINATOR as u128)
+        .ok_or(AmmError::DivisionByZero)? as u64;
+
+    Ok(fee)
+}
+
+/// Verify the curve invariant (k, or D for stable pools) does not decrease after swap
 pub fn verify_invariant(
     old_reserve_a: u64,
     old_reserve_b: u64,
     new_reserve_a: u64,
     new_reserve_b: u64,
+    curve: Curve,
 ) -> Result<()> {
+    if let Curve::Stable { amp } = curve {
+        let d_old = compute_stable_d(old_reserve_a, old_reserve_b, amp)?;
+        let d_new = compute_stable_d(new_reserve_a, new_reserve_b, amp)?;
+        // Newton converges to within one unit of D
+        require!(d_new + 1 >= d_old, AmmError::InvariantViolation);
+        return Ok(());
+    }
+
     let k_old = (old_reserve_a as u128)
         .checked_mul(old_reserve_b as u128)
         .ok_or(AmmError::MathOverflow)?;
@@ -174,3 +981,575 @@ pub fn verify_invariant(
 
     Ok(())
 }
+
+/// Verify that a deposit or withdrawal did not dilute the remaining LP
+///
+/// Value per LP token is sqrt(k) / supply, so it must not drop:
+///   k_new * old_supply^2 >= k_old * new_supply^2
+/// compared exactly with 256-bit products. Any rounding in the mint or burn
+/// math that favours the user over the pool trips this check.
+pub fn verify_lp_value(
+    old_reserve_a: u64,
+    old_reserve_b: u64,
+    old_supply: u64,
+    new_reserve_a: u64,
+    new_reserve_b: u64,
+    new_supply: u64,
+) -> Result<()> {
+    let k_old = old_reserve_a as u128 * old_reserve_b as u128;
+    let k_new = new_reserve_a as u128 * new_reserve_b as u128;
+    let lhs = mul_wide(k_new, old_supply as u128 * old_supply as u128);
+    let rhs = mul_wide(k_old, new_supply as u128 * new_supply as u128);
+
+    require!(lhs >= rhs, AmmError::InvariantViolation);
+
+    Ok(())
+}
+
+/// Advance a TWAP price accumulator by `price * elapsed` (Q64 price).
+///
+/// Like Uniswap v2, the accumulator is allowed to wrap around `u128::MAX`
+/// instead of erroring: a checked add would permanently freeze the oracle
+/// of a long-lived pool. Consumers must only ever use differences between
+/// two observations (see `cumulative_price_delta`), which stay correct
+/// across a single wrap.
+pub fn accumulate_price(cumulative: u128, price: u128, elapsed: u64) -> u128 {
+    cumulative.wrapping_add(price.wrapping_mul(elapsed as u128))
+}
+
+/// Difference between two accumulator observations, correct across a wrap
+///
+/// Divide the result by the elapsed time between the observations to get
+/// the time-weighted average price (Q64).
+pub fn cumulative_price_delta(newer: u128, older: u128) -> u128 {
+    newer.wrapping_sub(older)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn error_code<T: std::fmt::Debug>(result: Result<T>) -> u32 {
+        match result.unwrap_err() {
+            Error::AnchorError(error) => error.error_code_number,
+            Error::ProgramError(_) => 0,
+        }
+    }
+
+    /// Deterministic xorshift64 stream for the randomized invariant tests
+    struct Rng(u64);
+
+    impl Rng {
+        fn next(&mut self) -> u64 {
+            self.0 ^= self.0 << 13;
+            self.0 ^= self.0 >> 7;
+            self.0 ^= self.0 << 17;
+            self.0
+        }
+
+        /// Uniform-ish in `low..=high`, spread over magnitudes by first
+        /// picking a bit width
+        fn between(&mut self, low: u64, high: u64) -> u64 {
+            let bits = self.next() % 64 + 1;
+            let value = self.next() >> (64 - bits);
+            low + value % (high - low + 1)
+        }
+    }
+
+    #[test]
+    fn price_impact_is_zero_at_spot() {
+        assert_eq!(calculate_price_impact_bps(1_000, 2_000, 1_000_000, 2_000_000).unwrap(), 0);
+    }
+
+    #[test]
+    fn price_impact_measures_the_shortfall_from_spot() {
+        // Half the spot output is a 50% impact
+        assert_eq!(calculate_price_impact_bps(1_000, 1_000, 1_000_000, 2_000_000).unwrap(), 5_000);
+    }
+
+    #[test]
+    fn price_impact_ignores_token_decimals() {
+        // The same economic trade with 6-decimal A and 9-decimal B
+        let same_decimals = calculate_price_impact_bps(10, 9, 1_000, 1_000).unwrap();
+        let cross_decimals =
+            calculate_price_impact_bps(10_000_000, 9_000_000_000, 1_000_000_000, 1_000_000_000_000).unwrap();
+        assert_eq!(same_decimals, 1_000);
+        assert_eq!(cross_decimals, same_decimals);
+    }
+
+    #[test]
+    fn price_impact_survives_u64_extremes() {
+        let impact = calculate_price_impact_bps(u64::MAX, 1, u64::MAX, u64::MAX).unwrap();
+        assert_eq!(impact, 9_999);
+        assert!(calculate_price_impact_bps(0, 1, 1, 1).is_err());
+    }
+
+    #[test]
+    fn stable_d_equals_the_sum_when_balanced() {
+        for amp in [MIN_AMP, 100, MAX_AMP] {
+            assert_eq!(compute_stable_d(1_000_000, 1_000_000, amp).unwrap(), 2_000_000);
+        }
+    }
+
+    #[test]
+    fn stable_y_recovers_the_reserve_under_heavy_imbalance() {
+        for amp in [MIN_AMP, 100, MAX_AMP] {
+            let d = compute_stable_d(1_000, 1_000_000_000_000_000, amp).unwrap();
+            let y = compute_stable_y(1_000, d, amp).unwrap();
+            assert!(y.abs_diff(1_000_000_000_000_000) < 10_000);
+        }
+    }
+
+    #[test]
+    fn stable_d_reports_non_convergence_at_extreme_imbalance() {
+        let result = compute_stable_d(949_501_292_905, 31, MAX_AMP);
+        assert_eq!(error_code(result), u32::from(AmmError::InvariantConvergenceFailure));
+    }
+
+    #[test]
+    fn stable_y_reports_non_convergence_on_tiny_reserves() {
+        let d = compute_stable_d(1, 4, MIN_AMP).unwrap();
+        let result = compute_stable_y(1, d, MIN_AMP);
+        assert_eq!(error_code(result), u32::from(AmmError::InvariantConvergenceFailure));
+    }
+
+    #[test]
+    fn curve_spot_price_is_par_for_a_balanced_stable_pool() {
+        for amp in [MIN_AMP, 100, MAX_AMP] {
+            let curve = Curve::Stable { amp };
+            assert_eq!(curve_spot_price(1_000_000, 1_000_000, curve).unwrap(), Q64);
+        }
+        assert_eq!(
+            curve_spot_price(1_000, 4_000, Curve::ConstantProduct).unwrap(),
+            spot_price(1_000, 4_000)
+        );
+    }
+
+    #[test]
+    fn stable_spot_price_lies_between_par_and_the_product_price() {
+        // Base is scarce, so it trades above par on both curves
+        let (x, y) = (400_000_000u64, 1_600_000_000u64);
+        let product = spot_price(x, y);
+        let mut previous = product;
+        for amp in [MIN_AMP, 10, 100, 1_000] {
+            let price = curve_spot_price(x, y, Curve::Stable { amp }).unwrap();
+            assert!(price > Q64 && price < product);
+            // Higher amplification pins the price closer to par
+            assert!(price < previous);
+            previous = price;
+        }
+    }
+
+    #[test]
+    fn stable_spot_price_matches_a_small_trade() {
+        let (x, y, amp) = (700_000_000_000u64, 1_300_000_000_000u64, 50);
+        let price = curve_spot_price(x, y, Curve::Stable { amp }).unwrap();
+
+        // A tiny sale of base executes at the marginal price, up to
+        // the solver's one-unit tolerance
+        let amount_in = 1_000_000u64;
+        let amount_out = stable_swap_output(amount_in, x, y, amp).unwrap();
+        let execution = amount_out as u128 * Q64 / amount_in as u128;
+        assert!(price.abs_diff(execution) * 10_000 / price < 1);
+        // ... while the product price of the same reserves is far off
+        assert!(spot_price(x, y).abs_diff(execution) * 10_000 / price > 1_000);
+    }
+
+    #[test]
+    fn stable_curve_slips_less_than_constant_product_near_peg() {
+        let (reserve, fee, fee_den) = (1_000_000_000_000u64, 4, 10_000);
+        for amount_in in [100_000_000u64, 10_000_000_000, 100_000_000_000] {
+            let swap = |curve| {
+                calculate_swap_output(amount_in, reserve, reserve, fee, fee_den, 0, 0, curve).unwrap().0
+            };
+            let product = swap(Curve::ConstantProduct);
+            let stable = swap(Curve::Stable { amp: 100 });
+            assert!(stable > product);
+
+            let impact = |out| calculate_price_impact_bps(amount_in, out, reserve, reserve).unwrap();
+            assert!(impact(stable) <= impact(product));
+        }
+
+        // A trade of a tenth of the reserves: ~9% slippage on the product
+        // curve, well under 1% on the stable one
+        let amount_in = 100_000_000_000;
+        let product = calculate_swap_output(amount_in, reserve, reserve, 0, 1, 0, 0, Curve::ConstantProduct)
+            .unwrap()
+            .0;
+        let stable = calculate_swap_output(amount_in, reserve, reserve, 0, 1, 0, 0, Curve::Stable { amp: 100 })
+            .unwrap()
+            .0;
+        assert!(calculate_price_impact_bps(amount_in, product, reserve, reserve).unwrap() > 800);
+        assert!(calculate_price_impact_bps(amount_in, stable, reserve, reserve).unwrap() < 100);
+    }
+
+    #[test]
+    fn vault_shares_are_one_to_one_in_an_empty_vault() {
+        assert_eq!(calculate_vault_shares(5_000, 0, 0).unwrap(), 5_000);
+    }
+
+    #[test]
+    fn vault_donation_cannot_round_a_deposit_to_zero() {
+        // First depositor takes 1 share for 1 LP, then donates 1M LP of value
+        let total_shares = calculate_vault_shares(1, 0, 0).unwrap();
+        let total_assets = 1 + 1_000_000;
+
+        let victim_shares = calculate_vault_shares(10_000, total_assets, total_shares).unwrap();
+        assert!(victim_shares > 0);
+
+        // The attacker's single share redeems almost none of the donation
+        let attacker_assets =
+            calculate_vault_assets(total_shares, total_assets + 10_000, total_shares + victim_shares)
+                .unwrap();
+        assert!(attacker_assets < 10_000);
+    }
+
+    #[test]
+    fn vault_round_trip_never_returns_more_than_deposited() {
+        let shares = calculate_vault_shares(123_456, 1_000_000, 900_000).unwrap();
+        let assets =
+            calculate_vault_assets(shares, 1_000_000 + 123_456, 900_000 + shares).unwrap();
+        assert!(assets <= 123_456);
+    }
+
+    #[test]
+    fn accumulated_price_delta_survives_a_wrap() {
+        let older = u128::MAX - 5 * Q64;
+        let newer = accumulate_price(older, 2 * Q64, 10);
+        assert!(newer < older);
+        assert_eq!(cumulative_price_delta(newer, older) / 10, 2 * Q64);
+    }
+
+    #[test]
+    fn accumulated_price_delta_matches_plain_subtraction_without_a_wrap() {
+        let older = accumulate_price(0, Q64, 30);
+        let newer = accumulate_price(older, 3 * Q64, 30);
+        assert_eq!(cumulative_price_delta(newer, older), 90 * Q64);
+    }
+
+    #[test]
+    fn zap_swap_amount_solves_the_quadratic_exactly() {
+        // Without a fee, s^2 + 2Rs - AR = 0 has the root s = R when A = 3R
+        assert_eq!(optimal_swap_amount_for_zap(3_000_000, 1_000_000, 0, 10_000).unwrap(), 1_000_000);
+    }
+
+    #[test]
+    fn zap_swap_amount_is_the_floor_of_the_root() {
+        let (amount, reserve, fee_num, fee_den) = (1_234_567u64, 9_876_543u64, 30u64, 10_000u64);
+        let s = optimal_swap_amount_for_zap(amount, reserve, fee_num, fee_den).unwrap() as u128;
+        let (n, m, r, a) = (fee_den as u128, (fee_den - fee_num) as u128, reserve as u128, amount as u128);
+        let lhs = |s: u128| m * s * s + (n + m) * r * s;
+        assert!(lhs(s) <= n * a * r);
+        assert!(lhs(s + 1) > n * a * r);
+    }
+
+    #[test]
+    fn zap_swap_amount_handles_u64_extremes() {
+        let s = optimal_swap_amount_for_zap(u64::MAX, u64::MAX, 30, 10_000).unwrap();
+        assert!(s > 0 && s < u64::MAX / 2);
+    }
+
+    #[test]
+    fn zap_swap_amount_rejects_invalid_inputs() {
+        let result = optimal_swap_amount_for_zap(0, 1_000, 30, 10_000);
+        assert_eq!(error_code(result), u32::from(AmmError::AmountTooSmall));
+        let result = optimal_swap_amount_for_zap(1_000, 0, 30, 10_000);
+        assert_eq!(error_code(result), u32::from(AmmError::PoolNotInitialized));
+        let result = optimal_swap_amount_for_zap(1_000, 1_000, 10_000, 10_000);
+        assert_eq!(error_code(result), u32::from(AmmError::InvalidFeeParameters));
+    }
+
+    #[test]
+    fn product_swaps_hold_their_invariants_on_random_inputs() {
+        let mut rng = Rng(0x9e37_79b9_7f4a_7c15);
+        for _ in 0..10_000 {
+            let reserve_in = rng.between(1_000, 1 << 50);
+            let reserve_out = rng.between(1_000, 1 << 50);
+            let amount_in = rng.between(1, reserve_in * 4);
+            let fee = rng.between(0, 1_000);
+            let protocol = rng.between(0, 6);
+            let swap = |amount| {
+                calculate_swap_output(amount, reserve_in, reserve_out, fee, 10_000, protocol, 6, Curve::ConstantProduct)
+            };
+            let Ok((out, _, protocol_fee)) = swap(amount_in) else {
+                continue;
+            };
+
+            assert!(out < reserve_out);
+            // k never falls, even with the protocol's cut leaving the pool
+            let k_before = reserve_in as u128 * reserve_out as u128;
+            let k_after = (reserve_in + amount_in - protocol_fee) as u128 * (reserve_out - out) as u128;
+            assert!(k_after >= k_before);
+            // Paying more never buys less
+            let more = amount_in + rng.between(1, amount_in);
+            assert!(swap(more).unwrap().0 >= out);
+        }
+    }
+
+    #[test]
+    fn stable_swaps_hold_their_invariants_on_random_inputs() {
+        let mut rng = Rng(0xd1b5_4a32_d192_ed03);
+        for _ in 0..2_000 {
+            let reserve_in = rng.between(1_000_000, 1 << 45);
+            // Near peg: the other reserve within 4x
+            let reserve_out = rng.between(reserve_in / 4 + 1, reserve_in * 4);
+            let amount_in = rng.between(1, reserve_in);
+            let amp = rng.between(MIN_AMP, 10_000);
+            let curve = Curve::Stable { amp };
+            let swap = |amount| calculate_swap_output(amount, reserve_in, reserve_out, 4, 10_000, 0, 0, curve);
+            let Ok((out, _, _)) = swap(amount_in) else {
+                continue;
+            };
+
+            assert!(out < reserve_out);
+            let d_before = compute_stable_d(reserve_in, reserve_out, amp).unwrap();
+            let d_after = compute_stable_d(reserve_in + amount_in, reserve_out - out, amp).unwrap();
+            assert!(d_after >= d_before);
+            let more = amount_in + rng.between(1, amount_in);
+            assert!(swap(more).unwrap().0 >= out);
+        }
+    }
+
+    #[test]
+    fn chunked_swaps_sum_to_a_single_swap_within_rounding() {
+        let mut rng = Rng(0x6a09_e667_f3bc_c908);
+        for _ in 0..2_000 {
+            let reserve_in = rng.between(1_000_000, 1 << 50);
+            let reserve_out = rng.between(1_000_000, 1 << 50);
+            let amount_in = rng.between(1_000, reserve_in);
+            let chunks = rng.between(1, 16);
+            let fee = rng.between(0, 100);
+            let swap = |amount, reserve_in, reserve_out| {
+                calculate_swap_output(amount, reserve_in, reserve_out, fee, 10_000, 0, 1, Curve::ConstantProduct)
+                    .map(|(out, _, _)| out)
+            };
+
+            // As swap_split walks the chunks; a chunk too small to buy
+            // anything fails there as it does here
+            let chunk_size = amount_in / chunks;
+            let (mut chunk_in_reserve, mut chunk_out_reserve, mut summed) = (reserve_in, reserve_out, 0);
+            let walked: Result<()> = (0..chunks).try_for_each(|i| {
+                let chunk = if i + 1 == chunks { amount_in - chunk_size * i } else { chunk_size };
+                let out = swap(chunk, chunk_in_reserve, chunk_out_reserve)?;
+                chunk_in_reserve += chunk;
+                chunk_out_reserve -= out;
+                summed += out;
+                Ok(())
+            });
+            if walked.is_err() {
+                continue;
+            }
+
+            // Chunking moves the result by at most a unit of output rounding
+            // per chunk, plus the output value of the fee and of a unit of
+            // fee rounding per chunk: fees reinvested between chunks deepen
+            // the pool against later ones, while each chunk's fee rounds down
+            let single = swap(amount_in, reserve_in, reserve_out).unwrap();
+            let fee_in = (amount_in as u128 * fee as u128).div_ceil(10_000) + chunks as u128;
+            let bound = chunks as u128 + (fee_in * reserve_out as u128).div_ceil(reserve_in as u128);
+            assert!(single.abs_diff(summed) as u128 <= bound, "{single} vs {summed}");
+            if fee == 0 {
+                assert!(summed <= single && single - summed <= chunks);
+            }
+        }
+    }
+
+    #[test]
+    fn zap_swap_amount_strands_only_rounding_on_random_inputs() {
+        let mut rng = Rng(0x2545_f491_4f6c_dd1d);
+        for _ in 0..10_000 {
+            let reserve_in = rng.between(1_000, 1 << 50);
+            let reserve_out = rng.between(1_000, 1 << 50);
+            let amount = rng.between(2, reserve_in);
+            let fee = rng.between(0, 1_000);
+            let s = optimal_swap_amount_for_zap(amount, reserve_in, fee, 10_000).unwrap();
+            assert!(s < amount);
+            let Ok((out, _, _)) =
+                calculate_swap_output(s, reserve_in, reserve_out, fee, 10_000, 0, 0, Curve::ConstantProduct)
+            else {
+                continue;
+            };
+
+            // Input left over once `out` is paired at the post-swap ratio
+            let (pool_in, pool_out) = ((reserve_in + s) as u128, (reserve_out - out) as u128);
+            let paired_in = (out as u128 * pool_in).div_ceil(pool_out);
+            let stranded = (amount - s) as u128 - paired_in.min((amount - s) as u128);
+            // Flooring the root, the fee and the output each strand at most
+            // a unit of input or about one unit of output's worth
+            assert!(stranded <= 3 || stranded * pool_out <= 3 * pool_in);
+        }
+    }
+
+    #[test]
+    fn initial_liquidity_accepts_deposits_above_u64_product() {
+        assert_eq!(calculate_initial_liquidity(u64::MAX, u64::MAX).unwrap(), u64::MAX);
+        assert_eq!(calculate_initial_liquidity(1 << 40, 1 << 40).unwrap(), 1 << 40);
+    }
+
+    #[test]
+    fn initial_liquidity_still_enforces_the_minimum() {
+        let result = calculate_initial_liquidity(999, 999);
+        assert_eq!(error_code(result), u32::from(AmmError::InitialLiquidityTooSmall));
+        assert_eq!(calculate_initial_liquidity(1_000, 1_000).unwrap(), MINIMUM_LIQUIDITY);
+    }
+
+    #[test]
+    fn sqrt_returns_the_floor_root_around_perfect_squares() {
+        for root in [1u128, 2, 3, 1_000, u32::MAX as u128, u64::MAX as u128] {
+            let square = root * root;
+            assert_eq!(sqrt(square).unwrap() as u128, root);
+            assert_eq!(sqrt(square - 1).unwrap() as u128, root - 1);
+            if let Some(above) = square.checked_add(1) {
+                assert_eq!(sqrt(above).unwrap() as u128, root);
+            }
+        }
+    }
+
+    #[test]
+    fn sqrt_holds_the_floor_invariant_across_bit_widths() {
+        assert_eq!(sqrt(0).unwrap(), 0);
+        assert_eq!(sqrt(u128::MAX).unwrap(), u64::MAX);
+        for shift in 0..128 {
+            for y in [1u128 << shift, (1u128 << shift) | 1, u128::MAX >> shift] {
+                let root = sqrt(y).unwrap() as u128;
+                assert!(root * root <= y);
+                assert!((root + 1).checked_mul(root + 1).is_none_or(|next| y < next));
+            }
+        }
+    }
+
+    #[test]
+    fn dust_burns_redeem_the_side_that_rounds_to_something() {
+        // One LP is worth 0.001 A and 1_000 B
+        assert_eq!(calculate_amounts_for_liquidity(1, 1_000_000, 1_000_000_000_000, 1_000_000_000).unwrap(), (0, 1_000));
+        // At 1_000 LP the A side crosses its first whole unit
+        assert_eq!(calculate_amounts_for_liquidity(999, 1_000_000, 1_000_000_000_000, 1_000_000_000).unwrap().0, 0);
+        assert_eq!(calculate_amounts_for_liquidity(1_000, 1_000_000, 1_000_000_000_000, 1_000_000_000).unwrap().0, 1);
+        // Rounding is always down, in the pool's favour
+        assert_eq!(calculate_amounts_for_liquidity(3, 10, 10, 4).unwrap(), (7, 7));
+    }
+
+    #[test]
+    fn dust_burns_rounding_to_nothing_on_both_sides_are_rejected() {
+        let result = calculate_amounts_for_liquidity(1, 1_000, 1_000, 1_000_000);
+        assert_eq!(error_code(result), u32::from(AmmError::BurnAmountTooSmall));
+        assert_eq!(calculate_amounts_for_liquidity(1_000, 1_000, 1_000, 1_000_000).unwrap(), (1, 1));
+    }
+
+    #[test]
+    fn balanced_deposits_mint_exactly_and_strand_at_most_a_unit() {
+        let mut rng = Rng(0x2545_f491_4f6c_dd1d);
+        for _ in 0..10_000 {
+            let reserve_base = rng.between(1_000, 1 << 50);
+            let reserve_other = rng.between(1_000, 1 << 50);
+            let supply = rng.between(1_000, 1 << 50);
+            let amount = rng.between(1, reserve_base);
+            let Ok((used_base, used_other, liquidity)) =
+                calculate_balanced_deposit(amount, reserve_base, reserve_other, supply)
+            else {
+                continue;
+            };
+
+            assert!(used_base <= amount);
+            assert_eq!(
+                calculate_liquidity_to_mint(used_base, used_other, reserve_base, reserve_other, supply).unwrap(),
+                liquidity
+            );
+            // Burning the new LP straight away returns the deposit less at
+            // most one unit of rounding per side (a burn rounding to nothing
+            // on both sides is rejected, which is the same outcome)
+            let (back_base, back_other) = calculate_amounts_for_liquidity(
+                liquidity,
+                reserve_base + used_base,
+                reserve_other + used_other,
+                supply + liquidity,
+            )
+            .unwrap_or((0, 0));
+            assert!(back_base <= used_base && used_base - back_base <= 1);
+            assert!(back_other <= used_other && used_other - back_other <= 1);
+        }
+    }
+
+    #[test]
+    fn balanced_deposits_reject_empty_pools_and_dust() {
+        let result = calculate_balanced_deposit(1_000, 0, 1_000, 1_000);
+        assert_eq!(error_code(result), u32::from(AmmError::PoolNotInitialized));
+        let result = calculate_balanced_deposit(1, 1_000_000, 1_000_000, 1_000);
+        assert_eq!(error_code(result), u32::from(AmmError::InsufficientLiquidityMinted));
+    }
+
+    #[test]
+    fn rounded_mints_and_burns_never_dilute_the_remaining_lp() {
+        let mut rng = Rng(0x9e37_79b9_7f4a_7c15);
+        for _ in 0..10_000 {
+            let reserve_a = rng.between(1_000, 1 << 50);
+            let reserve_b = rng.between(1_000, 1 << 50);
+            let supply = rng.between(1_000, 1 << 50);
+            let amount_a = rng.between(1, reserve_a);
+            let amount_b = rng.between(1, reserve_b);
+            if let Ok(minted) = calculate_liquidity_to_mint(amount_a, amount_b, reserve_a, reserve_b, supply) {
+                verify_lp_value(reserve_a, reserve_b, supply, reserve_a + amount_a, reserve_b + amount_b, supply + minted)
+                    .unwrap();
+            }
+
+            let burned = rng.between(1, supply - 1);
+            if let Ok((out_a, out_b)) = calculate_amounts_for_liquidity(burned, reserve_a, reserve_b, supply) {
+                verify_lp_value(reserve_a, reserve_b, supply, reserve_a - out_a, reserve_b - out_b, supply - burned)
+                    .unwrap();
+            }
+        }
+    }
+
+    #[test]
+    fn lp_value_rejects_a_unit_of_dilution() {
+        // An exact proportional deposit keeps the value per LP unchanged
+        verify_lp_value(1_000, 1_000, 1_000, 1_100, 1_100, 1_100).unwrap();
+        // Minting one LP too many for a deposit
+        let result = verify_lp_value(1_000, 1_000, 1_000, 1_100, 1_100, 1_101);
+        assert_eq!(error_code(result), u32::from(AmmError::InvariantViolation));
+        // Paying out one unit too many for a burn
+        let result = verify_lp_value(1_000, 1_000, 1_000, 899, 900, 900);
+        assert_eq!(error_code(result), u32::from(AmmError::InvariantViolation));
+    }
+
+    #[test]
+    fn price_limits_round_the_minimum_output_up() {
+        // Selling A at a floor of 1.5 B per A
+        let limit = 3 * Q64 / 2;
+        assert_eq!(min_out_for_price_limit(100, limit, true).unwrap(), 150);
+        assert_eq!(min_out_for_price_limit(101, limit, true).unwrap(), 152);
+        // Selling B at a ceiling of 2 B per A
+        assert_eq!(min_out_for_price_limit(100, 2 * Q64, false).unwrap(), 50);
+        assert_eq!(min_out_for_price_limit(101, 2 * Q64, false).unwrap(), 51);
+    }
+
+    #[test]
+    fn price_limits_reject_zero_and_unreachable_outputs() {
+        let result = min_out_for_price_limit(100, 0, true);
+        assert_eq!(error_code(result), u32::from(AmmError::InvalidPriceLimit));
+        let result = min_out_for_price_limit(u64::MAX, 2 * Q64, true);
+        assert_eq!(error_code(result), u32::from(AmmError::SlippageExceeded));
+        let result = min_out_for_price_limit(u64::MAX, 1, false);
+        assert_eq!(error_code(result), u32::from(AmmError::SlippageExceeded));
+    }
+
+    #[test]
+    fn pairing_in_ratio_takes_the_limiting_side_whole() {
+        assert_eq!(pair_in_ratio(100, 1_000, 1_000, 3_000).unwrap(), (100, 300));
+        assert_eq!(pair_in_ratio(100, 200, 1_000, 3_000).unwrap(), (66, 200));
+        assert_eq!(pair_in_ratio(0, 200, 1_000, 3_000).unwrap(), (0, 0));
+    }
+
+    #[test]
+    fn pairing_in_ratio_saturates_instead_of_truncating() {
+        // b_for_a is 2^64 exactly: truncated it would wrap to 0 and pass as
+        // a pairing of the whole A side with no B at all
+        let (amount_a, amount_b) = pair_in_ratio(1 << 32, 5, 1, 1 << 32).unwrap();
+        assert_eq!((amount_a, amount_b), (0, 5));
+        let (amount_a, amount_b) = pair_in_ratio(u64::MAX, u64::MAX, 1, u64::MAX).unwrap();
+        assert_eq!((amount_a, amount_b), (1, u64::MAX));
+
+        let result = pair_in_ratio(1, 1, 0, 1);
+        assert_eq!(error_code(result), u32::from(AmmError::PoolNotInitialized));
+    }
+}
@@ -0,0 +1,33 @@
+use anchor_lang::prelude::*;
+use crate::constants::*;
+
+/// Deposit terms a token owner has signed for a relayer to execute once
+/// PDA Seeds: ["deposit_intent", pool, owner]
+#[account]
+#[derive(InitSpace)]
+pub struct DepositIntent {
+    /// Bump seed for PDA
+    pub bump: u8,
+
+    /// Pool the deposit goes into
+    pub pool: Pubkey,
+
+    /// Owner of the deposited tokens and of the LP minted
+    pub owner: Pubkey,
+
+    /// Exact token A amount to deposit
+    pub amount_a: u64,
+
+    /// Exact token B amount to deposit
+    pub amount_b: u64,
+
+    /// Minimum LP the owner accepts
+    pub min_liquidity: u64,
+
+    /// Unix timestamp after which the intent can no longer be executed
+    pub deadline: i64,
+}
+
+impl DepositIntent {
+    pub const SEED_PREFIX: &'static [u8] = DEPOSIT_INTENT_SEED;
+}
@@ -0,0 +1,31 @@
+use anchor_lang::prelude::*;
+use crate::constants::*;
+
+/// Per-trader swap fee discount on one pool
+/// PDA Seeds: ["fee_tier", pool, user]
+#[account]
+#[derive(InitSpace)]
+pub struct FeeTier {
+    /// Bump seed for PDA
+    pub bump: u8,
+
+    /// Pool the discount applies to
+    pub pool: Pubkey,
+
+    /// Discounted trader
+    pub user: Pubkey,
+
+    /// Discount off the pool's swap fee, in BPS of volume
+    pub discount_bps: u16,
+}
+
+impl FeeTier {
+    pub const SEED_PREFIX: &'static [u8] = FEE_TIER_SEED;
+
+    /// Fee numerator over `fee_denominator` after the discount, floored at
+    /// zero in case the pool fee was lowered below the discount since
+    pub fn discounted_fee_numerator(&self, fee_numerator: u64, fee_denominator: u64) -> u64 {
+        let discount = self.discount_bps as u128 * fee_denominator as u128 / BPS_DENOMINATOR as u128;
+        (fee_numerator as u128).saturating_sub(discount) as u64
+    }
+}
@@ -0,0 +1,41 @@
+use anchor_lang::prelude::*;
+use crate::constants::*;
+
+/// LP tokens escrowed until a fixed unlock time
+/// PDA Seeds: ["liquidity_lock", pool, owner, nonce]
+#[account]
+#[derive(InitSpace)]
+pub struct LiquidityLock {
+    /// Bump seed for PDA
+    pub bump: u8,
+
+    /// Bump seed of the lock's LP escrow account
+    pub escrow_bump: u8,
+
+    /// Pool whose LP is locked
+    pub pool: Pubkey,
+
+    /// Owner the LP is returned to
+    pub owner: Pubkey,
+
+    /// Owner-chosen discriminator between locks in the same pool
+    pub nonce: u64,
+
+    /// LP tokens held in escrow
+    pub amount: u64,
+
+    /// Earliest time the LP can be unlocked
+    pub unlock_timestamp: i64,
+
+    /// Timestamp the lock was created
+    pub created_at: i64,
+}
+
+impl LiquidityLock {
+    pub const SEED_PREFIX: &'static [u8] = LIQUIDITY_LOCK_SEED;
+
+    /// Whether the lock has expired at `now`
+    pub fn is_unlocked(&self, now: i64) -> bool {
+        now >= self.unlock_timestamp
+    }
+}
@@ -1,5 +1,27 @@
-/// State module exports
+//! State module exports
 
 pub mod pool;
+pub mod oracle;
+pub mod swap_history;
+pub mod price_checkpoints;
+pub mod protocol_config;
+pub mod position;
+pub mod deposit_intent;
+pub mod position_receipt;
+pub mod liquidity_lock;
+pub mod user_swap_state;
+pub mod fee_tier;
+pub mod vault;
 
 pub use pool::*;
+pub use oracle::*;
+pub use swap_history::*;
+pub use price_checkpoints::*;
+pub use protocol_config::*;
+pub use position::*;
+pub use deposit_intent::*;
+pub use position_receipt::*;
+pub use liquidity_lock::*;
+pub use user_swap_state::*;
+pub use fee_tier::*;
+pub use vault::*;
@@ -0,0 +1,33 @@
+use anchor_lang::prelude::*;
+use crate::constants::*;
+
+/// Reference price pushed by an off-chain updater for oracle-priced pools
+/// PDA Seeds: ["oracle", pool]
+#[account]
+#[derive(InitSpace)]
+pub struct PriceOracle {
+    /// Bump seed for PDA
+    pub bump: u8,
+
+    /// Pool this oracle prices
+    pub pool: Pubkey,
+
+    /// Key allowed to push prices
+    pub updater: Pubkey,
+
+    /// Price of token A in token B (Q64 format)
+    pub price: u128,
+
+    /// Timestamp of the last price update
+    pub last_update_timestamp: i64,
+}
+
+impl PriceOracle {
+    pub const SEED_PREFIX: &'static [u8] = ORACLE_SEED;
+
+    /// Check whether the price is older than `max_staleness` seconds
+    pub fn is_stale(&self, now: i64, max_staleness: i64) -> bool {
+        self.last_update_timestamp == 0
+            || now.saturating_sub(self.last_update_timestamp) > max_staleness
+    }
+}
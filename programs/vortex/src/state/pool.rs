@@ -1,10 +1,14 @@
 use anchor_lang::prelude::*;
 use crate::constants::*;
+use anchor_spl::token;
+use crate::math::{accumulate_price, fee_growth_delta, fees_for_growth, scaled_price, spot_price, Curve};
+use crate::state::{Position, ProtocolConfig};
 
 /// Liquidity pool state
 /// PDA Seeds: ["pool", token_a_mint, token_b_mint]
 #[account]
 #[derive(InitSpace)]
+#[cfg_attr(test, derive(Default))]
 pub struct Pool {
     /// Version for future upgrades
     pub version: u8,
@@ -36,7 +40,7 @@ pub struct Pool {
     /// Reserve of token B
     pub reserve_b: u64,
 
-    /// Fee numerator (e.g., 3 for 0.3%)
+    /// Fee numerator (e.g., 3 for 0.3%); applies to A->B swaps
     pub fee_numerator: u64,
 
     /// Fee denominator (e.g., 1000 for 0.3%)
@@ -72,13 +76,249 @@ pub struct Pool {
     /// Last update slot
     pub last_update_slot: u64,
 
-    /// Reserved for future upgrades (128 bytes)
-    pub _reserved: [u8; 128],
+    /// Slot of the last reserve snapshot checkpoint
+    pub last_checkpoint_slot: u64,
+
+    /// Program whose CPIs into swap are fee-exempt (default = none)
+    pub exempt_program: Pubkey,
+
+    /// Flat fee on liquidity withdrawals sent to the treasury (BPS, 0 = off)
+    pub withdraw_fee_bps: u16,
+
+    /// Owner of the LP token account holding the locked MINIMUM_LIQUIDITY
+    pub locked_lp_recipient: Pubkey,
+
+    /// Price swaps from the reference oracle instead of the curve
+    pub oracle_priced: bool,
+
+    /// Spread charged on oracle-priced swaps (BPS)
+    pub oracle_spread_bps: u16,
+
+    /// Maximum oracle price age for oracle-priced swaps (seconds)
+    pub oracle_max_staleness: i64,
+
+    /// Reference price oracle (default = none)
+    pub oracle: Pubkey,
+
+    /// Granular per-token pause bitfield (see PAUSE_* constants)
+    pub pause_flags: u8,
+
+    /// Slots without an update after which reserves count as stale (0 = off)
+    pub stale_after_slots: u64,
+
+    /// Extra fee on the first swap after reserves go stale (BPS)
+    pub stale_surcharge_bps: u16,
+
+    /// Minimum reserve_b / reserve_a ratio after deposits (Q64, 0 = off)
+    pub min_ratio: u128,
+
+    /// Maximum reserve_b / reserve_a ratio after deposits (Q64, 0 = off)
+    pub max_ratio: u128,
+
+    /// Token A mint decimals
+    pub decimals_a: u8,
+
+    /// Token B mint decimals
+    pub decimals_b: u8,
+
+    /// Share of each swap fee rebated to the swapper as LP tokens (BPS)
+    pub rebate_bps: u16,
+
+    /// Remaining LP tokens that may be minted as swap rebates
+    pub rebate_budget: u64,
+
+    /// Minimum slots LP must be held before it can be removed (0 = disabled)
+    pub min_lp_hold_slots: u64,
+
+    /// Per-swap event detail (EVENT_VERBOSITY_*)
+    pub event_verbosity: u8,
+
+    /// Guardian allowed to pause (but never unpause) the pool
+    pub guardian: Pubkey,
+
+    /// Pool is being wound down; enables `emergency_withdraw` while paused
+    pub unwinding: bool,
+
+    /// Protocol's share of each swap fee, numerator (0 = off)
+    pub protocol_fee_numerator: u64,
+
+    /// Protocol's share of each swap fee, denominator
+    pub protocol_fee_denominator: u64,
+
+    /// Protocol fees in token A held in the vault but excluded from reserves
+    pub protocol_fees_a: u64,
+
+    /// Protocol fees in token B held in the vault but excluded from reserves
+    pub protocol_fees_b: u64,
+
+    /// Time-weighted accumulator of price_a (Q64 * seconds, wrapping)
+    pub price_cumulative_a: u128,
+
+    /// Time-weighted accumulator of price_b (Q64 * seconds, wrapping)
+    pub price_cumulative_b: u128,
+
+    /// Timestamp the price accumulators were last brought up to date
+    pub last_oracle_timestamp: i64,
+
+    /// Token program owning token A (SPL Token or Token-2022)
+    pub token_a_program: Pubkey,
+
+    /// Token program owning token B (SPL Token or Token-2022)
+    pub token_b_program: Pubkey,
+
+    /// Reject swaps while reserves have drifted from the vault balances
+    pub strict_reserves: bool,
+
+    /// Pricing curve (CURVE_*)
+    pub curve_type: u8,
+
+    /// StableSwap amplification coefficient (stable pools only)
+    pub amp: u64,
+
+    /// Highest post-swap price_a seen (Q64)
+    pub price_high: u128,
+
+    /// Lowest post-swap price_a seen (Q64, u128::MAX until the first swap)
+    pub price_low: u128,
+
+    /// Smallest accepted swap input, in base units of the input token
+    pub min_swap_amount: u64,
+
+    /// Token A swap volume in the current window
+    pub window_volume_a: u64,
+
+    /// Token B swap volume in the current window
+    pub window_volume_b: u64,
+
+    /// Start of the current volume window
+    pub window_start_timestamp: i64,
+
+    /// Length of a volume window (seconds, 0 = never reset)
+    pub window_duration: i64,
+
+    /// Fee numerator for B->A swaps (equal to `fee_numerator` unless
+    /// directional fees are set)
+    pub fee_numerator_b_to_a: u64,
+
+    /// Token A LP fees earned per LP token over the pool's life (Q64,
+    /// wrapping); see `pending_fees`
+    pub fee_growth_global_a: u128,
+
+    /// Token B LP fees earned per LP token over the pool's life (Q64, wrapping)
+    pub fee_growth_global_b: u128,
+
+    /// Minimum slots between two swaps by the same user (0 = off)
+    pub min_slot_between_swaps: u64,
+
+    /// Upper bound on either reserve (0 = unlimited)
+    pub max_reserve: u64,
+
+    /// Owner of the token accounts `collect_protocol_fees` pays out to; the
+    /// protocol config (treasury) unless governance redirects it
+    pub fee_recipient: Pubkey,
+
+    /// Reject new liquidity while swaps and withdrawals stay open
+    pub deposits_frozen: bool,
+
+    /// Floor either reserve may not be withdrawn below, except by a holder
+    /// burning their whole LP balance or position (0 = off)
+    pub min_reserve: u64,
+
+    /// Slot of the most recent deposit; starts the LP hold window for
+    /// withdrawals without a position of their own
+    pub last_deposit_slot: u64,
+
+    /// Reserved for future upgrades (10 bytes)
+    pub _reserved: [u8; 10],
 }
 
 impl Pool {
     pub const SEED_PREFIX: &'static [u8] = POOL_SEED;
 
+    /// Order two mints canonically: token A is the mint with the
+    /// lexicographically smaller key
+    pub fn canonical_mints(mint_x: Pubkey, mint_y: Pubkey) -> (Pubkey, Pubkey) {
+        if mint_x.as_ref() < mint_y.as_ref() {
+            (mint_x, mint_y)
+        } else {
+            (mint_y, mint_x)
+        }
+    }
+
+    /// Derive the pool PDA and bump for an already-ordered mint pair
+    pub fn find_address(token_a_mint: &Pubkey, token_b_mint: &Pubkey) -> (Pubkey, u8) {
+        Pubkey::find_program_address(
+            &[POOL_SEED, token_a_mint.as_ref(), token_b_mint.as_ref()],
+            &crate::ID,
+        )
+    }
+
+    /// Token program for token A; pools created before Token-2022 support
+    /// have no program stored and use legacy SPL Token
+    pub fn token_program_a(&self) -> Pubkey {
+        if self.token_a_program == Pubkey::default() {
+            token::ID
+        } else {
+            self.token_a_program
+        }
+    }
+
+    /// Token program for token B (see `token_program_a`)
+    pub fn token_program_b(&self) -> Pubkey {
+        if self.token_b_program == Pubkey::default() {
+            token::ID
+        } else {
+            self.token_b_program
+        }
+    }
+
+    /// Pricing curve used for swaps
+    pub fn curve(&self) -> Curve {
+        if self.curve_type == CURVE_STABLE {
+            Curve::Stable { amp: self.amp }
+        } else {
+            Curve::ConstantProduct
+        }
+    }
+
+    /// Upper bound for `min_swap_amount`: one whole token of the side with
+    /// fewer decimals, so the minimum can never price out ordinary swaps
+    pub fn max_min_swap_amount(&self) -> u64 {
+        10u64
+            .checked_pow(self.decimals_a.min(self.decimals_b) as u32)
+            .unwrap_or(u64::MAX)
+    }
+
+    /// Set the defaults of fields a pool from an older layout lacks, and
+    /// bump `version`. Zero is a valid setting for most fields; these are
+    /// the ones where it can only mean the field did not exist yet.
+    /// Decimals come from the mints and are always correct to overwrite;
+    /// they must be in place before `min_swap_amount` is clamped.
+    pub fn apply_migration_defaults(&mut self, now: i64, decimals_a: u8, decimals_b: u8) {
+        self.decimals_a = decimals_a;
+        self.decimals_b = decimals_b;
+        if self.fee_numerator_b_to_a == 0 {
+            self.fee_numerator_b_to_a = self.fee_numerator;
+        }
+        if self.fee_recipient == Pubkey::default() {
+            self.fee_recipient = ProtocolConfig::address();
+        }
+        if self.price_high == 0 && self.price_low == 0 {
+            self.price_low = u128::MAX;
+        }
+        if self.min_swap_amount == 0 {
+            self.min_swap_amount = MIN_SWAP_AMOUNT.min(self.max_min_swap_amount());
+        }
+        if self.window_start_timestamp == 0 {
+            self.window_start_timestamp = now;
+            self.window_duration = VOLUME_WINDOW_DURATION;
+        }
+        if self.last_oracle_timestamp == 0 {
+            self.last_oracle_timestamp = now;
+        }
+        self.version = PROTOCOL_VERSION;
+    }
+
     /// Check if pool is initialized
     pub fn is_initialized(&self) -> bool {
         self.reserve_a > 0 && self.reserve_b > 0
@@ -86,40 +326,130 @@ impl Pool {
 
     /// Get current price of token B per token A (Q64 format)
     pub fn price_a(&self) -> u128 {
-        if self.reserve_a == 0 {
-            return 0;
-        }
-        (self.reserve_b as u128)
-            .saturating_mul(Q64)
-            .saturating_div(self.reserve_a as u128)
+        spot_price(self.reserve_a, self.reserve_b)
     }
 
     /// Get current price of token A per token B (Q64 format)
     pub fn price_b(&self) -> u128 {
-        if self.reserve_b == 0 {
-            return 0;
-        }
-        (self.reserve_a as u128)
-            .saturating_mul(Q64)
-            .saturating_div(self.reserve_b as u128)
+        spot_price(self.reserve_b, self.reserve_a)
+    }
+
+    /// Whole-token price of token A in token B, scaled by 1e9
+    /// (`PRICE_SCALE_DECIMALS`), using the mints' stored decimals
+    pub fn price_a_scaled(&self) -> u128 {
+        scaled_price(self.reserve_a, self.reserve_b, self.decimals_a, self.decimals_b)
     }
 
-    /// Calculate invariant k = reserve_a * reserve_b
+    /// Whole-token price of token B in token A, scaled by 1e9
+    /// (`PRICE_SCALE_DECIMALS`), using the mints' stored decimals
+    pub fn price_b_scaled(&self) -> u128 {
+        scaled_price(self.reserve_b, self.reserve_a, self.decimals_b, self.decimals_a)
+    }
+
+    /// Price of token B per token A (Q64), or `None` for an empty pool
+    ///
+    /// Prefer this over `price_a()`, whose 0 for an empty pool is
+    /// indistinguishable from a genuinely tiny price.
+    pub fn try_price_a(&self) -> Option<u128> {
+        self.is_initialized().then(|| self.price_a())
+    }
+
+    /// Price of token A per token B (Q64), or `None` for an empty pool
+    pub fn try_price_b(&self) -> Option<u128> {
+        self.is_initialized().then(|| self.price_b())
+    }
+
+    /// Calculate invariant k = reserve_a * reserve_b (exact: the product of
+    /// two u64 values always fits in u128)
     pub fn k(&self) -> u128 {
-        (self.reserve_a as u128).saturating_mul(self.reserve_b as u128)
+        self.reserve_a as u128 * self.reserve_b as u128
+    }
+
+    /// Whether both reserves stay within `max_reserve` (0 = unlimited)
+    pub fn within_reserve_cap(&self, reserve_a: u64, reserve_b: u64) -> bool {
+        self.max_reserve == 0 || (reserve_a <= self.max_reserve && reserve_b <= self.max_reserve)
     }
 
-    /// Get fee in basis points
+    /// Whether `min_lp_hold_slots` have passed since the pool's last deposit
+    pub fn is_hold_elapsed(&self, current_slot: u64) -> bool {
+        current_slot.saturating_sub(self.last_deposit_slot) >= self.min_lp_hold_slots
+    }
+
+    /// Whether both reserves stay at or above `min_reserve` (0 = off)
+    pub fn above_reserve_floor(&self, reserve_a: u64, reserve_b: u64) -> bool {
+        reserve_a >= self.min_reserve && reserve_b >= self.min_reserve
+    }
+
+    /// Get fee in basis points (computed in u128, saturating at u64::MAX)
     pub fn fee_bps(&self) -> u64 {
         if self.fee_denominator == 0 {
             return 0;
         }
-        (self.fee_numerator * BPS_DENOMINATOR) / self.fee_denominator
+        let bps = (self.fee_numerator as u128 * BPS_DENOMINATOR as u128) / self.fee_denominator as u128;
+        u64::try_from(bps).unwrap_or(u64::MAX)
+    }
+
+    /// Fee numerator charged on a swap in the given direction
+    pub fn swap_fee_numerator(&self, a_to_b: bool) -> u64 {
+        if a_to_b {
+            self.fee_numerator
+        } else {
+            self.fee_numerator_b_to_a
+        }
+    }
+
+    /// Check whether a granular pause flag is set
+    pub fn is_token_paused(&self, flag: u8) -> bool {
+        self.pause_flags & flag != 0
+    }
+
+    /// Check a reserve ratio (Q64, reserve_b per reserve_a) against the band
+    pub fn ratio_in_band(&self, ratio: u128) -> bool {
+        (self.min_ratio == 0 || ratio >= self.min_ratio)
+            && (self.max_ratio == 0 || ratio <= self.max_ratio)
+    }
+
+    /// Whether `signer` may pause the pool (authority or guardian)
+    pub fn can_pause(&self, signer: &Pubkey) -> bool {
+        *signer == self.authority || (self.guardian != Pubkey::default() && *signer == self.guardian)
+    }
+
+    /// Whether a fee-exempt market-maker program is configured
+    pub fn has_exempt_program(&self) -> bool {
+        self.exempt_program != Pubkey::default()
+    }
+
+    /// Cumulative prices extrapolated to `now` at the current spot price
+    ///
+    /// TWAP over [t0, t1] = cumulative_price_delta(observe(t1), observe(t0)) / (t1 - t0)
+    pub fn observe(&self, now: i64) -> (u128, u128) {
+        let elapsed = now.saturating_sub(self.last_oracle_timestamp).max(0) as u64;
+        if elapsed == 0 || !self.is_initialized() {
+            return (self.price_cumulative_a, self.price_cumulative_b);
+        }
+        (
+            accumulate_price(self.price_cumulative_a, self.price_a(), elapsed),
+            accumulate_price(self.price_cumulative_b, self.price_b(), elapsed),
+        )
     }
 
-    /// Validate reserves match vault balances
+    /// Fold the time since the last update into the price accumulators.
+    /// Call before every reserve change so each price is weighted by how
+    /// long it was actually in effect.
+    pub fn accumulate_prices(&mut self, now: i64) {
+        let (cumulative_a, cumulative_b) = self.observe(now);
+        self.price_cumulative_a = cumulative_a;
+        self.price_cumulative_b = cumulative_b;
+        self.last_oracle_timestamp = now;
+    }
+
+    /// Validate reserves match vault balances. Each vault holds its reserve
+    /// plus uncollected protocol fees; drift up to `RESERVE_TOLERANCE` is allowed.
     pub fn validate_reserves(&self, vault_a_balance: u64, vault_b_balance: u64) -> bool {
-        self.reserve_a == vault_a_balance && self.reserve_b == vault_b_balance
+        let expected_a = self.reserve_a as u128 + self.protocol_fees_a as u128;
+        let expected_b = self.reserve_b as u128 + self.protocol_fees_b as u128;
+        expected_a.abs_diff(vault_a_balance as u128) <= RESERVE_TOLERANCE as u128
+            && expected_b.abs_diff(vault_b_balance as u128) <= RESERVE_TOLERANCE as u128
     }
 
     /// Update reserves
@@ -128,7 +458,54 @@ impl Pool {
         self.reserve_b = new_reserve_b;
     }
 
-    /// Record swap statistics
+    /// Advance the fee growth indexes by LP fees earned by `lp_supply` LP
+    /// tokens. The indexes wrap like the TWAP accumulators; only differences
+    /// between two readings are meaningful.
+    pub fn accrue_fee_growth(&mut self, fee_a: u64, fee_b: u64, lp_supply: u64) {
+        self.fee_growth_global_a = self.fee_growth_global_a.wrapping_add(fee_growth_delta(fee_a, lp_supply));
+        self.fee_growth_global_b = self.fee_growth_global_b.wrapping_add(fee_growth_delta(fee_b, lp_supply));
+    }
+
+    /// Fees a position has earned since it was opened, (token A, token B).
+    ///
+    /// Fees are reinvested into the reserves, so this is accounting only:
+    /// the amounts are already part of the position's LP value.
+    pub fn pending_fees(&self, position: &Position) -> (u64, u64) {
+        let earned = |global: u128, checkpoint: u128, owed: u64| {
+            owed.saturating_add(fees_for_growth(position.liquidity, global.wrapping_sub(checkpoint)))
+        };
+        (
+            earned(self.fee_growth_global_a, position.fee_growth_checkpoint_a, position.fees_owed_a),
+            earned(self.fee_growth_global_b, position.fee_growth_checkpoint_b, position.fees_owed_b),
+        )
+    }
+
+    /// Reset the window counters once the current window has ended,
+    /// advancing the start by whole windows so boundaries stay aligned
+    fn roll_volume_window(&mut self, now: i64) {
+        if self.window_duration <= 0 {
+            return;
+        }
+        let elapsed = now.saturating_sub(self.window_start_timestamp);
+        if elapsed >= self.window_duration {
+            self.window_start_timestamp += elapsed - elapsed % self.window_duration;
+            self.window_volume_a = 0;
+            self.window_volume_b = 0;
+        }
+    }
+
+    /// Swap volume (token A, token B) in the window containing `now`
+    pub fn window_volume(&self, now: i64) -> (u64, u64) {
+        if self.window_duration > 0
+            && now.saturating_sub(self.window_start_timestamp) >= self.window_duration
+        {
+            return (0, 0);
+        }
+        (self.window_volume_a, self.window_volume_b)
+    }
+
+    /// Record swap statistics. Call after the reserves are updated, so the
+    /// price range reflects the post-swap price.
     pub fn record_swap(
         &mut self,
         volume_a: u64,
@@ -138,6 +515,10 @@ impl Pool {
         timestamp: i64,
         slot: u64,
     ) {
+        self.roll_volume_window(timestamp);
+        self.window_volume_a = self.window_volume_a.saturating_add(volume_a);
+        self.window_volume_b = self.window_volume_b.saturating_add(volume_b);
+
         self.total_swaps = self.total_swaps.saturating_add(1);
         self.cumulative_volume_a = self.cumulative_volume_a.saturating_add(volume_a);
         self.cumulative_volume_b = self.cumulative_volume_b.saturating_add(volume_b);
@@ -145,5 +526,352 @@ impl Pool {
         self.cumulative_fees_b = self.cumulative_fees_b.saturating_add(fee_b);
         self.last_swap_timestamp = timestamp;
         self.last_update_slot = slot;
+
+        let price = self.price_a();
+        self.price_high = self.price_high.max(price);
+        self.price_low = self.price_low.min(price);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::math::cumulative_price_delta;
+
+    fn pool(reserve_a: u64, reserve_b: u64, decimals_a: u8, decimals_b: u8) -> Pool {
+        Pool {
+            reserve_a,
+            reserve_b,
+            decimals_a,
+            decimals_b,
+            ..Default::default()
+        }
+    }
+
+    /// Account size of a version-1 pool: the original fields followed by a
+    /// zeroed 128-byte `_reserved`
+    const V1_POOL_LEN: usize = 8 + 3 + 5 * 32 + 4 * 8 + 32 + 1 + 8 * 8 + 128;
+
+    #[test]
+    fn migrates_a_v1_sized_pool() {
+        let authority = Pubkey::new_unique();
+        let v1 = Pool {
+            version: 1,
+            reserve_a: 5_000_000,
+            reserve_b: 7_000_000_000,
+            fee_numerator: 30,
+            fee_denominator: 10_000,
+            authority,
+            last_update_slot: 42,
+            ..Default::default()
+        };
+        let mut data = Vec::new();
+        v1.try_serialize(&mut data).unwrap();
+        // Everything after the v1 fields is still zero, as in a real v1
+        // account's `_reserved`
+        assert!(data[V1_POOL_LEN..].iter().all(|b| *b == 0));
+
+        // Truncate to the v1 size, then grow it as `migrate_pool` does
+        data.truncate(V1_POOL_LEN);
+        data.resize(8 + Pool::INIT_SPACE, 0);
+        let mut pool = Pool::try_deserialize(&mut &data[..]).unwrap();
+        pool.apply_migration_defaults(1_700_000_000, 6, 9);
+        let mut migrated = Vec::new();
+        pool.try_serialize(&mut migrated).unwrap();
+        let pool = Pool::try_deserialize(&mut &migrated[..]).unwrap();
+
+        assert_eq!(pool.version, PROTOCOL_VERSION);
+        assert_eq!((pool.reserve_a, pool.reserve_b), (5_000_000, 7_000_000_000));
+        assert_eq!(pool.last_update_slot, 42);
+        assert_eq!((pool.decimals_a, pool.decimals_b), (6, 9));
+        assert_eq!(pool.min_swap_amount, MIN_SWAP_AMOUNT);
+        assert_eq!(pool.fee_numerator_b_to_a, 30);
+        assert_eq!(pool.fee_recipient, ProtocolConfig::address());
+        assert_eq!(pool.price_low, u128::MAX);
+        assert_eq!(pool.window_start_timestamp, 1_700_000_000);
+        assert_eq!(pool.window_duration, VOLUME_WINDOW_DURATION);
+        assert_eq!(pool.last_oracle_timestamp, 1_700_000_000);
+        // 5 A (6 decimals) against 7 B (9 decimals)
+        assert_eq!(pool.price_a_scaled(), 1_400_000_000);
+    }
+
+    #[test]
+    fn migration_clamps_min_swap_amount_by_the_mint_decimals() {
+        let mut pool = Pool::default();
+        pool.apply_migration_defaults(0, 1, 9);
+        // One whole token of the 1-decimal side
+        assert_eq!(pool.min_swap_amount, 10);
+
+        let mut pool = Pool {
+            min_swap_amount: 5_000,
+            ..Default::default()
+        };
+        pool.apply_migration_defaults(0, 6, 6);
+        assert_eq!(pool.min_swap_amount, 5_000);
+    }
+
+    #[test]
+    fn scaled_prices_use_the_stored_decimals() {
+        // 1,000 A (6 decimals) against 2,000 B (9 decimals): 1 A = 2 B
+        let pool = pool(1_000_000_000, 2_000_000_000_000, 6, 9);
+        assert_eq!(pool.price_a_scaled(), 2_000_000_000);
+        assert_eq!(pool.price_b_scaled(), 500_000_000);
+    }
+
+    #[test]
+    fn scaled_prices_match_raw_prices_for_equal_decimals() {
+        let pool = pool(4_000, 1_000, 6, 6);
+        assert_eq!(pool.price_a_scaled(), 250_000_000);
+        assert_eq!(pool.price_b_scaled(), 4_000_000_000);
+    }
+
+    #[test]
+    fn scaled_prices_are_zero_for_an_empty_pool() {
+        let pool = pool(0, 0, 9, 6);
+        assert_eq!(pool.price_a_scaled(), 0);
+        assert_eq!(pool.price_b_scaled(), 0);
+    }
+
+    #[test]
+    fn try_prices_are_none_for_an_empty_pool() {
+        assert_eq!(pool(0, 0, 6, 6).try_price_a(), None);
+        assert_eq!(pool(1_000, 0, 6, 6).try_price_b(), None);
+    }
+
+    #[test]
+    fn try_prices_keep_tiny_prices_distinct_from_empty() {
+        let pool = pool(u64::MAX, 1, 6, 6);
+        assert_eq!(pool.try_price_a(), Some(1));
+        assert_eq!(pool.try_price_b(), Some(u64::MAX as u128 * Q64));
+    }
+
+    #[test]
+    fn fee_bps_does_not_overflow_on_huge_numerators() {
+        let pool = Pool {
+            fee_numerator: u64::MAX,
+            fee_denominator: 1,
+            ..Default::default()
+        };
+        assert_eq!(pool.fee_bps(), u64::MAX);
+
+        let pool = Pool {
+            fee_numerator: u64::MAX / 2,
+            fee_denominator: u64::MAX,
+            ..Default::default()
+        };
+        assert_eq!(pool.fee_bps(), 4_999);
+    }
+
+    #[test]
+    fn pending_fees_split_swaps_by_share_across_deposits() {
+        let mut pool = Pool::default();
+        let early = Position { liquidity: 300, ..Default::default() };
+        pool.accrue_fee_growth(1_000, 0, 1_000);
+        pool.accrue_fee_growth(0, 500, 1_000);
+        assert_eq!(pool.pending_fees(&early), (300, 150));
+
+        // A later deposit starts from the current index, so the earlier
+        // swaps' fees stay with the LP that was there
+        let late = Position {
+            liquidity: 1_000,
+            fee_growth_checkpoint_a: pool.fee_growth_global_a,
+            fee_growth_checkpoint_b: pool.fee_growth_global_b,
+            ..Default::default()
+        };
+        assert_eq!(pool.pending_fees(&late), (0, 0));
+
+        // Swaps after the deposit are split over the grown supply
+        pool.accrue_fee_growth(2_000, 0, 2_000);
+        assert_eq!(pool.pending_fees(&early), (600, 150));
+        assert_eq!(pool.pending_fees(&late), (1_000, 0));
+    }
+
+    #[test]
+    fn fee_growth_ignores_an_empty_supply_and_survives_wrapping() {
+        let mut pool = Pool::default();
+        pool.accrue_fee_growth(1_000, 1_000, 0);
+        assert_eq!((pool.fee_growth_global_a, pool.fee_growth_global_b), (0, 0));
+
+        // Checkpoints taken just before the index wraps still see the growth
+        pool.fee_growth_global_a = u128::MAX - Q64 + 1;
+        let position = Position {
+            liquidity: 10,
+            fee_growth_checkpoint_a: pool.fee_growth_global_a,
+            ..Default::default()
+        };
+        pool.accrue_fee_growth(20, 0, 10);
+        assert!(pool.fee_growth_global_a < position.fee_growth_checkpoint_a);
+        assert_eq!(pool.pending_fees(&position), (20, 0));
+    }
+
+    #[test]
+    fn compounding_settles_fees_earned_since_the_last_compounding() {
+        let mut pool = Pool::default();
+        let mut position = Position { liquidity: 256, ..Default::default() };
+        assert_eq!(position.compound_fees(&pool), None);
+
+        // The position holds a quarter of the supply
+        pool.accrue_fee_growth(512, 128, 1_024);
+        assert_eq!(position.compound_fees(&pool), Some((128, 32)));
+        assert_eq!(pool.pending_fees(&position), (0, 0));
+        assert_eq!(position.compound_fees(&pool), None);
+
+        // Fees owed from a deposit checkpoint are compounded too
+        pool.accrue_fee_growth(0, 64, 1_024);
+        position.checkpoint_fees(&pool);
+        position.liquidity = 512;
+        pool.accrue_fee_growth(0, 64, 1_024);
+        assert_eq!(position.compound_fees(&pool), Some((0, 48)));
+        assert_eq!((position.fees_owed_a, position.fees_owed_b), (0, 0));
+        assert_eq!(position.liquidity, 512);
+    }
+
+    #[test]
+    fn reserves_reconcile_with_vaults_holding_protocol_fees() {
+        let pool = Pool { protocol_fees_a: 7, ..pool(1_000, 2_000, 6, 6) };
+        assert!(pool.validate_reserves(1_007, 2_000));
+        // Rounding drift within RESERVE_TOLERANCE either way is accepted
+        assert!(pool.validate_reserves(1_007 + RESERVE_TOLERANCE, 2_000 - RESERVE_TOLERANCE));
+
+        // A donation or a shortfall beyond the tolerance is not
+        assert!(!pool.validate_reserves(1_007 + RESERVE_TOLERANCE + 1, 2_000));
+        assert!(!pool.validate_reserves(1_007, 2_000 - RESERVE_TOLERANCE - 1));
+        assert!(!pool.validate_reserves(1_000, 2_000));
+    }
+
+    #[test]
+    fn swaps_widen_the_price_range_in_both_directions() {
+        let mut pool = Pool { price_low: u128::MAX, ..pool(1_000, 1_000, 6, 6) };
+        let swap_to = |pool: &mut Pool, reserve_a, reserve_b| {
+            pool.update_reserves(reserve_a, reserve_b);
+            pool.record_swap(0, 0, 0, 0, 0, 0);
+            pool.price_a()
+        };
+
+        let first = swap_to(&mut pool, 1_000, 2_000);
+        assert_eq!((pool.price_low, pool.price_high), (first, first));
+        let low = swap_to(&mut pool, 2_000, 1_000);
+        let high = swap_to(&mut pool, 1_000, 4_000);
+        assert_eq!((pool.price_low, pool.price_high), (low, high));
+
+        // A price back inside the range leaves both marks alone
+        swap_to(&mut pool, 1_000, 1_000);
+        assert_eq!((pool.price_low, pool.price_high), (low, high));
+    }
+
+    #[test]
+    fn window_volume_resets_on_aligned_window_boundaries() {
+        let mut pool = Pool { window_start_timestamp: 1_000, window_duration: 100, ..pool(1_000, 1_000, 6, 6) };
+        pool.record_swap(10, 20, 0, 0, 1_050, 0);
+        pool.record_swap(5, 5, 0, 0, 1_099, 0);
+        assert_eq!(pool.window_volume(1_099), (15, 25));
+        // Lifetime totals keep counting across windows
+        assert_eq!(pool.cumulative_volume_a, 15);
+
+        // Reads past the boundary see an empty window before any swap rolls it
+        assert_eq!(pool.window_volume(1_100), (0, 0));
+        pool.record_swap(7, 3, 0, 0, 1_350, 0);
+        assert_eq!(pool.window_start_timestamp, 1_300);
+        assert_eq!(pool.window_volume(1_350), (7, 3));
+        assert_eq!(pool.cumulative_volume_a, 22);
+    }
+
+    #[test]
+    fn window_volume_ignores_clock_regressions_and_a_zero_duration() {
+        let mut pool = Pool { window_start_timestamp: 1_000, window_duration: 100, ..pool(1_000, 1_000, 6, 6) };
+        pool.record_swap(10, 10, 0, 0, 1_050, 0);
+        pool.record_swap(10, 10, 0, 0, 900, 0);
+        assert_eq!(pool.window_start_timestamp, 1_000);
+        assert_eq!(pool.window_volume(1_050), (20, 20));
+
+        // Without a duration the window never rolls
+        let mut unwindowed = Pool { window_start_timestamp: 1_000, ..Default::default() };
+        unwindowed.record_swap(10, 10, 0, 0, 1_000_000, 0);
+        assert_eq!(unwindowed.window_volume(2_000_000), (10, 10));
+    }
+
+    #[test]
+    fn reserve_cap_is_inclusive_and_zero_means_unlimited() {
+        let capped = Pool { max_reserve: 1_000, ..pool(1, 1, 6, 6) };
+        assert!(capped.within_reserve_cap(1_000, 1_000));
+        assert!(!capped.within_reserve_cap(1_001, 1));
+        assert!(!capped.within_reserve_cap(1, 1_001));
+
+        let unlimited = pool(1, 1, 6, 6);
+        assert!(unlimited.within_reserve_cap(u64::MAX, u64::MAX));
+        // k is exact even at the u64 limit
+        assert_eq!(pool(u64::MAX, u64::MAX, 6, 6).k(), u64::MAX as u128 * u64::MAX as u128);
+    }
+
+    #[test]
+    fn hold_windows_run_from_the_last_deposit_slot() {
+        let pool = Pool { min_lp_hold_slots: 2, last_deposit_slot: 100, ..Default::default() };
+        assert!(!pool.is_hold_elapsed(100));
+        assert!(!pool.is_hold_elapsed(101));
+        assert!(pool.is_hold_elapsed(102));
+        // A clock behind the recorded slot never counts as elapsed
+        assert!(!pool.is_hold_elapsed(99));
+
+        let position = Position { last_deposit_slot: 100, ..Default::default() };
+        assert!(!position.is_hold_elapsed(101, 2));
+        assert!(position.is_hold_elapsed(102, 2));
+        assert!(position.is_hold_elapsed(100, 0));
+    }
+
+    #[test]
+    fn reserve_floor_is_inclusive_and_zero_means_off() {
+        let floored = Pool { min_reserve: 1_000, ..pool(1, 1, 6, 6) };
+        assert!(floored.above_reserve_floor(1_000, 1_000));
+        assert!(!floored.above_reserve_floor(999, 1_000));
+        assert!(!floored.above_reserve_floor(1_000, 999));
+
+        assert!(pool(1, 1, 6, 6).above_reserve_floor(0, 0));
+    }
+
+    #[test]
+    fn only_the_authority_or_a_set_guardian_can_pause() {
+        let (authority, guardian) = (Pubkey::new_unique(), Pubkey::new_unique());
+        let mut pool = Pool { authority, ..Default::default() };
+        assert!(pool.can_pause(&authority));
+        // An unset guardian is the default key, which must not gain the role
+        assert!(!pool.can_pause(&Pubkey::default()));
+        assert!(!pool.can_pause(&guardian));
+
+        pool.guardian = guardian;
+        assert!(pool.can_pause(&guardian));
+        assert!(!pool.can_pause(&Pubkey::new_unique()));
+    }
+
+    #[test]
+    fn twap_weights_each_price_by_how_long_it_held() {
+        // Price 2 for 30s, then 4 for 10s: TWAP = (2 * 30 + 4 * 10) / 40 = 2.5
+        let mut pool = Pool { last_oracle_timestamp: 100, ..pool(1_000, 2_000, 6, 6) };
+        let start = pool.observe(100).0;
+        pool.accumulate_prices(130);
+        pool.update_reserves(1_000, 4_000);
+        let end = pool.observe(140).0;
+        assert_eq!(cumulative_price_delta(end, start) / 40, 5 * Q64 / 2);
+
+        // Observing is read-only; accumulating twice at once adds nothing
+        let before = pool.price_cumulative_a;
+        pool.accumulate_prices(130);
+        assert_eq!(pool.price_cumulative_a, before);
+    }
+
+    #[test]
+    fn twap_ignores_empty_pools_and_clock_regressions() {
+        let mut empty = Pool { last_oracle_timestamp: 100, ..Default::default() };
+        empty.accumulate_prices(200);
+        assert_eq!(empty.price_cumulative_a, 0);
+
+        let mut pool = Pool { last_oracle_timestamp: 100, ..pool(1_000, 2_000, 6, 6) };
+        assert_eq!(pool.observe(50), (0, 0));
+
+        // Deltas stay correct across an accumulator wrap
+        pool.price_cumulative_a = u128::MAX - Q64 + 1;
+        let start = pool.price_cumulative_a;
+        let end = pool.observe(101).0;
+        assert!(end < start);
+        assert_eq!(cumulative_price_delta(end, start), 2 * Q64);
     }
 }
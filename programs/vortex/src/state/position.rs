@@ -0,0 +1,91 @@
+use anchor_lang::prelude::*;
+use crate::constants::*;
+use crate::state::Pool;
+
+/// Per-owner record of an LP's activity in a pool
+/// PDA Seeds: ["position", pool, owner]
+#[account]
+#[derive(InitSpace)]
+#[cfg_attr(test, derive(Default))]
+pub struct Position {
+    /// Bump seed for PDA
+    pub bump: u8,
+
+    /// Pool the position belongs to
+    pub pool: Pubkey,
+
+    /// Owner of the LP tokens
+    pub owner: Pubkey,
+
+    /// Slot of the owner's most recent deposit
+    pub last_deposit_slot: u64,
+
+    /// LP tokens attributed to this position by deposits and withdrawals
+    pub liquidity: u64,
+
+    /// Liquidity-weighted entry price of token A in token B (Q64)
+    pub entry_price: u128,
+
+    /// Liquidity-weighted LP share growth at entry (Q64, see `math::lp_share_growth`)
+    pub entry_growth: u128,
+
+    /// Pool fee growth index for token A at the last checkpoint (Q64)
+    pub fee_growth_checkpoint_a: u128,
+
+    /// Pool fee growth index for token B at the last checkpoint (Q64)
+    pub fee_growth_checkpoint_b: u128,
+
+    /// Token A fees earned before the last checkpoint
+    pub fees_owed_a: u64,
+
+    /// Token B fees earned before the last checkpoint
+    pub fees_owed_b: u64,
+}
+
+impl Position {
+    pub const SEED_PREFIX: &'static [u8] = POSITION_SEED;
+
+    /// Check whether liquidity deposited at `last_deposit_slot` may be
+    /// withdrawn at `current_slot` under a `min_hold_slots` window
+    pub fn is_hold_elapsed(&self, current_slot: u64, min_hold_slots: u64) -> bool {
+        current_slot.saturating_sub(self.last_deposit_slot) >= min_hold_slots
+    }
+
+    /// Fold fees earned since the last checkpoint into `fees_owed_a/b` and
+    /// checkpoint the pool's fee growth. Call before `liquidity` changes.
+    pub fn checkpoint_fees(&mut self, pool: &Pool) {
+        (self.fees_owed_a, self.fees_owed_b) = pool.pending_fees(self);
+        self.fee_growth_checkpoint_a = pool.fee_growth_global_a;
+        self.fee_growth_checkpoint_b = pool.fee_growth_global_b;
+    }
+
+    /// Compound the fees earned since the last compounding: checkpoint the
+    /// pool's fee growth and clear `fees_owed_a/b`, returning the amounts,
+    /// or `None` when nothing has been earned. LP fees are reinvested into
+    /// the reserves as they are charged, so the position's LP already holds
+    /// them and no tokens move.
+    pub fn compound_fees(&mut self, pool: &Pool) -> Option<(u64, u64)> {
+        let earned = pool.pending_fees(self);
+        if earned == (0, 0) {
+            return None;
+        }
+        self.checkpoint_fees(pool);
+        (self.fees_owed_a, self.fees_owed_b) = (0, 0);
+        Some(earned)
+    }
+
+    /// Record a deposit, blending the entry price and growth by liquidity
+    pub fn record_deposit(&mut self, slot: u64, liquidity: u64, price: u128, growth: u128) {
+        let total = self.liquidity as u128 + liquidity as u128;
+        // Weight of the new deposit in Q32
+        let weight = ((liquidity as u128) << 32).checked_div(total).unwrap_or(0);
+        let blend = |old: u128, new: u128| -> u128 {
+            (old >> 32) * ((1 << 32) - weight) + (new >> 32) * weight
+        };
+
+        self.entry_price = blend(self.entry_price, price);
+        self.entry_growth = blend(self.entry_growth, growth);
+        self.liquidity = self.liquidity.saturating_add(liquidity);
+        self.last_deposit_slot = slot;
+    }
+}
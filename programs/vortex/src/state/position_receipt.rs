@@ -0,0 +1,54 @@
+use anchor_lang::prelude::*;
+use crate::constants::*;
+
+/// Escrowed LP deposit tracked as its own position, so an owner can hold
+/// several independently accounted positions in one pool
+/// PDA Seeds: ["position", pool, owner, nonce]
+#[account]
+#[derive(InitSpace)]
+pub struct PositionReceipt {
+    /// Bump seed for PDA
+    pub bump: u8,
+
+    /// Bump seed of the receipt's LP escrow account
+    pub escrow_bump: u8,
+
+    /// Pool the receipt belongs to
+    pub pool: Pubkey,
+
+    /// Owner allowed to redeem the receipt
+    pub owner: Pubkey,
+
+    /// Owner-chosen discriminator between receipts in the same pool
+    pub nonce: u64,
+
+    /// LP tokens held in escrow
+    pub liquidity: u64,
+
+    /// Pool reserve of token A when the receipt was opened
+    pub entry_reserve_a: u64,
+
+    /// Pool reserve of token B when the receipt was opened
+    pub entry_reserve_b: u64,
+
+    /// LP supply when the receipt was opened
+    pub entry_lp_supply: u64,
+
+    /// Timestamp the receipt was opened
+    pub created_at: i64,
+}
+
+impl PositionReceipt {
+    pub const SEED_PREFIX: &'static [u8] = POSITION_SEED;
+
+    /// Token amounts backing this receipt at entry
+    pub fn entry_amounts(&self) -> (u64, u64) {
+        if self.entry_lp_supply == 0 {
+            return (0, 0);
+        }
+        let share = |reserve: u64| {
+            ((reserve as u128) * (self.liquidity as u128) / (self.entry_lp_supply as u128)) as u64
+        };
+        (share(self.entry_reserve_a), share(self.entry_reserve_b))
+    }
+}
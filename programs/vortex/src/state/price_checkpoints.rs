@@ -0,0 +1,163 @@
+use anchor_lang::prelude::*;
+use crate::constants::*;
+use crate::math::cumulative_price_delta;
+use crate::state::Pool;
+
+/// A pool's TWAP accumulators as of one timestamp
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Default, Debug, InitSpace)]
+pub struct PriceCheckpoint {
+    pub timestamp: i64,
+    pub price_cumulative_a: u128,
+    pub price_cumulative_b: u128,
+}
+
+/// Optional ring buffer of past TWAP accumulator readings, so a window's
+/// average price can be read on-chain without an off-chain observer
+/// PDA Seeds: ["price_checkpoints", pool]
+#[account]
+#[derive(InitSpace)]
+pub struct PriceCheckpoints {
+    /// Bump seed for PDA
+    pub bump: u8,
+
+    /// Pool whose accumulators are recorded
+    pub pool: Pubkey,
+
+    /// Index of the next slot to write
+    pub head: u8,
+
+    /// Number of valid entries (saturates at PRICE_CHECKPOINTS_LEN)
+    pub count: u8,
+
+    /// Ring buffer storage
+    pub checkpoints: [PriceCheckpoint; PRICE_CHECKPOINTS_LEN],
+}
+
+impl PriceCheckpoints {
+    pub const SEED_PREFIX: &'static [u8] = PRICE_CHECKPOINTS_SEED;
+
+    /// Append a checkpoint, overwriting the oldest once full. Checkpoints
+    /// less than PRICE_CHECKPOINT_INTERVAL after the latest are skipped, so
+    /// a run of cheap swaps cannot flush the window out of the buffer.
+    pub fn push(&mut self, checkpoint: PriceCheckpoint) {
+        if self.latest().is_some_and(|latest| {
+            checkpoint.timestamp < latest.timestamp.saturating_add(PRICE_CHECKPOINT_INTERVAL)
+        }) {
+            return;
+        }
+        self.checkpoints[self.head as usize] = checkpoint;
+        self.head = ((self.head as usize + 1) % PRICE_CHECKPOINTS_LEN) as u8;
+        if (self.count as usize) < PRICE_CHECKPOINTS_LEN {
+            self.count += 1;
+        }
+    }
+
+    /// Most recent checkpoint, if any
+    pub fn latest(&self) -> Option<PriceCheckpoint> {
+        if self.count == 0 {
+            return None;
+        }
+        let index = (self.head as usize + PRICE_CHECKPOINTS_LEN - 1) % PRICE_CHECKPOINTS_LEN;
+        Some(self.checkpoints[index])
+    }
+
+    /// Checkpoint taken before `now` whose timestamp is closest to `target`
+    pub fn closest_to(&self, target: i64, now: i64) -> Option<PriceCheckpoint> {
+        let count = self.count as usize;
+        let start = (self.head as usize + PRICE_CHECKPOINTS_LEN - count) % PRICE_CHECKPOINTS_LEN;
+        (0..count)
+            .map(|i| self.checkpoints[(start + i) % PRICE_CHECKPOINTS_LEN])
+            .filter(|checkpoint| checkpoint.timestamp < now)
+            .min_by_key(|checkpoint| checkpoint.timestamp.abs_diff(target))
+    }
+
+    /// Average prices (Q64) of token A and token B from the checkpoint
+    /// closest to `now - period` until `now`, with that checkpoint's
+    /// timestamp. Accumulators are extrapolated to `now` as in `Pool::observe`.
+    pub fn average_prices(&self, pool: &Pool, period: i64, now: i64) -> Option<(u128, u128, i64)> {
+        let checkpoint = self.closest_to(now.saturating_sub(period), now)?;
+        let (cumulative_a, cumulative_b) = pool.observe(now);
+        let elapsed = (now - checkpoint.timestamp) as u128;
+
+        Some((
+            cumulative_price_delta(cumulative_a, checkpoint.price_cumulative_a) / elapsed,
+            cumulative_price_delta(cumulative_b, checkpoint.price_cumulative_b) / elapsed,
+            checkpoint.timestamp,
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn buffer() -> PriceCheckpoints {
+        PriceCheckpoints {
+            bump: 0,
+            pool: Pubkey::default(),
+            head: 0,
+            count: 0,
+            checkpoints: [PriceCheckpoint::default(); PRICE_CHECKPOINTS_LEN],
+        }
+    }
+
+    fn checkpoint(timestamp: i64, price: u128) -> PriceCheckpoint {
+        PriceCheckpoint {
+            timestamp,
+            price_cumulative_a: price * timestamp as u128,
+            price_cumulative_b: price * timestamp as u128,
+        }
+    }
+
+    #[test]
+    fn push_skips_checkpoints_inside_the_interval() {
+        let mut buffer = buffer();
+        for timestamp in 0..PRICE_CHECKPOINT_INTERVAL * 3 {
+            buffer.push(checkpoint(timestamp, 1));
+        }
+        assert_eq!(buffer.count, 3);
+        assert_eq!(buffer.latest().unwrap().timestamp, PRICE_CHECKPOINT_INTERVAL * 2);
+    }
+
+    #[test]
+    fn push_wraps_and_keeps_the_newest() {
+        let mut buffer = buffer();
+        let total = PRICE_CHECKPOINTS_LEN as i64 + 4;
+        for i in 0..total {
+            buffer.push(checkpoint(i * PRICE_CHECKPOINT_INTERVAL, 1));
+        }
+        assert_eq!(buffer.count as usize, PRICE_CHECKPOINTS_LEN);
+        assert_eq!(buffer.latest().unwrap().timestamp, (total - 1) * PRICE_CHECKPOINT_INTERVAL);
+        // The oldest four were evicted
+        let oldest = buffer.closest_to(0, i64::MAX).unwrap();
+        assert_eq!(oldest.timestamp, 4 * PRICE_CHECKPOINT_INTERVAL);
+    }
+
+    #[test]
+    fn closest_to_ignores_checkpoints_from_now() {
+        let mut buffer = buffer();
+        buffer.push(checkpoint(1_000, 1));
+        assert!(buffer.closest_to(900, 1_000).is_none());
+        assert_eq!(buffer.closest_to(900, 1_001).unwrap().timestamp, 1_000);
+    }
+
+    #[test]
+    fn average_prices_over_the_window() {
+        // 1:2 pool, so price_a is 2 and price_b is 1/2 (Q64)
+        let pool = Pool {
+            reserve_a: 1_000,
+            reserve_b: 2_000,
+            price_cumulative_a: 2 * Q64 * 1_000,
+            price_cumulative_b: Q64 / 2 * 1_000,
+            last_oracle_timestamp: 1_000,
+            ..Default::default()
+        };
+        let mut buffer = buffer();
+        buffer.push(PriceCheckpoint { timestamp: 0, price_cumulative_a: 0, price_cumulative_b: 0 });
+
+        let (price_a, price_b, start) = buffer.average_prices(&pool, 2_000, 2_000).unwrap();
+        assert_eq!(start, 0);
+        assert_eq!(price_a, 2 * Q64);
+        assert_eq!(price_b, Q64 / 2);
+    }
+}
@@ -0,0 +1,23 @@
+use anchor_lang::prelude::*;
+use crate::constants::*;
+
+/// Program-wide settings controlled by protocol governance
+/// PDA Seeds: ["protocol_config"]
+#[account]
+#[derive(InitSpace)]
+pub struct ProtocolConfig {
+    /// Bump seed for PDA
+    pub bump: u8,
+
+    /// Protocol governance key
+    pub governance: Pubkey,
+}
+
+impl ProtocolConfig {
+    pub const SEED_PREFIX: &'static [u8] = PROTOCOL_CONFIG_SEED;
+
+    /// Address of the protocol config PDA, which owns every treasury vault
+    pub fn address() -> Pubkey {
+        Pubkey::find_program_address(&[PROTOCOL_CONFIG_SEED], &crate::ID).0
+    }
+}
@@ -0,0 +1,56 @@
+use anchor_lang::prelude::*;
+use crate::constants::*;
+
+/// A single entry in the on-chain trade tape
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Default, Debug, InitSpace)]
+pub struct SwapRecord {
+    pub amount_in: u64,
+    pub amount_out: u64,
+    pub a_to_b: bool,
+    /// Price of token A in token B after the swap (Q64)
+    pub price: u128,
+    pub timestamp: i64,
+}
+
+/// Optional ring buffer of a pool's most recent swaps
+/// PDA Seeds: ["swap_history", pool]
+#[account]
+#[derive(InitSpace)]
+pub struct SwapHistory {
+    /// Bump seed for PDA
+    pub bump: u8,
+
+    /// Pool whose swaps are recorded
+    pub pool: Pubkey,
+
+    /// Index of the next slot to write
+    pub head: u8,
+
+    /// Number of valid entries (saturates at SWAP_HISTORY_LEN)
+    pub count: u8,
+
+    /// Ring buffer storage
+    pub records: [SwapRecord; SWAP_HISTORY_LEN],
+}
+
+impl SwapHistory {
+    pub const SEED_PREFIX: &'static [u8] = SWAP_HISTORY_SEED;
+
+    /// Append a record, overwriting the oldest once full
+    pub fn push(&mut self, record: SwapRecord) {
+        self.records[self.head as usize] = record;
+        self.head = ((self.head as usize + 1) % SWAP_HISTORY_LEN) as u8;
+        if (self.count as usize) < SWAP_HISTORY_LEN {
+            self.count += 1;
+        }
+    }
+
+    /// Recorded swaps, oldest first
+    pub fn recent(&self) -> Vec<SwapRecord> {
+        let count = self.count as usize;
+        let start = (self.head as usize + SWAP_HISTORY_LEN - count) % SWAP_HISTORY_LEN;
+        (0..count)
+            .map(|i| self.records[(start + i) % SWAP_HISTORY_LEN])
+            .collect()
+    }
+}
@@ -0,0 +1,29 @@
+use anchor_lang::prelude::*;
+use crate::constants::*;
+
+/// Per-user swap record enforcing a pool's swap cooldown
+/// PDA Seeds: ["user_swap", pool, user]
+#[account]
+#[derive(InitSpace)]
+pub struct UserSwapState {
+    /// Bump seed for PDA
+    pub bump: u8,
+
+    /// Pool the record belongs to
+    pub pool: Pubkey,
+
+    /// Swapping user
+    pub user: Pubkey,
+
+    /// Slot of the user's most recent swap
+    pub last_swap_slot: u64,
+}
+
+impl UserSwapState {
+    pub const SEED_PREFIX: &'static [u8] = USER_SWAP_SEED;
+
+    /// Check whether a swap at `current_slot` clears a `cooldown_slots` window
+    pub fn is_cooldown_elapsed(&self, current_slot: u64, cooldown_slots: u64) -> bool {
+        current_slot.saturating_sub(self.last_swap_slot) >= cooldown_slots
+    }
+}
@@ -0,0 +1,38 @@
+use anchor_lang::prelude::*;
+use crate::constants::*;
+
+/// Auto-compounding LP vault: holds a pool's LP tokens for many depositors
+/// and issues shares against them
+/// PDA Seeds: ["lp_vault", pool]
+#[account]
+#[derive(InitSpace)]
+pub struct Vault {
+    /// Bump seed for PDA
+    pub bump: u8,
+
+    /// Pool whose LP the vault holds
+    pub pool: Pubkey,
+
+    /// Vault share mint
+    pub share_mint: Pubkey,
+
+    /// Vault's LP token account
+    pub lp_holdings: Pubkey,
+
+    /// Vault's token A account (harvest input)
+    pub token_a_holdings: Pubkey,
+
+    /// Vault's token B account (harvest input)
+    pub token_b_holdings: Pubkey,
+
+    /// LP tokens accounted to shareholders. Tracked here rather than read
+    /// from `lp_holdings` so direct LP donations cannot skew the share price.
+    pub total_lp: u64,
+
+    /// Slot of the last harvest
+    pub last_harvest_slot: u64,
+}
+
+impl Vault {
+    pub const SEED_PREFIX: &'static [u8] = LP_VAULT_SEED;
+}
@@ -0,0 +1,123 @@
+import { Keypair, PublicKey } from "@solana/web3.js";
+import { createAccount, getMint } from "@solana/spl-token";
+import { expect } from "chai";
+import {
+  INCINERATOR,
+  addLiquidity,
+  balance,
+  createPool,
+  expectError,
+  fundUser,
+  lockedLpAccount,
+  newUser,
+  payer,
+  pda,
+  program,
+  provider,
+  removeLiquidity,
+} from "./helpers";
+
+describe("add_liquidity locked liquidity", () => {
+  const SEED = 1_000_000_000n;
+  const MINIMUM_LIQUIDITY = 1_000n;
+
+  it("rejects a locked LP account owned by another wallet of the depositor", async () => {
+    const f = await createPool();
+    const lp = await fundUser(f, await newUser(), SEED, SEED);
+    const secondWallet = Keypair.generate();
+    const locked = await createAccount(provider.connection, payer, f.lpMint, secondWallet.publicKey, Keypair.generate());
+
+    await expectError(addLiquidity(f, lp, SEED, SEED, { lockedLpToken: locked }), "InvalidLockedLiquidityRecipient");
+    expect((await program.account.pool.fetch(f.pool)).reserveA.toNumber()).to.equal(0);
+  });
+
+  it("burns the locked LP to an incinerator-owned account", async () => {
+    const f = await createPool();
+    const lp = await fundUser(f, await newUser(), SEED, SEED);
+
+    await addLiquidity(f, lp, SEED, SEED, { lockedLpToken: await lockedLpAccount(f) });
+
+    const pool = await program.account.pool.fetch(f.pool);
+    expect(pool.lockedLpRecipient.equals(INCINERATOR)).to.be.true;
+  });
+
+  it("holds the locked LP in the pool's locked-LP PDA", async () => {
+    const f = await createPool();
+    const lp = await fundUser(f, await newUser(), SEED, SEED);
+    const lockPda = pda([Buffer.from("locked_lp"), f.pool.toBuffer()]);
+    const locked = await createAccount(provider.connection, payer, f.lpMint, lockPda, Keypair.generate());
+
+    await addLiquidity(f, lp, SEED, SEED, { lockedLpToken: locked });
+
+    const pool = await program.account.pool.fetch(f.pool);
+    expect(pool.lockedLpRecipient.equals(lockPda)).to.be.true;
+  });
+
+  it("rejects another pool's locked-LP PDA", async () => {
+    const f = await createPool();
+    const other = await createPool();
+    const lp = await fundUser(f, await newUser(), SEED, SEED);
+    const otherPda: PublicKey = pda([Buffer.from("locked_lp"), other.pool.toBuffer()]);
+    const locked = await createAccount(provider.connection, payer, f.lpMint, otherPda, Keypair.generate());
+
+    await expectError(addLiquidity(f, lp, SEED, SEED, { lockedLpToken: locked }), "InvalidLockedLiquidityRecipient");
+  });
+
+  it("mints MINIMUM_LIQUIDITY on top of the depositor's LP into a real LP account", async () => {
+    const f = await createPool();
+    const lp = await fundUser(f, await newUser(), SEED, SEED);
+    const locked = await lockedLpAccount(f);
+
+    await addLiquidity(f, lp, SEED, SEED, { lockedLpToken: locked });
+
+    const minted = await balance(lp.lpToken);
+    // sqrt(1e9 * 1e9) less the locked portion
+    expect(minted).to.equal(SEED - MINIMUM_LIQUIDITY);
+    expect(await balance(locked)).to.equal(MINIMUM_LIQUIDITY);
+    expect((await getMint(provider.connection, f.lpMint)).supply).to.equal(minted + MINIMUM_LIQUIDITY);
+  });
+
+  it("leaves the locked LP unredeemable after the depositor exits", async () => {
+    const f = await createPool();
+    const lp = await fundUser(f, await newUser(), SEED, SEED);
+    const locked = await lockedLpAccount(f);
+    await addLiquidity(f, lp, SEED, SEED, { lockedLpToken: locked });
+
+    await removeLiquidity(f, lp, await balance(lp.lpToken));
+    // Burning from the incinerator's account needs its signature
+    await expectError(removeLiquidity(f, { ...lp, lpToken: locked }, MINIMUM_LIQUIDITY), "0x4");
+
+    expect(await balance(locked)).to.equal(MINIMUM_LIQUIDITY);
+    expect((await getMint(provider.connection, f.lpMint)).supply).to.equal(MINIMUM_LIQUIDITY);
+    const pool = await program.account.pool.fetch(f.pool);
+    expect(pool.reserveA.toNumber()).to.be.greaterThan(0);
+    expect(pool.reserveB.toNumber()).to.be.greaterThan(0);
+  });
+});
+
+describe("add_liquidity LP mint authority", () => {
+  const SEED = 1_000_000_000n;
+
+  it("mints through the pool's LP mint authority PDA", async () => {
+    const f = await createPool();
+    const lp = await fundUser(f, await newUser(), SEED, SEED);
+    const mint = await getMint(provider.connection, f.lpMint);
+    expect(mint.mintAuthority!.equals(f.lpMintAuthority)).to.be.true;
+
+    await addLiquidity(f, lp, SEED, SEED, { lockedLpToken: await lockedLpAccount(f) });
+
+    expect(await balance(lp.lpToken)).to.equal(SEED - 1_000n);
+  });
+
+  it("rejects another pool's LP mint authority", async () => {
+    const f = await createPool();
+    const other = await createPool();
+    const lp = await fundUser(f, await newUser(), SEED, SEED);
+
+    await expectError(
+      addLiquidity(f, lp, SEED, SEED, { lockedLpToken: await lockedLpAccount(f), lpMintAuthority: other.lpMintAuthority }),
+      "ConstraintSeeds",
+    );
+    expect((await program.account.pool.fetch(f.pool)).reserveA.toNumber()).to.equal(0);
+  });
+});
@@ -0,0 +1,64 @@
+import { BN } from "@coral-xyz/anchor";
+import { getMint } from "@solana/spl-token";
+import { expect } from "chai";
+import {
+  FAR_DEADLINE,
+  PoolFixture,
+  UserAccounts,
+  addLiquidityAccounts,
+  balance,
+  createSeededPool,
+  expectError,
+  fundUser,
+  newUser,
+  program,
+  provider,
+} from "./helpers";
+
+describe("add_liquidity_balanced", () => {
+  const AMOUNT = 10_000_000n;
+  let f: PoolFixture;
+  let user: UserAccounts;
+
+  const addBalanced = (amount: bigint, baseIsA: boolean, maxOtherAmount: bigint) =>
+    program.methods
+      .addLiquidityBalanced(new BN(amount.toString()), baseIsA, new BN(maxOtherAmount.toString()), FAR_DEADLINE)
+      .accountsPartial(addLiquidityAccounts(f, user))
+      .signers([user.user])
+      .rpc();
+
+  beforeEach(async () => {
+    // 1:2 pool
+    ({ f } = await createSeededPool(1_000_000_000n, 2_000_000_000n));
+    user = await fundUser(f, await newUser(), AMOUNT, 3n * AMOUNT);
+  });
+
+  it("takes exactly the matching amount of the other token", async () => {
+    const supply = (await getMint(provider.connection, f.lpMint)).supply;
+    const before = await program.account.pool.fetch(f.pool);
+
+    await addBalanced(AMOUNT, true, 2n * AMOUNT);
+
+    expect(await balance(user.tokenA)).to.equal(0n);
+    expect(await balance(user.tokenB)).to.equal(AMOUNT);
+    const minted = await balance(user.lpToken);
+    expect(minted).to.equal((AMOUNT * supply) / 1_000_000_000n);
+    // Nothing unpaired: the pool's ratio is unchanged
+    const after = await program.account.pool.fetch(f.pool);
+    expect(BigInt(after.reserveA.sub(before.reserveA).toString())).to.equal(AMOUNT);
+    expect(BigInt(after.reserveB.sub(before.reserveB).toString())).to.equal(2n * AMOUNT);
+  });
+
+  it("sizes token A from a token B amount", async () => {
+    await addBalanced(2n * AMOUNT, false, AMOUNT);
+    expect(await balance(user.tokenA)).to.equal(0n);
+    expect(await balance(user.tokenB)).to.equal(AMOUNT);
+  });
+
+  it("rejects a counter-amount above max_other_amount", async () => {
+    await expectError(addBalanced(AMOUNT, true, 2n * AMOUNT - 1n), "SlippageExceeded");
+    expect(await balance(user.tokenA)).to.equal(AMOUNT);
+    expect(await balance(user.tokenB)).to.equal(3n * AMOUNT);
+    expect(await balance(user.lpToken)).to.equal(0n);
+  });
+});
@@ -0,0 +1,93 @@
+import { BN } from "@coral-xyz/anchor";
+import { PublicKey } from "@solana/web3.js";
+import { TOKEN_PROGRAM_ID, getMint } from "@solana/spl-token";
+import { expect } from "chai";
+import {
+  FAR_DEADLINE,
+  PoolFixture,
+  UserAccounts,
+  balance,
+  createSeededPool,
+  expectError,
+  fundUser,
+  newUser,
+  program,
+  provider,
+} from "./helpers";
+
+describe("add_liquidity_single_sided", () => {
+  const AMOUNT = 100_000_000n;
+  let f: PoolFixture;
+  let user: UserAccounts;
+
+  const zap = (tokenIn: PublicKey, mintIn: PublicKey, amountIn: bigint, minLiquidity = 0n) =>
+    program.methods
+      .addLiquiditySingleSided(new BN(amountIn.toString()), new BN(minLiquidity.toString()), FAR_DEADLINE)
+      .accountsPartial({
+        user: user.user.publicKey,
+        pool: f.pool,
+        tokenAVault: f.vaultA,
+        tokenBVault: f.vaultB,
+        lpMint: f.lpMint,
+        lpMintAuthority: f.lpMintAuthority,
+        userTokenIn: tokenIn,
+        userLpToken: user.lpToken,
+        position: null,
+        mintIn,
+        tokenProgramIn: TOKEN_PROGRAM_ID,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .signers([user.user])
+      .rpc();
+
+  beforeEach(async () => {
+    ({ f } = await createSeededPool());
+    user = await fundUser(f, await newUser(), AMOUNT, AMOUNT);
+  });
+
+  it("mints LP worth the deposit less the internal swap's fee", async () => {
+    const before = await program.account.pool.fetch(f.pool);
+    const supply = (await getMint(provider.connection, f.lpMint)).supply;
+
+    await zap(user.tokenA, f.mintA, AMOUNT);
+
+    expect(await balance(user.tokenA)).to.equal(0n);
+    const after = await program.account.pool.fetch(f.pool);
+    // The whole deposit joins reserve A; the swapped B never leaves the vault
+    expect(BigInt(after.reserveA.sub(before.reserveA).toString())).to.equal(AMOUNT);
+    expect(after.reserveB.eq(before.reserveB)).to.be.true;
+
+    // Priced at the new spot, the minted share is worth the deposit less
+    // about half the 0.3% fee; the unpaired dust is rounding only
+    const minted = await balance(user.lpToken);
+    const newSupply = supply + minted;
+    const reserveA = BigInt(after.reserveA.toString());
+    const reserveB = BigInt(after.reserveB.toString());
+    const shareA = (minted * reserveA) / newSupply;
+    const shareB = (minted * reserveB) / newSupply;
+    const valueInA = shareA + (shareB * reserveA) / reserveB;
+    expect(valueInA <= AMOUNT).to.equal(true);
+    expect(valueInA * 10_000n >= AMOUNT * 9_980n).to.equal(true);
+  });
+
+  it("zaps token B symmetrically", async () => {
+    const before = await program.account.pool.fetch(f.pool);
+    await zap(user.tokenB, f.mintB, AMOUNT);
+
+    expect(await balance(user.tokenB)).to.equal(0n);
+    const after = await program.account.pool.fetch(f.pool);
+    expect(BigInt(after.reserveB.sub(before.reserveB).toString())).to.equal(AMOUNT);
+    expect(after.reserveA.eq(before.reserveA)).to.be.true;
+    expect((await balance(user.lpToken)) > 0n).to.equal(true);
+  });
+
+  it("rejects a deposit minting less than the minimum", async () => {
+    const supply = (await getMint(provider.connection, f.lpMint)).supply;
+    // A balanced deposit of the same value would mint this much
+    const balancedShare = (AMOUNT * supply) / 2_000_000_000n;
+
+    await expectError(zap(user.tokenA, f.mintA, AMOUNT, 2n * balancedShare), "SlippageExceeded");
+    expect(await balance(user.tokenA)).to.equal(AMOUNT);
+    expect(await balance(user.lpToken)).to.equal(0n);
+  });
+});
@@ -0,0 +1,73 @@
+import { BN } from "@coral-xyz/anchor";
+import { Keypair } from "@solana/web3.js";
+import { expect } from "chai";
+import {
+  FAR_DEADLINE,
+  PoolFixture,
+  UserAccounts,
+  addLiquidityRemainingAccounts,
+  balance,
+  createSeededPool,
+  emittedEvents,
+  expectError,
+  fundUser,
+  newUser,
+  program,
+} from "./helpers";
+
+describe("batch_add_liquidity", () => {
+  const AMOUNT = 10_000_000n;
+  let pools: PoolFixture[];
+  let accounts: UserAccounts[];
+  let user: Keypair;
+
+  const batchAdd = (minLiquidity: bigint[]) =>
+    program.methods
+      .batchAddLiquidity(
+        minLiquidity.map((min) => ({
+          amountA: new BN(AMOUNT.toString()),
+          amountB: new BN(AMOUNT.toString()),
+          minLiquidity: new BN(min.toString()),
+        })),
+        FAR_DEADLINE,
+      )
+      .accountsPartial({ user: user.publicKey })
+      .remainingAccounts(pools.flatMap((f, i) => addLiquidityRemainingAccounts(f, accounts[i])))
+      .signers([user])
+      .rpc({ commitment: "confirmed" });
+
+  beforeEach(async () => {
+    pools = [(await createSeededPool()).f, (await createSeededPool()).f];
+    user = await newUser();
+    accounts = [];
+    for (const f of pools) {
+      accounts.push(await fundUser(f, user, AMOUNT, AMOUNT));
+    }
+  });
+
+  it("adds to both pools atomically, emitting LiquidityAdded for each", async () => {
+    const signature = await batchAdd([0n, 0n]);
+
+    for (const u of accounts) {
+      expect(await balance(u.tokenA)).to.equal(0n);
+      expect(await balance(u.tokenB)).to.equal(0n);
+      expect(await balance(u.lpToken) > 0n).to.be.true;
+    }
+    const added = (await emittedEvents(signature)).filter((e) => e.name.toLowerCase() === "liquidityadded");
+    expect(added.map((e) => e.data.pool.toBase58())).to.deep.equal(pools.map((f) => f.pool.toBase58()));
+  });
+
+  it("rolls back the first deposit when the second fails", async () => {
+    const reservesBefore = await program.account.pool.fetch(pools[0].pool);
+
+    // The second deposit cannot mint this many LP tokens
+    await expectError(batchAdd([0n, AMOUNT * 1_000n]), "SlippageExceeded");
+
+    expect(await balance(accounts[0].tokenA)).to.equal(AMOUNT);
+    expect(await balance(accounts[0].tokenB)).to.equal(AMOUNT);
+    expect(await balance(accounts[0].lpToken)).to.equal(0n);
+    const reservesAfter = await program.account.pool.fetch(pools[0].pool);
+    expect(reservesAfter.reserveA.eq(reservesBefore.reserveA)).to.be.true;
+    expect(reservesAfter.reserveB.eq(reservesBefore.reserveB)).to.be.true;
+  });
+});
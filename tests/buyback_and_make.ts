@@ -0,0 +1,96 @@
+import { BN } from "@coral-xyz/anchor";
+import { Keypair } from "@solana/web3.js";
+import { getMint } from "@solana/spl-token";
+import { expect } from "chai";
+import {
+  PoolFixture,
+  balance,
+  createSeededPool,
+  emittedEvents,
+  ensureProtocolConfig,
+  ensureTreasury,
+  expectError,
+  fundUser,
+  newUser,
+  payer,
+  program,
+  protocolConfig,
+  provider,
+  swap,
+} from "./helpers";
+
+describe("buyback_and_make", () => {
+  let f: PoolFixture;
+
+  const buyback = (governance: Keypair = payer, minLiquidity = 0) =>
+    program.methods
+      .buybackAndMake(new BN(minLiquidity))
+      .accountsPartial({
+        governance: governance.publicKey,
+        protocolConfig,
+        pool: f.pool,
+        lpMint: f.lpMint,
+      })
+      .signers([governance])
+      .rpc();
+
+  const accrueFees = async () => {
+    await program.methods
+      .setProtocolFee(new BN(1), new BN(6))
+      .accountsPartial({ authority: payer.publicKey, pool: f.pool })
+      .rpc();
+    const trader = await fundUser(f, await newUser(), 100_000_000n, 0n);
+    await swap(f, trader, 100_000_000n, true);
+  };
+
+  before(ensureProtocolConfig);
+
+  beforeEach(async () => {
+    ({ f } = await createSeededPool());
+    await ensureTreasury(f.lpMint);
+  });
+
+  it("adds accrued protocol fees as treasury-owned liquidity", async () => {
+    await accrueFees();
+    const before = await program.account.pool.fetch(f.pool);
+    const feesA = BigInt(before.protocolFeesA.toString());
+    expect(feesA > 0n).to.be.true;
+    const supplyBefore = (await getMint(provider.connection, f.lpMint)).supply;
+    const treasuryLp = await ensureTreasury(f.lpMint);
+
+    const sig = await buyback();
+
+    const after = await program.account.pool.fetch(f.pool);
+    const minted = await balance(treasuryLp);
+    expect(minted > 0n).to.be.true;
+    expect((await getMint(provider.connection, f.lpMint)).supply).to.equal(supplyBefore + minted);
+    // Single-sided fees are zapped in whole
+    expect(after.protocolFeesA.toNumber()).to.equal(0);
+    expect(BigInt(after.reserveA.toString())).to.equal(BigInt(before.reserveA.toString()) + feesA);
+    expect(after.reserveB.toString()).to.equal(before.reserveB.toString());
+    // Vault balances are untouched: the fees already sat there
+    expect(await balance(f.vaultA)).to.equal(
+      BigInt(after.reserveA.toString()) + BigInt(after.protocolFeesA.toString())
+    );
+
+    const event = (await emittedEvents(sig)).find((e) => e.name.toLowerCase() === "protocolliquidityadded");
+    expect(event).to.exist;
+    expect(event!.data.amountA.toString()).to.equal(feesA.toString());
+    expect(event!.data.liquidityMinted.toString()).to.equal(minted.toString());
+  });
+
+  it("rejects a min_liquidity above what the fees mint", async () => {
+    await accrueFees();
+    await expectError(buyback(payer, Number.MAX_SAFE_INTEGER), "SlippageExceeded");
+  });
+
+  it("rejects a non-governance signer", async () => {
+    await accrueFees();
+    const outsider = await newUser();
+    await expectError(buyback(outsider), "Unauthorized");
+  });
+
+  it("rejects a pool with no accrued protocol fees", async () => {
+    await expectError(buyback(), "NoProtocolFees");
+  });
+});
@@ -0,0 +1,41 @@
+import { mintTo } from "@solana/spl-token";
+import { expect } from "chai";
+import { PoolFixture, createSeededPool, expectError, fundUser, newUser, payer, program, provider, swap } from "./helpers";
+
+describe("check_reserves", () => {
+  let f: PoolFixture;
+
+  const checkReserves = (vaultA = f.vaultA, vaultB = f.vaultB) =>
+    program.methods.checkReserves().accountsPartial({ pool: f.pool, tokenAVault: vaultA, tokenBVault: vaultB }).view();
+
+  beforeEach(async () => {
+    ({ f } = await createSeededPool());
+  });
+
+  it("reports an in-sync pool after trading", async () => {
+    const trader = await fundUser(f, await newUser(), 1_000_000n, 0n);
+    await swap(f, trader, 1_000_000n, true);
+
+    const check = await checkReserves();
+    const pool = await program.account.pool.fetch(f.pool);
+    expect(check.inSync).to.equal(true);
+    expect(check.reserveA.eq(pool.reserveA)).to.be.true;
+    expect(check.reserveB.eq(pool.reserveB)).to.be.true;
+    expect(check.vaultABalance.eq(pool.reserveA.add(pool.protocolFeesA))).to.be.true;
+  });
+
+  it("flags a direct donation to a vault without changing state", async () => {
+    await mintTo(provider.connection, payer, f.mintB, f.vaultB, payer, 5_000n);
+    const before = await program.account.pool.fetch(f.pool);
+
+    const check = await checkReserves();
+    expect(check.inSync).to.equal(false);
+    expect(check.vaultBBalance.sub(check.reserveB).toNumber()).to.equal(5_000);
+    expect(check.reserveB.eq(before.reserveB)).to.be.true;
+    expect((await program.account.pool.fetch(f.pool)).protocolFeesB.eq(before.protocolFeesB)).to.be.true;
+  });
+
+  it("rejects vaults that are not the pool's", async () => {
+    await expectError(checkReserves(f.vaultB, f.vaultA), "InvalidVault");
+  });
+});
@@ -0,0 +1,101 @@
+import { Keypair } from "@solana/web3.js";
+import { TOKEN_PROGRAM_ID, getMint, mintTo } from "@solana/spl-token";
+import { expect } from "chai";
+import {
+  PoolFixture,
+  balance,
+  createPool,
+  createSeededPool,
+  ensureTreasury,
+  expectError,
+  fundUser,
+  newUser,
+  payer,
+  program,
+  provider,
+  removeLiquidity,
+  swap,
+} from "./helpers";
+
+describe("close_pool", () => {
+  const closePool = async (f: PoolFixture, authority: Keypair = payer) =>
+    program.methods
+      .closePool()
+      .accountsPartial({
+        authority: authority.publicKey,
+        pool: f.pool,
+        tokenAVault: f.vaultA,
+        tokenBVault: f.vaultB,
+        lpMint: f.lpMint,
+        treasuryTokenA: await ensureTreasury(f.mintA),
+        treasuryTokenB: await ensureTreasury(f.mintB),
+        tokenAMint: f.mintA,
+        tokenBMint: f.mintB,
+        tokenAProgram: TOKEN_PROGRAM_ID,
+        tokenBProgram: TOKEN_PROGRAM_ID,
+      })
+      .signers([authority])
+      .rpc();
+
+  it("closes a pool drained down to the locked liquidity, sweeping the dust to the treasury", async () => {
+    const { f, lp } = await createSeededPool();
+    const trader = await fundUser(f, await newUser(), 10_000_000n, 0n);
+    await swap(f, trader, 10_000_000n, true);
+    await removeLiquidity(f, lp, await balance(lp.lpToken));
+
+    const conn = provider.connection;
+    expect((await getMint(conn, f.lpMint)).supply).to.equal(1_000n);
+    const dustA = await balance(f.vaultA);
+    const dustB = await balance(f.vaultB);
+    expect(dustA).to.be.greaterThan(0n);
+    expect(dustB).to.be.greaterThan(0n);
+    const treasuryA = await balance(await ensureTreasury(f.mintA));
+    const treasuryB = await balance(await ensureTreasury(f.mintB));
+    const rent =
+      (await conn.getBalance(f.pool)) + (await conn.getBalance(f.vaultA)) + (await conn.getBalance(f.vaultB));
+    const before = await conn.getBalance(payer.publicKey);
+
+    await closePool(f);
+
+    expect(await conn.getAccountInfo(f.pool)).to.be.null;
+    expect(await conn.getAccountInfo(f.vaultA)).to.be.null;
+    expect(await conn.getAccountInfo(f.vaultB)).to.be.null;
+    expect(await balance(await ensureTreasury(f.mintA))).to.equal(treasuryA + dustA);
+    expect(await balance(await ensureTreasury(f.mintB))).to.equal(treasuryB + dustB);
+    // The payer also covered the transaction fee
+    expect(await conn.getBalance(payer.publicKey)).to.be.greaterThan(before + rent - 10_000);
+  });
+
+  it("closes a never-funded pool and sweeps a donation", async () => {
+    const f = await createPool();
+    await mintTo(provider.connection, payer, f.mintA, f.vaultA, payer, 7n);
+    const treasuryA = await balance(await ensureTreasury(f.mintA));
+
+    await closePool(f);
+
+    expect(await provider.connection.getAccountInfo(f.pool)).to.be.null;
+    expect(await balance(await ensureTreasury(f.mintA))).to.equal(treasuryA + 7n);
+  });
+
+  it("leaves the LP mint behind, so the pair cannot be created again", async () => {
+    const f = await createPool();
+    await closePool(f);
+
+    expect(await provider.connection.getAccountInfo(f.lpMint)).to.not.be.null;
+    await expectError(createPool([f.mintA, f.mintB]), "0x0");
+    expect(await provider.connection.getAccountInfo(f.pool)).to.be.null;
+  });
+
+  it("rejects a pool with liquidity beyond the locked minimum", async () => {
+    const { f, lp } = await createSeededPool();
+    await removeLiquidity(f, lp, (await balance(lp.lpToken)) - 1n);
+
+    await expectError(closePool(f), "PoolNotEmpty");
+    expect(await provider.connection.getAccountInfo(f.pool)).to.not.be.null;
+  });
+
+  it("rejects a signer other than the authority", async () => {
+    const f = await createPool();
+    await expectError(closePool(f, await newUser()), "Unauthorized");
+  });
+});
@@ -0,0 +1,94 @@
+import { Keypair, PublicKey, SystemProgram } from "@solana/web3.js";
+import { expect } from "chai";
+import {
+  PoolFixture,
+  UserAccounts,
+  addLiquidity,
+  createPool,
+  emittedEvents,
+  expectError,
+  fundUser,
+  lockedLpAccount,
+  newUser,
+  pda,
+  program,
+  swap,
+} from "./helpers";
+
+describe("compound_all", () => {
+  const SEED = 1_000_000_000n;
+
+  const positionAddress = (f: PoolFixture, owner: PublicKey) =>
+    pda([Buffer.from("position"), f.pool.toBuffer(), owner.toBuffer()]);
+
+  /** Pool seeded by `lp` through their position, then traded against in A */
+  const pooledPosition = async (lp: Keypair) => {
+    const f = await createPool();
+    const u: UserAccounts = await fundUser(f, lp, SEED, SEED);
+    const position = positionAddress(f, lp.publicKey);
+    await program.methods
+      .openPosition()
+      .accountsPartial({ owner: lp.publicKey, pool: f.pool, position, systemProgram: SystemProgram.programId })
+      .signers([lp])
+      .rpc();
+    await addLiquidity(f, u, SEED, SEED, { lockedLpToken: await lockedLpAccount(f), position });
+    const trader = await fundUser(f, await newUser(), 100_000_000n, 0n);
+    await swap(f, trader, 100_000_000n, true);
+    return { f, position };
+  };
+
+  const compoundAll = (owner: Keypair, pairs: { f: PoolFixture; position: PublicKey }[]) =>
+    program.methods
+      .compoundAll()
+      .accountsPartial({ owner: owner.publicKey })
+      .remainingAccounts(
+        pairs.flatMap(({ f, position }) => [
+          { pubkey: f.pool, isSigner: false, isWritable: false },
+          { pubkey: position, isSigner: false, isWritable: true },
+        ]),
+      )
+      .signers([owner])
+      .rpc();
+
+  it("compounds earned fees in every pool and skips pools with none", async () => {
+    const lp = await newUser();
+    const first = await pooledPosition(lp);
+    const second = await pooledPosition(lp);
+
+    const sig = await compoundAll(lp, [first, second]);
+    const events = (await emittedEvents(sig)).filter((e) => e.name.toLowerCase() === "feescompounded");
+    expect(events).to.have.length(2);
+    for (const { f, position } of [first, second]) {
+      const pool = await program.account.pool.fetch(f.pool);
+      const account = await program.account.position.fetch(position);
+      expect(account.feeGrowthCheckpointA.toString()).to.equal(pool.feeGrowthGlobalA.toString());
+      expect(account.feesOwedA.toNumber()).to.equal(0);
+      const event = events.find((e) => e.data.pool.equals(f.pool))!;
+      expect(event.data.amountA.toNumber()).to.be.greaterThan(0);
+      expect(event.data.liquidity.toString()).to.equal(account.liquidity.toString());
+    }
+
+    // Nothing has been earned since, so a second pass acts on no pool
+    const again = await compoundAll(lp, [first, second]);
+    expect((await emittedEvents(again)).filter((e) => e.name.toLowerCase() === "feescompounded")).to.be.empty;
+  });
+
+  it("rejects another owner's position", async () => {
+    const lp = await newUser();
+    const pair = await pooledPosition(lp);
+    await expectError(compoundAll(await newUser(), [pair]), "Unauthorized");
+  });
+
+  it("rejects a position paired with the wrong pool", async () => {
+    const lp = await newUser();
+    const first = await pooledPosition(lp);
+    const second = await pooledPosition(lp);
+    await expectError(compoundAll(lp, [{ f: first.f, position: second.position }]), "InvalidPosition");
+  });
+
+  it("rejects more positions than the compute bound", async () => {
+    const lp = await newUser();
+    const pair = await pooledPosition(lp);
+    await expectError(compoundAll(lp, Array(9).fill(pair)), "InvalidBatch");
+  });
+});
@@ -0,0 +1,41 @@
+import { BN } from "@coral-xyz/anchor";
+import { expect } from "chai";
+import {
+  FEE_DENOMINATOR,
+  FEE_NUMERATOR,
+  balance,
+  createSeededPool,
+  expectError,
+  fundUser,
+  newUser,
+  program,
+  swap,
+} from "./helpers";
+
+describe("compute_amount_out", () => {
+  const AMOUNT = 25_000_000n;
+
+  const computeAmountOut = (reserveIn: BN, reserveOut: BN, feeNumerator = FEE_NUMERATOR, feeDenominator = FEE_DENOMINATOR) =>
+    program.methods
+      .computeAmountOut(new BN(AMOUNT.toString()), reserveIn, reserveOut, feeNumerator, feeDenominator)
+      .view();
+
+  it("matches a swap against a pool with the same reserves", async () => {
+    const { f } = await createSeededPool(1_000_000_000n, 3_000_000_000n);
+    const trader = await fundUser(f, await newUser(), AMOUNT, 0n);
+    const pool = await program.account.pool.fetch(f.pool);
+
+    const computed = await computeAmountOut(pool.reserveA, pool.reserveB);
+    await swap(f, trader, AMOUNT, true);
+
+    expect(await balance(trader.tokenB)).to.equal(BigInt(computed.amountOut.toString()));
+    const after = await program.account.pool.fetch(f.pool);
+    expect(after.cumulativeFeesA.eq(computed.feeAmount)).to.be.true;
+  });
+
+  it("rejects a fee of 100% or more and empty reserves", async () => {
+    const reserve = new BN(1_000_000_000);
+    await expectError(computeAmountOut(reserve, reserve, new BN(10_000), new BN(10_000)), "InvalidFeeParameters");
+    await expectError(computeAmountOut(new BN(0), reserve), "PoolNotInitialized");
+  });
+});
@@ -0,0 +1,67 @@
+import { BN } from "@coral-xyz/anchor";
+import { expect } from "chai";
+import {
+  FAR_DEADLINE,
+  PoolFixture,
+  UserAccounts,
+  balance,
+  createSeededPool,
+  expectError,
+  fundUser,
+  newUser,
+  program,
+  swap,
+  swapAccounts,
+} from "./helpers";
+
+describe("compute_min_out", () => {
+  const AMOUNT = 25_000_000n;
+  let f: PoolFixture;
+
+  const computeMinOut = (slippageBps: number) =>
+    program.methods
+      .computeMinOut(new BN(AMOUNT.toString()), true, new BN(slippageBps))
+      .accountsPartial({ pool: f.pool })
+      .view();
+
+  const swapWithMin = (u: UserAccounts, minOut: BN) =>
+    program.methods
+      .swap(new BN(AMOUNT.toString()), minOut, true, new BN(0), new BN(0), FAR_DEADLINE, new BN(0))
+      .accountsPartial(swapAccounts(f, u))
+      .signers([u.user])
+      .rpc();
+
+  beforeEach(async () => {
+    ({ f } = await createSeededPool());
+  });
+
+  it("takes the tolerance off the quote, rounding down", async () => {
+    const quote = await program.methods.quoteSwap(new BN(AMOUNT.toString()), true).accountsPartial({ pool: f.pool }).view();
+    const out = BigInt(quote.amountOut.toString());
+
+    expect(BigInt((await computeMinOut(0)).toString())).to.equal(out);
+    expect(BigInt((await computeMinOut(50)).toString())).to.equal((out * 9_950n) / 10_000n);
+    expect(BigInt((await computeMinOut(10_000)).toString())).to.equal(0n);
+  });
+
+  it("returns a minimum a swap at unchanged reserves clears", async () => {
+    const trader = await fundUser(f, await newUser(), AMOUNT, 0n);
+    const minOut = await computeMinOut(0);
+
+    await swapWithMin(trader, minOut);
+    expect(await balance(trader.tokenB)).to.equal(BigInt(minOut.toString()));
+  });
+
+  it("returns a minimum an adverse move beyond the tolerance fails", async () => {
+    const trader = await fundUser(f, await newUser(), AMOUNT, 0n);
+    const frontRunner = await fundUser(f, await newUser(), 100_000_000n, 0n);
+    const minOut = await computeMinOut(50);
+
+    await swap(f, frontRunner, 100_000_000n, true);
+    await expectError(swapWithMin(trader, minOut), "SlippageExceeded");
+  });
+
+  it("rejects a tolerance above 100%", async () => {
+    await expectError(computeMinOut(10_001), "InvalidSlippage");
+  });
+});
@@ -0,0 +1,38 @@
+import { expect } from "chai";
+import {
+  addLiquidity,
+  createSeededPool,
+  fundUser,
+  newUser,
+  provider,
+  removeLiquidity,
+  swap,
+} from "./helpers";
+
+// The CU logs around the hot handlers are behind the `debug` feature; the
+// default build the suite runs against must not pay for them.
+describe("compute unit logs", () => {
+  const logs = async (signature: string) => {
+    const tx = await provider.connection.getTransaction(signature, {
+      commitment: "confirmed",
+      maxSupportedTransactionVersion: 0,
+    });
+    return tx?.meta?.logMessages ?? [];
+  };
+
+  it("are absent from swaps and liquidity changes in the default build", async () => {
+    const { f } = await createSeededPool();
+    const user = await fundUser(f, await newUser(), 20_000_000n, 10_000_000n);
+
+    const signatures = [
+      await addLiquidity(f, user, 10_000_000n, 10_000_000n),
+      await swap(f, user, 10_000_000n, true),
+      await removeLiquidity(f, user, 1_000_000n),
+    ];
+    for (const signature of signatures) {
+      const messages = await logs(signature);
+      expect(messages).to.not.be.empty;
+      expect(messages.some((line) => /: (start|end)$/.test(line))).to.be.false;
+    }
+  });
+});
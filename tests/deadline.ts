@@ -0,0 +1,82 @@
+import { BN } from "@coral-xyz/anchor";
+import { expect } from "chai";
+import {
+  PoolFixture,
+  UserAccounts,
+  addLiquidityAccounts,
+  balance,
+  createSeededPool,
+  expectError,
+  fundUser,
+  newUser,
+  program,
+  provider,
+  removeLiquidityAccounts,
+  swapAccounts,
+} from "./helpers";
+
+describe("deadlines", () => {
+  const AMOUNT = 1_000_000n;
+  let f: PoolFixture;
+  let lp: UserAccounts;
+  let trader: UserAccounts;
+
+  const amount = (v: bigint) => new BN(v.toString());
+
+  /** Cluster time offset by `seconds` */
+  async function clockPlus(seconds: number): Promise<BN> {
+    const slot = await provider.connection.getSlot();
+    return new BN((await provider.connection.getBlockTime(slot))! + seconds);
+  }
+
+  const swapBy = (deadline: BN) =>
+    program.methods
+      .swap(amount(AMOUNT), new BN(0), true, new BN(0), new BN(0), deadline, new BN(0))
+      .accountsPartial(swapAccounts(f, trader))
+      .signers([trader.user])
+      .rpc();
+
+  const addBy = (deadline: BN) =>
+    program.methods
+      .addLiquidity(amount(AMOUNT), amount(AMOUNT), new BN(0), deadline)
+      .accountsPartial(addLiquidityAccounts(f, trader))
+      .signers([trader.user])
+      .rpc();
+
+  const removeBy = (liquidity: bigint, deadline: BN) =>
+    program.methods
+      .removeLiquidity(amount(liquidity), new BN(0), new BN(0), new BN(0), new BN(0), deadline)
+      .accountsPartial(removeLiquidityAccounts(f, lp))
+      .signers([lp.user])
+      .rpc();
+
+  beforeEach(async () => {
+    ({ f, lp } = await createSeededPool());
+    trader = await fundUser(f, await newUser(), 2n * AMOUNT, AMOUNT);
+  });
+
+  it("executes before the deadline", async () => {
+    await swapBy(await clockPlus(60));
+    expect(await balance(trader.tokenA)).to.equal(AMOUNT);
+
+    await addBy(await clockPlus(60));
+    expect(await balance(trader.tokenA)).to.equal(0n);
+    expect((await balance(trader.lpToken)) > 0n).to.equal(true);
+
+    const lpBefore = await balance(lp.lpToken);
+    await removeBy(lpBefore / 2n, await clockPlus(60));
+    expect(await balance(lp.lpToken)).to.equal(lpBefore - lpBefore / 2n);
+  });
+
+  it("rejects a swap, deposit or withdrawal landing after its deadline", async () => {
+    const passed = await clockPlus(-60);
+    const reservesBefore = (await program.account.pool.fetch(f.pool)).reserveA;
+
+    await expectError(swapBy(passed), "DeadlineExceeded");
+    await expectError(addBy(passed), "DeadlineExceeded");
+    await expectError(removeBy(1_000n, passed), "DeadlineExceeded");
+
+    expect(await balance(trader.tokenA)).to.equal(2n * AMOUNT);
+    expect((await program.account.pool.fetch(f.pool)).reserveA.eq(reservesBefore)).to.be.true;
+  });
+});
@@ -0,0 +1,75 @@
+import { BN } from "@coral-xyz/anchor";
+import { PublicKey, SystemProgram } from "@solana/web3.js";
+import { TOKEN_PROGRAM_ID } from "@solana/spl-token";
+import { expect } from "chai";
+import {
+  FAR_DEADLINE,
+  PoolFixture,
+  UserAccounts,
+  addLiquidityAccounts,
+  balance,
+  createSeededPool,
+  expectError,
+  fundUser,
+  newUser,
+  pda,
+  program,
+} from "./helpers";
+
+describe("delegated add_liquidity", () => {
+  const AMOUNT = 10_000_000n;
+  let f: PoolFixture;
+  let owner: UserAccounts;
+  let intent: PublicKey;
+
+  const createIntent = (amountA: bigint, amountB: bigint) =>
+    program.methods
+      .createDepositIntent(new BN(amountA.toString()), new BN(amountB.toString()), new BN(0), FAR_DEADLINE)
+      .accountsPartial({
+        owner: owner.user.publicKey,
+        pool: f.pool,
+        depositIntent: intent,
+        userTokenA: owner.tokenA,
+        userTokenB: owner.tokenB,
+        tokenAProgram: TOKEN_PROGRAM_ID,
+        tokenBProgram: TOKEN_PROGRAM_ID,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([owner.user])
+      .rpc();
+
+  const relayDeposit = async (amountA: bigint, amountB: bigint, depositIntent: PublicKey | null) => {
+    const relayer = await newUser();
+    return program.methods
+      .addLiquidity(new BN(amountA.toString()), new BN(amountB.toString()), new BN(0), FAR_DEADLINE)
+      .accountsPartial({ ...addLiquidityAccounts(f, owner, relayer.publicKey), depositIntent })
+      .signers([relayer])
+      .rpc();
+  };
+
+  beforeEach(async () => {
+    ({ f } = await createSeededPool());
+    owner = await fundUser(f, await newUser(), AMOUNT, AMOUNT);
+    intent = pda([Buffer.from("deposit_intent"), f.pool.toBuffer(), owner.user.publicKey.toBuffer()]);
+  });
+
+  it("deposits the owner's approved amounts for a relayer", async () => {
+    await createIntent(AMOUNT, AMOUNT);
+    await relayDeposit(AMOUNT, AMOUNT, intent);
+
+    expect(await balance(owner.tokenA)).to.equal(0n);
+    expect(await balance(owner.tokenB)).to.equal(0n);
+    expect(await balance(owner.lpToken) > 0n).to.equal(true);
+    // The intent is single-use
+    expect(await program.provider.connection.getAccountInfo(intent)).to.equal(null);
+  });
+
+  it("rejects a delegate without the owner's intent", async () => {
+    await expectError(relayDeposit(AMOUNT, AMOUNT, null), "DepositIntentRequired");
+  });
+
+  it("rejects a delegate that deviates from the intent", async () => {
+    await createIntent(AMOUNT / 2n, AMOUNT / 2n);
+    await expectError(relayDeposit(AMOUNT, AMOUNT, intent), "DepositIntentMismatch");
+  });
+});
@@ -0,0 +1,64 @@
+import { Keypair } from "@solana/web3.js";
+import { expect } from "chai";
+import {
+  PoolFixture,
+  UserAccounts,
+  addLiquidity,
+  balance,
+  createSeededPool,
+  expectError,
+  fundUser,
+  newUser,
+  payer,
+  program,
+  removeLiquidity,
+  swap,
+} from "./helpers";
+
+describe("deposits_frozen", () => {
+  const AMOUNT = 10_000_000n;
+  let f: PoolFixture;
+  let lp: UserAccounts;
+  let user: UserAccounts;
+
+  const setDepositsFrozen = (frozen: boolean, authority: Keypair = payer) =>
+    program.methods
+      .setDepositsFrozen(frozen)
+      .accountsPartial({ authority: authority.publicKey, pool: f.pool })
+      .signers([authority])
+      .rpc();
+
+  beforeEach(async () => {
+    ({ f, lp } = await createSeededPool());
+    user = await fundUser(f, await newUser(), 2n * AMOUNT, 2n * AMOUNT);
+  });
+
+  it("rejects deposits while swaps and withdrawals stay open", async () => {
+    await setDepositsFrozen(true);
+    expect((await program.account.pool.fetch(f.pool)).depositsFrozen).to.be.true;
+
+    await expectError(addLiquidity(f, user, AMOUNT, AMOUNT), "DepositsFrozen");
+    expect(await balance(user.tokenA)).to.equal(2n * AMOUNT);
+
+    await swap(f, user, AMOUNT, true);
+    expect(await balance(user.tokenB)).to.be.greaterThan(2n * AMOUNT);
+
+    const lpBalance = await balance(lp.lpToken);
+    await removeLiquidity(f, lp, lpBalance / 2n);
+    expect(await balance(lp.lpToken)).to.equal(lpBalance - lpBalance / 2n);
+  });
+
+  it("accepts deposits again once unfrozen", async () => {
+    await setDepositsFrozen(true);
+    await setDepositsFrozen(false);
+
+    await addLiquidity(f, user, AMOUNT, AMOUNT);
+    expect(await balance(user.lpToken)).to.be.greaterThan(0n);
+  });
+
+  it("rejects a signer other than the pool authority", async () => {
+    const stranger = await newUser();
+    await expectError(setDepositsFrozen(true, stranger), "Unauthorized");
+    expect((await program.account.pool.fetch(f.pool)).depositsFrozen).to.be.false;
+  });
+});
@@ -0,0 +1,84 @@
+import { BN } from "@coral-xyz/anchor";
+import { Keypair } from "@solana/web3.js";
+import { expect } from "chai";
+import {
+  PoolFixture,
+  UserAccounts,
+  balance,
+  createSeededPool,
+  expectError,
+  fundUser,
+  newUser,
+  payer,
+  program,
+  swap,
+} from "./helpers";
+
+describe("directional swap fees", () => {
+  const RESERVE = 1_000_000_000n;
+  const AMOUNT = 10_000_000n;
+  const BUY_FEE = 30n;
+  const SELL_FEE = 300n;
+  let f: PoolFixture;
+  let trader: UserAccounts;
+
+  const setDirectionalFees = (aToB: bigint, bToA: bigint, authority: Keypair = payer) =>
+    program.methods
+      .setDirectionalFees(new BN(aToB.toString()), new BN(bToA.toString()))
+      .accountsPartial({ authority: authority.publicKey, pool: f.pool })
+      .signers([authority])
+      .rpc();
+
+  const expectedOut = (amountIn: bigint, fee: bigint, reserveIn: bigint, reserveOut: bigint) => {
+    const withFee = amountIn - (amountIn * fee) / 10_000n;
+    return (withFee * reserveOut) / (reserveIn + withFee);
+  };
+
+  beforeEach(async () => {
+    ({ f } = await createSeededPool(RESERVE, RESERVE));
+    trader = await fundUser(f, await newUser(), AMOUNT, AMOUNT);
+  });
+
+  it("charges each direction its own fee and books it to the input token", async () => {
+    await setDirectionalFees(BUY_FEE, SELL_FEE);
+
+    await swap(f, trader, AMOUNT, true);
+    const boughtB = expectedOut(AMOUNT, BUY_FEE, RESERVE, RESERVE);
+    expect(await balance(trader.tokenB)).to.equal(AMOUNT + boughtB);
+    let pool = await program.account.pool.fetch(f.pool);
+    expect(BigInt(pool.cumulativeFeesA.toString())).to.equal((AMOUNT * BUY_FEE) / 10_000n);
+    expect(pool.cumulativeFeesB.toNumber()).to.equal(0);
+
+    const reserveA = BigInt(pool.reserveA.toString());
+    const reserveB = BigInt(pool.reserveB.toString());
+    await swap(f, trader, AMOUNT, false);
+    expect(await balance(trader.tokenA)).to.equal(expectedOut(AMOUNT, SELL_FEE, reserveB, reserveA));
+    pool = await program.account.pool.fetch(f.pool);
+    expect(BigInt(pool.cumulativeFeesB.toString())).to.equal((AMOUNT * SELL_FEE) / 10_000n);
+    expect(BigInt(pool.cumulativeFeesA.toString())).to.equal((AMOUNT * BUY_FEE) / 10_000n);
+  });
+
+  it("keeps update_fee symmetric", async () => {
+    await setDirectionalFees(BUY_FEE, SELL_FEE);
+    await program.methods
+      .updateFee(new BN(50), new BN(10_000))
+      .accountsPartial({ authority: payer.publicKey, pool: f.pool })
+      .rpc();
+
+    const pool = await program.account.pool.fetch(f.pool);
+    expect(pool.feeNumerator.toNumber()).to.equal(50);
+    expect(pool.feeNumeratorBToA.toNumber()).to.equal(50);
+  });
+
+  it("rejects a direction above the maximum fee", async () => {
+    await expectError(setDirectionalFees(BUY_FEE, 1_001n), "InvalidFeeParameters");
+    const pool = await program.account.pool.fetch(f.pool);
+    expect(pool.feeNumerator.toNumber()).to.equal(30);
+    expect(pool.feeNumeratorBToA.toNumber()).to.equal(30);
+  });
+
+  it("rejects a non-authority signer", async () => {
+    await expectError(setDirectionalFees(BUY_FEE, SELL_FEE, await newUser()), "Unauthorized");
+    expect((await program.account.pool.fetch(f.pool)).feeNumeratorBToA.toNumber()).to.equal(30);
+  });
+});
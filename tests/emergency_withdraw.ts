@@ -0,0 +1,137 @@
+import { BN } from "@coral-xyz/anchor";
+import { Keypair } from "@solana/web3.js";
+import { TOKEN_PROGRAM_ID, getMint } from "@solana/spl-token";
+import { expect } from "chai";
+import {
+  PoolFixture,
+  UserAccounts,
+  balance,
+  createSeededPool,
+  expectError,
+  newUser,
+  pausePool,
+  payer,
+  program,
+  provider,
+  removeLiquidity,
+  unpausePool,
+} from "./helpers";
+
+describe("emergency_withdraw", () => {
+  let f: PoolFixture;
+  let lp: UserAccounts;
+
+  const beginUnwind = (authority: Keypair = payer) =>
+    program.methods
+      .beginUnwind()
+      .accountsPartial({ authority: authority.publicKey, pool: f.pool })
+      .signers([authority])
+      .rpc();
+
+  const emergencyWithdraw = (u: UserAccounts, liquidity: bigint, signer: Keypair = u.user) =>
+    program.methods
+      .emergencyWithdraw(new BN(liquidity.toString()))
+      .accountsPartial({
+        user: signer.publicKey,
+        pool: f.pool,
+        tokenAVault: f.vaultA,
+        tokenBVault: f.vaultB,
+        lpMint: f.lpMint,
+        userTokenA: u.tokenA,
+        userTokenB: u.tokenB,
+        userLpToken: u.lpToken,
+        tokenAMint: f.mintA,
+        tokenBMint: f.mintB,
+        tokenAProgram: TOKEN_PROGRAM_ID,
+        tokenBProgram: TOKEN_PROGRAM_ID,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .signers([signer])
+      .rpc();
+
+  beforeEach(async () => {
+    ({ f, lp } = await createSeededPool());
+  });
+
+  it("pays a proportional share out of a paused, unwinding pool and books it", async () => {
+    const guardian = await newUser();
+    await program.methods
+      .setGuardian(guardian.publicKey)
+      .accountsPartial({ authority: payer.publicKey, pool: f.pool })
+      .rpc();
+    await pausePool(f, guardian);
+    await beginUnwind();
+
+    const before = await program.account.pool.fetch(f.pool);
+    const supply = (await getMint(provider.connection, f.lpMint)).supply;
+    const liquidity = (await balance(lp.lpToken)) / 2n;
+    const expectedA = (liquidity * BigInt(before.reserveA.toString())) / supply;
+    const expectedB = (liquidity * BigInt(before.reserveB.toString())) / supply;
+
+    await emergencyWithdraw(lp, liquidity);
+
+    expect(await balance(lp.tokenA)).to.equal(expectedA);
+    expect(await balance(lp.tokenB)).to.equal(expectedB);
+    expect((await getMint(provider.connection, f.lpMint)).supply).to.equal(supply - liquidity);
+    const after = await program.account.pool.fetch(f.pool);
+    expect(BigInt(after.reserveA.toString())).to.equal(BigInt(before.reserveA.toString()) - expectedA);
+    expect(BigInt(after.reserveB.toString())).to.equal(BigInt(before.reserveB.toString()) - expectedB);
+    // Reserves still match the vaults
+    expect(await balance(f.vaultA)).to.equal(BigInt(after.reserveA.toString()));
+    expect(await balance(f.vaultB)).to.equal(BigInt(after.reserveB.toString()));
+  });
+
+  it("exits past an LP hold window that blocks remove_liquidity", async () => {
+    // The window runs from the pool's last deposit for LP without a position
+    await program.methods
+      .setMinLpHoldSlots(new BN(1_000_000))
+      .accountsPartial({ authority: payer.publicKey, pool: f.pool })
+      .rpc();
+    const liquidity = await balance(lp.lpToken);
+    await expectError(removeLiquidity(f, lp, liquidity), "LiquidityLockedTooRecent");
+
+    await pausePool(f);
+    await beginUnwind();
+    await emergencyWithdraw(lp, liquidity);
+
+    expect(await balance(lp.lpToken)).to.equal(0n);
+    expect((await balance(lp.tokenA)) > 0n).to.equal(true);
+  });
+
+  it("rejects a pool that is not paused", async () => {
+    await expectError(emergencyWithdraw(lp, 1_000n), "PoolNotUnwinding");
+    expect(await balance(lp.tokenA)).to.equal(0n);
+  });
+
+  it("rejects a paused pool that is not unwinding, and one unpaused again", async () => {
+    await pausePool(f);
+    await expectError(emergencyWithdraw(lp, 1_000n), "PoolNotUnwinding");
+
+    await beginUnwind();
+    await unpausePool(f);
+    await expectError(emergencyWithdraw(lp, 1_000n), "PoolNotUnwinding");
+    expect(await balance(lp.tokenA)).to.equal(0n);
+  });
+
+  it("rejects a guardian or outsider opening the unwind", async () => {
+    const guardian = await newUser();
+    await program.methods
+      .setGuardian(guardian.publicKey)
+      .accountsPartial({ authority: payer.publicKey, pool: f.pool })
+      .rpc();
+    await pausePool(f, guardian);
+
+    await expectError(beginUnwind(guardian), "Unauthorized");
+    await expectError(pausePool(f, await newUser()), "Unauthorized");
+    expect((await program.account.pool.fetch(f.pool)).unwinding).to.equal(false);
+  });
+
+  it("rejects burning another LP's tokens", async () => {
+    await pausePool(f);
+    await beginUnwind();
+    const thief = await newUser();
+    // The SPL burn needs the LP account owner's signature
+    await expectError(emergencyWithdraw(lp, 1_000n, thief), "0x4");
+    expect(await balance(lp.tokenA)).to.equal(0n);
+  });
+});
@@ -0,0 +1,63 @@
+import { expect } from "chai";
+import {
+  PoolFixture,
+  UserAccounts,
+  createSeededPool,
+  emittedEvents,
+  expectError,
+  fundUser,
+  newUser,
+  payer,
+  program,
+  swap,
+} from "./helpers";
+
+// Mirrors the EVENT_VERBOSITY_* constants
+const FULL = 0;
+const COMPACT = 1;
+const NONE = 2;
+
+describe("event verbosity", () => {
+  let f: PoolFixture;
+  let trader: UserAccounts;
+
+  const setEventVerbosity = (verbosity: number, authority = payer) =>
+    program.methods
+      .setEventVerbosity(verbosity)
+      .accountsPartial({ authority: authority.publicKey, pool: f.pool })
+      .signers([authority])
+      .rpc();
+
+  const swapEvents = async () =>
+    (await emittedEvents(await swap(f, trader, 1_000_000n, true)))
+      .map((e) => e.name.toLowerCase())
+      .filter((name) => name.startsWith("swapexecuted"));
+
+  beforeEach(async () => {
+    ({ f } = await createSeededPool());
+    trader = await fundUser(f, await newUser(), 10_000_000n, 0n);
+  });
+
+  it("emits the full, compact or no swap event per the pool's setting", async () => {
+    expect(await swapEvents()).to.deep.equal(["swapexecuted"]);
+
+    await setEventVerbosity(COMPACT);
+    expect((await program.account.pool.fetch(f.pool)).eventVerbosity).to.equal(COMPACT);
+    expect(await swapEvents()).to.deep.equal(["swapexecutedcompact"]);
+
+    await setEventVerbosity(NONE);
+    expect(await swapEvents()).to.be.empty;
+
+    await setEventVerbosity(FULL);
+    expect(await swapEvents()).to.deep.equal(["swapexecuted"]);
+  });
+
+  it("rejects an unknown verbosity", async () => {
+    await expectError(setEventVerbosity(NONE + 1), "InvalidEventVerbosity");
+    expect((await program.account.pool.fetch(f.pool)).eventVerbosity).to.equal(FULL);
+  });
+
+  it("rejects a non-authority signer", async () => {
+    await expectError(setEventVerbosity(NONE, await newUser()), "Unauthorized");
+  });
+});
@@ -0,0 +1,136 @@
+import { BN } from "@coral-xyz/anchor";
+import { Keypair, PublicKey } from "@solana/web3.js";
+import { TOKEN_PROGRAM_ID, createAccount } from "@solana/spl-token";
+import { expect } from "chai";
+import {
+  PoolFixture,
+  addLiquidity,
+  balance,
+  createPool,
+  createSeededPool,
+  emittedEvents,
+  ensureProtocolConfig,
+  ensureTreasury,
+  expectError,
+  fundUser,
+  lockedLpAccount,
+  newUser,
+  payer,
+  program,
+  protocolConfig,
+  provider,
+  swap,
+} from "./helpers";
+
+describe("protocol fee recipient", () => {
+  let f: PoolFixture;
+  let recipient: Keypair;
+  let accrued: bigint;
+
+  const setFeeRecipient = (feeRecipient: PublicKey, governance: Keypair = payer) =>
+    program.methods
+      .setFeeRecipient(feeRecipient)
+      .accountsPartial({ governance: governance.publicKey, protocolConfig, pool: f.pool })
+      .signers([governance])
+      .rpc();
+
+  const tokenAccounts = async (owner: PublicKey) => {
+    const conn = provider.connection;
+    return {
+      a: await createAccount(conn, payer, f.mintA, owner, Keypair.generate()),
+      b: await createAccount(conn, payer, f.mintB, owner, Keypair.generate()),
+    };
+  };
+
+  const collect = (to: { a: PublicKey; b: PublicKey }) =>
+    program.methods
+      .collectProtocolFees()
+      .accountsPartial({
+        authority: payer.publicKey,
+        pool: f.pool,
+        tokenAVault: f.vaultA,
+        tokenBVault: f.vaultB,
+        recipientTokenA: to.a,
+        recipientTokenB: to.b,
+        tokenAMint: f.mintA,
+        tokenBMint: f.mintB,
+        tokenAProgram: TOKEN_PROGRAM_ID,
+        tokenBProgram: TOKEN_PROGRAM_ID,
+      })
+      .rpc();
+
+  before(ensureProtocolConfig);
+
+  beforeEach(async () => {
+    ({ f } = await createSeededPool());
+    recipient = Keypair.generate();
+
+    // Protocol takes a sixth of the swap fee; one swap accrues some in A
+    await program.methods
+      .setProtocolFee(new BN(1), new BN(6))
+      .accountsPartial({ authority: payer.publicKey, pool: f.pool })
+      .rpc();
+    const trader = await fundUser(f, await newUser(), 100_000_000n, 0n);
+    await swap(f, trader, 100_000_000n, true);
+    accrued = BigInt((await program.account.pool.fetch(f.pool)).protocolFeesA.toString());
+    expect(accrued > 0n).to.be.true;
+  });
+
+  it("defaults to the protocol treasury", async () => {
+    const pool = await program.account.pool.fetch(f.pool);
+    expect(pool.feeRecipient.equals(protocolConfig)).to.be.true;
+  });
+
+  it("collects into the treasury vaults by default", async () => {
+    const to = { a: await ensureTreasury(f.mintA), b: await ensureTreasury(f.mintB) };
+    const treasuryBefore = await balance(to.a);
+    const before = await program.account.pool.fetch(f.pool);
+
+    const events = await emittedEvents(await collect(to));
+
+    expect(await balance(to.a)).to.equal(treasuryBefore + accrued);
+    const pool = await program.account.pool.fetch(f.pool);
+    expect(pool.protocolFeesA.isZero()).to.be.true;
+    expect(pool.protocolFeesB.isZero()).to.be.true;
+    // Protocol fees were never part of the reserves
+    expect(pool.reserveA.eq(before.reserveA)).to.be.true;
+    expect(pool.reserveB.eq(before.reserveB)).to.be.true;
+    const accrual = events.find((e) => e.name.toLowerCase() === "treasuryaccrued");
+    expect(accrual?.data.mint.equals(f.mintA)).to.be.true;
+    expect(BigInt(accrual?.data.amount.toString())).to.equal(accrued);
+  });
+
+  it("rejects a treasury-owned account other than the treasury vault", async () => {
+    await expectError(collect(await tokenAccounts(protocolConfig)), "InvalidFeeRecipient");
+  });
+
+  it("pays collected fees to a recipient set by governance", async () => {
+    await setFeeRecipient(recipient.publicKey);
+    expect((await program.account.pool.fetch(f.pool)).feeRecipient.equals(recipient.publicKey)).to.be.true;
+
+    const to = await tokenAccounts(recipient.publicKey);
+    await collect(to);
+
+    expect(await balance(to.a)).to.equal(accrued);
+    expect((await program.account.pool.fetch(f.pool)).protocolFeesA.isZero()).to.be.true;
+  });
+
+  it("rejects collecting to accounts the recipient does not own", async () => {
+    await setFeeRecipient(recipient.publicKey);
+    await expectError(collect(await tokenAccounts(payer.publicKey)), "InvalidFeeRecipient");
+  });
+
+  it("rejects the default pubkey as recipient", async () => {
+    await expectError(setFeeRecipient(PublicKey.default), "InvalidFeeRecipient");
+  });
+
+  it("rejects the pool authority when it is not governance", async () => {
+    const creator = await newUser();
+    f = await createPool(undefined, creator);
+    const lp = await fundUser(f, await newUser(), 1_000_000n, 1_000_000n);
+    await addLiquidity(f, lp, 1_000_000n, 1_000_000n, { lockedLpToken: await lockedLpAccount(f) });
+
+    await expectError(setFeeRecipient(creator.publicKey, creator), "Unauthorized");
+    expect((await program.account.pool.fetch(f.pool)).feeRecipient.equals(protocolConfig)).to.be.true;
+  });
+});
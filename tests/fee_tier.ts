@@ -0,0 +1,82 @@
+import { PublicKey, SystemProgram } from "@solana/web3.js";
+import { expect } from "chai";
+import {
+  PoolFixture,
+  UserAccounts,
+  balance,
+  createSeededPool,
+  expectError,
+  fundUser,
+  newUser,
+  payer,
+  pda,
+  program,
+  swap,
+} from "./helpers";
+
+describe("fee tier", () => {
+  const SEED = 1_000_000_000n;
+  const AMOUNT_IN = 1_000_000n;
+  let f: PoolFixture;
+  let trader: UserAccounts;
+  let feeTier: PublicKey;
+
+  /** Constant-product output for a swap charged `feeBps` on the input */
+  const expectedOut = (feeBps: bigint) => {
+    const inWithFee = AMOUNT_IN - (AMOUNT_IN * feeBps) / 10_000n;
+    return (inWithFee * SEED) / (SEED + inWithFee);
+  };
+
+  const createFeeTier = (user: PublicKey, discountBps: number) =>
+    program.methods
+      .createFeeTier(user, discountBps)
+      .accountsPartial({
+        authority: payer.publicKey,
+        pool: f.pool,
+        feeTier: pda([Buffer.from("fee_tier"), f.pool.toBuffer(), user.toBuffer()]),
+        systemProgram: SystemProgram.programId,
+      })
+      .rpc();
+
+  const swapOut = async (extra: Record<string, PublicKey | null> = {}) => {
+    await swap(f, trader, AMOUNT_IN, true, extra);
+    return balance(trader.tokenB);
+  };
+
+  beforeEach(async () => {
+    ({ f } = await createSeededPool(SEED, SEED));
+    trader = await fundUser(f, await newUser(), AMOUNT_IN, 0n);
+    feeTier = pda([Buffer.from("fee_tier"), f.pool.toBuffer(), trader.user.publicKey.toBuffer()]);
+  });
+
+  it("charges the full pool fee without a fee tier", async () => {
+    expect(await swapOut()).to.equal(expectedOut(30n));
+  });
+
+  it("charges the discounted fee with the trader's fee tier", async () => {
+    await createFeeTier(trader.user.publicKey, 10);
+    expect(await swapOut({ feeTier })).to.equal(expectedOut(20n));
+  });
+
+  it("applies an updated discount", async () => {
+    await createFeeTier(trader.user.publicKey, 10);
+    await program.methods
+      .updateFeeTier(30)
+      .accountsPartial({ authority: payer.publicKey, pool: f.pool, feeTier })
+      .rpc();
+
+    expect(await swapOut({ feeTier })).to.equal(expectedOut(0n));
+  });
+
+  it("rejects another trader's fee tier", async () => {
+    const other = await newUser();
+    await createFeeTier(other.publicKey, 10);
+    const otherTier = pda([Buffer.from("fee_tier"), f.pool.toBuffer(), other.publicKey.toBuffer()]);
+
+    await expectError(swap(f, trader, AMOUNT_IN, true, { feeTier: otherTier }), "ConstraintSeeds");
+  });
+
+  it("rejects a discount larger than the pool fee", async () => {
+    await expectError(createFeeTier(trader.user.publicKey, 31), "InvalidFeeDiscount");
+  });
+});
@@ -0,0 +1,97 @@
+import { BN } from "@coral-xyz/anchor";
+import { PublicKey } from "@solana/web3.js";
+import { TOKEN_PROGRAM_ID, createTransferInstruction } from "@solana/spl-token";
+import { expect } from "chai";
+import {
+  PoolFixture,
+  UserAccounts,
+  balance,
+  createSeededPool,
+  expectError,
+  fundUser,
+  newUser,
+  payer,
+  program,
+} from "./helpers";
+
+describe("flash_loan", () => {
+  const AMOUNT = 100_000_000n;
+  let f: PoolFixture;
+  let borrower: UserAccounts;
+
+  /** Borrow `AMOUNT` of one side, with an SPL transfer of `repay` back to
+   * the vault as the callback (the borrower's signature passes through) */
+  const flashLoan = (borrowA: boolean, repay: bigint, callbackProgram: PublicKey = TOKEN_PROGRAM_ID) => {
+    const [borrowerToken, vault, mint] = borrowA
+      ? [borrower.tokenA, f.vaultA, f.mintA]
+      : [borrower.tokenB, f.vaultB, f.mintB];
+    const repayIx = createTransferInstruction(borrowerToken, vault, borrower.user.publicKey, repay);
+    return program.methods
+      .flashLoan(new BN(AMOUNT.toString()), Buffer.from(repayIx.data))
+      .accountsPartial({
+        borrower: borrower.user.publicKey,
+        pool: f.pool,
+        lpMint: f.lpMint,
+        tokenAVault: f.vaultA,
+        tokenBVault: f.vaultB,
+        borrowerToken,
+        mint,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .remainingAccounts([{ pubkey: callbackProgram, isSigner: false, isWritable: false }, ...repayIx.keys])
+      .signers([borrower.user])
+      .rpc();
+  };
+
+  before(async () => {
+    ({ f } = await createSeededPool());
+    // 0.3% on A->B swaps, 1% on B->A swaps
+    await program.methods
+      .setDirectionalFees(new BN(30), new BN(100))
+      .accountsPartial({ authority: payer.publicKey, pool: f.pool })
+      .rpc();
+    // Only enough to pay fees; the principal comes from the loan
+    borrower = await fundUser(f, await newUser(), 10_000_000n, 10_000_000n);
+  });
+
+  it("lends token A against repayment plus the A->B fee rate", async () => {
+    const fee = (AMOUNT * 30n + 9_999n) / 10_000n;
+    const before = await program.account.pool.fetch(f.pool);
+    const vaultBefore = await balance(f.vaultA);
+
+    await flashLoan(true, AMOUNT + fee);
+
+    const after = await program.account.pool.fetch(f.pool);
+    expect(BigInt(after.reserveA.sub(before.reserveA).toString())).to.equal(fee);
+    expect(after.reserveB.eq(before.reserveB)).to.be.true;
+    expect(await balance(f.vaultA)).to.equal(vaultBefore + fee);
+    expect(BigInt(after.cumulativeFeesA.sub(before.cumulativeFeesA).toString())).to.equal(fee);
+  });
+
+  it("charges token B loans the B->A fee rate", async () => {
+    const aRateFee = (AMOUNT * 30n + 9_999n) / 10_000n;
+    const fee = (AMOUNT * 100n + 9_999n) / 10_000n;
+    await expectError(flashLoan(false, AMOUNT + aRateFee), "FlashLoanNotRepaid");
+
+    const before = await program.account.pool.fetch(f.pool);
+    await flashLoan(false, AMOUNT + fee);
+
+    const after = await program.account.pool.fetch(f.pool);
+    expect(BigInt(after.reserveB.sub(before.reserveB).toString())).to.equal(fee);
+    expect(BigInt(after.cumulativeFeesB.sub(before.cumulativeFeesB).toString())).to.equal(fee);
+  });
+
+  it("reverts the loan when only the principal is repaid", async () => {
+    const before = await program.account.pool.fetch(f.pool);
+    const vaultBefore = await balance(f.vaultA);
+
+    await expectError(flashLoan(true, AMOUNT), "FlashLoanNotRepaid");
+
+    expect((await program.account.pool.fetch(f.pool)).reserveA.eq(before.reserveA)).to.be.true;
+    expect(await balance(f.vaultA)).to.equal(vaultBefore);
+  });
+
+  it("rejects a callback into the pool program", async () => {
+    await expectError(flashLoan(true, AMOUNT, program.programId), "InvalidFlashLoanCallback");
+  });
+});
@@ -0,0 +1,49 @@
+import { expect } from "chai";
+import {
+  PoolFixture,
+  Q64,
+  UserAccounts,
+  createSeededPool,
+  expectError,
+  fundUser,
+  newUser,
+  program,
+  swap,
+} from "./helpers";
+
+describe("get_pool_info", () => {
+  const AMOUNT = 10_000_000n;
+  let f: PoolFixture;
+  let trader: UserAccounts;
+
+  const poolInfo = (pool = f.pool) => program.methods.getPoolInfo().accountsPartial({ pool }).view();
+
+  beforeEach(async () => {
+    ({ f } = await createSeededPool());
+    trader = await fundUser(f, await newUser(), AMOUNT, AMOUNT);
+  });
+
+  it("derives prices, invariant and stats from live state", async () => {
+    await swap(f, trader, AMOUNT, true);
+    await swap(f, trader, AMOUNT / 2n, false);
+
+    const info = await poolInfo();
+    const pool = await program.account.pool.fetch(f.pool);
+    expect(info.priceA.eq(pool.reserveB.mul(Q64).div(pool.reserveA))).to.be.true;
+    expect(info.priceB.eq(pool.reserveA.mul(Q64).div(pool.reserveB))).to.be.true;
+    expect(info.k.eq(pool.reserveA.mul(pool.reserveB))).to.be.true;
+    expect(info.feeBps.toNumber()).to.equal(30);
+    expect(info.totalSwaps.toNumber()).to.equal(2);
+    expect(info.cumulativeVolumeA.eq(pool.cumulativeVolumeA)).to.be.true;
+    expect(info.cumulativeVolumeB.eq(pool.cumulativeVolumeB)).to.be.true;
+    expect(info.cumulativeFeesA.eq(pool.cumulativeFeesA)).to.be.true;
+    expect(info.cumulativeFeesB.eq(pool.cumulativeFeesB)).to.be.true;
+    expect(info.windowVolumeA.eq(pool.windowVolumeA)).to.be.true;
+    expect(info.windowVolumeB.eq(pool.windowVolumeB)).to.be.true;
+  });
+
+  it("rejects an account that is not a pool", async () => {
+    // A vault sits at a pool-derived address but is owned by the token program
+    await expectError(poolInfo(f.vaultA), "AccountOwnedByWrongProgram");
+  });
+});
@@ -0,0 +1,78 @@
+import { Keypair, PublicKey } from "@solana/web3.js";
+import { expect } from "chai";
+import {
+  PoolFixture,
+  createSeededPool,
+  emittedEvents,
+  expectError,
+  fundUser,
+  newUser,
+  pausePool,
+  payer,
+  program,
+  swap,
+  unpausePool,
+} from "./helpers";
+
+describe("pause guardian", () => {
+  let f: PoolFixture;
+  let guardian: Keypair;
+
+  beforeEach(async () => {
+    ({ f } = await createSeededPool());
+    guardian = await newUser();
+    await program.methods
+      .setGuardian(guardian.publicKey)
+      .accountsPartial({ authority: payer.publicKey, pool: f.pool })
+      .rpc();
+  });
+
+  it("lets the guardian pause, halting swaps until the authority unpauses", async () => {
+    const trader = await fundUser(f, await newUser(), 2_000_000n, 0n);
+    const sig = await pausePool(f, guardian);
+
+    const paused = (await emittedEvents(sig)).find((e) => e.name.toLowerCase() === "poolpaused");
+    expect(paused!.data.authority.equals(guardian.publicKey)).to.be.true;
+    expect((await program.account.pool.fetch(f.pool)).paused).to.equal(true);
+    await expectError(swap(f, trader, 1_000_000n, true), "PoolPaused");
+
+    await unpausePool(f);
+    expect((await program.account.pool.fetch(f.pool)).paused).to.equal(false);
+    await swap(f, trader, 1_000_000n, true);
+  });
+
+  it("rejects the guardian unpausing", async () => {
+    await pausePool(f, guardian);
+    await expectError(
+      program.methods
+        .unpausePool()
+        .accountsPartial({ authority: guardian.publicKey, pool: f.pool })
+        .signers([guardian])
+        .rpc(),
+      "Unauthorized",
+    );
+    expect((await program.account.pool.fetch(f.pool)).paused).to.equal(true);
+  });
+
+  it("rejects a removed guardian and outsiders pausing", async () => {
+    await expectError(pausePool(f, await newUser()), "Unauthorized");
+
+    await program.methods
+      .setGuardian(PublicKey.default)
+      .accountsPartial({ authority: payer.publicKey, pool: f.pool })
+      .rpc();
+    await expectError(pausePool(f, guardian), "Unauthorized");
+    expect((await program.account.pool.fetch(f.pool)).paused).to.equal(false);
+  });
+
+  it("rejects the guardian changing the guardian", async () => {
+    await expectError(
+      program.methods
+        .setGuardian(guardian.publicKey)
+        .accountsPartial({ authority: guardian.publicKey, pool: f.pool })
+        .signers([guardian])
+        .rpc(),
+      "Unauthorized",
+    );
+  });
+});
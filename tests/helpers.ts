@@ -0,0 +1,384 @@
+import * as anchor from "@coral-xyz/anchor";
+import { BN, BorshCoder, EventParser, Program } from "@coral-xyz/anchor";
+import {
+  Keypair,
+  LAMPORTS_PER_SOL,
+  PublicKey,
+  SystemProgram,
+  SYSVAR_RENT_PUBKEY,
+} from "@solana/web3.js";
+import {
+  TOKEN_PROGRAM_ID,
+  createAccount,
+  createMint,
+  mintTo,
+} from "@solana/spl-token";
+import { expect } from "chai";
+import { Vortex } from "../target/types/vortex";
+
+anchor.setProvider(anchor.AnchorProvider.env());
+
+export const provider = anchor.getProvider() as anchor.AnchorProvider;
+export const program = anchor.workspace.vortex as Program<Vortex>;
+export const payer = (provider.wallet as anchor.Wallet).payer;
+
+export const FEE_NUMERATOR = new BN(30);
+export const FEE_DENOMINATOR = new BN(10_000);
+export const CURVE_CONSTANT_PRODUCT = 0;
+
+export const sleep = (ms: number) => new Promise((r) => setTimeout(r, ms));
+
+export function pda(seeds: (Buffer | Uint8Array)[]): PublicKey {
+  return PublicKey.findProgramAddressSync(seeds, program.programId)[0];
+}
+
+export async function airdrop(to: PublicKey, sol = 10): Promise<void> {
+  const sig = await provider.connection.requestAirdrop(to, sol * LAMPORTS_PER_SOL);
+  await provider.connection.confirmTransaction(sig, "confirmed");
+}
+
+export async function newUser(): Promise<Keypair> {
+  const user = Keypair.generate();
+  await airdrop(user.publicKey);
+  return user;
+}
+
+export async function balance(account: PublicKey): Promise<bigint> {
+  const info = await provider.connection.getTokenAccountBalance(account);
+  return BigInt(info.value.amount);
+}
+
+/** Expect `promise` to fail with the named `AmmError` variant */
+export async function expectError(promise: Promise<unknown>, code: string): Promise<void> {
+  try {
+    await promise;
+  } catch (err: any) {
+    const actual = err?.error?.errorCode?.code ?? err?.message ?? String(err);
+    expect(String(actual)).to.contain(code);
+    return;
+  }
+  expect.fail(`expected ${code}`);
+}
+
+export interface PoolFixture {
+  mintA: PublicKey;
+  mintB: PublicKey;
+  pool: PublicKey;
+  vaultA: PublicKey;
+  vaultB: PublicKey;
+  lpMint: PublicKey;
+  lpMintAuthority: PublicKey;
+}
+
+export interface UserAccounts {
+  user: Keypair;
+  tokenA: PublicKey;
+  tokenB: PublicKey;
+  lpToken: PublicKey;
+}
+
+/** Order two mints as `initialize_pool` requires */
+export function sortMints(x: PublicKey, y: PublicKey): [PublicKey, PublicKey] {
+  return Buffer.compare(x.toBuffer(), y.toBuffer()) < 0 ? [x, y] : [y, x];
+}
+
+/** Two fresh mints, ordered as `initialize_pool` requires */
+export async function createPair(decimals = 6): Promise<[PublicKey, PublicKey]> {
+  const x = await createMint(provider.connection, payer, payer.publicKey, null, decimals);
+  const y = await createMint(provider.connection, payer, payer.publicKey, null, decimals);
+  return sortMints(x, y);
+}
+
+export function poolAddresses(mintA: PublicKey, mintB: PublicKey): PoolFixture {
+  const pool = pda([Buffer.from("pool"), mintA.toBuffer(), mintB.toBuffer()]);
+  return {
+    mintA,
+    mintB,
+    pool,
+    vaultA: pda([Buffer.from("vault_a"), pool.toBuffer()]),
+    vaultB: pda([Buffer.from("vault_b"), pool.toBuffer()]),
+    lpMint: pda([Buffer.from("lp_mint"), pool.toBuffer()]),
+    lpMintAuthority: pda([Buffer.from("lp_mint_authority"), pool.toBuffer()]),
+  };
+}
+
+/** Create an empty constant-product pool, owned by the provider wallet
+ * unless another `authority` is given */
+export async function createPool(
+  mints?: [PublicKey, PublicKey],
+  authority: Keypair = payer,
+  lpDecimals = 9,
+): Promise<PoolFixture> {
+  const [mintA, mintB] = mints ?? (await createPair());
+  const f = poolAddresses(mintA, mintB);
+  await program.methods
+    .initializePool(FEE_NUMERATOR, FEE_DENOMINATOR, CURVE_CONSTANT_PRODUCT, new BN(0), new BN(0), lpDecimals)
+    .accountsPartial({
+      authority: authority.publicKey,
+      tokenAMint: mintA,
+      tokenBMint: mintB,
+      pool: f.pool,
+      tokenAVault: f.vaultA,
+      tokenBVault: f.vaultB,
+      lpMint: f.lpMint,
+      lpMintAuthority: f.lpMintAuthority,
+      tokenAProgram: TOKEN_PROGRAM_ID,
+      tokenBProgram: TOKEN_PROGRAM_ID,
+      tokenProgram: TOKEN_PROGRAM_ID,
+      systemProgram: SystemProgram.programId,
+      rent: SYSVAR_RENT_PUBKEY,
+    })
+    .signers([authority])
+    .rpc();
+  return f;
+}
+
+export const protocolConfig = pda([Buffer.from("protocol_config")]);
+
+export const treasuryAddress = (mint: PublicKey) => pda([Buffer.from("treasury"), mint.toBuffer()]);
+
+/** Create the singleton protocol config, governed by the provider wallet
+ * (the program's upgrade authority), if an earlier test has not */
+export async function ensureProtocolConfig(): Promise<void> {
+  if (await provider.connection.getAccountInfo(protocolConfig)) return;
+  const programData = PublicKey.findProgramAddressSync(
+    [program.programId.toBuffer()],
+    new PublicKey("BPFLoaderUpgradeab1e11111111111111111111111"),
+  )[0];
+  await program.methods
+    .initializeProtocolConfig()
+    .accountsPartial({
+      governance: payer.publicKey,
+      protocolConfig,
+      program: program.programId,
+      programData,
+      systemProgram: SystemProgram.programId,
+    })
+    .rpc();
+}
+
+/** The treasury vault for `mint`, created if needed */
+export async function ensureTreasury(mint: PublicKey): Promise<PublicKey> {
+  await ensureProtocolConfig();
+  const treasury = treasuryAddress(mint);
+  if (await provider.connection.getAccountInfo(treasury)) return treasury;
+  await program.methods
+    .initializeTreasury()
+    .accountsPartial({
+      payer: payer.publicKey,
+      protocolConfig,
+      mint,
+      treasury,
+      tokenProgram: TOKEN_PROGRAM_ID,
+      systemProgram: SystemProgram.programId,
+      rent: SYSVAR_RENT_PUBKEY,
+    })
+    .rpc();
+  return treasury;
+}
+
+/** Token and LP accounts for `user`, funded with `amountA`/`amountB` */
+export async function fundUser(
+  f: PoolFixture,
+  user: Keypair,
+  amountA: bigint,
+  amountB: bigint,
+): Promise<UserAccounts> {
+  const conn = provider.connection;
+  const tokenA = await createAccount(conn, payer, f.mintA, user.publicKey, Keypair.generate());
+  const tokenB = await createAccount(conn, payer, f.mintB, user.publicKey, Keypair.generate());
+  const lpToken = await createAccount(conn, payer, f.lpMint, user.publicKey, Keypair.generate());
+  if (amountA > 0n) await mintTo(conn, payer, f.mintA, tokenA, payer, amountA);
+  if (amountB > 0n) await mintTo(conn, payer, f.mintB, tokenB, payer, amountB);
+  return { user, tokenA, tokenB, lpToken };
+}
+
+export function addLiquidityAccounts(f: PoolFixture, u: UserAccounts, signer?: PublicKey) {
+  return {
+    user: signer ?? u.user.publicKey,
+    pool: f.pool,
+    tokenAVault: f.vaultA,
+    tokenBVault: f.vaultB,
+    lpMint: f.lpMint,
+    lpMintAuthority: f.lpMintAuthority,
+    userTokenA: u.tokenA,
+    userTokenB: u.tokenB,
+    userLpToken: u.lpToken,
+    lockedLpToken: null,
+    position: null,
+    depositIntent: null,
+    tokenAMint: f.mintA,
+    tokenBMint: f.mintB,
+    tokenAProgram: TOKEN_PROGRAM_ID,
+    tokenBProgram: TOKEN_PROGRAM_ID,
+    tokenProgram: TOKEN_PROGRAM_ID,
+  };
+}
+
+export const FAR_DEADLINE = new BN(4_000_000_000);
+
+export async function addLiquidity(
+  f: PoolFixture,
+  u: UserAccounts,
+  amountA: bigint,
+  amountB: bigint,
+  extra: Record<string, PublicKey | null> = {},
+): Promise<string> {
+  return program.methods
+    .addLiquidity(new BN(amountA.toString()), new BN(amountB.toString()), new BN(0), FAR_DEADLINE)
+    .accountsPartial({ ...addLiquidityAccounts(f, u), ...extra })
+    .signers([u.user])
+    .rpc();
+}
+
+export const INCINERATOR = new PublicKey("1nc1nerator11111111111111111111111111111111");
+
+/** Incinerator-owned LP account to receive the first deposit's locked
+ * MINIMUM_LIQUIDITY */
+export async function lockedLpAccount(f: PoolFixture): Promise<PublicKey> {
+  return createAccount(provider.connection, payer, f.lpMint, INCINERATOR, Keypair.generate());
+}
+
+/** Pool seeded with `amountA`/`amountB` by a fresh LP; the locked
+ * MINIMUM_LIQUIDITY is burned to the incinerator */
+export async function createSeededPool(
+  amountA = 1_000_000_000n,
+  amountB = 1_000_000_000n,
+): Promise<{ f: PoolFixture; lp: UserAccounts }> {
+  const f = await createPool();
+  const lp = await fundUser(f, await newUser(), amountA, amountB);
+  await addLiquidity(f, lp, amountA, amountB, { lockedLpToken: await lockedLpAccount(f) });
+  return { f, lp };
+}
+
+export function swapAccounts(f: PoolFixture, u: UserAccounts) {
+  return {
+    user: u.user.publicKey,
+    pool: f.pool,
+    tokenAVault: f.vaultA,
+    tokenBVault: f.vaultB,
+    userTokenA: u.tokenA,
+    userTokenB: u.tokenB,
+    tokenAMint: f.mintA,
+    tokenBMint: f.mintB,
+    tokenAProgram: TOKEN_PROGRAM_ID,
+    tokenBProgram: TOKEN_PROGRAM_ID,
+    tokenProgram: TOKEN_PROGRAM_ID,
+    instructionsSysvar: null,
+    oracle: null,
+    swapHistory: null,
+    priceCheckpoints: null,
+    lpMint: f.lpMint,
+    lpMintAuthority: null,
+    userLpToken: null,
+    userSwapState: null,
+    feeTier: null,
+  };
+}
+
+export async function swap(
+  f: PoolFixture,
+  u: UserAccounts,
+  amountIn: bigint,
+  aToB: boolean,
+  extra: Record<string, PublicKey | null> = {},
+): Promise<string> {
+  return program.methods
+    .swap(new BN(amountIn.toString()), new BN(0), aToB, new BN(0), new BN(0), FAR_DEADLINE, new BN(0))
+    .accountsPartial({ ...swapAccounts(f, u), ...extra })
+    .signers([u.user])
+    .rpc();
+}
+
+/** Pause `f` as its authority, or as the guardian when `signer` is given */
+export async function pausePool(f: PoolFixture, signer: Keypair = payer): Promise<string> {
+  return program.methods
+    .pausePool()
+    .accountsPartial({ signer: signer.publicKey, pool: f.pool })
+    .signers([signer])
+    .rpc();
+}
+
+export async function unpausePool(f: PoolFixture): Promise<string> {
+  return program.methods.unpausePool().accountsPartial({ authority: payer.publicKey, pool: f.pool }).rpc();
+}
+
+/** Q64 fixed-point price, as `Pool::price_a` */
+export const Q64 = new BN(1).shln(64);
+
+export function removeLiquidityAccounts(f: PoolFixture, u: UserAccounts) {
+  return {
+    user: u.user.publicKey,
+    pool: f.pool,
+    tokenAVault: f.vaultA,
+    tokenBVault: f.vaultB,
+    lpMint: f.lpMint,
+    userTokenA: u.tokenA,
+    userTokenB: u.tokenB,
+    userLpToken: u.lpToken,
+    treasuryTokenA: null,
+    treasuryTokenB: null,
+    position: null,
+    tokenAMint: f.mintA,
+    tokenBMint: f.mintB,
+    tokenAProgram: TOKEN_PROGRAM_ID,
+    tokenBProgram: TOKEN_PROGRAM_ID,
+    tokenProgram: TOKEN_PROGRAM_ID,
+  };
+}
+
+export async function removeLiquidity(
+  f: PoolFixture,
+  u: UserAccounts,
+  liquidity: bigint,
+  extra: Record<string, PublicKey | null> = {},
+): Promise<string> {
+  return program.methods
+    .removeLiquidity(new BN(liquidity.toString()), new BN(0), new BN(0), new BN(0), new BN(0), FAR_DEADLINE)
+    .accountsPartial({ ...removeLiquidityAccounts(f, u), ...extra })
+    .signers([u.user])
+    .rpc();
+}
+
+/** A `Swap` account list as `remaining_accounts`, in struct order; unused
+ * optional accounts are passed as the program id */
+export function swapRemainingAccounts(f: PoolFixture, u: UserAccounts) {
+  const writable = [f.pool, f.vaultA, f.vaultB, f.lpMint, u.tokenA, u.tokenB, u.user.publicKey];
+  return Object.values(swapAccounts(f, u)).map((key) => ({
+    pubkey: key ?? program.programId,
+    isSigner: key !== null && key.equals(u.user.publicKey),
+    isWritable: key !== null && writable.some((k) => k.equals(key)),
+  }));
+}
+
+/** An `AddLiquidity` account list as `remaining_accounts`, in struct order;
+ * unused optional accounts are passed as the program id */
+export function addLiquidityRemainingAccounts(f: PoolFixture, u: UserAccounts) {
+  const writable = [f.pool, f.vaultA, f.vaultB, f.lpMint, u.tokenA, u.tokenB, u.lpToken];
+  return Object.values(addLiquidityAccounts(f, u)).map((key) => ({
+    pubkey: key ?? program.programId,
+    isSigner: key !== null && key.equals(u.user.publicKey),
+    isWritable: key !== null && writable.some((k) => k.equals(key)),
+  }));
+}
+
+/** A `RemoveLiquidity` account list as `remaining_accounts`, in struct
+ * order; unused optional accounts are passed as the program id */
+export function removeLiquidityRemainingAccounts(f: PoolFixture, u: UserAccounts) {
+  const writable = [f.pool, f.vaultA, f.vaultB, f.lpMint, u.tokenA, u.tokenB, u.lpToken];
+  return Object.values(removeLiquidityAccounts(f, u)).map((key) => ({
+    pubkey: key ?? program.programId,
+    isSigner: key !== null && key.equals(u.user.publicKey),
+    isWritable: key !== null && writable.some((k) => k.equals(key)),
+  }));
+}
+
+/** Events emitted by the program in a confirmed transaction */
+export async function emittedEvents(signature: string): Promise<{ name: string; data: any }[]> {
+  const tx = await provider.connection.getTransaction(signature, {
+    commitment: "confirmed",
+    maxSupportedTransactionVersion: 0,
+  });
+  const parser = new EventParser(program.programId, new BorshCoder(program.idl));
+  return [...parser.parseLogs(tx?.meta?.logMessages ?? [])];
+}
@@ -0,0 +1,34 @@
+import { getMint } from "@solana/spl-token";
+import { expect } from "chai";
+import { createPair, createPool, expectError, payer, poolAddresses, program, provider } from "./helpers";
+
+describe("initialize_pool", () => {
+  it("rejects mints passed in reversed order", async () => {
+    const [mintA, mintB] = await createPair();
+    await expectError(createPool([mintB, mintA]), "InvalidTokenOrder");
+  });
+
+  it("creates the pool for the canonical order of the same pair", async () => {
+    const [mintA, mintB] = await createPair();
+    const f = await createPool([mintA, mintB]);
+
+    const pool = await program.account.pool.fetch(f.pool);
+    expect(pool.tokenAMint.equals(mintA)).to.be.true;
+    expect(pool.tokenBMint.equals(mintB)).to.be.true;
+  });
+
+  it("creates the LP mint with the requested decimals", async () => {
+    for (const lpDecimals of [0, 6, 9]) {
+      const f = await createPool(undefined, payer, lpDecimals);
+      const lpMint = await getMint(provider.connection, f.lpMint);
+      expect(lpMint.decimals).to.equal(lpDecimals);
+      expect(lpMint.mintAuthority!.equals(f.lpMintAuthority)).to.be.true;
+    }
+  });
+
+  it("rejects LP decimals above MAX_LP_DECIMALS", async () => {
+    const [mintA, mintB] = await createPair();
+    await expectError(createPool([mintA, mintB], payer, 10), "InvalidLpDecimals");
+    expect(await provider.connection.getAccountInfo(poolAddresses(mintA, mintB).lpMint)).to.be.null;
+  });
+});
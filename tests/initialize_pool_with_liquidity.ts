@@ -0,0 +1,104 @@
+import { BN } from "@coral-xyz/anchor";
+import { Keypair, SYSVAR_RENT_PUBKEY, SystemProgram } from "@solana/web3.js";
+import { TOKEN_PROGRAM_ID, createAccount, getAssociatedTokenAddressSync, getMint, mintTo } from "@solana/spl-token";
+import { expect } from "chai";
+import {
+  CURVE_CONSTANT_PRODUCT,
+  FAR_DEADLINE,
+  FEE_DENOMINATOR,
+  FEE_NUMERATOR,
+  INCINERATOR,
+  PoolFixture,
+  balance,
+  createPair,
+  expectError,
+  payer,
+  poolAddresses,
+  program,
+  provider,
+} from "./helpers";
+
+describe("initialize_pool_with_liquidity", () => {
+  const AMOUNT_A = 1_000_000_000n;
+  const AMOUNT_B = 4_000_000_000n;
+  const MINIMUM_LIQUIDITY = 1_000n;
+
+  async function createFunded(minLiquidity: bigint) {
+    const [mintA, mintB] = await createPair();
+    const f: PoolFixture = poolAddresses(mintA, mintB);
+    const conn = provider.connection;
+    const tokenA = await createAccount(conn, payer, mintA, payer.publicKey, Keypair.generate());
+    const tokenB = await createAccount(conn, payer, mintB, payer.publicKey, Keypair.generate());
+    await mintTo(conn, payer, mintA, tokenA, payer, AMOUNT_A);
+    await mintTo(conn, payer, mintB, tokenB, payer, AMOUNT_B);
+    const userLpToken = getAssociatedTokenAddressSync(f.lpMint, payer.publicKey);
+    const lockedLpToken = getAssociatedTokenAddressSync(f.lpMint, INCINERATOR, true);
+
+    const create = () =>
+      program.methods
+        .initializePoolWithLiquidity(
+          FEE_NUMERATOR,
+          FEE_DENOMINATOR,
+          CURVE_CONSTANT_PRODUCT,
+          new BN(0),
+          new BN(0),
+          9,
+          new BN(AMOUNT_A.toString()),
+          new BN(AMOUNT_B.toString()),
+          new BN(minLiquidity.toString()),
+          FAR_DEADLINE,
+        )
+        .accountsPartial({
+          init: {
+            authority: payer.publicKey,
+            tokenAMint: mintA,
+            tokenBMint: mintB,
+            pool: f.pool,
+            tokenAVault: f.vaultA,
+            tokenBVault: f.vaultB,
+            lpMint: f.lpMint,
+            lpMintAuthority: f.lpMintAuthority,
+            tokenAProgram: TOKEN_PROGRAM_ID,
+            tokenBProgram: TOKEN_PROGRAM_ID,
+            tokenProgram: TOKEN_PROGRAM_ID,
+            systemProgram: SystemProgram.programId,
+            rent: SYSVAR_RENT_PUBKEY,
+          },
+          userTokenA: tokenA,
+          userTokenB: tokenB,
+          userLpToken,
+          lockedLpToken,
+          incinerator: INCINERATOR,
+          tokenProgram: TOKEN_PROGRAM_ID,
+        })
+        .rpc();
+    return { f, tokenA, tokenB, userLpToken, lockedLpToken, create };
+  }
+
+  it("creates and funds the pool in one transaction", async () => {
+    const { f, tokenA, tokenB, userLpToken, lockedLpToken, create } = await createFunded(0n);
+    await create();
+
+    const pool = await program.account.pool.fetch(f.pool);
+    expect(pool.reserveA.toString()).to.equal(AMOUNT_A.toString());
+    expect(pool.reserveB.toString()).to.equal(AMOUNT_B.toString());
+    expect(await balance(f.vaultA)).to.equal(AMOUNT_A);
+    expect(await balance(f.vaultB)).to.equal(AMOUNT_B);
+    expect(await balance(tokenA)).to.equal(0n);
+    expect(await balance(tokenB)).to.equal(0n);
+
+    // sqrt(1e9 * 4e9) = 2e9, less the locked minimum
+    expect(await balance(userLpToken)).to.equal(2_000_000_000n - MINIMUM_LIQUIDITY);
+    expect(await balance(lockedLpToken)).to.equal(MINIMUM_LIQUIDITY);
+    expect((await getMint(provider.connection, f.lpMint)).supply).to.equal(2_000_000_000n);
+  });
+
+  it("leaves no pool behind when the deposit fails", async () => {
+    const { f, tokenA, create } = await createFunded(2_000_000_000n);
+    await expectError(create(), "SlippageExceeded");
+
+    expect(await provider.connection.getAccountInfo(f.pool)).to.be.null;
+    expect(await provider.connection.getAccountInfo(f.lpMint)).to.be.null;
+    expect(await balance(tokenA)).to.equal(AMOUNT_A);
+  });
+});
@@ -0,0 +1,131 @@
+import { BN } from "@coral-xyz/anchor";
+import { SystemProgram } from "@solana/web3.js";
+import { TOKEN_PROGRAM_ID } from "@solana/spl-token";
+import { expect } from "chai";
+import {
+  PoolFixture,
+  UserAccounts,
+  balance,
+  createSeededPool,
+  expectError,
+  payer,
+  pda,
+  program,
+  provider,
+  sleep,
+} from "./helpers";
+
+/** Unix time as the program's Clock sysvar sees it */
+async function clusterTime(): Promise<number> {
+  const slot = await provider.connection.getSlot("confirmed");
+  return (await provider.connection.getBlockTime(slot)) ?? Math.floor(Date.now() / 1000);
+}
+
+/** Wait until the cluster clock reaches `timestamp` */
+async function warpTo(timestamp: number): Promise<void> {
+  while ((await clusterTime()) < timestamp) {
+    await sleep(500);
+  }
+}
+
+describe("liquidity lock", () => {
+  const LOCK_SECONDS = 4;
+  let f: PoolFixture;
+  let lp: UserAccounts;
+  let nonce = 0;
+
+  const lockAddresses = (n: number) => {
+    const lock = pda([
+      Buffer.from("liquidity_lock"),
+      f.pool.toBuffer(),
+      lp.user.publicKey.toBuffer(),
+      new BN(n).toArrayLike(Buffer, "le", 8),
+    ]);
+    return { lock, escrow: pda([Buffer.from("lock_escrow"), lock.toBuffer()]) };
+  };
+
+  const lock = (n: number, amount: bigint, unlockTimestamp: number) => {
+    const { lock, escrow } = lockAddresses(n);
+    return program.methods
+      .lockLiquidity(new BN(n), new BN(amount.toString()), new BN(unlockTimestamp))
+      .accountsPartial({
+        owner: lp.user.publicKey,
+        pool: f.pool,
+        lpMint: f.lpMint,
+        lock,
+        lockLpToken: escrow,
+        ownerLpToken: lp.lpToken,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([lp.user])
+      .rpc();
+  };
+
+  const unlock = (n: number) => {
+    const { lock, escrow } = lockAddresses(n);
+    return program.methods
+      .unlockLiquidity()
+      .accountsPartial({
+        owner: lp.user.publicKey,
+        lock,
+        lockLpToken: escrow,
+        ownerLpToken: lp.lpToken,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .signers([lp.user])
+      .rpc();
+  };
+
+  before(async () => {
+    ({ f, lp } = await createSeededPool());
+  });
+
+  it("holds the LP until the unlock time, then releases it", async () => {
+    const n = nonce++;
+    const held = await balance(lp.lpToken);
+    const unlockAt = (await clusterTime()) + LOCK_SECONDS;
+    await lock(n, 1_000n, unlockAt);
+
+    const { lock: lockKey, escrow } = lockAddresses(n);
+    expect(await balance(escrow)).to.equal(1_000n);
+    expect(await balance(lp.lpToken)).to.equal(held - 1_000n);
+
+    // Before the unlock time the escrow stays put
+    await expectError(unlock(n), "StillLocked");
+
+    await warpTo(unlockAt);
+    await unlock(n);
+
+    expect(await balance(lp.lpToken)).to.equal(held);
+    expect(await provider.connection.getAccountInfo(lockKey)).to.be.null;
+    expect(await provider.connection.getAccountInfo(escrow)).to.be.null;
+  });
+
+  it("rejects an unlock time that is not in the future", async () => {
+    await expectError(lock(nonce++, 1_000n, await clusterTime()), "InvalidUnlockTimestamp");
+  });
+
+  it("rejects unlocking someone else's lock", async () => {
+    const n = nonce++;
+    const unlockAt = (await clusterTime()) + LOCK_SECONDS;
+    await lock(n, 1_000n, unlockAt);
+    await warpTo(unlockAt);
+
+    // The lock PDA is derived from the signer, so another owner cannot match it
+    const { lock: lockKey, escrow } = lockAddresses(n);
+    await expectError(
+      program.methods
+        .unlockLiquidity()
+        .accountsPartial({
+          owner: payer.publicKey,
+          lock: lockKey,
+          lockLpToken: escrow,
+          ownerLpToken: lp.lpToken,
+          tokenProgram: TOKEN_PROGRAM_ID,
+        })
+        .rpc(),
+      "ConstraintSeeds",
+    );
+  });
+});
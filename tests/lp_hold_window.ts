@@ -0,0 +1,113 @@
+import { BN } from "@coral-xyz/anchor";
+import { PublicKey, SystemProgram, Transaction } from "@solana/web3.js";
+import { transfer } from "@solana/spl-token";
+import { expect } from "chai";
+import {
+  FAR_DEADLINE,
+  PoolFixture,
+  UserAccounts,
+  addLiquidity,
+  addLiquidityAccounts,
+  balance,
+  createSeededPool,
+  expectError,
+  fundUser,
+  newUser,
+  payer,
+  pda,
+  program,
+  provider,
+  removeLiquidity,
+  removeLiquidityAccounts,
+  sleep,
+} from "./helpers";
+
+describe("LP hold window", () => {
+  const HOLD_SLOTS = 2;
+  const AMOUNT = 10_000_000n;
+  let f: PoolFixture;
+  let lp: UserAccounts;
+  let user: UserAccounts;
+  let position: PublicKey;
+
+  const waitForSlot = async (slot: number) => {
+    while ((await provider.connection.getSlot("confirmed")) < slot) await sleep(200);
+  };
+
+  const depositSlot = async () => (await program.account.pool.fetch(f.pool)).lastDepositSlot.toNumber();
+
+  beforeEach(async () => {
+    ({ f, lp } = await createSeededPool());
+    await program.methods
+      .setMinLpHoldSlots(new BN(HOLD_SLOTS))
+      .accountsPartial({ authority: payer.publicKey, pool: f.pool })
+      .rpc();
+
+    user = await fundUser(f, await newUser(), AMOUNT, AMOUNT);
+    position = pda([Buffer.from("position"), f.pool.toBuffer(), user.user.publicKey.toBuffer()]);
+    await program.methods
+      .openPosition()
+      .accountsPartial({ owner: user.user.publicKey, pool: f.pool, position, systemProgram: SystemProgram.programId })
+      .signers([user.user])
+      .rpc();
+  });
+
+  it("rejects removing liquidity in the slot it was added", async () => {
+    const add = await program.methods
+      .addLiquidity(new BN(AMOUNT.toString()), new BN(AMOUNT.toString()), new BN(0), FAR_DEADLINE)
+      .accountsPartial({ ...addLiquidityAccounts(f, user), position })
+      .instruction();
+    const remove = await program.methods
+      .removeLiquidity(new BN(1_000), new BN(0), new BN(0), new BN(0), new BN(0), FAR_DEADLINE)
+      .accountsPartial({ ...removeLiquidityAccounts(f, user), position })
+      .instruction();
+
+    await expectError(
+      provider.sendAndConfirm(new Transaction().add(add, remove), [user.user]),
+      "LiquidityLockedTooRecent",
+    );
+    expect(await balance(user.tokenA)).to.equal(AMOUNT);
+  });
+
+  it("accepts the withdrawal once the window has elapsed", async () => {
+    await addLiquidity(f, user, AMOUNT, AMOUNT, { position });
+    const liquidity = await balance(user.lpToken);
+    await waitForSlot((await depositSlot()) + HOLD_SLOTS);
+
+    await removeLiquidity(f, user, liquidity, { position });
+    expect(await balance(user.lpToken)).to.equal(0n);
+    expect((await program.account.position.fetch(position)).liquidity.toNumber()).to.equal(0);
+  });
+
+  it("times LP without a position from the pool's last deposit", async () => {
+    // The seeding LP deposited before the window was set and has no position
+    await addLiquidity(f, user, AMOUNT, AMOUNT, { position });
+    await expectError(removeLiquidity(f, lp, 1_000n), "LiquidityLockedTooRecent");
+
+    await waitForSlot((await depositSlot()) + HOLD_SLOTS);
+    const liquidity = await balance(lp.lpToken);
+    await removeLiquidity(f, lp, liquidity);
+    expect(await balance(lp.lpToken)).to.equal(0n);
+  });
+
+  it("times LP beyond what the position records from the pool's last deposit", async () => {
+    await addLiquidity(f, user, AMOUNT, AMOUNT, { position });
+    await waitForSlot((await depositSlot()) + HOLD_SLOTS);
+
+    // A fresh deposit elsewhere, moved into the held position's wallet
+    const other = await fundUser(f, await newUser(), AMOUNT, AMOUNT);
+    const otherPosition = pda([Buffer.from("position"), f.pool.toBuffer(), other.user.publicKey.toBuffer()]);
+    await program.methods
+      .openPosition()
+      .accountsPartial({ owner: other.user.publicKey, pool: f.pool, position: otherPosition, systemProgram: SystemProgram.programId })
+      .signers([other.user])
+      .rpc();
+    await addLiquidity(f, other, AMOUNT, AMOUNT, { position: otherPosition });
+    const recorded = await balance(user.lpToken);
+    await transfer(provider.connection, payer, other.lpToken, user.lpToken, other.user, 1_000n);
+
+    await expectError(removeLiquidity(f, user, recorded + 1_000n, { position }), "LiquidityLockedTooRecent");
+    await removeLiquidity(f, user, recorded, { position });
+    expect(await balance(user.lpToken)).to.equal(1_000n);
+  });
+});
@@ -0,0 +1,55 @@
+import { BN } from "@coral-xyz/anchor";
+import { expect } from "chai";
+import {
+  PoolFixture,
+  UserAccounts,
+  balance,
+  createSeededPool,
+  ensureTreasury,
+  expectError,
+  fundUser,
+  newUser,
+  payer,
+  program,
+  removeLiquidity,
+  swap,
+} from "./helpers";
+
+describe("lp_value", () => {
+  let f: PoolFixture;
+  let lp: UserAccounts;
+
+  const lpValue = (lpAmount: bigint, lpMint = f.lpMint) =>
+    program.methods.lpValue(new BN(lpAmount.toString())).accountsPartial({ pool: f.pool, lpMint }).view();
+
+  beforeEach(async () => {
+    ({ f, lp } = await createSeededPool(1_000_000_000n, 3_000_000_000n));
+    // Grow the reserves past the deposit so the value is not a round share
+    const trader = await fundUser(f, await newUser(), 50_000_000n, 0n);
+    await swap(f, trader, 50_000_000n, true);
+  });
+
+  it("matches what burning the LP pays out, net of the withdrawal fee", async () => {
+    await program.methods
+      .setWithdrawFee(25)
+      .accountsPartial({ authority: payer.publicKey, pool: f.pool })
+      .rpc();
+    const burned = (await balance(lp.lpToken)) / 3n;
+
+    const value = await lpValue(burned);
+    await removeLiquidity(f, lp, burned, {
+      treasuryTokenA: await ensureTreasury(f.mintA),
+      treasuryTokenB: await ensureTreasury(f.mintB),
+    });
+
+    expect(await balance(lp.tokenA)).to.equal(BigInt(value.amountA.toString()));
+    expect(await balance(lp.tokenB)).to.equal(BigInt(value.amountB.toString()));
+  });
+
+  it("rejects a zero amount and another pool's LP mint", async () => {
+    await expectError(lpValue(0n), "InsufficientLiquidityBurned");
+
+    const { f: other } = await createSeededPool();
+    await expectError(lpValue(1_000n, other.lpMint), "InvalidVault");
+  });
+});
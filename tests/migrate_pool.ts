@@ -0,0 +1,29 @@
+import { Keypair } from "@solana/web3.js";
+import { PoolFixture, createPool, expectError, newUser, payer, program } from "./helpers";
+
+describe("migrate_pool", () => {
+  const migratePool = (f: PoolFixture, authority: Keypair = payer) =>
+    program.methods
+      .migratePool()
+      .accountsPartial({
+        authority: authority.publicKey,
+        pool: f.pool,
+        tokenAMint: f.mintA,
+        tokenBMint: f.mintB,
+      })
+      .signers([authority])
+      .rpc();
+
+  // Migrating a real v1 account is covered by the layout test next to
+  // `Pool::apply_migration_defaults`; a live validator only has current pools
+
+  it("rejects a pool already at the current version", async () => {
+    const f = await createPool();
+    await expectError(migratePool(f), "MigrationNotNeeded");
+  });
+
+  it("rejects a signer other than the authority", async () => {
+    const f = await createPool();
+    await expectError(migratePool(f, await newUser()), "Unauthorized");
+  });
+});
@@ -0,0 +1,95 @@
+import { BN } from "@coral-xyz/anchor";
+import { Keypair, SystemProgram } from "@solana/web3.js";
+import { transfer } from "@solana/spl-token";
+import { expect } from "chai";
+import {
+  PoolFixture,
+  UserAccounts,
+  addLiquidity,
+  balance,
+  createSeededPool,
+  expectError,
+  fundUser,
+  newUser,
+  payer,
+  pda,
+  program,
+  provider,
+  removeLiquidity,
+} from "./helpers";
+
+describe("min_reserve", () => {
+  const RESERVE = 1_000_000_000n;
+  const AMOUNT = 100_000_000n;
+  let f: PoolFixture;
+  let lp: UserAccounts;
+  let user: UserAccounts;
+
+  const setMinReserve = (minReserve: bigint, authority: Keypair = payer) =>
+    program.methods
+      .setMinReserve(new BN(minReserve.toString()))
+      .accountsPartial({ authority: authority.publicKey, pool: f.pool })
+      .signers([authority])
+      .rpc();
+
+  beforeEach(async () => {
+    ({ f, lp } = await createSeededPool(RESERVE, RESERVE));
+    user = await fundUser(f, await newUser(), AMOUNT, AMOUNT);
+    await addLiquidity(f, user, AMOUNT, AMOUNT);
+  });
+
+  it("accepts partial withdrawals that keep both reserves on the floor", async () => {
+    await setMinReserve(RESERVE + AMOUNT / 2n);
+    await removeLiquidity(f, lp, AMOUNT / 2n);
+
+    const pool = await program.account.pool.fetch(f.pool);
+    expect(pool.reserveA.toString()).to.equal((RESERVE + AMOUNT / 2n).toString());
+    expect(pool.reserveB.toString()).to.equal((RESERVE + AMOUNT / 2n).toString());
+  });
+
+  it("rejects partial withdrawals that would cross the floor", async () => {
+    await setMinReserve(RESERVE + 2n);
+
+    const lpBalance = await balance(user.lpToken);
+    await expectError(removeLiquidity(f, user, lpBalance - 1n), "WouldDrainPool");
+    await expectError(removeLiquidity(f, lp, 2n * AMOUNT), "WouldDrainPool");
+    expect(await balance(user.lpToken)).to.equal(lpBalance);
+  });
+
+  it("lets each of two LPs exit in full below the floor", async () => {
+    await setMinReserve(RESERVE + AMOUNT);
+
+    // The first LP out leaves the other LP's liquidity behind
+    await removeLiquidity(f, user, await balance(user.lpToken));
+    expect(await balance(user.lpToken)).to.equal(0n);
+    expect(await balance(user.tokenA)).to.equal(AMOUNT);
+
+    await removeLiquidity(f, lp, await balance(lp.lpToken));
+    expect(await balance(lp.lpToken)).to.equal(0n);
+    const pool = await program.account.pool.fetch(f.pool);
+    expect(pool.reserveA.lt(new BN(RESERVE.toString()))).to.be.true;
+  });
+
+  it("lets a holder burn their whole position below the floor", async () => {
+    const holder = await fundUser(f, await newUser(), AMOUNT, AMOUNT);
+    const position = pda([Buffer.from("position"), f.pool.toBuffer(), holder.user.publicKey.toBuffer()]);
+    await program.methods
+      .openPosition()
+      .accountsPartial({ owner: holder.user.publicKey, pool: f.pool, position, systemProgram: SystemProgram.programId })
+      .signers([holder.user])
+      .rpc();
+    await addLiquidity(f, holder, AMOUNT, AMOUNT, { position });
+    const recorded = await balance(holder.lpToken);
+    // LP beyond the position, so the burn is not the whole balance
+    await transfer(provider.connection, payer, lp.lpToken, holder.lpToken, lp.user, 1_000n);
+    await setMinReserve(RESERVE + 2n * AMOUNT);
+
+    await expectError(removeLiquidity(f, holder, recorded - 1n, { position }), "WouldDrainPool");
+    await removeLiquidity(f, holder, recorded, { position });
+    expect(await balance(holder.lpToken)).to.equal(1_000n);
+  });
+
+  it("rejects a signer other than the pool authority", async () => {
+    await expectError(setMinReserve(RESERVE, await newUser()), "Unauthorized");
+  });
+});
@@ -0,0 +1,65 @@
+import { BN } from "@coral-xyz/anchor";
+import { Keypair } from "@solana/web3.js";
+import { expect } from "chai";
+import {
+  PoolFixture,
+  UserAccounts,
+  balance,
+  createSeededPool,
+  expectError,
+  fundUser,
+  newUser,
+  payer,
+  program,
+  swap,
+} from "./helpers";
+
+describe("per-pool min_swap_amount", () => {
+  const FUNDS = 1_000_000n;
+  let f: PoolFixture;
+  let other: PoolFixture;
+  let trader: UserAccounts;
+
+  const setMinSwapAmount = (pool: PoolFixture, amount: bigint, authority: Keypair = payer) =>
+    program.methods
+      .setMinSwapAmount(new BN(amount.toString()))
+      .accountsPartial({ authority: authority.publicKey, pool: pool.pool })
+      .signers([authority])
+      .rpc();
+
+  beforeEach(async () => {
+    ({ f } = await createSeededPool());
+    ({ f: other } = await createSeededPool());
+    trader = await fundUser(f, await newUser(), FUNDS, 0n);
+  });
+
+  it("defaults to MIN_SWAP_AMOUNT and enforces a raised minimum on that pool only", async () => {
+    expect((await program.account.pool.fetch(f.pool)).minSwapAmount.toNumber()).to.equal(100);
+    await expectError(swap(f, trader, 99n, true), "AmountTooSmall");
+    await swap(f, trader, 100n, true);
+
+    await setMinSwapAmount(f, 10_000n);
+    expect((await program.account.pool.fetch(f.pool)).minSwapAmount.toNumber()).to.equal(10_000);
+    await expectError(swap(f, trader, 9_999n, true), "AmountTooSmall");
+    await swap(f, trader, 10_000n, true);
+    expect(await balance(trader.tokenA)).to.equal(FUNDS - 10_100n);
+
+    // The other pool keeps its own minimum
+    const otherTrader = await fundUser(other, await newUser(), 100n, 0n);
+    await swap(other, otherTrader, 100n, true);
+    expect(await balance(otherTrader.tokenA)).to.equal(0n);
+  });
+
+  it("rejects a zero minimum or one above a whole token", async () => {
+    // Both mints have 6 decimals, so the cap is 10^6
+    await setMinSwapAmount(f, 1_000_000n);
+    await expectError(setMinSwapAmount(f, 1_000_001n), "InvalidMinSwapAmount");
+    await expectError(setMinSwapAmount(f, 0n), "InvalidMinSwapAmount");
+    expect((await program.account.pool.fetch(f.pool)).minSwapAmount.toNumber()).to.equal(1_000_000);
+  });
+
+  it("rejects a non-authority signer", async () => {
+    await expectError(setMinSwapAmount(f, 10_000n, await newUser()), "Unauthorized");
+    expect((await program.account.pool.fetch(f.pool)).minSwapAmount.toNumber()).to.equal(100);
+  });
+});
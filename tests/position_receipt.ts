@@ -0,0 +1,110 @@
+import { BN } from "@coral-xyz/anchor";
+import { PublicKey } from "@solana/web3.js";
+import { getMint } from "@solana/spl-token";
+import { expect } from "chai";
+import {
+  FAR_DEADLINE,
+  PoolFixture,
+  UserAccounts,
+  balance,
+  createSeededPool,
+  expectError,
+  fundUser,
+  newUser,
+  pda,
+  program,
+  provider,
+  removeLiquidityRemainingAccounts,
+} from "./helpers";
+
+describe("position receipts", () => {
+  let f: PoolFixture;
+  let lp: UserAccounts;
+
+  const receiptAddress = (owner: PublicKey, nonce: number) =>
+    pda([Buffer.from("position"), f.pool.toBuffer(), owner.toBuffer(), new BN(nonce).toArrayLike(Buffer, "le", 8)]);
+  const escrowAddress = (receipt: PublicKey) => pda([Buffer.from("receipt_escrow"), receipt.toBuffer()]);
+
+  const openReceipt = (nonce: number, liquidity: bigint) =>
+    program.methods
+      .openPositionReceipt(new BN(nonce), new BN(liquidity.toString()))
+      .accountsPartial({
+        owner: lp.user.publicKey,
+        pool: f.pool,
+        lpMint: f.lpMint,
+        receipt: receiptAddress(lp.user.publicKey, nonce),
+        receiptLpToken: escrowAddress(receiptAddress(lp.user.publicKey, nonce)),
+        ownerLpToken: lp.lpToken,
+      })
+      .signers([lp.user])
+      .rpc();
+
+  const redeem = (receipt: PublicKey, u: UserAccounts) =>
+    program.methods
+      .removeLiquidityFromPosition(new BN(0), new BN(0), FAR_DEADLINE)
+      .accountsPartial({
+        owner: u.user.publicKey,
+        receipt,
+        receiptLpToken: escrowAddress(receipt),
+        ownerLpToken: u.lpToken,
+      })
+      .remainingAccounts(removeLiquidityRemainingAccounts(f, u))
+      .signers([u.user])
+      .rpc();
+
+  beforeEach(async () => {
+    ({ f, lp } = await createSeededPool());
+  });
+
+  it("opens independent receipts and redeems one through remove_liquidity", async () => {
+    const total = await balance(lp.lpToken);
+    const first = total / 4n;
+    const second = total / 8n;
+    const pool = await program.account.pool.fetch(f.pool);
+    const supply = (await getMint(provider.connection, f.lpMint)).supply;
+
+    await openReceipt(1, first);
+    await openReceipt(2, second);
+
+    const one = receiptAddress(lp.user.publicKey, 1);
+    const receipt = await program.account.positionReceipt.fetch(one);
+    expect(receipt.owner.equals(lp.user.publicKey)).to.be.true;
+    expect(receipt.pool.equals(f.pool)).to.be.true;
+    expect(receipt.nonce.toNumber()).to.equal(1);
+    expect(receipt.liquidity.toString()).to.equal(first.toString());
+    expect(receipt.entryReserveA.eq(pool.reserveA)).to.be.true;
+    expect(receipt.entryLpSupply.toString()).to.equal(supply.toString());
+    expect(await balance(escrowAddress(one))).to.equal(first);
+    expect(await balance(lp.lpToken)).to.equal(total - first - second);
+
+    await redeem(one, lp);
+
+    // Receipt and escrow closed; the escrowed LP was burned for its share
+    expect(await provider.connection.getAccountInfo(one)).to.be.null;
+    expect(await provider.connection.getAccountInfo(escrowAddress(one))).to.be.null;
+    expect((await getMint(provider.connection, f.lpMint)).supply).to.equal(supply - first);
+    expect(await balance(lp.tokenA)).to.equal((first * BigInt(pool.reserveA.toString())) / supply);
+    expect(await balance(lp.lpToken)).to.equal(total - first - second);
+
+    const two = await program.account.positionReceipt.fetch(receiptAddress(lp.user.publicKey, 2));
+    expect(two.liquidity.toString()).to.equal(second.toString());
+  });
+
+  it("rejects reusing a nonce", async () => {
+    await openReceipt(7, 1_000n);
+    // The system program's AccountAlreadyInUse
+    await expectError(openReceipt(7, 1_000n), "0x0");
+    expect((await program.account.positionReceipt.fetch(receiptAddress(lp.user.publicKey, 7))).liquidity.toNumber()).to.equal(1_000);
+  });
+
+  it("rejects redemption by anyone but the owner", async () => {
+    await openReceipt(1, 1_000_000n);
+    const receipt = receiptAddress(lp.user.publicKey, 1);
+    const thief = await fundUser(f, await newUser(), 0n, 0n);
+
+    // The receipt's seeds include the owner
+    await expectError(redeem(receipt, thief), "ConstraintSeeds");
+    expect(await balance(escrowAddress(receipt))).to.equal(1_000_000n);
+    expect(await balance(thief.tokenA)).to.equal(0n);
+  });
+});
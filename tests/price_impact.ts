@@ -0,0 +1,55 @@
+import { BN } from "@coral-xyz/anchor";
+import { expect } from "chai";
+import {
+  FAR_DEADLINE,
+  PoolFixture,
+  UserAccounts,
+  balance,
+  createSeededPool,
+  expectError,
+  fundUser,
+  newUser,
+  program,
+  swapAccounts,
+} from "./helpers";
+
+describe("max_price_impact_bps", () => {
+  const RESERVE = 1_000_000_000n;
+  const AMOUNT = 100_000_000n;
+  let f: PoolFixture;
+  let trader: UserAccounts;
+
+  const swapWithin = (maxPriceImpactBps: bigint) =>
+    program.methods
+      .swap(new BN(AMOUNT.toString()), new BN(0), true, new BN(0), new BN(0), FAR_DEADLINE, new BN(maxPriceImpactBps.toString()))
+      .accountsPartial(swapAccounts(f, trader))
+      .signers([trader.user])
+      .rpc();
+
+  // Mirrors calculate_price_impact_bps for a 10% trade into the fresh
+  // 1:1 pool: the 0.3% fee plus about 9% of curve slippage
+  const withFee = AMOUNT - (AMOUNT * 30n) / 10_000n;
+  const amountOut = (withFee * RESERVE) / (RESERVE + withFee);
+  const impactBps = ((AMOUNT * RESERVE - amountOut * RESERVE) * 10_000n) / (AMOUNT * RESERVE);
+
+  beforeEach(async () => {
+    ({ f } = await createSeededPool(RESERVE, RESERVE));
+    trader = await fundUser(f, await newUser(), AMOUNT, 0n);
+  });
+
+  it("executes at exactly the bound", async () => {
+    await swapWithin(impactBps);
+    expect(await balance(trader.tokenB)).to.equal(amountOut);
+  });
+
+  it("is disabled by a zero bound", async () => {
+    await swapWithin(0n);
+    expect(await balance(trader.tokenB)).to.equal(amountOut);
+  });
+
+  it("rejects a bound one basis point below the impact", async () => {
+    await expectError(swapWithin(impactBps - 1n), "PriceImpactTooHigh");
+    expect(await balance(trader.tokenA)).to.equal(AMOUNT);
+    expect((await program.account.pool.fetch(f.pool)).reserveB.toString()).to.equal(RESERVE.toString());
+  });
+});
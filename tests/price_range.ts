@@ -0,0 +1,74 @@
+import { BN } from "@coral-xyz/anchor";
+import { expect } from "chai";
+import {
+  FAR_DEADLINE,
+  PoolFixture,
+  UserAccounts,
+  createSeededPool,
+  emittedEvents,
+  expectError,
+  fundUser,
+  newUser,
+  program,
+  provider,
+  swap,
+  swapAccounts,
+} from "./helpers";
+
+describe("price high/low marks", () => {
+  const AMOUNT = 50_000_000n;
+  let f: PoolFixture;
+  let trader: UserAccounts;
+
+  const priceUpdate = async (signature: string) => {
+    await provider.connection.confirmTransaction(signature, "confirmed");
+    const events = (await emittedEvents(signature)).filter((e) => e.name.toLowerCase() === "oraclepriceupdate");
+    expect(events).to.have.length(1);
+    return events[0].data;
+  };
+
+  beforeEach(async () => {
+    ({ f } = await createSeededPool());
+    trader = await fundUser(f, await newUser(), 4n * AMOUNT, 4n * AMOUNT);
+  });
+
+  it("records the extremes of a price moving both ways", async () => {
+    const prices: BN[] = [];
+    // A sold (price of A falls), then bought back past the start
+    for (const aToB of [true, true, false, false, false]) {
+      prices.push((await priceUpdate(await swap(f, trader, AMOUNT, aToB))).price);
+    }
+    const low = prices.reduce((a, b) => BN.min(a, b));
+    const high = prices.reduce((a, b) => BN.max(a, b));
+    expect(low.eq(prices[1])).to.be.true;
+    expect(high.eq(prices[4])).to.be.true;
+
+    const pool = await program.account.pool.fetch(f.pool);
+    expect(pool.priceLow.eq(low)).to.be.true;
+    expect(pool.priceHigh.eq(high)).to.be.true;
+
+    // The event reports the range as of that swap
+    const last = await priceUpdate(await swap(f, trader, AMOUNT, true));
+    expect(last.priceLow.eq(low)).to.be.true;
+    expect(last.priceHigh.eq(high)).to.be.true;
+  });
+
+  it("leaves the marks untouched by a rejected swap", async () => {
+    await swap(f, trader, AMOUNT, true);
+    const before = await program.account.pool.fetch(f.pool);
+
+    const unreachable = new BN(AMOUNT.toString());
+    await expectError(
+      program.methods
+        .swap(new BN(AMOUNT.toString()), unreachable, false, new BN(0), new BN(0), FAR_DEADLINE, new BN(0))
+        .accountsPartial(swapAccounts(f, trader))
+        .signers([trader.user])
+        .rpc(),
+      "SlippageExceeded",
+    );
+
+    const after = await program.account.pool.fetch(f.pool);
+    expect(after.priceLow.eq(before.priceLow)).to.be.true;
+    expect(after.priceHigh.eq(before.priceHigh)).to.be.true;
+  });
+});
@@ -0,0 +1,92 @@
+import { BN } from "@coral-xyz/anchor";
+import { getMint, mintTo } from "@solana/spl-token";
+import { expect } from "chai";
+import {
+  FAR_DEADLINE,
+  PoolFixture,
+  Q64,
+  UserAccounts,
+  balance,
+  createSeededPool,
+  expectError,
+  payer,
+  program,
+  provider,
+  removeLiquidityAccounts,
+} from "./helpers";
+
+describe("rebalance_position", () => {
+  const TARGET_A = 10_000_000n;
+  let f: PoolFixture;
+  let lp: UserAccounts;
+
+  const rebalance = (referencePrice: BN, maxSlippageBps: number) =>
+    program.methods
+      .rebalancePosition(new BN(TARGET_A.toString()), referencePrice, new BN(maxSlippageBps), FAR_DEADLINE)
+      .accountsPartial({ remove: removeLiquidityAccounts(f, lp) })
+      .signers([lp.user])
+      .rpc();
+
+  beforeEach(async () => {
+    // 1:1 pool; the LP holds only LP tokens
+    ({ f, lp } = await createSeededPool());
+  });
+
+  it("burns LP and buys the shortfall at the reference price", async () => {
+    const lpBefore = await balance(lp.lpToken);
+    const supply = (await getMint(provider.connection, f.lpMint)).supply;
+    const pool = await program.account.pool.fetch(f.pool);
+    const reserveB = BigInt(pool.reserveB.toString());
+
+    await rebalance(Q64, 200);
+
+    expect(await balance(lp.tokenA)).to.equal(TARGET_A);
+    const burned = lpBefore - (await balance(lp.lpToken));
+    expect(burned > 0n).to.equal(true);
+    // Only enough LP to cover the purchase is withdrawn: at 1:1 a share is
+    // worth twice its B side, which covers the B cost of the target (fee,
+    // 1% price impact and the 2% headroom included)
+    const withdrawnValue = (2n * burned * reserveB) / supply;
+    expect(withdrawnValue >= 2n * TARGET_A).to.equal(true);
+    expect(withdrawnValue * 10_000n <= 2n * TARGET_A * 10_500n).to.equal(true);
+    // The withdrawn A side overshoots the target and its excess is sold,
+    // so the wallet keeps the withdrawn value less that swap's fee
+    const heldValue = TARGET_A + (await balance(lp.tokenB));
+    expect(heldValue <= withdrawnValue).to.equal(true);
+    expect(heldValue * 10_000n >= withdrawnValue * 9_950n).to.equal(true);
+  });
+
+  it("buys the shortfall with wallet B without burning LP", async () => {
+    await mintTo(provider.connection, payer, f.mintB, lp.tokenB, payer, 2n * TARGET_A);
+    const lpBefore = await balance(lp.lpToken);
+
+    await rebalance(Q64, 200);
+
+    expect(await balance(lp.tokenA)).to.equal(TARGET_A);
+    expect(await balance(lp.lpToken)).to.equal(lpBefore);
+    // Paid the 1:1 reference plus at most the 2% slippage
+    const spent = 2n * TARGET_A - (await balance(lp.tokenB));
+    expect(spent >= TARGET_A).to.equal(true);
+    expect(spent * 10_000n <= TARGET_A * 10_200n).to.equal(true);
+  });
+
+  it("sells A above the target for B", async () => {
+    await mintTo(provider.connection, payer, f.mintA, lp.tokenA, payer, 2n * TARGET_A);
+    const lpBefore = await balance(lp.lpToken);
+
+    await rebalance(Q64, 200);
+
+    expect(await balance(lp.tokenA)).to.equal(TARGET_A);
+    expect(await balance(lp.lpToken)).to.equal(lpBefore);
+    const excess = TARGET_A;
+    // 1:1 reference less the fee and price impact, within the 2% slippage
+    const received = await balance(lp.tokenB);
+    expect(received < excess).to.equal(true);
+    expect(received * 10_000n >= excess * 9_800n).to.equal(true);
+  });
+
+  it("rejects a pool price away from the reference", async () => {
+    await expectError(rebalance(Q64.muln(2), 200), "PriceOutOfBand");
+    expect(await balance(lp.tokenA)).to.equal(0n);
+  });
+});
@@ -0,0 +1,68 @@
+import { BN } from "@coral-xyz/anchor";
+import { Keypair } from "@solana/web3.js";
+import { expect } from "chai";
+import {
+  PoolFixture,
+  UserAccounts,
+  addLiquidity,
+  balance,
+  createSeededPool,
+  expectError,
+  fundUser,
+  newUser,
+  payer,
+  program,
+  swap,
+} from "./helpers";
+
+describe("max_reserve", () => {
+  const RESERVE = 1_000_000_000n;
+  const AMOUNT = 10_000_000n;
+  let f: PoolFixture;
+  let user: UserAccounts;
+
+  const setMaxReserve = (maxReserve: bigint, authority: Keypair = payer) =>
+    program.methods
+      .setMaxReserve(new BN(maxReserve.toString()))
+      .accountsPartial({ authority: authority.publicKey, pool: f.pool })
+      .signers([authority])
+      .rpc();
+
+  beforeEach(async () => {
+    ({ f } = await createSeededPool(RESERVE, RESERVE));
+    user = await fundUser(f, await newUser(), 2n * AMOUNT, 2n * AMOUNT);
+  });
+
+  it("accepts a deposit landing exactly on the cap", async () => {
+    await setMaxReserve(RESERVE + AMOUNT);
+    await addLiquidity(f, user, AMOUNT, AMOUNT);
+
+    const pool = await program.account.pool.fetch(f.pool);
+    expect(pool.reserveA.toString()).to.equal((RESERVE + AMOUNT).toString());
+    expect(pool.reserveB.toString()).to.equal((RESERVE + AMOUNT).toString());
+  });
+
+  it("rejects deposits and swaps pushing a reserve past the cap", async () => {
+    await setMaxReserve(RESERVE + AMOUNT - 1n);
+
+    await expectError(addLiquidity(f, user, AMOUNT, AMOUNT), "ReserveCapExceeded");
+    await expectError(swap(f, user, AMOUNT, true), "ReserveCapExceeded");
+    expect(await balance(user.tokenA)).to.equal(2n * AMOUNT);
+
+    // Swapping the other way lowers reserve A and stays under the cap
+    await swap(f, user, AMOUNT, false);
+    expect(await balance(user.tokenB)).to.equal(AMOUNT);
+  });
+
+  it("is lifted by a zero cap", async () => {
+    await setMaxReserve(RESERVE);
+    await setMaxReserve(0n);
+    await addLiquidity(f, user, AMOUNT, AMOUNT);
+    expect((await balance(user.lpToken)) > 0n).to.equal(true);
+  });
+
+  it("rejects a non-authority signer", async () => {
+    await expectError(setMaxReserve(RESERVE, await newUser()), "Unauthorized");
+    expect((await program.account.pool.fetch(f.pool)).maxReserve.toNumber()).to.equal(0);
+  });
+});
@@ -0,0 +1,88 @@
+import { BN } from "@coral-xyz/anchor";
+import { TOKEN_PROGRAM_ID, createTransferInstruction, mintTo } from "@solana/spl-token";
+import { expect } from "chai";
+import {
+  PoolFixture,
+  UserAccounts,
+  createSeededPool,
+  emittedEvents,
+  fundUser,
+  newUser,
+  payer,
+  program,
+  provider,
+  swap,
+} from "./helpers";
+
+const reservesSynced = async (signature: string) =>
+  (await emittedEvents(signature)).filter((e) => e.name.toLowerCase() === "reservessynced").map((e) => e.data);
+
+/** Enum variant name as decoded by Anchor, e.g. `{ sync: {} }` -> "sync" */
+const variant = (value: object) => Object.keys(value)[0].toLowerCase();
+
+describe("ReservesSynced", () => {
+  let f: PoolFixture;
+
+  beforeEach(async () => {
+    ({ f } = await createSeededPool());
+  });
+
+  it("reports Sync with unchanged reserves from sync_reserves", async () => {
+    await mintTo(provider.connection, payer, f.mintA, f.vaultA, payer, 5_000n);
+
+    const signature = await program.methods
+      .syncReserves()
+      .accountsPartial({
+        authority: payer.publicKey,
+        pool: f.pool,
+        tokenAVault: f.vaultA,
+        tokenBVault: f.vaultB,
+      })
+      .rpc({ commitment: "confirmed" });
+
+    const [event, ...rest] = await reservesSynced(signature);
+    expect(rest).to.be.empty;
+    expect(variant(event.source)).to.equal("sync");
+    expect(event.newReserveA.eq(event.oldReserveA)).to.be.true;
+    expect(event.newReserveB.eq(event.oldReserveB)).to.be.true;
+    expect(event.excessA.toNumber()).to.equal(5_000);
+  });
+
+  it("reports Donation with the repaid surplus from flash_loan", async () => {
+    const AMOUNT = 1_000_000n;
+    const SURPLUS = 10_000n;
+    const borrower: UserAccounts = await fundUser(f, await newUser(), SURPLUS, 0n);
+
+    // The SPL Token program itself serves as the callback, repaying the vault
+    const repay = createTransferInstruction(borrower.tokenA, f.vaultA, borrower.user.publicKey, AMOUNT + SURPLUS);
+    const signature = await program.methods
+      .flashLoan(new BN(AMOUNT.toString()), Buffer.from(repay.data))
+      .accountsPartial({
+        borrower: borrower.user.publicKey,
+        pool: f.pool,
+        lpMint: f.lpMint,
+        tokenAVault: f.vaultA,
+        tokenBVault: f.vaultB,
+        borrowerToken: borrower.tokenA,
+        mint: f.mintA,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .remainingAccounts([{ pubkey: TOKEN_PROGRAM_ID, isSigner: false, isWritable: false }, ...repay.keys])
+      .signers([borrower.user])
+      .rpc({ commitment: "confirmed" });
+
+    const [event, ...rest] = await reservesSynced(signature);
+    expect(rest).to.be.empty;
+    expect(variant(event.source)).to.equal("donation");
+    expect(BigInt(event.newReserveA.sub(event.oldReserveA).toString())).to.equal(SURPLUS);
+    expect(event.newReserveB.eq(event.oldReserveB)).to.be.true;
+  });
+
+  it("is not emitted by a swap", async () => {
+    const trader = await fundUser(f, await newUser(), 1_000_000n, 0n);
+    const signature = await swap(f, trader, 1_000_000n, true);
+    await provider.connection.confirmTransaction(signature, "confirmed");
+
+    expect(await reservesSynced(signature)).to.be.empty;
+  });
+});
@@ -0,0 +1,80 @@
+import { BN } from "@coral-xyz/anchor";
+import { Transaction } from "@solana/web3.js";
+import { expect } from "chai";
+import {
+  FAR_DEADLINE,
+  PoolFixture,
+  UserAccounts,
+  addLiquidityAccounts,
+  balance,
+  createSeededPool,
+  expectError,
+  fundUser,
+  newUser,
+  program,
+  provider,
+  removeLiquidityAccounts,
+} from "./helpers";
+
+describe("instruction return data", () => {
+  const AMOUNT = 10_000_000n;
+  let f: PoolFixture;
+  let lp: UserAccounts;
+
+  /** Simulate `tx` signed by `u` and return the program's return data */
+  async function simulateReturn(tx: Transaction, u: UserAccounts): Promise<Buffer> {
+    tx.feePayer = u.user.publicKey;
+    tx.recentBlockhash = (await provider.connection.getLatestBlockhash()).blockhash;
+    const { value } = await provider.connection.simulateTransaction(tx, [u.user]);
+    expect(value.err).to.be.null;
+    expect(value.returnData?.programId).to.equal(program.programId.toBase58());
+    return Buffer.from(value.returnData!.data[0], "base64");
+  }
+
+  const removeIx = (liquidity: bigint, minAmountA = 0n) =>
+    program.methods
+      .removeLiquidity(new BN(liquidity.toString()), new BN(minAmountA.toString()), new BN(0), new BN(0), new BN(0), FAR_DEADLINE)
+      .accountsPartial(removeLiquidityAccounts(f, lp))
+      .signers([lp.user]);
+
+  beforeEach(async () => {
+    ({ f, lp } = await createSeededPool());
+  });
+
+  it("returns the LP minted by add_liquidity", async () => {
+    const depositor = await fundUser(f, await newUser(), AMOUNT, AMOUNT);
+    const method = program.methods
+      .addLiquidity(new BN(AMOUNT.toString()), new BN(AMOUNT.toString()), new BN(0), FAR_DEADLINE)
+      .accountsPartial(addLiquidityAccounts(f, depositor))
+      .signers([depositor.user]);
+
+    const data = await simulateReturn(await method.transaction(), depositor);
+    expect(data.length).to.equal(8);
+    const liquidity = data.readBigUInt64LE(0);
+    expect(liquidity > 0n).to.equal(true);
+
+    await method.rpc();
+    expect(await balance(depositor.lpToken)).to.equal(liquidity);
+  });
+
+  it("returns the amounts paid out by remove_liquidity", async () => {
+    const liquidity = (await balance(lp.lpToken)) / 4n;
+    const data = await simulateReturn(await removeIx(liquidity).transaction(), lp);
+    expect(data.length).to.equal(16);
+    const amountA = data.readBigUInt64LE(0);
+    const amountB = data.readBigUInt64LE(8);
+    expect(amountA > 0n).to.equal(true);
+
+    await removeIx(liquidity).rpc();
+    expect(await balance(lp.tokenA)).to.equal(amountA);
+    expect(await balance(lp.tokenB)).to.equal(amountB);
+  });
+
+  it("rejects a minimum above the amount it would return", async () => {
+    const liquidity = (await balance(lp.lpToken)) / 4n;
+    const amountA = (await simulateReturn(await removeIx(liquidity).transaction(), lp)).readBigUInt64LE(0);
+
+    await expectError(removeIx(liquidity, amountA + 1n).rpc(), "SlippageExceeded");
+    expect(await balance(lp.tokenA)).to.equal(0n);
+  });
+});
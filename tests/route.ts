@@ -0,0 +1,81 @@
+import { BN } from "@coral-xyz/anchor";
+import { Keypair, PublicKey } from "@solana/web3.js";
+import { createAccount, createMint, mintTo } from "@solana/spl-token";
+import { expect } from "chai";
+import {
+  FAR_DEADLINE,
+  PoolFixture,
+  addLiquidity,
+  balance,
+  createPool,
+  fundUser,
+  lockedLpAccount,
+  newUser,
+  payer,
+  program,
+  provider,
+  sortMints,
+  swapRemainingAccounts,
+} from "./helpers";
+
+describe("route_swap", () => {
+  const SEED = 1_000_000_000n;
+  let x: PublicKey;
+  let y: PublicKey;
+  let z: PublicKey;
+  let xy: PoolFixture;
+  let yz: PoolFixture;
+
+  const seed = async (f: PoolFixture) => {
+    const lp = await fundUser(f, await newUser(), SEED, SEED);
+    await addLiquidity(f, lp, SEED, SEED, { lockedLpToken: await lockedLpAccount(f) });
+  };
+
+  before(async () => {
+    const mint = () => createMint(provider.connection, payer, payer.publicKey, null, 6);
+    [x, y, z] = [await mint(), await mint(), await mint()];
+    xy = await createPool(sortMints(x, y));
+    yz = await createPool(sortMints(y, z));
+    await seed(xy);
+    await seed(yz);
+
+    // One whole token of dust threshold on the second pool
+    await program.methods
+      .setMinSwapAmount(new BN(1_000_000))
+      .accountsPartial({ authority: payer.publicKey, pool: yz.pool })
+      .rpc();
+  });
+
+  it("routes X -> Y -> Z when the intermediate amount is below the second pool's minimum", async () => {
+    const trader = await newUser();
+    const conn = provider.connection;
+    const account = async (mint: PublicKey) => createAccount(conn, payer, mint, trader.publicKey, Keypair.generate());
+    const [tx, ty, tz] = [await account(x), await account(y), await account(z)];
+    await mintTo(conn, payer, x, tx, payer, 200_000n);
+
+    const byMint = (mint: PublicKey) => (mint.equals(x) ? tx : mint.equals(y) ? ty : tz);
+    const hop = (f: PoolFixture) =>
+      swapRemainingAccounts(f, {
+        user: trader,
+        tokenA: byMint(f.mintA),
+        tokenB: byMint(f.mintB),
+        lpToken: PublicKey.default,
+      });
+
+    await program.methods
+      .routeSwap(
+        new BN(200_000),
+        new BN(1),
+        [{ aToB: xy.mintA.equals(x) }, { aToB: yz.mintA.equals(y) }],
+        FAR_DEADLINE,
+      )
+      .accountsPartial({ user: trader.publicKey })
+      .remainingAccounts([...hop(xy), ...hop(yz)])
+      .signers([trader])
+      .rpc();
+
+    expect(await balance(tx)).to.equal(0n);
+    expect(await balance(ty)).to.equal(0n);
+    expect((await balance(tz)) > 0n).to.equal(true);
+  });
+});
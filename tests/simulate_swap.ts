@@ -0,0 +1,71 @@
+import { BN } from "@coral-xyz/anchor";
+import { mintTo } from "@solana/spl-token";
+import { expect } from "chai";
+import {
+  PoolFixture,
+  UserAccounts,
+  balance,
+  createSeededPool,
+  expectError,
+  fundUser,
+  newUser,
+  payer,
+  program,
+  provider,
+  sleep,
+  swap,
+} from "./helpers";
+
+describe("simulate_swap", () => {
+  const AMOUNT = 10_000_000n;
+  const SURCHARGE_BPS = 100;
+  let f: PoolFixture;
+  let trader: UserAccounts;
+
+  const simulate = (vaultA = f.vaultA, vaultB = f.vaultB) =>
+    program.methods
+      .simulateSwap(new BN(AMOUNT.toString()), true)
+      .accountsPartial({ pool: f.pool, tokenAVault: vaultA, tokenBVault: vaultB, tokenAMint: f.mintA, tokenBMint: f.mintB })
+      .view();
+
+  const quote = () => program.methods.quoteSwap(new BN(AMOUNT.toString()), true).accountsPartial({ pool: f.pool }).view();
+
+  beforeEach(async () => {
+    ({ f } = await createSeededPool());
+    trader = await fundUser(f, await newUser(), AMOUNT, 0n);
+  });
+
+  it("matches the executed swap where quote_swap does not", async () => {
+    // Let the reserves go stale so the swap pays the surcharge
+    await program.methods
+      .setStalenessSurcharge(new BN(1), SURCHARGE_BPS)
+      .accountsPartial({ authority: payer.publicKey, pool: f.pool })
+      .rpc();
+    await sleep(2_000);
+
+    const simulated = await simulate();
+    const quoted = await quote();
+    expect(simulated.amountInReceived.toString()).to.equal(AMOUNT.toString());
+    expect(simulated.amountOut.lt(quoted.amountOut)).to.be.true;
+    const surcharge = (AMOUNT * BigInt(SURCHARGE_BPS)) / 10_000n;
+    expect(simulated.feeAmount.gt(quoted.feeAmount)).to.be.true;
+    expect(BigInt(simulated.feeAmount.toString()) >= surcharge).to.equal(true);
+    expect(simulated.inSync).to.equal(true);
+
+    await swap(f, trader, AMOUNT, true);
+    expect(await balance(trader.tokenB)).to.equal(BigInt(simulated.amountOut.toString()));
+  });
+
+  it("reports a donated vault as out of sync without pricing the donation", async () => {
+    const before = await simulate();
+    await mintTo(provider.connection, payer, f.mintB, f.vaultB, payer, 50_000_000n);
+
+    const after = await simulate();
+    expect(after.inSync).to.equal(false);
+    expect(after.amountOut.eq(before.amountOut)).to.be.true;
+  });
+
+  it("rejects vaults that are not the pool's", async () => {
+    await expectError(simulate(f.vaultB, f.vaultA), "InvalidVault");
+  });
+});
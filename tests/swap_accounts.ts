@@ -0,0 +1,47 @@
+import { expect } from "chai";
+import {
+  PoolFixture,
+  UserAccounts,
+  balance,
+  createSeededPool,
+  expectError,
+  fundUser,
+  newUser,
+  swap,
+} from "./helpers";
+
+describe("swap accounts", () => {
+  const AMOUNT = 10_000_000n;
+  let f: PoolFixture;
+  let trader: UserAccounts;
+  let recipient: UserAccounts;
+
+  beforeEach(async () => {
+    ({ f } = await createSeededPool());
+    trader = await fundUser(f, await newUser(), AMOUNT, AMOUNT);
+    recipient = await fundUser(f, await newUser(), AMOUNT, AMOUNT);
+  });
+
+  it("pays the output to an account the signer does not own", async () => {
+    await swap(f, trader, AMOUNT, true, { userTokenB: recipient.tokenB });
+    expect(await balance(trader.tokenA)).to.equal(0n);
+    expect(await balance(trader.tokenB)).to.equal(AMOUNT);
+    expect(await balance(recipient.tokenB)).to.be.greaterThan(AMOUNT);
+
+    await swap(f, trader, AMOUNT, false, { userTokenA: recipient.tokenA });
+    expect(await balance(trader.tokenB)).to.equal(0n);
+    expect(await balance(recipient.tokenA)).to.be.greaterThan(AMOUNT);
+  });
+
+  it("rejects an input account the signer does not own", async () => {
+    await expectError(swap(f, trader, AMOUNT, true, { userTokenA: recipient.tokenA }), "Unauthorized");
+    await expectError(swap(f, trader, AMOUNT, false, { userTokenB: recipient.tokenB }), "Unauthorized");
+    expect(await balance(recipient.tokenA)).to.equal(AMOUNT);
+    expect(await balance(recipient.tokenB)).to.equal(AMOUNT);
+  });
+
+  it("rejects one vault passed for both sides", async () => {
+    await expectError(swap(f, trader, AMOUNT, true, { tokenBVault: f.vaultA }), "InvalidVault");
+    await expectError(swap(f, trader, AMOUNT, true, { tokenAVault: f.vaultB }), "InvalidVault");
+  });
+});
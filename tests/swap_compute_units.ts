@@ -0,0 +1,38 @@
+import { BN } from "@coral-xyz/anchor";
+import { expect } from "chai";
+import {
+  FAR_DEADLINE,
+  createSeededPool,
+  fundUser,
+  newUser,
+  program,
+  provider,
+  swapAccounts,
+} from "./helpers";
+
+// Ceiling for a plain swap (no oracle, history, checkpoints or fee tier),
+// leaving integrators most of the 200k default per-instruction budget.
+// Raise it deliberately, with the measured figure, when a change to the
+// swap path needs more.
+const SWAP_CU_BUDGET = 60_000;
+
+describe("swap compute units", () => {
+  it("stays within the swap CU budget in both directions", async () => {
+    const { f } = await createSeededPool();
+    const trader = await fundUser(f, await newUser(), 10_000_000n, 10_000_000n);
+
+    for (const aToB of [true, false]) {
+      const tx = await program.methods
+        .swap(new BN(1_000_000), new BN(0), aToB, new BN(0), new BN(0), FAR_DEADLINE, new BN(0))
+        .accountsPartial(swapAccounts(f, trader))
+        .transaction();
+      tx.feePayer = trader.user.publicKey;
+      tx.recentBlockhash = (await provider.connection.getLatestBlockhash()).blockhash;
+
+      const { value } = await provider.connection.simulateTransaction(tx, [trader.user]);
+      expect(value.err).to.be.null;
+      expect(value.unitsConsumed).to.be.greaterThan(0);
+      expect(value.unitsConsumed).to.be.at.most(SWAP_CU_BUDGET);
+    }
+  });
+});
@@ -0,0 +1,82 @@
+import { BN } from "@coral-xyz/anchor";
+import { PublicKey } from "@solana/web3.js";
+import { expect } from "chai";
+import {
+  PoolFixture,
+  UserAccounts,
+  balance,
+  createSeededPool,
+  expectError,
+  fundUser,
+  newUser,
+  payer,
+  pda,
+  program,
+  provider,
+  swap,
+} from "./helpers";
+
+describe("swap cooldown", () => {
+  const COOLDOWN_SLOTS = 4;
+  const AMOUNT = 1_000_000n;
+  let f: PoolFixture;
+
+  const userSwapState = (user: PublicKey) => pda([Buffer.from("user_swap"), f.pool.toBuffer(), user.toBuffer()]);
+
+  async function trader(): Promise<UserAccounts> {
+    const u = await fundUser(f, await newUser(), 3n * AMOUNT, 0n);
+    await program.methods
+      .openUserSwapState()
+      .accountsPartial({ user: u.user.publicKey, pool: f.pool, userSwapState: userSwapState(u.user.publicKey) })
+      .signers([u.user])
+      .rpc();
+    return u;
+  }
+
+  const swapWithState = (u: UserAccounts) => swap(f, u, AMOUNT, true, { userSwapState: userSwapState(u.user.publicKey) });
+
+  async function waitSlots(slots: number): Promise<void> {
+    const target = (await provider.connection.getSlot()) + slots;
+    while ((await provider.connection.getSlot()) < target) {
+      await new Promise((r) => setTimeout(r, 200));
+    }
+  }
+
+  beforeEach(async () => {
+    ({ f } = await createSeededPool());
+    await program.methods
+      .setSwapCooldown(new BN(COOLDOWN_SLOTS))
+      .accountsPartial({ authority: payer.publicKey, pool: f.pool })
+      .rpc();
+  });
+
+  it("blocks a back-to-back swap and lets it through after the cooldown", async () => {
+    const u = await trader();
+    await swapWithState(u);
+    const record = await program.account.userSwapState.fetch(userSwapState(u.user.publicKey));
+    expect(record.lastSwapSlot.toNumber()).to.be.greaterThan(0);
+
+    await expectError(swapWithState(u), "SwapCooldown");
+    expect(await balance(u.tokenA)).to.equal(2n * AMOUNT);
+
+    // Other traders keep their own clocks
+    const other = await trader();
+    await swapWithState(other);
+
+    await waitSlots(COOLDOWN_SLOTS + 1);
+    await swapWithState(u);
+    expect(await balance(u.tokenA)).to.equal(AMOUNT);
+  });
+
+  it("requires the swap record while a cooldown is set, and not once it is lifted", async () => {
+    const u = await fundUser(f, await newUser(), AMOUNT, 0n);
+    await expectError(swap(f, u, AMOUNT, true), "UserSwapStateRequired");
+
+    await program.methods
+      .setSwapCooldown(new BN(0))
+      .accountsPartial({ authority: payer.publicKey, pool: f.pool })
+      .rpc();
+    await swap(f, u, AMOUNT, true);
+    expect(await balance(u.tokenA)).to.equal(0n);
+  });
+});
@@ -0,0 +1,129 @@
+import { BN } from "@coral-xyz/anchor";
+import { Keypair, PublicKey, SystemProgram, Transaction } from "@solana/web3.js";
+import {
+  NATIVE_MINT,
+  TOKEN_PROGRAM_ID,
+  createAccount,
+  createMint,
+  createWrappedNativeAccount,
+  mintTo,
+} from "@solana/spl-token";
+import { expect } from "chai";
+import {
+  FAR_DEADLINE,
+  PoolFixture,
+  addLiquidity,
+  balance,
+  createPool,
+  emittedEvents,
+  lockedLpAccount,
+  newUser,
+  payer,
+  pda,
+  program,
+  provider,
+  sortMints,
+  swapRemainingAccounts,
+} from "./helpers";
+
+describe("swap_sol", () => {
+  const SEED = 1_000_000_000n;
+  let f: PoolFixture;
+  let trader: Keypair;
+  let traderToken: PublicKey;
+  let wsol: PublicKey;
+
+  before(async () => {
+    const other = await createMint(provider.connection, payer, payer.publicKey, null, 9);
+    f = await createPool(sortMints(NATIVE_MINT, other));
+
+    const lp = await newUser();
+    const conn = provider.connection;
+    const lpSol = await createWrappedNativeAccount(conn, payer, lp.publicKey, Number(SEED), Keypair.generate());
+    const lpOther = await createAccount(conn, payer, other, lp.publicKey, Keypair.generate());
+    await mintTo(conn, payer, other, lpOther, payer, SEED);
+    const lpToken = await createAccount(conn, payer, f.lpMint, lp.publicKey, Keypair.generate());
+    const solIsA = f.mintA.equals(NATIVE_MINT);
+    const locked = await lockedLpAccount(f);
+    await addLiquidity(
+      f,
+      {
+        user: lp,
+        tokenA: solIsA ? lpSol : lpOther,
+        tokenB: solIsA ? lpOther : lpSol,
+        lpToken,
+      },
+      SEED,
+      SEED,
+      { lockedLpToken: locked },
+    );
+
+    trader = await newUser();
+    traderToken = await createAccount(conn, payer, other, trader.publicKey, Keypair.generate());
+    wsol = pda([Buffer.from("wsol"), trader.publicKey.toBuffer()]);
+  });
+
+  const swapSol = (amount: number, sellingSol: boolean) => {
+    const solIsA = f.mintA.equals(NATIVE_MINT);
+    const remaining = swapRemainingAccounts(f, {
+      user: trader,
+      tokenA: solIsA ? wsol : traderToken,
+      tokenB: solIsA ? traderToken : wsol,
+      lpToken: PublicKey.default,
+    });
+    return program.methods
+      .swapSol(new BN(amount), new BN(0), solIsA === sellingSol, FAR_DEADLINE)
+      .accountsPartial({
+        user: trader.publicKey,
+        wsolAccount: wsol,
+        nativeMint: NATIVE_MINT,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        systemProgram: SystemProgram.programId,
+      })
+      .remainingAccounts(remaining)
+      .signers([trader])
+      .rpc();
+  };
+  const sellSol = (amount: number) => swapSol(amount, true);
+
+  it("sells native SOL and closes the temporary account", async () => {
+    await sellSol(10_000_000);
+
+    expect((await balance(traderToken)) > 0n).to.equal(true);
+    expect(await provider.connection.getAccountInfo(wsol)).to.equal(null);
+  });
+
+  it("buys native SOL, crediting the lamports and closing the temporary account", async () => {
+    const tokens = await balance(traderToken);
+    expect(tokens > 0n).to.equal(true);
+    const lamportsBefore = await provider.connection.getBalance(trader.publicKey);
+
+    const sig = await swapSol(Number(tokens), false);
+
+    expect(await balance(traderToken)).to.equal(0n);
+    expect(await provider.connection.getAccountInfo(wsol)).to.equal(null);
+    const swapped = (await emittedEvents(sig)).find((e) => e.name.toLowerCase() === "swapexecuted");
+    expect(swapped).to.exist;
+    const bought = swapped!.data.amountOut.toNumber();
+    expect(bought).to.be.greaterThan(0);
+    // The provider wallet pays the fee and the wSOL rent is refunded on
+    // close, so the trader gains exactly the SOL bought
+    expect(await provider.connection.getBalance(trader.publicKey)).to.equal(lamportsBefore + bought);
+  });
+
+  it("still works when the wSOL PDA was pre-funded", async () => {
+    const griefer = await newUser();
+    await provider.sendAndConfirm(
+      new Transaction().add(
+        SystemProgram.transfer({ fromPubkey: griefer.publicKey, toPubkey: wsol, lamports: 5_000_000 }),
+      ),
+      [griefer],
+    );
+    const before = await balance(traderToken);
+
+    await sellSol(10_000_000);
+
+    expect((await balance(traderToken)) > before).to.equal(true);
+    expect(await provider.connection.getAccountInfo(wsol)).to.equal(null);
+  });
+});
@@ -0,0 +1,58 @@
+import { BN } from "@coral-xyz/anchor";
+import { expect } from "chai";
+import {
+  FAR_DEADLINE,
+  PoolFixture,
+  UserAccounts,
+  balance,
+  createSeededPool,
+  expectError,
+  fundUser,
+  newUser,
+  program,
+  swapAccounts,
+} from "./helpers";
+
+describe("swap_split", () => {
+  const RESERVE = 1_000_000_000n;
+  const AMOUNT = 200_000_000n;
+  let f: PoolFixture;
+  let trader: UserAccounts;
+
+  const swapSplit = (chunks: number, maxPriceImpactBps: bigint) =>
+    program.methods
+      .swapSplit(new BN(AMOUNT.toString()), new BN(0), true, chunks, new BN(maxPriceImpactBps.toString()), FAR_DEADLINE)
+      .accountsPartial(swapAccounts(f, trader))
+      .signers([trader.user])
+      .rpc();
+
+  // A 20% trade into the fresh 1:1 pool, executed as one swap
+  const withFee = AMOUNT - (AMOUNT * 30n) / 10_000n;
+  const amountOut = (withFee * RESERVE) / (RESERVE + withFee);
+  const impactBps = ((AMOUNT - amountOut) * 10_000n) / AMOUNT;
+
+  beforeEach(async () => {
+    ({ f } = await createSeededPool(RESERVE, RESERVE));
+    trader = await fundUser(f, await newUser(), AMOUNT, 0n);
+  });
+
+  it("clears every chunk under the cap and pays out the single-swap amount", async () => {
+    await swapSplit(4, impactBps + 5n);
+    expect(await balance(trader.tokenA)).to.equal(0n);
+    expect(await balance(trader.tokenB)).to.equal(amountOut);
+  });
+
+  it("aborts at the chunk that breaches a tight cap", async () => {
+    // The first chunk (5% of the pool) clears about 5% impact; the later
+    // ones push the cumulative impact past 10%
+    await expectError(swapSplit(4, 1_000n), "PriceImpactTooHigh");
+    expect(await balance(trader.tokenA)).to.equal(AMOUNT);
+    expect((await program.account.pool.fetch(f.pool)).reserveB.toString()).to.equal(RESERVE.toString());
+  });
+
+  it("rejects a chunk count outside 1..=MAX_SWAP_SPLIT_CHUNKS", async () => {
+    await expectError(swapSplit(0, 0n), "InvalidSwapSplit");
+    await expectError(swapSplit(17, 0n), "InvalidSwapSplit");
+    expect(await balance(trader.tokenA)).to.equal(AMOUNT);
+  });
+});
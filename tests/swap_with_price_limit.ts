@@ -0,0 +1,62 @@
+import { BN } from "@coral-xyz/anchor";
+import { expect } from "chai";
+import {
+  FAR_DEADLINE,
+  PoolFixture,
+  UserAccounts,
+  balance,
+  createSeededPool,
+  expectError,
+  fundUser,
+  newUser,
+  program,
+  swapAccounts,
+} from "./helpers";
+
+describe("swap_with_price_limit", () => {
+  const AMOUNT = 10_000_000n;
+  const Q64 = 1n << 64n;
+  let f: PoolFixture;
+  let trader: UserAccounts;
+
+  const swapWithLimit = (priceLimit: bigint, aToB: boolean) =>
+    program.methods
+      .swapWithPriceLimit(new BN(AMOUNT.toString()), new BN(priceLimit.toString()), aToB, FAR_DEADLINE)
+      .accountsPartial(swapAccounts(f, trader))
+      .signers([trader.user])
+      .rpc();
+
+  const quoteOut = async (aToB: boolean) => {
+    const quote = await program.methods.quoteSwap(new BN(AMOUNT.toString()), aToB).accountsPartial({ pool: f.pool }).view();
+    return BigInt(quote.amountOut.toString());
+  };
+
+  beforeEach(async () => {
+    ({ f } = await createSeededPool());
+    trader = await fundUser(f, await newUser(), AMOUNT, AMOUNT);
+  });
+
+  it("executes when the price is exactly at the limit", async () => {
+    // Selling A: a floor on B received per A
+    const outB = await quoteOut(true);
+    await swapWithLimit((outB * Q64) / AMOUNT, true);
+    expect(await balance(trader.tokenB)).to.equal(AMOUNT + outB);
+
+    // Selling B: a ceiling on B paid per A
+    const outA = await quoteOut(false);
+    await swapWithLimit((AMOUNT * Q64 + outA - 1n) / outA, false);
+    expect(await balance(trader.tokenA)).to.equal(outA);
+  });
+
+  it("rejects a limit the execution price cannot reach", async () => {
+    // Fees alone keep a 1:1 pool from filling at its spot price
+    await expectError(swapWithLimit(Q64, true), "SlippageExceeded");
+    await expectError(swapWithLimit(Q64, false), "SlippageExceeded");
+    expect(await balance(trader.tokenA)).to.equal(AMOUNT);
+    expect(await balance(trader.tokenB)).to.equal(AMOUNT);
+  });
+
+  it("rejects a zero limit", async () => {
+    await expectError(swapWithLimit(0n, true), "InvalidPriceLimit");
+  });
+});
@@ -0,0 +1,81 @@
+import { Keypair } from "@solana/web3.js";
+import { mintTo } from "@solana/spl-token";
+import { expect } from "chai";
+import {
+  PoolFixture,
+  UserAccounts,
+  balance,
+  createSeededPool,
+  expectError,
+  fundUser,
+  newUser,
+  payer,
+  program,
+  provider,
+  swap,
+} from "./helpers";
+
+describe("strict reserves and sync_reserves", () => {
+  const DONATION = 5_000n;
+  const AMOUNT = 1_000_000n;
+  let f: PoolFixture;
+  let trader: UserAccounts;
+
+  const setStrictReserves = (strict: boolean) =>
+    program.methods
+      .setStrictReserves(strict)
+      .accountsPartial({ authority: payer.publicKey, pool: f.pool })
+      .rpc();
+
+  const syncReserves = (authority: Keypair = payer) =>
+    program.methods
+      .syncReserves()
+      .accountsPartial({
+        authority: authority.publicKey,
+        pool: f.pool,
+        tokenAVault: f.vaultA,
+        tokenBVault: f.vaultB,
+      })
+      .signers([authority])
+      .rpc();
+
+  beforeEach(async () => {
+    ({ f } = await createSeededPool());
+    trader = await fundUser(f, await newUser(), AMOUNT, 0n);
+    await setStrictReserves(true);
+  });
+
+  it("rejects swaps on a donated vault until synced, booking the donation as protocol fees", async () => {
+    await mintTo(provider.connection, payer, f.mintA, f.vaultA, payer, DONATION);
+    await expectError(swap(f, trader, AMOUNT, true), "VaultBalanceMismatch");
+    expect(await balance(trader.tokenA)).to.equal(AMOUNT);
+
+    const before = await program.account.pool.fetch(f.pool);
+    await syncReserves();
+    const after = await program.account.pool.fetch(f.pool);
+    // The price is untouched: the donation belongs to the protocol, not LPs
+    expect(after.reserveA.eq(before.reserveA)).to.be.true;
+    expect(after.reserveB.eq(before.reserveB)).to.be.true;
+    expect(BigInt(after.protocolFeesA.sub(before.protocolFeesA).toString())).to.equal(DONATION);
+    expect(after.protocolFeesB.eq(before.protocolFeesB)).to.be.true;
+
+    await swap(f, trader, AMOUNT, true);
+    expect(await balance(trader.tokenA)).to.equal(0n);
+  });
+
+  it("lets swaps through a drifted vault when strict mode is off", async () => {
+    await mintTo(provider.connection, payer, f.mintA, f.vaultA, payer, DONATION);
+    await setStrictReserves(false);
+
+    await swap(f, trader, AMOUNT, true);
+    expect(await balance(trader.tokenA)).to.equal(0n);
+  });
+
+  it("rejects a non-authority sync", async () => {
+    await mintTo(provider.connection, payer, f.mintA, f.vaultA, payer, DONATION);
+    const before = await program.account.pool.fetch(f.pool);
+
+    await expectError(syncReserves(await newUser()), "Unauthorized");
+    expect((await program.account.pool.fetch(f.pool)).protocolFeesA.eq(before.protocolFeesA)).to.be.true;
+  });
+});
@@ -0,0 +1,99 @@
+import { BN } from "@coral-xyz/anchor";
+import { Keypair, PublicKey, SYSVAR_RENT_PUBKEY, SystemProgram } from "@solana/web3.js";
+import { TOKEN_2022_PROGRAM_ID, TOKEN_PROGRAM_ID, createAccount, createMint, mintTo } from "@solana/spl-token";
+import { expect } from "chai";
+import {
+  CURVE_CONSTANT_PRODUCT,
+  FEE_DENOMINATOR,
+  FEE_NUMERATOR,
+  PoolFixture,
+  UserAccounts,
+  addLiquidity,
+  balance,
+  expectError,
+  lockedLpAccount,
+  newUser,
+  payer,
+  poolAddresses,
+  program,
+  provider,
+  sortMints,
+  swap,
+} from "./helpers";
+
+describe("Token-2022 mints", () => {
+  const SEED = 1_000_000_000n;
+  let f: PoolFixture;
+  let programA: PublicKey;
+  let programB: PublicKey;
+  let lp: UserAccounts;
+
+  const programs = () => ({ tokenAProgram: programA, tokenBProgram: programB });
+
+  before(async () => {
+    // One Token-2022 mint paired with a classic SPL mint
+    const conn = provider.connection;
+    const mint2022 = await createMint(conn, payer, payer.publicKey, null, 6, Keypair.generate(), undefined, TOKEN_2022_PROGRAM_ID);
+    const mintSpl = await createMint(conn, payer, payer.publicKey, null, 6);
+    const [mintA, mintB] = sortMints(mint2022, mintSpl);
+    const aIs2022 = mintA.equals(mint2022);
+    programA = aIs2022 ? TOKEN_2022_PROGRAM_ID : TOKEN_PROGRAM_ID;
+    programB = aIs2022 ? TOKEN_PROGRAM_ID : TOKEN_2022_PROGRAM_ID;
+
+    f = poolAddresses(mintA, mintB);
+    await program.methods
+      .initializePool(FEE_NUMERATOR, FEE_DENOMINATOR, CURVE_CONSTANT_PRODUCT, new BN(0), new BN(0), 9)
+      .accountsPartial({
+        authority: payer.publicKey,
+        tokenAMint: mintA,
+        tokenBMint: mintB,
+        pool: f.pool,
+        tokenAVault: f.vaultA,
+        tokenBVault: f.vaultB,
+        lpMint: f.lpMint,
+        lpMintAuthority: f.lpMintAuthority,
+        ...programs(),
+        tokenProgram: TOKEN_PROGRAM_ID,
+        systemProgram: SystemProgram.programId,
+        rent: SYSVAR_RENT_PUBKEY,
+      })
+      .rpc();
+
+    lp = await fund(await newUser(), SEED, SEED);
+    await addLiquidity(f, lp, SEED, SEED, { lockedLpToken: await lockedLpAccount(f), ...programs() });
+  });
+
+  async function fund(user: Keypair, amountA: bigint, amountB: bigint): Promise<UserAccounts> {
+    const conn = provider.connection;
+    const tokenA = await createAccount(conn, payer, f.mintA, user.publicKey, Keypair.generate(), undefined, programA);
+    const tokenB = await createAccount(conn, payer, f.mintB, user.publicKey, Keypair.generate(), undefined, programB);
+    const lpToken = await createAccount(conn, payer, f.lpMint, user.publicKey, Keypair.generate());
+    if (amountA > 0n) await mintTo(conn, payer, f.mintA, tokenA, payer, amountA, [], undefined, programA);
+    if (amountB > 0n) await mintTo(conn, payer, f.mintB, tokenB, payer, amountB, [], undefined, programB);
+    return { user, tokenA, tokenB, lpToken };
+  }
+
+  it("records each mint's token program and trades through both", async () => {
+    const pool = await program.account.pool.fetch(f.pool);
+    expect(pool.tokenAProgram.equals(programA)).to.be.true;
+    expect(pool.tokenBProgram.equals(programB)).to.be.true;
+    expect(pool.reserveA.toString()).to.equal(SEED.toString());
+
+    const trader = await fund(await newUser(), 1_000_000n, 0n);
+    await swap(f, trader, 1_000_000n, true, programs());
+
+    expect(await balance(trader.tokenA)).to.equal(0n);
+    const received = await balance(trader.tokenB);
+    expect(received > 0n).to.equal(true);
+    expect(BigInt((await program.account.pool.fetch(f.pool)).reserveB.toString())).to.equal(SEED - received);
+  });
+
+  it("rejects the wrong token program for a mint", async () => {
+    const trader = await fund(await newUser(), 1_000_000n, 0n);
+    await expectError(
+      swap(f, trader, 1_000_000n, true, { tokenAProgram: programB, tokenBProgram: programA }),
+      "InvalidTokenProgram",
+    );
+    expect(await balance(trader.tokenA)).to.equal(1_000_000n);
+  });
+});
@@ -0,0 +1,88 @@
+import { expect } from "chai";
+import {
+  PoolFixture,
+  UserAccounts,
+  addLiquidity,
+  balance,
+  createSeededPool,
+  expectError,
+  fundUser,
+  newUser,
+  payer,
+  program,
+  removeLiquidity,
+  swap,
+} from "./helpers";
+
+// Mirrors the PAUSE_* constants
+const PAUSE_SWAP_A_IN = 1 << 0;
+const PAUSE_SWAP_B_IN = 1 << 1;
+const PAUSE_DEPOSIT_A = 1 << 2;
+
+describe("per-token pause", () => {
+  const AMOUNT = 10_000_000n;
+  let f: PoolFixture;
+  let lp: UserAccounts;
+  let trader: UserAccounts;
+
+  const setTokenPause = (flags: number, authority = payer) =>
+    program.methods
+      .setTokenPause(flags)
+      .accountsPartial({ authority: authority.publicKey, pool: f.pool })
+      .signers([authority])
+      .rpc();
+
+  beforeEach(async () => {
+    ({ f, lp } = await createSeededPool());
+    trader = await fundUser(f, await newUser(), AMOUNT, AMOUNT);
+  });
+
+  it("blocks deposits but not swaps or withdrawals while deposits of A are paused", async () => {
+    await setTokenPause(PAUSE_DEPOSIT_A);
+    expect((await program.account.pool.fetch(f.pool)).pauseFlags).to.equal(PAUSE_DEPOSIT_A);
+
+    await expectError(addLiquidity(f, trader, AMOUNT, AMOUNT), "TokenPaused");
+
+    await swap(f, trader, AMOUNT, true);
+    await swap(f, trader, AMOUNT, false);
+    const lpBefore = await balance(lp.lpToken);
+    await removeLiquidity(f, lp, lpBefore / 2n);
+    expect(await balance(lp.lpToken)).to.equal(lpBefore - lpBefore / 2n);
+  });
+
+  it("pauses only the direction selling B", async () => {
+    await setTokenPause(PAUSE_SWAP_B_IN);
+
+    await expectError(swap(f, trader, AMOUNT, false), "TokenPaused");
+    expect(await balance(trader.tokenB)).to.equal(AMOUNT);
+
+    await swap(f, trader, AMOUNT, true);
+    expect(await balance(trader.tokenA)).to.equal(0n);
+    expect((await balance(trader.tokenB)) > AMOUNT).to.equal(true);
+
+    // Deposits are unaffected by a swap pause
+    const depositor = await fundUser(f, await newUser(), AMOUNT, AMOUNT);
+    await addLiquidity(f, depositor, AMOUNT, AMOUNT);
+    expect((await balance(depositor.lpToken)) > 0n).to.equal(true);
+  });
+
+  it("pauses both directions when both swap flags are set, and clearing resumes them", async () => {
+    await setTokenPause(PAUSE_SWAP_A_IN | PAUSE_SWAP_B_IN);
+    await expectError(swap(f, trader, AMOUNT, true), "TokenPaused");
+    await expectError(swap(f, trader, AMOUNT, false), "TokenPaused");
+
+    await setTokenPause(0);
+    await swap(f, trader, AMOUNT, true);
+    expect(await balance(trader.tokenA)).to.equal(0n);
+  });
+
+  it("rejects unknown flags", async () => {
+    await expectError(setTokenPause(1 << 4), "InvalidPauseFlags");
+    expect((await program.account.pool.fetch(f.pool)).pauseFlags).to.equal(0);
+  });
+
+  it("rejects a non-authority signer", async () => {
+    await expectError(setTokenPause(PAUSE_SWAP_A_IN, await newUser()), "Unauthorized");
+    expect((await program.account.pool.fetch(f.pool)).pauseFlags).to.equal(0);
+  });
+});
@@ -0,0 +1,173 @@
+import { BN } from "@coral-xyz/anchor";
+import {
+  Keypair,
+  PublicKey,
+  SYSVAR_RENT_PUBKEY,
+  SystemProgram,
+  Transaction,
+  sendAndConfirmTransaction,
+} from "@solana/web3.js";
+import {
+  ExtensionType,
+  TOKEN_2022_PROGRAM_ID,
+  TOKEN_PROGRAM_ID,
+  createAccount,
+  createInitializeMintInstruction,
+  createInitializeTransferFeeConfigInstruction,
+  createMint,
+  getMintLen,
+  mintTo,
+} from "@solana/spl-token";
+import { expect } from "chai";
+import {
+  CURVE_CONSTANT_PRODUCT,
+  FAR_DEADLINE,
+  FEE_DENOMINATOR,
+  FEE_NUMERATOR,
+  PoolFixture,
+  UserAccounts,
+  addLiquidity,
+  balance,
+  expectError,
+  lockedLpAccount,
+  newUser,
+  payer,
+  poolAddresses,
+  program,
+  provider,
+  sortMints,
+  swapAccounts,
+} from "./helpers";
+
+describe("fee-on-transfer mints", () => {
+  const SEED = 1_000_000_000n;
+  const TRANSFER_FEE_BPS = 100;
+  const AMOUNT = 10_000_000n;
+  let f: PoolFixture;
+  let programA: PublicKey;
+  let programB: PublicKey;
+  let feeOnA: boolean;
+
+  const programs = () => ({ tokenAProgram: programA, tokenBProgram: programB });
+  // What lands after the 1% transfer fee (exact for the round amounts used)
+  const afterFee = (amount: bigint) => amount - (amount * BigInt(TRANSFER_FEE_BPS)) / 10_000n;
+
+  async function createTransferFeeMint(): Promise<PublicKey> {
+    const conn = provider.connection;
+    const mint = Keypair.generate();
+    const space = getMintLen([ExtensionType.TransferFeeConfig]);
+    const tx = new Transaction().add(
+      SystemProgram.createAccount({
+        fromPubkey: payer.publicKey,
+        newAccountPubkey: mint.publicKey,
+        space,
+        lamports: await conn.getMinimumBalanceForRentExemption(space),
+        programId: TOKEN_2022_PROGRAM_ID,
+      }),
+      createInitializeTransferFeeConfigInstruction(
+        mint.publicKey,
+        payer.publicKey,
+        payer.publicKey,
+        TRANSFER_FEE_BPS,
+        BigInt(Number.MAX_SAFE_INTEGER),
+        TOKEN_2022_PROGRAM_ID,
+      ),
+      createInitializeMintInstruction(mint.publicKey, 6, payer.publicKey, null, TOKEN_2022_PROGRAM_ID),
+    );
+    await sendAndConfirmTransaction(conn, tx, [payer, mint]);
+    return mint.publicKey;
+  }
+
+  async function fund(user: Keypair, amountA: bigint, amountB: bigint): Promise<UserAccounts> {
+    const conn = provider.connection;
+    const tokenA = await createAccount(conn, payer, f.mintA, user.publicKey, Keypair.generate(), undefined, programA);
+    const tokenB = await createAccount(conn, payer, f.mintB, user.publicKey, Keypair.generate(), undefined, programB);
+    const lpToken = await createAccount(conn, payer, f.lpMint, user.publicKey, Keypair.generate());
+    if (amountA > 0n) await mintTo(conn, payer, f.mintA, tokenA, payer, amountA, [], undefined, programA);
+    if (amountB > 0n) await mintTo(conn, payer, f.mintB, tokenB, payer, amountB, [], undefined, programB);
+    return { user, tokenA, tokenB, lpToken };
+  }
+
+  const swapFeeSide = (u: UserAccounts, amountIn: bigint, minAmountOut: bigint) =>
+    program.methods
+      .swap(new BN(amountIn.toString()), new BN(minAmountOut.toString()), feeOnA, new BN(0), new BN(0), FAR_DEADLINE, new BN(0))
+      .accountsPartial({ ...swapAccounts(f, u), ...programs() })
+      .signers([u.user])
+      .rpc();
+
+  async function expectReservesMatchVaults(): Promise<void> {
+    const pool = await program.account.pool.fetch(f.pool);
+    const held = (reserve: BN, protocolFees: BN) => BigInt(reserve.toString()) + BigInt(protocolFees.toString());
+    expect(await balance(f.vaultA)).to.equal(held(pool.reserveA, pool.protocolFeesA));
+    expect(await balance(f.vaultB)).to.equal(held(pool.reserveB, pool.protocolFeesB));
+  }
+
+  before(async () => {
+    const feeMint = await createTransferFeeMint();
+    const plainMint = await createMint(provider.connection, payer, payer.publicKey, null, 6);
+    const [mintA, mintB] = sortMints(feeMint, plainMint);
+    feeOnA = mintA.equals(feeMint);
+    programA = feeOnA ? TOKEN_2022_PROGRAM_ID : TOKEN_PROGRAM_ID;
+    programB = feeOnA ? TOKEN_PROGRAM_ID : TOKEN_2022_PROGRAM_ID;
+
+    f = poolAddresses(mintA, mintB);
+    await program.methods
+      .initializePool(FEE_NUMERATOR, FEE_DENOMINATOR, CURVE_CONSTANT_PRODUCT, new BN(0), new BN(0), 9)
+      .accountsPartial({
+        authority: payer.publicKey,
+        tokenAMint: mintA,
+        tokenBMint: mintB,
+        pool: f.pool,
+        tokenAVault: f.vaultA,
+        tokenBVault: f.vaultB,
+        lpMint: f.lpMint,
+        lpMintAuthority: f.lpMintAuthority,
+        ...programs(),
+        tokenProgram: TOKEN_PROGRAM_ID,
+        systemProgram: SystemProgram.programId,
+        rent: SYSVAR_RENT_PUBKEY,
+      })
+      .rpc();
+  });
+
+  it("credits only what reaches the vault on deposit", async () => {
+    const lp = await fund(await newUser(), SEED, SEED);
+    await addLiquidity(f, lp, SEED, SEED, { lockedLpToken: await lockedLpAccount(f), ...programs() });
+
+    const pool = await program.account.pool.fetch(f.pool);
+    const feeReserve = feeOnA ? pool.reserveA : pool.reserveB;
+    const plainReserve = feeOnA ? pool.reserveB : pool.reserveA;
+    expect(BigInt(feeReserve.toString())).to.equal(afterFee(SEED));
+    expect(BigInt(plainReserve.toString())).to.equal(SEED);
+    await expectReservesMatchVaults();
+  });
+
+  it("prices a swap off the input that arrived", async () => {
+    const trader = await fund(await newUser(), feeOnA ? AMOUNT : 0n, feeOnA ? 0n : AMOUNT);
+    const before = await program.account.pool.fetch(f.pool);
+    const reserveIn = BigInt((feeOnA ? before.reserveA : before.reserveB).toString());
+    const reserveOut = BigInt((feeOnA ? before.reserveB : before.reserveA).toString());
+
+    await swapFeeSide(trader, AMOUNT, 0n);
+
+    // Constant product on the post-fee input, less the 0.3% pool fee
+    const arrived = afterFee(AMOUNT);
+    const withFee = arrived - (arrived * 30n) / 10_000n;
+    const expectedOut = (withFee * reserveOut) / (reserveIn + withFee);
+    expect(await balance(feeOnA ? trader.tokenB : trader.tokenA)).to.equal(expectedOut);
+    await expectReservesMatchVaults();
+  });
+
+  it("rejects a minimum output only reachable if the full input had arrived", async () => {
+    const trader = await fund(await newUser(), feeOnA ? AMOUNT : 0n, feeOnA ? 0n : AMOUNT);
+    const pool = await program.account.pool.fetch(f.pool);
+    const reserveIn = BigInt((feeOnA ? pool.reserveA : pool.reserveB).toString());
+    const reserveOut = BigInt((feeOnA ? pool.reserveB : pool.reserveA).toString());
+    const withFee = AMOUNT - (AMOUNT * 30n) / 10_000n;
+    const quotedOnSent = (withFee * reserveOut) / (reserveIn + withFee);
+
+    await expectError(swapFeeSide(trader, AMOUNT, quotedOnSent), "SlippageExceeded");
+    expect(await balance(feeOnA ? trader.tokenA : trader.tokenB)).to.equal(AMOUNT);
+    await expectReservesMatchVaults();
+  });
+});
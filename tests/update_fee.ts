@@ -0,0 +1,62 @@
+import { BN } from "@coral-xyz/anchor";
+import { expect } from "chai";
+import {
+  PoolFixture,
+  UserAccounts,
+  createSeededPool,
+  emittedEvents,
+  expectError,
+  fundUser,
+  newUser,
+  payer,
+  program,
+  swap,
+} from "./helpers";
+
+describe("update_fee", () => {
+  let f: PoolFixture;
+  let trader: UserAccounts;
+
+  const updateFee = (numerator: number, denominator: number, authority = payer) =>
+    program.methods
+      .updateFee(new BN(numerator), new BN(denominator))
+      .accountsPartial({ authority: authority.publicKey, pool: f.pool })
+      .signers([authority])
+      .rpc();
+
+  const swapFee = async (aToB: boolean) => {
+    const sig = await swap(f, trader, 1_000_000n, aToB);
+    const event = (await emittedEvents(sig)).find((e) => e.name.toLowerCase() === "swapexecuted");
+    return event!.data.feeAmount.toNumber();
+  };
+
+  beforeEach(async () => {
+    ({ f } = await createSeededPool());
+    trader = await fundUser(f, await newUser(), 10_000_000n, 10_000_000n);
+  });
+
+  it("charges the new fee in both directions from the next swap", async () => {
+    expect(await swapFee(true)).to.equal(3_000);
+
+    const sig = await updateFee(100, 10_000);
+
+    const event = (await emittedEvents(sig)).find((e) => e.name.toLowerCase() === "feeupdated");
+    expect(event!.data.oldFeeNumerator.toNumber()).to.equal(30);
+    expect(event!.data.newFeeNumerator.toNumber()).to.equal(100);
+    expect(event!.data.feeBps.toNumber()).to.equal(100);
+    const pool = await program.account.pool.fetch(f.pool);
+    expect(pool.feeNumerator.toNumber()).to.equal(100);
+    expect(pool.feeNumeratorBToA.toNumber()).to.equal(100);
+    expect(await swapFee(true)).to.equal(10_000);
+    expect(await swapFee(false)).to.equal(10_000);
+  });
+
+  it("rejects a fee above the maximum", async () => {
+    await expectError(updateFee(1_001, 10_000), "InvalidFeeParameters");
+    expect((await program.account.pool.fetch(f.pool)).feeNumerator.toNumber()).to.equal(30);
+  });
+
+  it("rejects a non-authority signer", async () => {
+    await expectError(updateFee(100, 10_000, await newUser()), "Unauthorized");
+  });
+});
@@ -0,0 +1,94 @@
+import { PublicKey, SystemProgram, SYSVAR_RENT_PUBKEY } from "@solana/web3.js";
+import { TOKEN_PROGRAM_ID } from "@solana/spl-token";
+import { expect } from "chai";
+import {
+  PoolFixture,
+  createSeededPool,
+  expectError,
+  pausePool,
+  payer,
+  pda,
+  program,
+  unpausePool,
+} from "./helpers";
+
+describe("vault harvest", () => {
+  let f: PoolFixture;
+  let vault: PublicKey;
+  let priceCheckpoints: PublicKey;
+
+  const holdings = (mint: PublicKey) => pda([Buffer.from("vault_holdings"), vault.toBuffer(), mint.toBuffer()]);
+
+  const harvest = () =>
+    program.methods
+      .harvest()
+      .accountsPartial({
+        caller: payer.publicKey,
+        pool: f.pool,
+        vault,
+        tokenAVault: f.vaultA,
+        tokenBVault: f.vaultB,
+        lpMint: f.lpMint,
+        lpMintAuthority: f.lpMintAuthority,
+        shareMint: pda([Buffer.from("vault_share_mint"), vault.toBuffer()]),
+        lpHoldings: holdings(f.lpMint),
+        tokenAHoldings: holdings(f.mintA),
+        tokenBHoldings: holdings(f.mintB),
+        tokenAMint: f.mintA,
+        tokenBMint: f.mintB,
+        priceCheckpoints,
+        tokenAProgram: TOKEN_PROGRAM_ID,
+        tokenBProgram: TOKEN_PROGRAM_ID,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .rpc();
+
+  before(async () => {
+    ({ f } = await createSeededPool());
+    vault = pda([Buffer.from("lp_vault"), f.pool.toBuffer()]);
+    priceCheckpoints = pda([Buffer.from("price_checkpoints"), f.pool.toBuffer()]);
+
+    await program.methods
+      .initializeVault()
+      .accountsPartial({
+        payer: payer.publicKey,
+        pool: f.pool,
+        vault,
+        lpMint: f.lpMint,
+        shareMint: pda([Buffer.from("vault_share_mint"), vault.toBuffer()]),
+        tokenAMint: f.mintA,
+        tokenBMint: f.mintB,
+        lpHoldings: holdings(f.lpMint),
+        tokenAHoldings: holdings(f.mintA),
+        tokenBHoldings: holdings(f.mintB),
+        tokenAProgram: TOKEN_PROGRAM_ID,
+        tokenBProgram: TOKEN_PROGRAM_ID,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        systemProgram: SystemProgram.programId,
+        rent: SYSVAR_RENT_PUBKEY,
+      })
+      .rpc();
+    await program.methods
+      .initializePriceCheckpoints()
+      .accountsPartial({ payer: payer.publicKey, pool: f.pool, priceCheckpoints })
+      .rpc();
+  });
+
+  it("rejects a harvest while the pool is paused", async () => {
+    await pausePool(f);
+    const before = await program.account.pool.fetch(f.pool);
+
+    await expectError(harvest(), "PoolPaused");
+
+    const after = await program.account.pool.fetch(f.pool);
+    expect(after.reserveA.eq(before.reserveA)).to.be.true;
+    expect(after.reserveB.eq(before.reserveB)).to.be.true;
+    expect((await program.account.vault.fetch(vault)).totalLp.toNumber()).to.equal(0);
+  });
+
+  it("gets past the pause check once unpaused", async () => {
+    await unpausePool(f);
+    // No swap has recorded a checkpoint yet, so the TWAP guard is next
+    await expectError(harvest(), "NoPriceCheckpoint");
+  });
+});